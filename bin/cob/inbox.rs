@@ -22,6 +22,30 @@ impl Default for SortBy {
     }
 }
 
+impl SortBy {
+    /// The fields notifications can be sorted by.
+    pub const FIELDS: [&'static str; 3] = ["timestamp", "project", "id"];
+
+    /// Sorting by the given field in its natural direction: newest first for
+    /// timestamps, ascending otherwise.
+    pub fn with_field(field: &'static str) -> Self {
+        Self {
+            reverse: field == "timestamp",
+            field,
+        }
+    }
+
+    /// Sorting by the field that follows the current one in `FIELDS`.
+    pub fn next(&self) -> Self {
+        let current = Self::FIELDS
+            .iter()
+            .position(|field| *field == self.field)
+            .unwrap_or_default();
+
+        Self::with_field(Self::FIELDS[(current + 1) % Self::FIELDS.len()])
+    }
+}
+
 pub fn all(repository: &Repository, profile: &Profile) -> Result<Vec<Notification>> {
     let all = profile
         .notifications_mut()?
@@ -1,5 +1,6 @@
 use std::fmt;
 use std::fmt::Write as _;
+use std::ops::Range;
 
 use anyhow::Result;
 
@@ -81,12 +82,68 @@ pub fn all(profile: &Profile, repository: &Repository) -> Result<Vec<(IssueId, I
     Ok(issues.flatten().collect())
 }
 
+/// Reads the issues in the given range from the cache, in the order they are
+/// cached, and calls `f` for every batch of at most `size` issues, instead of
+/// collecting them all at once. Besides the batch, `f` is given the number of
+/// issues in the range, such that the loading progress can be shown. Returns the
+/// number of issues in the cache.
+pub fn batched(
+    profile: &Profile,
+    repository: &Repository,
+    range: Range<usize>,
+    size: usize,
+    mut f: impl FnMut(Vec<(IssueId, Issue)>, usize),
+) -> Result<usize> {
+    let cache = profile.issues(repository)?;
+    let total = cache.counts()?.total();
+    let in_range = range.end.min(total).saturating_sub(range.start);
+    let mut batch = Vec::with_capacity(size);
+
+    for issue in cache.list()?.flatten().skip(range.start).take(in_range) {
+        batch.push(issue);
+
+        if batch.len() >= size {
+            f(std::mem::take(&mut batch), in_range);
+        }
+    }
+    if !batch.is_empty() {
+        f(batch, in_range);
+    }
+
+    Ok(total)
+}
+
 #[allow(dead_code)]
 pub fn find(profile: &Profile, repository: &Repository, id: &IssueId) -> Result<Option<Issue>> {
     let cache = profile.issues(repository)?;
     Ok(cache.get(id)?)
 }
 
+/// Assigns the given user to the issue, or unassigns them if they're assigned
+/// already. Returns `true` if the user is assigned afterwards.
+pub fn toggle_assignee<G: Signer>(
+    profile: &Profile,
+    repository: &Repository,
+    signer: &G,
+    id: &IssueId,
+    assignee: Did,
+) -> Result<bool> {
+    let mut issues = profile.issues_mut(repository)?;
+    let mut issue = issues.get_mut(id)?;
+
+    let mut assignees = issue.assignees().cloned().collect::<Vec<_>>();
+    let assigned = if assignees.contains(&assignee) {
+        assignees.retain(|did| *did != assignee);
+        false
+    } else {
+        assignees.push(assignee);
+        true
+    };
+    issue.assign(assignees, signer)?;
+
+    Ok(assigned)
+}
+
 #[allow(dead_code)]
 pub fn create<G: Signer>(
     profile: &Profile,
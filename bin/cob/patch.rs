@@ -81,6 +81,34 @@ pub fn all(profile: &Profile, repository: &Repository) -> Result<Vec<(PatchId, P
     Ok(patches.flatten().collect())
 }
 
+/// Reads all patches from the cache and calls `f` for every batch of at most
+/// `size` patches, instead of collecting them all at once. Besides the batch, `f`
+/// is given the number of all patches, such that the loading progress can be
+/// shown.
+pub fn batched(
+    profile: &Profile,
+    repository: &Repository,
+    size: usize,
+    mut f: impl FnMut(Vec<(PatchId, Patch)>, usize),
+) -> Result<()> {
+    let cache = profile.patches(repository)?;
+    let total = cache.counts()?.total();
+    let mut batch = Vec::with_capacity(size);
+
+    for patch in cache.list()?.flatten() {
+        batch.push(patch);
+
+        if batch.len() >= size {
+            f(std::mem::take(&mut batch), total);
+        }
+    }
+    if !batch.is_empty() {
+        f(batch, total);
+    }
+
+    Ok(())
+}
+
 pub fn find(profile: &Profile, repository: &Repository, id: &PatchId) -> Result<Option<Patch>> {
     let cache = profile.patches(repository)?;
     Ok(cache.get(id)?)
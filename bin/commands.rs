@@ -6,3 +6,52 @@ pub mod tui_inbox;
 pub mod tui_issue;
 #[path = "commands/patch.rs"]
 pub mod tui_patch;
+
+use anyhow::anyhow;
+
+use ratatui::Viewport;
+
+use radicle_cli::terminal;
+
+/// Parses the value of the viewport options all `select` operations take, given
+/// the name of the one that was read: `--inline <rows>` or `--fullscreen`.
+pub fn viewport(name: &str, parser: &mut lexopt::Parser) -> anyhow::Result<Viewport> {
+    match name {
+        "inline" => {
+            let val = parser.value()?;
+            let rows = terminal::args::string(&val)
+                .parse::<u16>()
+                .ok()
+                .filter(|rows| *rows > 0)
+                .ok_or_else(|| anyhow!("invalid number of rows '{}'", val.to_string_lossy()))?;
+
+            Ok(Viewport::Inline(rows))
+        }
+        "fullscreen" => Ok(Viewport::Fullscreen),
+        unknown => Err(anyhow!("unknown viewport option '--{unknown}'")),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use ratatui::Viewport;
+
+    #[test]
+    fn viewport_options_should_be_parsed() -> anyhow::Result<()> {
+        let mut parser = lexopt::Parser::from_args(["12", "0"]);
+
+        assert_eq!(
+            super::viewport("inline", &mut parser)?,
+            Viewport::Inline(12)
+        );
+        assert!(super::viewport("inline", &mut parser).is_err());
+        assert_eq!(
+            super::viewport("fullscreen", &mut parser)?,
+            Viewport::Fullscreen
+        );
+
+        Ok(())
+    }
+}
@@ -31,7 +31,7 @@ impl Args for Options {
 }
 
 pub fn run(_options: Options, ctx: impl Context) -> anyhow::Result<()> {
-    term::print("Usage: rad-tui <command> [--help]");
+    term::print("Usage: rad-tui [--config <path>] <command> [--help]");
 
     if let Err(e) = ctx.profile() {
         term::blank();
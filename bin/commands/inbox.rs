@@ -4,15 +4,24 @@ mod common;
 mod select;
 
 use std::ffi::OsString;
+use std::path::PathBuf;
+use std::time::Duration;
 
 use anyhow::anyhow;
 
+use ratatui::Viewport;
+
 use radicle_cli::terminal;
 use radicle_cli::terminal::{Args, Error, Help};
 
 use self::common::{Mode, RepositoryMode, SelectionMode};
 
 use crate::cob::inbox;
+use crate::commands;
+use crate::history::{self, Interface};
+use crate::output;
+use crate::ui::onboarding;
+use crate::ui::picker::AutoPick;
 
 pub const HELP: Help = Help {
     name: "inbox",
@@ -26,8 +35,27 @@ Usage
 Other options
 
     --mode <MODE>           Set selection mode; see MODE below (default: operation)
-    
-    --sort-by <field>       Sort by `id` or `timestamp` (default: timestamp)
+    --repo <RID>            Show notifications of the given repository only
+    --all-repos, -a         Show notifications of all repositories
+    --filter <search>       Start with the given search instead of the one applied last
+    --inline <rows>         Render inline, using the given amount of rows (default: 20)
+    --fullscreen            Render in fullscreen
+    --refresh <secs>        Reload notifications every given number of seconds
+                            (default: no reload)
+    --notify                Post a desktop notification if new notifications arrive
+                            on reload
+    --select-first          Select the first listed notification without showing
+                            the interface
+    --select-last           Select the last listed notification without showing
+                            the interface
+    --print                 Print the listed notifications to stdout instead of showing
+                            the interface
+    --no-color              Print without colors, e.g. to pipe the output
+    --output-file <path>    Write the selection to the given file instead of stderr.
+                            Nothing is written if the interface is quit
+
+    --sort-by <field>       Sort by `id`, `project` or `timestamp`
+                            (default: project if --all-repos is given, timestamp otherwise)
     --reverse, -r           Reverse the list
     --help                  Print help
 
@@ -53,7 +81,15 @@ pub enum OperationName {
 pub struct SelectOptions {
     mode: Mode,
     filter: inbox::Filter,
+    search: Option<String>,
     sort_by: inbox::SortBy,
+    viewport: Option<Viewport>,
+    refresh: Option<Duration>,
+    notify: bool,
+    pick: Option<AutoPick>,
+    print: bool,
+    no_color: bool,
+    output_file: Option<PathBuf>,
 }
 
 impl Args for Options {
@@ -85,6 +121,46 @@ impl Args for Options {
                     };
                     select_opts.mode = select_opts.mode.with_selection(selection_mode)
                 }
+                Long("filter") if op == Some(OperationName::Select) => {
+                    let val = parser.value()?;
+                    select_opts.search = Some(terminal::args::string(&val));
+                }
+
+                Long(name @ ("inline" | "fullscreen")) if op == Some(OperationName::Select) => {
+                    // The name borrows the parser, which the value is read from.
+                    let name = name.to_owned();
+                    select_opts.viewport = Some(commands::viewport(&name, &mut parser)?);
+                }
+                Long("refresh") if op == Some(OperationName::Select) => {
+                    let val = parser.value()?;
+                    let secs = terminal::args::string(&val)
+                        .parse::<u64>()
+                        .ok()
+                        .filter(|secs| *secs > 0)
+                        .ok_or_else(|| {
+                            anyhow!("invalid refresh interval '{}'", val.to_string_lossy())
+                        })?;
+
+                    select_opts.refresh = Some(Duration::from_secs(secs));
+                }
+                Long("notify") if op == Some(OperationName::Select) => {
+                    select_opts.notify = true;
+                }
+                Long("select-first") if op == Some(OperationName::Select) => {
+                    select_opts.pick = Some(AutoPick::First);
+                }
+                Long("select-last") if op == Some(OperationName::Select) => {
+                    select_opts.pick = Some(AutoPick::Last);
+                }
+                Long("print") if op == Some(OperationName::Select) => {
+                    select_opts.print = true;
+                }
+                Long("no-color") if op == Some(OperationName::Select) => {
+                    select_opts.no_color = true;
+                }
+                Long("output-file") if op == Some(OperationName::Select) => {
+                    select_opts.output_file = Some(PathBuf::from(parser.value()?));
+                }
 
                 Long("reverse") | Short('r') => {
                     reverse = Some(true);
@@ -95,6 +171,7 @@ impl Args for Options {
                     match terminal::args::string(&val).as_str() {
                         "timestamp" => field = Some("timestamp"),
                         "id" => field = Some("id"),
+                        "project" => field = Some("project"),
                         other => anyhow::bail!("unknown sorting field '{other}'"),
                     }
                 }
@@ -105,7 +182,7 @@ impl Args for Options {
 
                     repository_mode = Some(RepositoryMode::ByRepo((repo, None)));
                 }
-                Long("all") | Short('a') if repository_mode.is_none() => {
+                Long("all-repos") | Long("all") | Short('a') if repository_mode.is_none() => {
                     repository_mode = Some(RepositoryMode::All);
                 }
 
@@ -125,6 +202,8 @@ impl Args for Options {
                 field,
                 reverse: reverse.unwrap_or(false),
             }
+        } else if *select_opts.mode.repository() == RepositoryMode::All {
+            inbox::SortBy::with_field("project")
         } else {
             inbox::SortBy::default()
         };
@@ -145,7 +224,9 @@ pub async fn run(options: Options, ctx: impl terminal::Context) -> anyhow::Resul
 
     match options.op {
         Operation::Select { opts } => {
-            let profile = ctx.profile()?;
+            let Some(profile) = onboarding::profile(&ctx, !opts.print).await? else {
+                return Ok(());
+            };
             let repository = profile.storage.repository(rid).unwrap();
 
             if let Err(err) = crate::log::enable() {
@@ -153,23 +234,40 @@ pub async fn run(options: Options, ctx: impl terminal::Context) -> anyhow::Resul
             }
             log::info!("Starting patch selection interface in project {}..", rid);
 
+            // The search applied last is restored if no search is given.
+            let search = opts.search.clone().or_else(|| {
+                let interactive = !opts.print && opts.pick.is_none();
+                interactive
+                    .then(|| {
+                        let repo = opts.mode.repository().rid(rid);
+                        history::last_search(repo.as_ref(), Interface::Inbox)
+                    })
+                    .flatten()
+            });
+
             let context = select::Context {
                 profile,
                 repository,
                 mode: opts.mode,
                 filter: opts.filter.clone(),
+                search,
                 sort_by: opts.sort_by,
+                viewport: opts
+                    .viewport
+                    .unwrap_or(Viewport::Inline(radicle_tui::terminal::INLINE_HEIGHT)),
+                refresh: opts.refresh,
+                notify: opts.notify,
+                pick: opts.pick,
             };
-            let output = select::App::new(context).run().await?;
-
-            let output = output
-                .map(|o| serde_json::to_string(&o).unwrap_or_default())
-                .unwrap_or_default();
+            let app = select::App::new(context);
+            if opts.print {
+                println!("{}", app.print(!opts.no_color)?);
+                return Ok(());
+            }
+            let selection = app.run().await?;
 
-            log::info!("About to print to `stderr`: {}", output);
+            output::selection(selection, opts.output_file.as_deref())?;
             log::info!("Exiting inbox selection interface..");
-
-            eprint!("{output}");
         }
     }
 
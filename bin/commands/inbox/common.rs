@@ -23,6 +23,18 @@ pub enum RepositoryMode {
     ByRepo((RepoId, Option<String>)),
 }
 
+impl RepositoryMode {
+    /// Returns the repository notifications are shown of, given the one of the
+    /// working directory, or `None` if they're shown of all repositories.
+    pub fn rid(&self, contextual: RepoId) -> Option<RepoId> {
+        match self {
+            RepositoryMode::Contextual => Some(contextual),
+            RepositoryMode::All => None,
+            RepositoryMode::ByRepo((rid, _)) => Some(*rid),
+        }
+    }
+}
+
 #[derive(Clone, Default, Debug, PartialEq, Eq)]
 pub struct Mode {
     selection: SelectionMode,
@@ -1,7 +1,9 @@
 #[path = "select/ui.rs"]
 mod ui;
 
+use std::collections::HashSet;
 use std::str::FromStr;
+use std::time::Duration;
 
 use anyhow::Result;
 
@@ -12,7 +14,7 @@ use ratatui::layout::Constraint;
 use ratatui::style::Stylize;
 use ratatui::text::Text;
 
-use radicle::identity::Project;
+use radicle::identity::{Project, RepoId};
 use radicle::node::notifications::NotificationId;
 use radicle::storage::git::Repository;
 use radicle::storage::ReadRepository;
@@ -22,6 +24,10 @@ use radicle::Profile;
 use radicle_tui as tui;
 
 use tui::store;
+use tui::task::desktop::DesktopNotify;
+use tui::task::interval::Interval;
+use tui::task::{self, Process};
+use tui::ui::print;
 use tui::ui::rm::widget::container::{Container, Footer, FooterProps, Header, HeaderProps};
 use tui::ui::rm::widget::input::{TextView, TextViewProps, TextViewState};
 use tui::ui::rm::widget::window::{
@@ -29,18 +35,25 @@ use tui::ui::rm::widget::window::{
 };
 use tui::ui::rm::widget::{ToWidget, Widget};
 use tui::ui::span;
+use tui::ui::toast::ToastLevel;
 use tui::ui::BufferedValue;
 use tui::ui::Column;
+use tui::ui::Shortcut;
 use tui::{BoxedAny, Channel, Exit, PageStack};
 
 use crate::cob::inbox;
-use crate::ui::items::{Filter, NotificationItem, NotificationItemFilter};
+use crate::history::{self, Interface};
+use crate::settings::{Action, Settings};
+use crate::ui::items::{
+    group_notifications, Filter, NotificationGroup, NotificationItem, NotificationItemFilter,
+};
+use crate::ui::picker::AutoPick;
 
 use self::ui::Browser;
 use self::ui::BrowserProps;
 
 use super::common::SelectionMode;
-use super::common::{Mode, RepositoryMode};
+use super::common::{InboxOperation, Mode, RepositoryMode};
 
 type Selection = tui::Selection<NotificationId>;
 
@@ -50,7 +63,16 @@ pub struct Context {
     pub repository: Repository,
     pub mode: Mode,
     pub filter: inbox::Filter,
+    /// The search notifications are filtered by on start.
+    pub search: Option<String>,
     pub sort_by: inbox::SortBy,
+    pub viewport: Viewport,
+    /// The interval notifications are reloaded in, if any.
+    pub refresh: Option<Duration>,
+    /// Post a desktop notification if new notifications arrive on reload.
+    pub notify: bool,
+    /// The notification that is selected right away, without running the browser.
+    pub pick: Option<AutoPick>,
 }
 
 pub struct App {
@@ -70,15 +92,126 @@ pub struct BrowserState {
     filter: NotificationItemFilter,
     search: BufferedValue<String>,
     show_search: bool,
+    /// If notifications appeared since the selection was last changed.
+    updated: bool,
+    /// If notifications of the same COB or branch are grouped.
+    grouped: bool,
+    /// The keys of the groups whose notifications are all shown.
+    expanded: HashSet<String>,
 }
 
 impl BrowserState {
+    /// Returns the notifications that match the filter. If grouped, these are
+    /// grouped afterwards.
     pub fn notifications(&self) -> Vec<NotificationItem> {
-        self.items
+        let notifications = self
+            .items
             .iter()
             .filter(|patch| self.filter.matches(patch))
             .cloned()
-            .collect()
+            .collect::<Vec<_>>();
+
+        if self.grouped {
+            group_notifications(&notifications, &self.expanded)
+        } else {
+            notifications
+        }
+    }
+
+    /// Groups notifications of the same COB or branch, or lists them all again.
+    /// The selected notification or its group stays selected.
+    pub fn toggle_grouping(&mut self) {
+        let selected = self.selected_id();
+        self.grouped = !self.grouped;
+        self.expanded.clear();
+        self.select(selected);
+    }
+
+    /// Expands the group with the given key, or collapses it if it's expanded.
+    pub fn toggle_group(&mut self, key: String) {
+        let selected = self.selected_id();
+        if !self.expanded.remove(&key) {
+            self.expanded.insert(key);
+        }
+        self.select(selected);
+    }
+
+    /// Returns the id of the selected notification, or the ids of all
+    /// notifications of its group if it's collapsed.
+    pub fn selected_ids(&self) -> Vec<NotificationId> {
+        let notifications = self.notifications();
+        let Some(selected) = self
+            .selected
+            .and_then(|selected| notifications.get(selected))
+        else {
+            return vec![];
+        };
+
+        match selected.group {
+            Some(NotificationGroup::Collapsed(_)) => {
+                let key = selected.group_key();
+                self.items
+                    .iter()
+                    .filter(|notif| self.filter.matches(notif) && notif.group_key() == key)
+                    .map(|notif| notif.id)
+                    .collect()
+            }
+            _ => vec![selected.id],
+        }
+    }
+
+    fn selected_id(&self) -> Option<NotificationId> {
+        self.selected
+            .and_then(|selected| self.notifications().get(selected).map(|notif| notif.id))
+    }
+
+    /// Selects the notification with the given id if it's shown, or the group
+    /// it was collapsed into. Otherwise, the closest one to the current selection
+    /// is selected, or none if nothing is shown.
+    fn select(&mut self, id: Option<NotificationId>) {
+        let notifications = self.notifications();
+        let key = id
+            .and_then(|id| self.items.iter().find(|notif| notif.id == id))
+            .map(|notif| notif.group_key());
+
+        self.selected = id
+            .and_then(|id| notifications.iter().position(|notif| notif.id == id))
+            .or_else(|| {
+                notifications
+                    .iter()
+                    .position(|notif| notif.group.is_some() && Some(notif.group_key()) == key)
+            })
+            .or_else(|| {
+                self.selected
+                    .filter(|_| !notifications.is_empty())
+                    .map(|selected| selected.min(notifications.len() - 1))
+            });
+    }
+
+    /// Replaces all items with the reloaded ones given, which need to be sorted
+    /// already. The selected notification stays selected if it's still shown.
+    /// If it's gone, e.g. because its issue or patch was deleted in the meantime,
+    /// the one that took its place is selected.
+    pub fn refresh(&mut self, items: Vec<NotificationItem>) {
+        let selected = self.selected_id();
+        let known = self
+            .items
+            .iter()
+            .map(|notif| notif.id)
+            .collect::<HashSet<_>>();
+
+        self.updated |= items.iter().any(|notif| !known.contains(&notif.id));
+        self.items = items;
+        self.select(selected);
+    }
+
+    /// Sorts all items in the given order. The selected notification stays
+    /// selected.
+    pub fn sort(&mut self, sort_by: &inbox::SortBy) {
+        let selected = self.selected_id();
+
+        self.items.sort_by(|a, b| a.compare(b, sort_by));
+        self.select(selected);
     }
 }
 
@@ -89,11 +222,17 @@ pub struct HelpState {
 
 #[derive(Clone, Debug)]
 pub struct State {
+    /// The repository searches are remembered for, or `None` if notifications
+    /// of all repositories are shown.
+    repo: Option<RepoId>,
     mode: Mode,
     project: Project,
+    sort_by: inbox::SortBy,
     pages: PageStack<AppPage>,
     browser: BrowserState,
     help: HelpState,
+    /// Toasts requested since the frontend took them the last time.
+    toasts: Vec<(ToastLevel, String)>,
 }
 
 impl TryFrom<&Context> for State {
@@ -103,54 +242,14 @@ impl TryFrom<&Context> for State {
         let doc = context.repository.identity_doc()?;
         let project = doc.project()?;
 
-        let search = BufferedValue::new(String::new());
+        let search = BufferedValue::new(context.search.clone().unwrap_or_default());
         let filter = NotificationItemFilter::from_str(&search.read()).unwrap_or_default();
 
-        let mut notifications = match &context.mode.repository() {
-            RepositoryMode::All => {
-                let mut repos = context.profile.storage.repositories()?;
-                repos.sort_by_key(|r| r.rid);
-
-                let mut notifs = vec![];
-                for repo in repos {
-                    let repo = context.profile.storage.repository(repo.rid)?;
-
-                    let items = inbox::all(&repo, &context.profile)?
-                        .iter()
-                        .map(|notif| NotificationItem::new(&context.profile, &repo, notif))
-                        .filter_map(|item| item.ok())
-                        .flatten()
-                        .collect::<Vec<_>>();
-
-                    notifs.extend(items);
-                }
-
-                notifs
-            }
-            RepositoryMode::Contextual => {
-                let notifs = inbox::all(&context.repository, &context.profile)?;
-
-                notifs
-                    .iter()
-                    .map(|notif| {
-                        NotificationItem::new(&context.profile, &context.repository, notif)
-                    })
-                    .filter_map(|item| item.ok())
-                    .flatten()
-                    .collect::<Vec<_>>()
-            }
-            RepositoryMode::ByRepo((rid, _)) => {
-                let repo = context.profile.storage.repository(*rid)?;
-                let notifs = inbox::all(&repo, &context.profile)?;
-
-                notifs
-                    .iter()
-                    .map(|notif| NotificationItem::new(&context.profile, &repo, notif))
-                    .filter_map(|item| item.ok())
-                    .flatten()
-                    .collect::<Vec<_>>()
-            }
-        };
+        let mut notifications = load_items(
+            &context.profile,
+            context.repository.id,
+            context.mode.repository(),
+        )?;
 
         // Set project name
         let mode = match &context.mode.repository() {
@@ -172,23 +271,13 @@ impl TryFrom<&Context> for State {
         };
 
         // Apply sorting
-        match context.sort_by.field {
-            "timestamp" => notifications.sort_by(|a, b| a.timestamp.cmp(&b.timestamp)),
-            "id" => notifications.sort_by(|a, b| a.id.cmp(&b.id)),
-            _ => {}
-        }
-        if context.sort_by.reverse {
-            notifications.reverse();
-        }
-
-        // Sort by project if all notifications are shown
-        if let RepositoryMode::All = mode.repository() {
-            notifications.sort_by(|a, b| a.project.cmp(&b.project));
-        }
+        notifications.sort_by(|a, b| a.compare(b, &context.sort_by));
 
         Ok(Self {
-            mode: context.mode.clone(),
+            repo: context.mode.repository().rid(context.repository.id),
+            mode,
             project,
+            sort_by: context.sort_by,
             pages: PageStack::new(vec![AppPage::Browse]),
             browser: BrowserState {
                 items: notifications,
@@ -196,10 +285,14 @@ impl TryFrom<&Context> for State {
                 filter,
                 search,
                 show_search: false,
+                updated: false,
+                grouped: false,
+                expanded: HashSet::new(),
             },
             help: HelpState {
                 text: TextViewState::default().content(help_text()),
             },
+            toasts: vec![],
         })
     }
 }
@@ -212,9 +305,16 @@ pub enum Message {
     UpdateSearch { value: String },
     ApplySearch,
     CloseSearch,
+    ResetSearch,
+    CycleSort,
+    ToggleGrouping,
+    ToggleGroup { key: String },
+    Clear,
     OpenHelp,
     LeavePage,
     ScrollHelp { state: TextViewState },
+    Refresh { items: Vec<NotificationItem> },
+    RefreshFailed { error: String },
 }
 
 impl store::Update<Message> for State {
@@ -225,6 +325,7 @@ impl store::Update<Message> for State {
             Message::Exit { selection } => Some(Exit { value: selection }),
             Message::Select { selected } => {
                 self.browser.selected = selected;
+                self.browser.updated = false;
                 None
             }
             Message::OpenSearch => {
@@ -247,6 +348,7 @@ impl store::Update<Message> for State {
             Message::ApplySearch => {
                 self.browser.search.apply();
                 self.browser.show_search = false;
+                self.remember_search();
                 None
             }
             Message::CloseSearch => {
@@ -257,6 +359,37 @@ impl store::Update<Message> for State {
 
                 None
             }
+            Message::ResetSearch => {
+                self.browser.search.write(String::new());
+                self.browser.search.apply();
+                self.browser.filter = NotificationItemFilter::default();
+                self.browser.selected = Some(0);
+                self.remember_search();
+                None
+            }
+            Message::CycleSort => {
+                self.sort_by = self.sort_by.next();
+                self.browser.sort(&self.sort_by);
+                None
+            }
+            Message::ToggleGrouping => {
+                self.browser.toggle_grouping();
+                None
+            }
+            Message::ToggleGroup { key } => {
+                self.browser.toggle_group(key);
+                None
+            }
+            Message::Clear => {
+                let ids = self.browser.selected_ids();
+                (!ids.is_empty()).then(|| Exit {
+                    value: Some(
+                        Selection::default()
+                            .with_operation(InboxOperation::Clear.to_string())
+                            .with_ids(ids),
+                    ),
+                })
+            }
             Message::OpenHelp => {
                 self.pages.push(AppPage::Help);
                 None
@@ -269,20 +402,101 @@ impl store::Update<Message> for State {
                 self.help.text = state;
                 None
             }
+            Message::Refresh { mut items } => {
+                items.sort_by(|a, b| a.compare(b, &self.sort_by));
+                self.browser.refresh(items);
+                None
+            }
+            Message::RefreshFailed { error } => {
+                self.toasts.push((
+                    ToastLevel::Error,
+                    format!("Could not reload notifications: {error}"),
+                ));
+                None
+            }
+        }
+    }
+
+    fn update_batch(&mut self, messages: Vec<Message>) -> Option<Exit<Selection>> {
+        store::update_pages(self, messages)
+    }
+
+    fn take_toasts(&mut self) -> Vec<(ToastLevel, String)> {
+        std::mem::take(&mut self.toasts)
+    }
+}
+
+impl store::Pages for State {
+    type Page = AppPage;
+
+    fn page(&self) -> Option<AppPage> {
+        self.pages.peek().ok().cloned()
+    }
+
+    fn on_exit_page(&mut self, page: &AppPage) {
+        if *page == AppPage::Help {
+            self.help.text = TextViewState::default().content(help_text());
         }
     }
 }
 
+impl State {
+    /// Remembers the applied search, such that the next session starts with it.
+    fn remember_search(&self) {
+        history::save_search(
+            self.repo.as_ref(),
+            Interface::Inbox,
+            &self.browser.search.read(),
+        );
+    }
+}
+
 impl App {
     pub fn new(context: Context) -> Self {
         Self { context }
     }
 
+    /// Renders all notifications that match the filter in the columns of the
+    /// notification list as plain text, instead of running the interface.
+    pub fn print(&self, color: bool) -> Result<String> {
+        let state = State::try_from(&self.context)?;
+        let show_project = *state.mode.repository() == RepositoryMode::All;
+
+        Ok(print::table(
+            &state.browser.notifications(),
+            &ui::columns(show_project),
+            print::terminal_width(),
+            color,
+        ))
+    }
+
     pub async fn run(&self) -> Result<Option<Selection>> {
         let channel = Channel::default();
         let state = State::try_from(&self.context)?;
         let tx = channel.tx.clone();
 
+        if let Some(pick) = self.context.pick {
+            let notification = pick.pick(state.browser.notifications());
+
+            return Ok(notification.map(|notif| match state.mode.selection() {
+                SelectionMode::Operation => Selection::default()
+                    .with_operation(InboxOperation::Show.to_string())
+                    .with_id(notif.id),
+                SelectionMode::Id => Selection::default().with_id(notif.id),
+            }));
+        }
+
+        if let Some(period) = self.context.refresh {
+            let refresh = refresh(&self.context, period);
+
+            if self.context.notify {
+                let summary = new_notifications(&state.browser.notifications());
+                task::spawn(DesktopNotify::new("Radicle", refresh, summary), tx.clone());
+            } else {
+                task::spawn(refresh, tx.clone());
+            }
+        }
+
         let window = Window::default()
             .page(AppPage::Browse, browser_page(&state, &channel))
             .page(AppPage::Help, help_page(&state, &channel))
@@ -294,7 +508,86 @@ impl App {
                     .into()
             });
 
-        tui::rm(state, window, Viewport::Inline(20), channel).await
+        tui::rm(state, window, self.context.viewport.clone(), channel).await
+    }
+}
+
+/// Loads all notifications of the repositories given by `mode`. Notifications
+/// of the repository `rid` are loaded if the mode is contextual.
+fn load_items(
+    profile: &Profile,
+    rid: RepoId,
+    mode: &RepositoryMode,
+) -> Result<Vec<NotificationItem>> {
+    let settings = Settings::current();
+    let rids = match mode {
+        RepositoryMode::All => {
+            let mut repos = profile.storage.repositories()?;
+            repos.sort_by_key(|r| r.rid);
+
+            repos.into_iter().map(|repo| repo.rid).collect()
+        }
+        RepositoryMode::Contextual => vec![rid],
+        RepositoryMode::ByRepo((rid, _)) => vec![*rid],
+    };
+
+    let mut notifs = vec![];
+    for rid in rids {
+        let repo = profile.storage.repository(rid)?;
+
+        let items = inbox::all(&repo, profile)?
+            .iter()
+            .map(|notif| NotificationItem::new(profile, &repo, notif))
+            .filter_map(|item| item.ok())
+            .flatten()
+            .map(|item| item.with_settings(settings))
+            .collect::<Vec<_>>();
+
+        notifs.extend(items);
+    }
+
+    Ok(notifs)
+}
+
+/// Builds a process that reloads all notifications periodically. The reload runs
+/// on a blocking thread, see `Interval`.
+fn refresh(context: &Context, period: Duration) -> impl Process<Message> {
+    let profile = context.profile.clone();
+    let rid = context.repository.id;
+    let mode = context.mode.repository().clone();
+
+    Interval::new(period, move || match load_items(&profile, rid, &mode) {
+        Ok(items) => Some(Message::Refresh { items }),
+        Err(err) => {
+            log::warn!("Failed to reload notifications: {}", err);
+            Some(Message::RefreshFailed {
+                error: err.to_string(),
+            })
+        }
+    })
+}
+
+/// Returns a function that summarizes the notifications of a reload that are
+/// neither seen nor listed before.
+fn new_notifications(
+    items: &[NotificationItem],
+) -> impl FnMut(&Message) -> Option<String> + Send + 'static {
+    let mut known = items.iter().map(|item| item.id).collect::<HashSet<_>>();
+
+    move |message| {
+        let Message::Refresh { items } = message else {
+            return None;
+        };
+        let new = items
+            .iter()
+            .filter(|item| known.insert(item.id) && !item.seen)
+            .collect::<Vec<_>>();
+
+        match new.as_slice() {
+            [] => None,
+            [item] => Some(format!("New notification in {}", item.project)),
+            _ => Some(format!("{} new notifications", new.len())),
+        }
     }
 }
 
@@ -308,22 +601,31 @@ fn browser_page(_state: &State, channel: &Channel<Message>) -> Widget<State, Mes
     let shortcuts = Shortcuts::default()
         .to_widget(tx.clone())
         .on_update(|state: &State| {
-            let shortcuts = if state.browser.show_search {
-                vec![("esc", "cancel"), ("enter", "apply")]
+            let shortcuts: Vec<Shortcut> = if state.browser.show_search {
+                vec![("esc", "cancel").into(), ("enter", "apply").into()]
             } else {
-                match state.mode.selection() {
-                    SelectionMode::Id => vec![("enter", "select"), ("/", "search")],
+                let mut shortcuts = match state.mode.selection() {
+                    SelectionMode::Id => vec![
+                        ("enter", "select").into(),
+                        ("s", "sort").into(),
+                        ("/", "search").into(),
+                    ],
                     SelectionMode::Operation => vec![
-                        ("enter", "show"),
-                        ("c", "clear"),
-                        ("/", "search"),
-                        ("?", "help"),
+                        ("enter", "show").into(),
+                        ("c", "clear").into(),
+                        ("s", "sort").into(),
+                        ("/", "search").into(),
+                        Settings::current().keys.shortcut(Action::Help, "help"),
                     ],
+                };
+                if state.browser.grouped {
+                    shortcuts.insert(shortcuts.len() - 1, ("space", "expand").into());
                 }
+                shortcuts
             };
 
             ShortcutsProps::default()
-                .shortcuts(&shortcuts)
+                .shortcuts(shortcuts)
                 .to_boxed_any()
                 .into()
         });
@@ -338,10 +640,16 @@ fn browser_page(_state: &State, channel: &Channel<Message>) -> Widget<State, Mes
                 .and_then(|props| props.inner_ref::<PageProps>())
                 .unwrap_or(&default);
 
+            let keys = &Settings::current().keys;
             if props.handle_keys {
                 match key {
-                    Key::Esc | Key::Ctrl('c') => Some(Message::Exit { selection: None }),
-                    Key::Char('?') => Some(Message::OpenHelp),
+                    key if keys.matches(Action::Quit, key) => {
+                        Some(Message::Exit { selection: None })
+                    }
+                    key if keys.matches(Action::Help, key) => Some(Message::OpenHelp),
+                    Key::Char('s') => Some(Message::CycleSort),
+                    Key::Char('g') => Some(Message::ToggleGrouping),
+                    Key::Ctrl('r') => Some(Message::ResetSearch),
                     _ => None,
                 }
             } else {
@@ -403,7 +711,7 @@ fn help_page(_state: &State, channel: &Channel<Message>) -> Widget<State, Messag
 
     let shortcuts = Shortcuts::default().to_widget(tx.clone()).on_update(|_| {
         ShortcutsProps::default()
-            .shortcuts(&[("?", "close")])
+            .shortcuts([Settings::current().keys.shortcut(Action::Help, "close")])
             .to_boxed_any()
             .into()
     });
@@ -412,10 +720,13 @@ fn help_page(_state: &State, channel: &Channel<Message>) -> Widget<State, Messag
         .content(content)
         .shortcuts(shortcuts)
         .to_widget(tx.clone())
-        .on_event(|key, _, _| match key {
-            Key::Esc | Key::Ctrl('c') => Some(Message::Exit { selection: None }),
-            Key::Char('?') => Some(Message::LeavePage),
-            _ => None,
+        .on_event(|key, _, _| {
+            let keys = &Settings::current().keys;
+            match key {
+                key if keys.matches(Action::Quit, key) => Some(Message::Exit { selection: None }),
+                key if keys.matches(Action::Help, key) => Some(Message::LeavePage),
+                _ => None,
+            }
         })
         .on_update(|_| PageProps::default().handle_keys(true).to_boxed_any().into())
 }
@@ -436,12 +747,129 @@ fn help_text() -> String {
 `enter`:    Select notification (if --mode id)
 `enter`:    Show notification
 `c`:        Clear notifications
+`s`:        Cycle sorting (timestamp, project, id)
+`g`:        Group notifications of the same patch, issue or branch
+`space`:    Expand or collapse a group of notifications
 `/`:        Search
-`?`:        Show help
+`^R`:       Clear the search
+`:`:        Pick an operation from a palette
+`?,F1`:     Show help
 
 # Searching
 
-Pattern:    is:<state> | is:patch | is:issue | <search>
-Example:    is:unseen is:patch Print"#
+Pattern:    is:<state> | is:patch | is:issue | repo:<name> | <search>
+            since:<time> | until:<time>, e.g. since:7d or until:2024-01-01
+Example:    is:unseen is:patch repo:heartwood Print"#
         .into()
 }
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+
+    use pretty_assertions::assert_eq;
+
+    use radicle::cob::Timestamp;
+    use radicle::node::notifications::NotificationId;
+
+    use radicle_tui as tui;
+
+    use tui::ui::BufferedValue;
+
+    use crate::cob::inbox::SortBy;
+    use crate::test::fixtures::notification;
+    use crate::ui::items::{NotificationItem, NotificationItemFilter, NotificationKindItem};
+
+    use super::BrowserState;
+
+    fn browser(ids: &[NotificationId], selected: usize) -> BrowserState {
+        BrowserState {
+            items: ids.iter().copied().map(notification).collect(),
+            selected: Some(selected),
+            filter: NotificationItemFilter::default(),
+            search: BufferedValue::new(String::new()),
+            show_search: false,
+            updated: false,
+            grouped: false,
+            expanded: HashSet::new(),
+        }
+    }
+
+    fn selected_id(browser: &BrowserState) -> Option<NotificationId> {
+        browser
+            .selected
+            .map(|selected| browser.notifications()[selected].id)
+    }
+
+    #[test]
+    fn refresh_should_drop_notifications_of_deleted_cobs() {
+        // The COB of the selected notification was deleted: its neighbor below
+        // takes its place.
+        let mut state = browser(&[1, 2, 3, 4], 1);
+        state.refresh([1, 3, 4].map(notification).to_vec());
+        assert_eq!(state.notifications().len(), 3);
+        assert_eq!(selected_id(&state), Some(3));
+        assert!(!state.updated);
+
+        // The last one is gone, so the one above is selected.
+        let mut state = browser(&[1, 2, 3], 2);
+        state.refresh([1, 2].map(notification).to_vec());
+        assert_eq!(selected_id(&state), Some(2));
+
+        // Another one is gone, the selection stays where it is.
+        let mut state = browser(&[1, 2, 3], 2);
+        state.refresh([2, 3].map(notification).to_vec());
+        assert_eq!(selected_id(&state), Some(3));
+
+        // Nothing is left to select.
+        let mut state = browser(&[1], 0);
+        state.refresh(vec![]);
+        assert_eq!(state.notifications().len(), 0);
+        assert_eq!(state.selected, None);
+    }
+
+    #[test]
+    fn sorting_should_keep_the_selected_notification() {
+        let mut state = browser(&[1, 2, 3], 0);
+        for notif in &mut state.items {
+            notif.timestamp = Timestamp::from_secs(1709211909 + u64::from(notif.id));
+        }
+
+        state.sort(&SortBy::default());
+        assert_eq!(
+            state.items.iter().map(|notif| notif.id).collect::<Vec<_>>(),
+            [3, 2, 1]
+        );
+        assert_eq!(selected_id(&state), Some(1));
+
+        state.sort(&SortBy::with_field("id"));
+        assert_eq!(selected_id(&state), Some(1));
+        assert_eq!(state.selected, Some(0));
+    }
+
+    #[test]
+    fn collapsed_group_should_be_selected_as_a_whole() {
+        let branch = |id: NotificationId| NotificationItem {
+            kind: NotificationKindItem::Branch {
+                name: "feature".to_string(),
+                summary: String::new(),
+                status: String::new(),
+                id: None,
+            },
+            timestamp: Timestamp::from_secs(1709211909 + u64::from(id)),
+            ..notification(id)
+        };
+        let mut state = browser(&[1], 0);
+        state.items = vec![branch(2), notification(1), branch(3)];
+        state.grouped = true;
+
+        assert_eq!(state.notifications().len(), 2);
+        assert_eq!(state.selected_ids(), vec![2, 3]);
+
+        state.toggle_group(state.notifications()[0].group_key());
+        assert_eq!(state.selected_ids(), vec![3]);
+
+        state.selected = Some(2);
+        assert_eq!(state.selected_ids(), vec![1]);
+    }
+}
@@ -20,7 +20,7 @@ use tui::ui::rm::widget::list::{Table, TableProps};
 use tui::ui::rm::widget::{self, ViewProps};
 use tui::ui::rm::widget::{RenderProps, ToWidget, View};
 use tui::ui::span;
-use tui::ui::Column;
+use tui::ui::{Cells, Column};
 
 use tui::{BoxedAny, Selection};
 
@@ -31,24 +31,47 @@ use super::{Message, State};
 
 type Widget = widget::Widget<State, Message>;
 
+/// Returns the columns notifications are listed in. The project is only shown
+/// if notifications of all repositories are listed.
+pub fn columns<'a>(show_project: bool) -> Vec<Column<'a>> {
+    [
+        Column::new("", Constraint::Length(5)),
+        Column::new("", Constraint::Length(3)),
+        Column::new("", Constraint::Fill(5)),
+        Column::new("", Constraint::Fill(1)).skip(!show_project),
+        Column::new("", Constraint::Fill(1))
+            .hide_small()
+            .hide_medium(),
+        Column::new("", Constraint::Length(8)),
+        Column::new("", Constraint::Length(10)),
+        Column::new("", Constraint::Min(12)).hide_small(),
+        Column::new("", Constraint::Min(14)).hide_small(),
+    ]
+    .to_vec()
+}
+
 #[derive(Clone, Default)]
 pub struct BrowserProps<'a> {
     /// Application mode: openation and id or id only.
     mode: Mode,
-    /// Table title
-    header: String,
     /// Filtered notifications.
     notifications: Vec<NotificationItem>,
     /// Current (selected) table index
     selected: Option<usize>,
     /// Notification statistics.
     stats: HashMap<String, usize>,
+    /// Header columns, including the table title
+    header_columns: Vec<Column<'a>>,
     /// Table columns
     columns: Vec<Column<'a>>,
+    /// The index of the column notifications are sorted by, and if ascending.
+    active_sort: Option<(usize, bool)>,
     /// If search widget should be shown.
     show_search: bool,
     /// Current search string.
     search: String,
+    /// If notifications appeared since the selection was last changed.
+    updated: bool,
 }
 
 impl<'a> From<&State> for BrowserProps<'a> {
@@ -73,29 +96,39 @@ impl<'a> From<&State> for BrowserProps<'a> {
         }
         let stats = HashMap::from([("Seen".to_string(), seen), ("Unseen".to_string(), unseen)]);
 
+        let show_project = *state.mode.repository() == RepositoryMode::All;
+        let active_sort = match state.sort_by.field {
+            "id" => Some(0),
+            "project" => Some(3),
+            "timestamp" => Some(8),
+            _ => None,
+        }
+        .map(|column| (column, !state.sort_by.reverse));
+
         Self {
             mode: state.mode.clone(),
-            header,
             notifications,
             selected: state.browser.selected,
             stats,
-            columns: [
-                Column::new("", Constraint::Length(5)),
+            header_columns: [
+                Column::new("ID", Constraint::Length(5)),
                 Column::new("", Constraint::Length(3)),
-                Column::new("", Constraint::Fill(5)),
-                Column::new("", Constraint::Fill(1))
-                    .skip(*state.mode.repository() != RepositoryMode::All),
+                Column::new(Text::from(header), Constraint::Fill(5)),
+                Column::new("Project", Constraint::Fill(1)).skip(!show_project),
                 Column::new("", Constraint::Fill(1))
                     .hide_small()
                     .hide_medium(),
                 Column::new("", Constraint::Length(8)),
                 Column::new("", Constraint::Length(10)),
                 Column::new("", Constraint::Min(12)).hide_small(),
-                Column::new("", Constraint::Min(14)).hide_small(),
+                Column::new("Updated", Constraint::Min(14)).hide_small(),
             ]
             .to_vec(),
+            columns: columns(show_project),
+            active_sort,
             search: state.browser.search.read(),
             show_search: state.browser.show_search,
+            updated: state.browser.updated,
         }
     }
 }
@@ -115,18 +148,13 @@ impl Browser {
                     // TODO: remove and use state directly
                     let props = BrowserProps::from(state);
                     HeaderProps::default()
-                        .columns(
-                            [
-                                Column::new("", Constraint::Length(0)),
-                                Column::new(Text::from(props.header), Constraint::Fill(1)),
-                            ]
-                            .to_vec(),
-                        )
+                        .columns(props.header_columns)
+                        .active_sort(props.active_sort)
                         .to_boxed_any()
                         .into()
                 }))
                 .content(
-                    Table::<State, Message, NotificationItem, 9>::default()
+                    Table::<State, Message, NotificationItem, Cells<9>>::default()
                         .to_widget(tx.clone())
                         .on_event(|_, s, _| {
                             let (selected, _) =
@@ -184,6 +212,12 @@ impl View for Browser {
     type Message = Message;
     type State = State;
 
+    fn takes_text(&self, props: Option<&ViewProps>) -> bool {
+        props
+            .and_then(|props| props.inner_ref::<BrowserProps>())
+            .is_some_and(|props| props.show_search)
+    }
+
     fn handle_event(&mut self, props: Option<&ViewProps>, key: Key) -> Option<Self::Message> {
         let default = BrowserProps::default();
         let props = props
@@ -220,15 +254,14 @@ impl View for Browser {
                             selection: Some(selection),
                         }
                     }),
-                Key::Char('c') => props
+                // A collapsed group is cleared as a whole.
+                Key::Char('c') => Some(Message::Clear),
+                Key::Char(' ') => props
                     .selected
                     .and_then(|selected| props.notifications.get(selected))
-                    .map(|notif| Message::Exit {
-                        selection: Some(
-                            Selection::default()
-                                .with_operation(InboxOperation::Clear.to_string())
-                                .with_id(notif.id),
-                        ),
+                    .filter(|notif| notif.group.is_some())
+                    .map(|notif| Message::ToggleGroup {
+                        key: notif.group_key(),
                     }),
                 _ => {
                     self.notifications.handle_event(key);
@@ -291,7 +324,12 @@ fn browse_footer<'a>(props: &BrowserProps<'a>) -> Vec<Column<'a>> {
         span::default(&props.notifications.len().to_string()).dim(),
     ]);
 
-    match NotificationItemFilter::from_str(&props.search)
+    let updated = Line::from(vec![
+        span::default("● ").cyan().dim(),
+        span::default("updated").dim(),
+    ]);
+
+    let mut columns = match NotificationItemFilter::from_str(&props.search)
         .unwrap_or_default()
         .state()
     {
@@ -324,5 +362,17 @@ fn browse_footer<'a>(props: &BrowserProps<'a>) -> Vec<Column<'a>> {
             Column::new(Text::from(sum.clone()), Constraint::Min(sum.width() as u16)),
         ]
         .to_vec(),
+    };
+
+    if props.updated {
+        columns.insert(
+            1,
+            Column::new(
+                Text::from(updated.clone()),
+                Constraint::Min(updated.width() as u16),
+            ),
+        );
     }
+
+    columns
 }
@@ -4,11 +4,14 @@ mod common;
 mod select;
 
 use std::ffi::OsString;
+use std::path::PathBuf;
 
 use anyhow::anyhow;
 
 use lazy_static::lazy_static;
 
+use ratatui::Viewport;
+
 use radicle::identity::RepoId;
 use radicle::issue;
 
@@ -16,6 +19,11 @@ use radicle_cli::terminal;
 use radicle_cli::terminal::{Args, Error, Help};
 
 use crate::cob;
+use crate::commands;
+use crate::history::{self, Interface};
+use crate::output;
+use crate::ui::onboarding;
+use crate::ui::picker::AutoPick;
 use crate::ui::TerminalInfo;
 
 lazy_static! {
@@ -36,10 +44,32 @@ Usage
 Select options
 
     --mode <MODE>           Set selection mode; see MODE below (default: operation)
+    --filter <search>       Start with the given search instead of the one applied last
+    --inline <rows>         Render inline, using the given amount of rows (default: 20)
+    --fullscreen            Render in fullscreen
+    --watch                 Reload issues when they change in storage
+    --limit <n>             Load at most n issues at first; the next n are loaded with `L`
+    --restore <json>        Select the issue of a previous selection again
+    --fuzzy                 Narrow issues down in a fuzzy finder and select an issue id
+    --select-first          Select the first (newest) matching issue without showing
+                            the interface
+    --select-last           Select the last (oldest) matching issue without showing
+                            the interface
+    --preview               Show the issue preview on start (default)
+    --no-preview            Hide the issue preview on start
+    --focus <SECTION>       Focus the given section on start; see SECTION below (default: list)
+    --print                 Print the matching issues to stdout instead of showing the
+                            interface
+    --no-color              Print without colors, e.g. to pipe the output
+    --output-file <path>    Write the selection to the given file instead of stderr.
+                            Nothing is written if the interface is quit
 
     The MODE argument can be 'operation' or 'id'. 'operation' selects an issue id and
     an operation, whereas 'id' selects an issue id only.
 
+    The SECTION argument can be 'list', 'details' or 'comment'. Sections of the preview
+    can only be focused if the preview is shown.
+
 Other options
 
     --help               Print help
@@ -64,6 +94,18 @@ pub enum OperationName {
 pub struct SelectOptions {
     mode: common::Mode,
     filter: cob::issue::Filter,
+    search: Option<String>,
+    viewport: Option<Viewport>,
+    watch: bool,
+    limit: Option<usize>,
+    restore: Option<select::Selection>,
+    preview: Option<bool>,
+    section: Option<select::Section>,
+    fuzzy: bool,
+    pick: Option<AutoPick>,
+    print: bool,
+    no_color: bool,
+    output_file: Option<PathBuf>,
 }
 
 impl Args for Options {
@@ -118,6 +160,71 @@ impl Args for Options {
                         select_opts.filter = select_opts.filter.with_assgined(true);
                     }
                 }
+                Long("filter") if op == Some(OperationName::Select) => {
+                    let val = parser.value()?;
+                    select_opts.search = Some(terminal::args::string(&val));
+                }
+
+                Long(name @ ("inline" | "fullscreen")) if op == Some(OperationName::Select) => {
+                    // The name borrows the parser, which the value is read from.
+                    let name = name.to_owned();
+                    select_opts.viewport = Some(commands::viewport(&name, &mut parser)?);
+                }
+                Long("watch") if op == Some(OperationName::Select) => {
+                    select_opts.watch = true;
+                }
+                Long("limit") if op == Some(OperationName::Select) => {
+                    let val = parser.value()?;
+                    let limit = terminal::args::string(&val)
+                        .parse::<usize>()
+                        .ok()
+                        .filter(|limit| *limit > 0)
+                        .ok_or_else(|| anyhow!("invalid limit '{}'", val.to_string_lossy()))?;
+
+                    select_opts.limit = Some(limit);
+                }
+                Long("restore") if op == Some(OperationName::Select) => {
+                    let val = parser.value()?;
+                    let selection = serde_json::from_str(&terminal::args::string(&val))
+                        .map_err(|err| anyhow!("invalid selection to restore: {err}"))?;
+
+                    select_opts.restore = Some(selection);
+                }
+                Long("preview") if op == Some(OperationName::Select) => {
+                    select_opts.preview = Some(true);
+                }
+                Long("no-preview") if op == Some(OperationName::Select) => {
+                    select_opts.preview = Some(false);
+                }
+                Long("focus") if op == Some(OperationName::Select) => {
+                    let val = parser.value()?;
+                    let val = val.to_str().unwrap_or_default();
+
+                    select_opts.section = Some(match val {
+                        "list" => select::Section::Browser,
+                        "details" => select::Section::Details,
+                        "comment" => select::Section::Comment,
+                        unknown => anyhow::bail!("unknown section '{}'", unknown),
+                    });
+                }
+                Long("fuzzy") if op == Some(OperationName::Select) => {
+                    select_opts.fuzzy = true;
+                }
+                Long("select-first") if op == Some(OperationName::Select) => {
+                    select_opts.pick = Some(AutoPick::First);
+                }
+                Long("select-last") if op == Some(OperationName::Select) => {
+                    select_opts.pick = Some(AutoPick::Last);
+                }
+                Long("print") if op == Some(OperationName::Select) => {
+                    select_opts.print = true;
+                }
+                Long("no-color") if op == Some(OperationName::Select) => {
+                    select_opts.no_color = true;
+                }
+                Long("output-file") if op == Some(OperationName::Select) => {
+                    select_opts.output_file = Some(PathBuf::from(parser.value()?));
+                }
 
                 Long("repo") => {
                     let val = parser.value()?;
@@ -152,7 +259,9 @@ pub async fn run(options: Options, ctx: impl terminal::Context) -> anyhow::Resul
 
     match options.op {
         Operation::Select { opts } => {
-            let profile = ctx.profile()?;
+            let Some(profile) = onboarding::profile(&ctx, !opts.print).await? else {
+                return Ok(());
+            };
             let rid = options.repo.unwrap_or(rid);
             let repository = profile.storage.repository(rid).unwrap();
 
@@ -161,23 +270,41 @@ pub async fn run(options: Options, ctx: impl terminal::Context) -> anyhow::Resul
             }
             log::info!("Starting issue selection interface in project {}..", rid);
 
+            // The search applied last is restored if neither a search nor a filter is given.
+            let search = opts.search.clone().or_else(|| {
+                let interactive = !opts.print && opts.pick.is_none();
+                (interactive && opts.filter == cob::issue::Filter::default())
+                    .then(|| history::last_search(Some(&rid), Interface::Issues))
+                    .flatten()
+            });
+
             let context = select::Context {
                 profile,
                 repository,
                 mode: opts.mode,
                 filter: opts.filter.clone(),
+                search,
+                viewport: opts
+                    .viewport
+                    .unwrap_or(Viewport::Inline(radicle_tui::terminal::INLINE_HEIGHT)),
+                watch: opts.watch,
+                limit: opts.limit,
+                restore: opts.restore,
+                preview: opts.preview.unwrap_or(true),
+                section: opts.section.unwrap_or_default(),
+                fuzzy: opts.fuzzy,
+                pick: opts.pick,
             };
 
-            let output = select::App::new(context, terminal_info).run().await?;
-
-            let output = output
-                .map(|o| serde_json::to_string(&o).unwrap_or_default())
-                .unwrap_or_default();
+            let app = select::App::new(context, terminal_info);
+            if opts.print {
+                println!("{}", app.print(!opts.no_color)?);
+                return Ok(());
+            }
+            let selection = app.run().await?;
 
-            log::info!("About to print to `stderr`: {}", output);
+            output::selection(selection, opts.output_file.as_deref())?;
             log::info!("Exiting issue selection interface..");
-
-            eprint!("{output}");
         }
     }
 
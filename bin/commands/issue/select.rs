@@ -2,29 +2,42 @@
 mod ui;
 
 use std::collections::{HashMap, HashSet};
+use std::ops::Range;
 use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
 
 use anyhow::{bail, Result};
 
 use ratatui::Viewport;
 use termion::event::Key;
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
 
 use ratatui::layout::Constraint;
 use ratatui::style::Stylize;
-use ratatui::text::Text;
+use ratatui::text::{Line, Text};
 
 use radicle::cob::thread::CommentId;
 use radicle::git::Oid;
+use radicle::identity::RepoId;
 use radicle::issue::IssueId;
 use radicle::storage::git::Repository;
+use radicle::storage::{ReadRepository, ReadStorage};
 use radicle::Profile;
 
 use radicle_tui as tui;
 
 use tui::store;
+use tui::task::watch::Watch;
+use tui::task::{self, Process};
+use tui::terminal::External;
+use tui::ui::format::markdown;
+use tui::ui::keyhint::PrefixMap;
+use tui::ui::print;
 use tui::ui::rm::widget::container::{
-    Container, ContainerProps, Footer, FooterProps, Header, HeaderProps, SectionGroup,
-    SectionGroupProps, SplitContainer, SplitContainerFocus, SplitContainerProps,
+    Container, ContainerProps, Footer, FooterProps, Header, HeaderProps, Popup, PopupProps,
+    SectionGroup, SectionGroupProps, SplitContainer, SplitContainerFocus, SplitContainerProps,
+    SplitContainerState,
 };
 use tui::ui::rm::widget::input::{TextView, TextViewProps, TextViewState};
 use tui::ui::rm::widget::list::{Tree, TreeProps};
@@ -34,12 +47,15 @@ use tui::ui::rm::widget::window::{
 use tui::ui::rm::widget::{PredefinedLayout, ToWidget, Widget};
 use tui::ui::theme::Theme;
 use tui::ui::Column;
-use tui::ui::{span, BufferedValue};
+use tui::ui::{span, BufferedValue, Shortcut};
 use tui::{BoxedAny, Channel, Exit, PageStack};
 
 use crate::cob::issue;
-use crate::settings::{self, ThemeBundle, ThemeMode};
-use crate::ui::items::{CommentItem, IssueItem, IssueItemFilter};
+use crate::history::{self, Interface};
+use crate::settings::{Action, Settings};
+use crate::ui::format;
+use crate::ui::items::{AuthorItem, CommentItem, Filter, IssueItem, IssueItemFilter};
+use crate::ui::picker::{AutoPick, Picker, PickerItem};
 use crate::ui::rm::{BrowserState, IssueDetails, IssueDetailsProps};
 use crate::ui::TerminalInfo;
 
@@ -47,13 +63,42 @@ use self::ui::{Browser, BrowserProps};
 
 use super::common::{IssueOperation, Mode};
 
-type Selection = tui::Selection<IssueId>;
+pub type Selection = tui::Selection<IssueId>;
+
+/// Number of issues that are loaded and sent to the UI at once.
+const LOAD_BATCH_SIZE: usize = 50;
 
 pub struct Context {
     pub profile: Profile,
     pub repository: Repository,
     pub mode: Mode,
     pub filter: issue::Filter,
+    /// The search issues are filtered by on start, instead of the one of `filter`.
+    pub search: Option<String>,
+    pub viewport: Viewport,
+    pub watch: bool,
+    /// The number of issues loaded at first and on every request to load more.
+    /// All issues are loaded at once if not set.
+    pub limit: Option<usize>,
+    /// A previous selection whose issue should be selected again.
+    pub restore: Option<Selection>,
+    /// If the preview is shown on start.
+    pub preview: bool,
+    /// The section focused on start. Falls back to the browser if the
+    /// preview is hidden.
+    pub section: Section,
+    /// If the fuzzy picker is run instead of the browser.
+    pub fuzzy: bool,
+    /// The issue that is selected right away, without running the browser.
+    pub pick: Option<AutoPick>,
+}
+
+impl Context {
+    fn search(&self) -> String {
+        self.search
+            .clone()
+            .unwrap_or_else(|| self.filter.to_string())
+    }
 }
 
 pub struct App {
@@ -106,8 +151,19 @@ pub struct PreviewState {
     issue: Option<IssueItem>,
     /// Tree selection per issue.
     selected_comments: HashMap<IssueId, Vec<CommentId>>,
+    /// Folded comment paths per issue.
+    folded_comments: HashMap<IssueId, HashSet<Vec<String>>>,
+    /// If the fold prefix key (`z`) was pressed.
+    fold_pending: bool,
     /// State of currently selected comment
     comment: TextViewState,
+    /// Reading position in the selected comment per issue.
+    positions: HashMap<IssueId, (usize, usize)>,
+    /// If the authors of the selected comment's reactions are listed.
+    show_reactions: bool,
+    /// If the markdown of the selected comment is shown as it is, instead of
+    /// being rendered.
+    raw: bool,
 }
 
 impl PreviewState {
@@ -134,6 +190,22 @@ impl PreviewState {
         })
     }
 
+    /// The content to show in a pager: the issue title followed by the selected
+    /// comment, or by the issue description if no comment is selected.
+    pub fn pager_content(&self) -> Option<String> {
+        let issue = self.issue.as_ref()?;
+        let comment = self
+            .selected_comment()
+            .cloned()
+            .or_else(|| issue.root_comments().first().cloned());
+
+        Some(format!(
+            "{}\n\n{}",
+            issue.title,
+            comment.map(|comment| comment.body).unwrap_or_default()
+        ))
+    }
+
     pub fn selected_comment_ids(&self) -> Vec<String> {
         self.issue
             .as_ref()
@@ -143,6 +215,53 @@ impl PreviewState {
     }
 
     pub fn opened_comments(&self) -> HashSet<Vec<String>> {
+        let folded = self.folded_comments();
+        self.all_opened_comments()
+            .into_iter()
+            .filter(|path| !folded.contains(path))
+            .collect()
+    }
+
+    /// Toggles the fold of the subtree the selected comment belongs to. If the
+    /// selected comment has replies, its own subtree is toggled, otherwise the one
+    /// of its parent.
+    pub fn toggle_fold(&mut self) {
+        if let Some(target) = self.fold_target() {
+            if self.folded_comments().contains(&target) {
+                self.unfold(&target);
+            } else {
+                self.fold(target);
+            }
+        }
+    }
+
+    /// Folds the subtree the selected comment belongs to.
+    pub fn fold_selected(&mut self) {
+        if let Some(target) = self.fold_target() {
+            self.fold(target);
+        }
+    }
+
+    /// Folds all comment subtrees and selects the root of the current selection.
+    pub fn fold_all(&mut self) {
+        let all = self.all_opened_comments();
+        if let Some(item) = &self.issue {
+            self.folded_comments.insert(item.id, all);
+        }
+        let selected = self.selected_comment_ids();
+        if let Some(root) = selected.first() {
+            self.select_path(&[root.clone()]);
+        }
+    }
+
+    /// Unfolds all comment subtrees.
+    pub fn unfold_all(&mut self) {
+        if let Some(item) = &self.issue {
+            self.folded_comments.remove(&item.id);
+        }
+    }
+
+    fn all_opened_comments(&self) -> HashSet<Vec<String>> {
         let mut opened = HashSet::new();
         if let Some(item) = &self.issue {
             for comment in item.root_comments() {
@@ -151,6 +270,103 @@ impl PreviewState {
         }
         opened
     }
+
+    fn folded_comments(&self) -> HashSet<Vec<String>> {
+        self.issue
+            .as_ref()
+            .and_then(|item| self.folded_comments.get(&item.id))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    fn fold_target(&self) -> Option<Vec<String>> {
+        let selected = self.selected_comment_ids();
+        let has_replies = self
+            .selected_comment()
+            .map(|comment| !comment.replies.is_empty())
+            .unwrap_or_default();
+
+        if has_replies {
+            Some(selected)
+        } else if selected.len() > 1 {
+            Some(selected[..selected.len() - 1].to_vec())
+        } else {
+            None
+        }
+    }
+
+    fn fold(&mut self, path: Vec<String>) {
+        let selected = self.selected_comment_ids();
+
+        if let Some(item) = &self.issue {
+            self.folded_comments
+                .entry(item.id)
+                .or_default()
+                .insert(path.clone());
+        }
+
+        // Keep the selection visible by moving it to the folded ancestor.
+        if selected.len() > path.len() && selected.starts_with(&path) {
+            self.select_path(&path);
+        }
+    }
+
+    fn unfold(&mut self, path: &[String]) {
+        if let Some(item) = &self.issue {
+            if let Some(folded) = self.folded_comments.get_mut(&item.id) {
+                folded.remove(path);
+            }
+        }
+    }
+
+    /// Returns if the fold prefix was pressed right before the key the given
+    /// message was sent for, since fold commands are only valid then. Messages
+    /// of background tasks, e.g. the loader, don't cancel the prefix.
+    fn take_fold_pending(&mut self, message: &Message) -> bool {
+        if message.is_background() {
+            self.fold_pending
+        } else {
+            std::mem::take(&mut self.fold_pending)
+        }
+    }
+
+    /// Pre-selects the first comment of all issues given that don't have a
+    /// selection yet.
+    fn preselect_comments(&mut self, items: &[IssueItem]) {
+        for item in items {
+            self.selected_comments.entry(item.id).or_insert_with(|| {
+                item.root_comments()
+                    .first()
+                    .map(|comment| vec![comment.id])
+                    .unwrap_or_default()
+            });
+        }
+    }
+
+    /// Selects the issue given. The reading position in the comment of the
+    /// issue that was selected before is kept, and the one of the issue given
+    /// is restored, such that navigating away doesn't lose it.
+    fn select_issue(&mut self, issue: Option<IssueItem>) {
+        if let Some(item) = &self.issue {
+            self.positions.insert(item.id, self.comment.cursor);
+        }
+        self.comment.cursor = issue
+            .as_ref()
+            .and_then(|item| self.positions.get(&item.id).copied())
+            .unwrap_or_default();
+        self.issue = issue;
+    }
+
+    fn select_path(&mut self, path: &[String]) {
+        if let Some(item) = &self.issue {
+            let selection = path
+                .iter()
+                .filter_map(|id| Oid::from_str(id).ok())
+                .collect::<Vec<_>>();
+            self.selected_comments.insert(item.id, selection);
+            self.comment.reset_cursor();
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -163,10 +379,41 @@ pub struct State {
     mode: Mode,
     pages: PageStack<AppPage>,
     browser: BrowserState<IssueItem, IssueItemFilter>,
+    /// The search of the filter given on start, which the search can be reset to.
+    default_search: String,
     preview: PreviewState,
     section: Option<Section>,
+    /// If issues are listed in 5 instead of 10 columns.
+    compact: bool,
+    /// Split ratios of resizable containers per page.
+    splits: HashMap<AppPage, SplitContainerState>,
     help: HelpState,
     theme: Theme,
+    /// An external program requested to run, e.g. a pager.
+    external: Option<External>,
+    /// If issues are still being loaded.
+    loading: bool,
+    /// Current frame of the loading spinner.
+    spinner: usize,
+    /// The number of issues loaded so far and the number of all issues.
+    progress: (usize, usize),
+    /// The number of issues that are not loaded yet, because of the limit.
+    remaining: usize,
+    /// Requests the loader to load the next issues, see `Loader`.
+    requests: Option<UnboundedSender<()>>,
+    /// Profile used to write to the repository, e.g. to assign issues.
+    profile: Profile,
+    /// The repository issues are loaded from.
+    rid: RepoId,
+    /// Error of the last action, shown in a popup until dismissed.
+    error: Option<String>,
+    /// Issues of a previous selection that are selected again while loading, unless
+    /// the user selected another issue in the meantime.
+    restore: Vec<IssueId>,
+    /// Issues that were reloaded while batches were still loading, together
+    /// with the batches loaded since. They replace the listed issues once
+    /// loading finished.
+    pending_refresh: Option<Vec<IssueItem>>,
 }
 
 impl TryFrom<(&Context, &TerminalInfo)> for State {
@@ -174,62 +421,59 @@ impl TryFrom<(&Context, &TerminalInfo)> for State {
 
     fn try_from(value: (&Context, &TerminalInfo)) -> Result<Self, Self::Error> {
         let (context, terminal_info) = value;
-        let settings = settings::Settings::default();
+        let settings = Settings::current();
 
-        let issues = issue::all(&context.profile, &context.repository)?;
-        let search = BufferedValue::new(context.filter.to_string());
+        let search = BufferedValue::new(context.search());
         let filter = IssueItemFilter::from_str(&search.read()).unwrap_or_default();
 
-        let default_bundle = ThemeBundle::default();
-        let theme_bundle = settings.theme.active_bundle().unwrap_or(&default_bundle);
-        let theme = match settings.theme.mode() {
-            ThemeMode::Auto => {
-                if terminal_info.is_dark() {
-                    theme_bundle.dark.clone()
-                } else {
-                    theme_bundle.light.clone()
-                }
-            }
-            ThemeMode::Light => theme_bundle.light.clone(),
-            ThemeMode::Dark => theme_bundle.dark.clone(),
-        };
-
-        // Convert into UI items
-        let mut items = vec![];
-        for issue in issues {
-            if let Ok(item) = IssueItem::new(&context.profile, issue.clone()) {
-                items.push(item);
-            }
-        }
-        items.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
-
-        // Pre-select first comment
-        let mut selected_comments = HashMap::new();
-        for item in &items {
-            selected_comments.insert(
-                item.id,
-                item.root_comments()
-                    .first()
-                    .map(|comment| vec![comment.id])
-                    .unwrap_or_default(),
-            );
-        }
+        let theme = settings
+            .theme
+            .theme(terminal_info)
+            .with_density(settings.density);
 
+        // Issues are loaded asynchronously and added via `Message::ItemsLoaded`.
         Ok(Self {
             mode: context.mode.clone(),
             pages: PageStack::new(vec![AppPage::Browser]),
-            browser: BrowserState::build(items.clone(), filter, search),
+            browser: BrowserState::build(vec![], filter, search),
+            default_search: context.filter.to_string(),
             preview: PreviewState {
-                show: true,
-                issue: items.first().cloned(),
-                selected_comments,
+                show: context.preview,
+                issue: None,
+                selected_comments: HashMap::new(),
+                folded_comments: HashMap::new(),
+                fold_pending: false,
                 comment: TextViewState::default(),
+                positions: HashMap::new(),
+                show_reactions: false,
+                raw: false,
             },
-            section: Some(Section::Browser),
+            section: Some(if context.preview {
+                context.section.clone()
+            } else {
+                Section::Browser
+            }),
+            compact: false,
+            splits: HashMap::new(),
             help: HelpState {
                 text: TextViewState::default().content(help_text()),
             },
             theme,
+            external: None,
+            loading: true,
+            spinner: 0,
+            progress: (0, 0),
+            remaining: 0,
+            requests: None,
+            profile: context.profile.clone(),
+            rid: context.repository.id,
+            error: None,
+            restore: context
+                .restore
+                .as_ref()
+                .map(|selection| selection.ids.clone())
+                .unwrap_or_default(),
+            pending_refresh: None,
         })
     }
 }
@@ -237,26 +481,88 @@ impl TryFrom<(&Context, &TerminalInfo)> for State {
 #[derive(Clone, Debug)]
 pub enum Message {
     Quit,
-    Exit { operation: Option<IssueOperation> },
+    Exit {
+        operation: Option<IssueOperation>,
+    },
     ExitFromMode,
-    SelectIssue { selected: Option<usize> },
+    SelectIssue {
+        selected: Option<usize>,
+    },
     OpenSearch,
-    UpdateSearch { value: String },
+    UpdateSearch {
+        value: String,
+    },
     ApplySearch,
     CloseSearch,
+    ResetSearch,
+    ToggleAuthored,
+    CycleStateFilter,
     TogglePreview,
-    FocusSection { section: Option<Section> },
-    SelectComment { selected: Option<Vec<CommentId>> },
-    ScrollComment { state: TextViewState },
+    ToggleCompact,
+    ToggleDensity,
+    FocusSection {
+        section: Option<Section>,
+    },
+    SelectComment {
+        selected: Option<Vec<CommentId>>,
+    },
+    FoldPrefix,
+    ToggleFold,
+    Fold,
+    FoldAll,
+    UnfoldAll,
+    ScrollComment {
+        state: TextViewState,
+    },
+    ToggleReactions,
+    ToggleMarkdown,
+    ToggleAssignSelf,
+    ClosePopup,
+    ResizeSplit {
+        state: SplitContainerState,
+    },
+    OpenInPager,
     OpenHelp,
     LeavePage,
-    ScrollHelp { state: TextViewState },
+    PageBack,
+    PageForward,
+    ScrollHelp {
+        state: TextViewState,
+    },
+    ItemsLoaded {
+        batch: Vec<IssueItem>,
+        /// The number of issues read so far, including the batch.
+        loaded: usize,
+        /// The number of issues to be read.
+        total: usize,
+    },
+    LoadingFinished {
+        /// The number of issues that were not loaded, because of the limit.
+        remaining: usize,
+    },
+    LoadMore,
+    Refresh {
+        items: Vec<IssueItem>,
+    },
+}
+
+impl Message {
+    /// Returns `true` if the message is sent by a background task, e.g. the
+    /// loader or the watcher, rather than for a key that was pressed.
+    fn is_background(&self) -> bool {
+        matches!(
+            self,
+            Message::ItemsLoaded { .. } | Message::LoadingFinished { .. } | Message::Refresh { .. }
+        )
+    }
 }
 
 impl store::Update<Message> for State {
     type Return = Selection;
 
     fn update(&mut self, message: Message) -> Option<Exit<Selection>> {
+        let fold_pending = self.preview.take_fold_pending(&message);
+
         match message {
             Message::Quit => Some(Exit { value: None }),
             Message::Exit { operation } => self.browser.selected_item().map(|issue| Exit {
@@ -266,24 +572,14 @@ impl store::Update<Message> for State {
                     args: vec![],
                 }),
             }),
-            Message::ExitFromMode => {
-                let operation = match self.mode {
-                    Mode::Operation => Some(IssueOperation::Show.to_string()),
-                    Mode::Id => None,
-                };
-
-                self.browser.selected_item().map(|issue| Exit {
-                    value: Some(Selection {
-                        operation,
-                        ids: vec![issue.id],
-                        args: vec![],
-                    }),
-                })
-            }
+            Message::ExitFromMode => self.selection().map(|selection| Exit {
+                value: Some(selection),
+            }),
             Message::SelectIssue { selected } => {
+                self.restore.clear();
                 self.browser.select_item(selected);
-                self.preview.issue = self.browser.selected_item().cloned();
-                self.preview.comment.reset_cursor();
+                self.preview
+                    .select_issue(self.browser.selected_item().cloned());
                 None
             }
             Message::TogglePreview => {
@@ -291,6 +587,14 @@ impl store::Update<Message> for State {
                 self.section = Some(Section::Browser);
                 None
             }
+            Message::ToggleCompact => {
+                self.compact = !self.compact;
+                None
+            }
+            Message::ToggleDensity => {
+                self.theme.density = self.theme.density.toggle();
+                None
+            }
             Message::FocusSection { section } => {
                 self.section = section;
                 None
@@ -304,10 +608,72 @@ impl store::Update<Message> for State {
                 self.preview.comment.reset_cursor();
                 None
             }
+            Message::FoldPrefix => {
+                self.preview.fold_pending = true;
+                None
+            }
+            Message::ToggleFold => {
+                if fold_pending {
+                    self.preview.toggle_fold();
+                }
+                None
+            }
+            Message::Fold => {
+                if fold_pending {
+                    self.preview.fold_selected();
+                }
+                None
+            }
+            Message::FoldAll => {
+                if fold_pending {
+                    self.preview.fold_all();
+                }
+                None
+            }
+            Message::UnfoldAll => {
+                if fold_pending {
+                    self.preview.unfold_all();
+                }
+                None
+            }
             Message::ScrollComment { state } => {
                 self.preview.comment = state;
                 None
             }
+            Message::ToggleReactions => {
+                let has_reactions = self
+                    .preview
+                    .selected_comment()
+                    .map(|comment| !comment.reactions.is_empty())
+                    .unwrap_or_default();
+                self.preview.show_reactions = !self.preview.show_reactions && has_reactions;
+                None
+            }
+            Message::ToggleMarkdown => {
+                self.preview.raw = !self.preview.raw;
+                None
+            }
+            Message::ToggleAssignSelf => {
+                if let Err(err) = self.toggle_assign_self() {
+                    log::warn!("Failed to update assignees: {}", err);
+                    self.error = Some(format!("Could not update assignees: {err}"));
+                }
+                None
+            }
+            Message::ClosePopup => {
+                if self.error.is_some() {
+                    self.error = None;
+                } else {
+                    self.preview.show_reactions = false;
+                }
+                None
+            }
+            Message::ResizeSplit { state } => {
+                if let Some(page) = self.pages.peek() {
+                    self.splits.insert(page.clone(), state);
+                }
+                None
+            }
             Message::OpenSearch => {
                 self.browser.show_search();
                 None
@@ -320,6 +686,7 @@ impl store::Update<Message> for State {
             Message::ApplySearch => {
                 self.browser.hide_search();
                 self.browser.apply_search();
+                self.remember_search();
                 None
             }
             Message::CloseSearch => {
@@ -330,20 +697,219 @@ impl store::Update<Message> for State {
                 self.preview.comment.reset_cursor();
                 None
             }
+            Message::ResetSearch => {
+                self.browser.update_search(self.default_search.clone());
+                self.browser.apply_search();
+                self.remember_search();
+
+                self.preview.issue = self.browser.select_first_item().cloned();
+                self.preview.comment.reset_cursor();
+                None
+            }
+            Message::ToggleAuthored => {
+                self.browser.toggle_search_word("is:authored");
+                self.remember_search();
+
+                self.preview.issue = self.browser.select_first_item().cloned();
+                self.preview.comment.reset_cursor();
+                None
+            }
+            Message::CycleStateFilter => {
+                self.browser.cycle_search_state(&IssueItemFilter::STATES);
+                self.remember_search();
+
+                self.preview.issue = self.browser.select_first_item().cloned();
+                self.preview.comment.reset_cursor();
+                None
+            }
+            Message::OpenInPager => {
+                self.external = self.preview.pager_content().map(External::Pager);
+                None
+            }
             Message::OpenHelp => {
                 self.pages.push(AppPage::Help);
                 None
             }
-            Message::LeavePage => {
-                self.pages.pop();
+            Message::LeavePage | Message::PageBack => {
+                self.pages.back();
+                None
+            }
+            Message::PageForward => {
+                self.pages.forward();
                 None
             }
             Message::ScrollHelp { state } => {
                 self.help.text = state;
                 None
             }
+            Message::ItemsLoaded {
+                batch,
+                loaded,
+                total,
+            } => {
+                self.progress = (loaded, total);
+                if let Some(pending) = &mut self.pending_refresh {
+                    pending.retain(|item| !batch.iter().any(|loaded| loaded.id == item.id));
+                    pending.extend(batch.iter().cloned());
+                }
+                self.preview.preselect_comments(&batch);
+
+                // The preview is only updated if the selected issue changed, such
+                // that it isn't reset while the user reads it.
+                let selected = self.browser.selected_item().map(|issue| issue.id);
+                self.browser.extend_sorted(
+                    batch,
+                    |a, b| b.timestamp.cmp(&a.timestamp),
+                    |issue| issue.id,
+                );
+                self.restore_selection();
+                if self.browser.selected_item().map(|issue| issue.id) != selected {
+                    self.preview
+                        .select_issue(self.browser.selected_item().cloned());
+                }
+                None
+            }
+            Message::LoadingFinished { remaining } => {
+                self.loading = false;
+                self.remaining = remaining;
+                self.restore.clear();
+                if let Some(items) = self.pending_refresh.take() {
+                    self.replace_issues(items);
+                }
+                None
+            }
+            Message::LoadMore => {
+                if let Some(requests) = self.requests.as_ref().filter(|_| self.can_load_more()) {
+                    self.loading = requests.send(()).is_ok();
+                }
+                None
+            }
+            Message::Refresh { items } => {
+                // Batches that are still loading would be added twice, so the
+                // reloaded issues are applied once loading finished.
+                if self.loading {
+                    self.pending_refresh = Some(items);
+                } else {
+                    self.replace_issues(items);
+                }
+                None
+            }
+        }
+    }
+
+    fn tick(&mut self) {
+        if self.loading {
+            self.spinner = self.spinner.wrapping_add(1);
         }
     }
+
+    fn take_external(&mut self) -> Option<External> {
+        self.external.take()
+    }
+
+    fn update_batch(&mut self, messages: Vec<Message>) -> Option<Exit<Selection>> {
+        store::update_pages(self, messages)
+    }
+}
+
+impl store::Pages for State {
+    type Page = AppPage;
+
+    fn page(&self) -> Option<AppPage> {
+        self.pages.peek().ok().cloned()
+    }
+
+    fn on_exit_page(&mut self, page: &AppPage) {
+        if *page == AppPage::Help {
+            self.help.text = TextViewState::default().content(help_text());
+        }
+    }
+}
+
+impl State {
+    /// Returns the selection that is returned if the selected issue is chosen
+    /// with `enter`, depending on the mode.
+    pub fn selection(&self) -> Option<Selection> {
+        let operation = match self.mode {
+            Mode::Operation => Some(IssueOperation::Show.to_string()),
+            Mode::Id => None,
+        };
+
+        self.browser.selected_item().map(|issue| Selection {
+            operation,
+            ids: vec![issue.id],
+            args: vec![],
+        })
+    }
+
+    /// Returns the `rad` command the selection of the selected issue is
+    /// forwarded to. Only operations are forwarded, plain ids are not.
+    pub fn command_preview(&self) -> Option<String> {
+        self.selection()
+            .filter(|selection| selection.operation.is_some())
+            .map(|selection| selection.to_command_preview("rad issue"))
+    }
+
+    /// Remembers the applied search, such that the next session starts with it.
+    fn remember_search(&self) {
+        history::save_search(
+            Some(&self.rid),
+            Interface::Issues,
+            &self.browser.read_search(),
+        );
+    }
+
+    /// Selects the first issue of a previous selection that was loaded already.
+    /// Ids that are not loaded (yet) are ignored, the selection is kept then.
+    fn restore_selection(&mut self) {
+        let restore = &self.restore;
+        if !restore.is_empty() {
+            self.browser
+                .select_item_by(|issue| restore.contains(&issue.id));
+        }
+    }
+
+    /// Replaces the listed issues with the reloaded ones given. The selection
+    /// is kept if the selected issue is still listed.
+    fn replace_issues(&mut self, mut items: Vec<IssueItem>) {
+        items.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+        self.preview.preselect_comments(&items);
+        self.browser.replace_items(items, |item| item.id);
+        self.preview.issue = self.browser.selected_item().cloned();
+    }
+
+    /// Assigns the selected issue to the local user, or unassigns them. The
+    /// selection is kept valid, since the issue might not match the filter
+    /// (e.g. `is:assigned`) anymore afterwards.
+    fn toggle_assign_self(&mut self) -> Result<()> {
+        let Some(id) = self.browser.selected_item().map(|item| item.id) else {
+            return Ok(());
+        };
+        let repository = self.profile.storage.repository(self.rid)?;
+        let signer = self.profile.signer()?;
+        let did = self.profile.did();
+
+        let assigned = issue::toggle_assignee(&self.profile, &repository, &signer, &id, did)?;
+
+        if let Some(item) = self.browser.selected_item_mut() {
+            item.assign(AuthorItem::new(Some(*did), &self.profile), assigned);
+        }
+        self.browser.clamp_selection();
+        self.preview.issue = self.browser.selected_item().cloned();
+
+        Ok(())
+    }
+
+    /// Returns true if issues were left out because of the limit and none are
+    /// being loaded.
+    fn can_load_more(&self) -> bool {
+        !self.loading && self.remaining > 0
+    }
+
+    /// Returns true if a popup is shown on top of the browser page.
+    fn is_popup_shown(&self) -> bool {
+        self.preview.show_reactions || self.error.is_some()
+    }
 }
 
 impl App {
@@ -354,11 +920,60 @@ impl App {
         }
     }
 
+    /// Renders all issues that match the filter in the columns of the issue
+    /// list as plain text, instead of running the interface.
+    pub fn print(&self, color: bool) -> Result<String> {
+        let settings = Settings::current();
+        let issues = load_filtered_issues(&self.context)?
+            .into_iter()
+            .map(|item| item.with_settings(settings))
+            .collect::<Vec<_>>();
+
+        Ok(print::table(
+            &issues,
+            &ui::columns(false),
+            print::terminal_width(),
+            color,
+        ))
+    }
+
     pub async fn run(&self) -> Result<Option<Selection>> {
+        if let Some(pick) = self.context.pick {
+            let operation = match self.context.mode {
+                Mode::Operation => Some(IssueOperation::Show.to_string()),
+                Mode::Id => None,
+            };
+            let issue = pick.pick(load_filtered_issues(&self.context)?);
+
+            return Ok(issue.map(|issue| Selection {
+                operation,
+                ids: vec![issue.id],
+                args: vec![],
+            }));
+        }
+        if self.context.fuzzy {
+            return pick(&self.context).await;
+        }
+
         let channel = Channel::default();
-        let state = State::try_from((&self.context, &self.terminal_info))?;
+        let mut state = State::try_from((&self.context, &self.terminal_info))?;
         let tx = channel.tx.clone();
 
+        let (requests_tx, requests_rx) = mpsc::unbounded_channel();
+        let loader = Loader {
+            profile: self.context.profile.clone(),
+            rid: self.context.repository.id,
+            limit: self.context.limit,
+            cursor: Arc::new(AtomicUsize::new(0)),
+            requests: requests_rx,
+        };
+        state.requests = Some(requests_tx);
+
+        if self.context.watch {
+            task::spawn(watch(&self.context, loader.cursor.clone()), tx.clone());
+        }
+        task::spawn(loader, tx.clone());
+
         let window = Window::default()
             .page(AppPage::Browser, browser_page(&channel))
             .page(AppPage::Help, help_page(&channel))
@@ -370,31 +985,211 @@ impl App {
                     .into()
             });
 
-        tui::rm(state, window, Viewport::Inline(20), channel).await
+        tui::rm(state, window, self.context.viewport.clone(), channel).await
+    }
+}
+
+/// A `Process` that loads the issues of a repository in pages of at most `limit`
+/// issues, or all at once without a limit. The first page is loaded right away,
+/// every other one once it's requested, see `Message::LoadMore`.
+struct Loader {
+    profile: Profile,
+    rid: RepoId,
+    limit: Option<usize>,
+    /// The number of issues read so far. The watcher only reloads these.
+    cursor: Arc<AtomicUsize>,
+    requests: UnboundedReceiver<()>,
+}
+
+impl Process<Message> for Loader {
+    async fn run(mut self, tx: UnboundedSender<Message>) -> anyhow::Result<()> {
+        loop {
+            let (profile, rid, loader_tx) = (self.profile.clone(), self.rid, tx.clone());
+            let start = self.cursor.load(Ordering::Relaxed);
+            let end = self
+                .limit
+                .map(|limit| start.saturating_add(limit))
+                .unwrap_or(usize::MAX);
+
+            let loaded = tokio::task::spawn_blocking(move || {
+                load_issues(&profile, rid, start..end, &loader_tx)
+            })
+            .await?;
+            let remaining = match loaded {
+                Ok(total) => {
+                    self.cursor.store(end.min(total), Ordering::Relaxed);
+                    total.saturating_sub(end)
+                }
+                Err(err) => {
+                    log::warn!("Failed to load issues: {}", err);
+                    0
+                }
+            };
+            if tx.send(Message::LoadingFinished { remaining }).is_err() {
+                return Ok(());
+            }
+
+            if self.requests.recv().await.is_none() {
+                return Ok(());
+            }
+        }
     }
 }
 
+/// Loads the issues of the given repository in the given range and sends them in
+/// batches. Returns the number of all issues.
+fn load_issues(
+    profile: &Profile,
+    rid: RepoId,
+    range: Range<usize>,
+    tx: &UnboundedSender<Message>,
+) -> Result<usize> {
+    let repository = profile.storage.repository(rid)?;
+    let settings = Settings::current();
+
+    let mut loaded = 0;
+    issue::batched(
+        profile,
+        &repository,
+        range,
+        LOAD_BATCH_SIZE,
+        |batch, total| {
+            loaded += batch.len();
+            let batch = batch
+                .into_iter()
+                .filter_map(|issue| IssueItem::new(profile, issue).ok())
+                .map(|item| item.with_settings(settings))
+                .collect();
+            let _ = tx.send(Message::ItemsLoaded {
+                batch,
+                loaded,
+                total,
+            });
+        },
+    )
+}
+
+/// Loads all issues that match the filter given, newest first. With a limit,
+/// only the newest issues that match are returned.
+fn load_filtered_issues(context: &Context) -> Result<Vec<IssueItem>> {
+    let filter = IssueItemFilter::from_str(&context.search()).unwrap_or_default();
+    let prefix = &Settings::current().milestone_prefix;
+    let mut issues = issue::all(&context.profile, &context.repository)?
+        .into_iter()
+        .filter_map(|issue| IssueItem::new(&context.profile, issue).ok())
+        .map(|issue| issue.with_milestone_prefix(prefix))
+        .filter(|issue| filter.matches(issue))
+        .collect::<Vec<_>>();
+    issues.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+    issues.truncate(context.limit.unwrap_or(usize::MAX));
+
+    Ok(issues)
+}
+
+/// Runs the fuzzy picker on all issues that match the filter given and returns
+/// the id of the issue picked.
+async fn pick(context: &Context) -> Result<Option<Selection>> {
+    let items = load_filtered_issues(context)?
+        .into_iter()
+        .map(|issue| {
+            PickerItem::new(
+                issue.id,
+                format!("{} {}", format::cob(&issue.id), issue.title),
+            )
+        })
+        .collect();
+
+    tui::im(Picker::new(items), Viewport::Fullscreen, Channel::default()).await
+}
+
+/// Builds a process that reloads all issues whenever the refs of the repository
+/// change. If issues are loaded with a limit, only the ones read so far by the
+/// loader are reloaded, up to its `cursor`.
+fn watch(context: &Context, cursor: Arc<AtomicUsize>) -> impl Process<Message> {
+    let profile = context.profile.clone();
+    let rid = context.repository.id;
+    let limited = context.limit.is_some();
+
+    Watch::new(move || {
+        let count = limited.then(|| cursor.load(Ordering::Relaxed));
+        match load_all_issues(&profile, rid, count) {
+            Ok(items) => Some(Message::Refresh { items }),
+            Err(err) => {
+                log::warn!("Failed to reload issues: {}", err);
+                None
+            }
+        }
+    })
+    .path(context.repository.path().join("refs"))
+}
+
+/// Loads all issues of the given repository at once, or the given number of
+/// issues, in the order they are cached.
+fn load_all_issues(profile: &Profile, rid: RepoId, count: Option<usize>) -> Result<Vec<IssueItem>> {
+    let repository = profile.storage.repository(rid)?;
+    let settings = Settings::current();
+
+    Ok(issue::all(profile, &repository)?
+        .into_iter()
+        .take(count.unwrap_or(usize::MAX))
+        .filter_map(|issue| IssueItem::new(profile, issue).ok())
+        .map(|item| item.with_settings(settings))
+        .collect())
+}
+
 fn browser_page(channel: &Channel<Message>) -> Widget<State, Message> {
     let tx = channel.tx.clone();
 
     let shortcuts = Shortcuts::default()
         .to_widget(tx.clone())
         .on_update(|state: &State| {
-            let shortcuts = if state.browser.is_search_shown() {
-                vec![("esc", "cancel"), ("enter", "apply")]
+            let keys = &Settings::current().keys;
+            let shortcuts: Vec<Shortcut> = if state.browser.is_search_shown() {
+                vec![("esc", "cancel").into(), ("enter", "apply").into()]
+            } else if state.is_popup_shown() {
+                vec![("esc", "close").into()]
             } else {
                 let mut shortcuts = match state.mode {
-                    Mode::Id => vec![("enter", "select")],
-                    Mode::Operation => vec![("enter", "show"), ("e", "edit")],
+                    Mode::Id => vec![("enter", "select").into()],
+                    Mode::Operation => vec![
+                        ("enter", "show").into(),
+                        keys.shortcut(Action::Edit, "edit"),
+                    ],
                 };
                 if state.section == Some(Section::Browser) {
-                    shortcuts = [shortcuts, [("/", "search")].to_vec()].concat()
+                    shortcuts.extend(
+                        [
+                            ("/", "search"),
+                            ("m", "mine"),
+                            ("s", "state"),
+                            ("a", "assign me"),
+                            ("v", "compact"),
+                        ]
+                        .map(Shortcut::from),
+                    );
+                    if state.can_load_more() {
+                        shortcuts.push(("L", "load more").into());
+                    }
+                }
+                if state.section == Some(Section::Details) {
+                    shortcuts.extend([("za", "fold"), ("+/-", "resize")].map(Shortcut::from));
                 }
-                [shortcuts, [("p", "toggle preview"), ("?", "help")].to_vec()].concat()
+                if state.section == Some(Section::Details)
+                    || state.section == Some(Section::Comment)
+                {
+                    shortcuts.push(("r", "reactions").into());
+                    let markdown = if state.preview.raw { "rendered" } else { "raw" };
+                    shortcuts.push(("t", markdown).into());
+                }
+                shortcuts.extend([
+                    keys.shortcut(Action::Preview, "toggle preview"),
+                    keys.shortcut(Action::Help, "help"),
+                ]);
+                shortcuts
             };
 
             ShortcutsProps::default()
-                .shortcuts(&shortcuts)
+                .shortcuts(shortcuts)
                 .shortcuts_keys_style(state.theme.shortcuts_keys_style)
                 .shortcuts_action_style(state.theme.shortcuts_action_style)
                 .to_boxed_any()
@@ -417,18 +1212,18 @@ fn browser_page(channel: &Channel<Message>) -> Widget<State, Message> {
                         }),
                     })
                 })
-                .on_update(|state: &State| {
+                .bind(|state: &State| {
                     SectionGroupProps::default()
                         .handle_keys(state.preview.show && !state.browser.is_search_shown())
                         .layout(PredefinedLayout::Expandable3 {
                             left_only: !state.preview.show,
                         })
                         .focus(state.section.as_ref().map(|s| s.clone().into()))
-                        .to_boxed_any()
-                        .into()
                 }),
         )
+        .overlay(popup(channel))
         .shortcuts(shortcuts)
+        .prefixes(prefixes())
         .to_widget(tx.clone())
         .on_event(|key, _, props| {
             let default = PageProps::default();
@@ -436,24 +1231,92 @@ fn browser_page(channel: &Channel<Message>) -> Widget<State, Message> {
                 .and_then(|props| props.inner_ref::<PageProps>())
                 .unwrap_or(&default);
 
+            let keys = &Settings::current().keys;
             if props.handle_keys {
                 match key {
-                    Key::Esc | Key::Ctrl('c') => Some(Message::Quit),
-                    Key::Char('p') => Some(Message::TogglePreview),
-                    Key::Char('?') => Some(Message::OpenHelp),
-                    Key::Char('\n') => Some(Message::ExitFromMode),
-                    Key::Char('e') => Some(Message::Exit {
+                    key if keys.matches(Action::Quit, key) => Some(Message::Quit),
+                    key if keys.matches(Action::Help, key) => Some(Message::OpenHelp),
+                    key if keys.matches(Action::Preview, key) => Some(Message::TogglePreview),
+                    key if keys.matches(Action::Edit, key) => Some(Message::Exit {
                         operation: Some(IssueOperation::Edit),
                     }),
+                    Key::Char('v') => Some(Message::ToggleCompact),
+                    Key::Char('D') => Some(Message::ToggleDensity),
+                    Key::Char('r') => Some(Message::ToggleReactions),
+                    Key::Char('t') => Some(Message::ToggleMarkdown),
+                    Key::Char('m') => Some(Message::ToggleAuthored),
+                    Key::Char('s') => Some(Message::CycleStateFilter),
+                    Key::Ctrl('r') => Some(Message::ResetSearch),
+                    Key::Char('L') => Some(Message::LoadMore),
+                    Key::Char('o') => Some(Message::OpenInPager),
+                    Key::AltLeft => Some(Message::PageBack),
+                    Key::AltRight => Some(Message::PageForward),
+                    Key::Char('\n') => Some(Message::ExitFromMode),
                     _ => None,
                 }
             } else {
                 None
             }
         })
-        .on_update(|state: &State| {
+        .bind(|state: &State| {
             PageProps::default()
-                .handle_keys(!state.browser.is_search_shown())
+                .handle_keys(!state.browser.is_search_shown() && !state.is_popup_shown())
+                .show_overlay(state.is_popup_shown())
+                .theme(state.theme.clone())
+        })
+}
+
+/// Prefix keys of the browser page and the keys that can follow them.
+fn prefixes() -> PrefixMap {
+    PrefixMap::default().prefix(
+        Key::Char('z'),
+        [
+            ("a", "toggle fold"),
+            ("c", "fold"),
+            ("M", "fold all"),
+            ("R", "unfold all"),
+        ],
+    )
+}
+
+/// A popup that shows the error of the last action if there is one, and who
+/// reacted to the selected comment otherwise.
+fn popup(channel: &Channel<Message>) -> Widget<State, Message> {
+    let tx = channel.tx.clone();
+
+    Popup::default()
+        .to_widget(tx.clone())
+        .on_event(|key, _, _| match key {
+            Key::Ctrl('c') => Some(Message::Quit),
+            Key::Esc | Key::Char('r') | Key::Char('\n') => Some(Message::ClosePopup),
+            _ => None,
+        })
+        .on_update(|state: &State| {
+            if let Some(error) = &state.error {
+                return PopupProps::default()
+                    .title("Error")
+                    .lines(vec![Line::from(error.clone())])
+                    .border_style(state.theme.focus_border_style)
+                    .to_boxed_any()
+                    .into();
+            }
+
+            let lines = state
+                .preview
+                .selected_comment()
+                .map(|comment| {
+                    comment
+                        .reactions
+                        .iter()
+                        .map(|reaction| reaction.to_line())
+                        .collect()
+                })
+                .unwrap_or_default();
+
+            PopupProps::default()
+                .title("Reactions")
+                .lines(lines)
+                .border_style(state.theme.focus_border_style)
                 .to_boxed_any()
                 .into()
         })
@@ -474,14 +1337,24 @@ fn issue(channel: &Channel<Message>) -> Widget<State, Message> {
         .top(issue_details(channel))
         .bottom(comment_tree(channel))
         .to_widget(tx.clone())
-        .on_update(|state| {
+        .on_event(|key, vs, _| match key {
+            Key::Char('+') | Key::Char('-') => vs
+                .and_then(|vs| vs.unwrap_split_container())
+                .map(|state| Message::ResizeSplit { state }),
+            _ => None,
+        })
+        .bind(|state: &State| {
+            let split = state
+                .splits
+                .get(&AppPage::Browser)
+                .cloned()
+                .unwrap_or(SplitContainerState::new(30));
+
             SplitContainerProps::default()
-                .heights([Constraint::Length(5), Constraint::Min(1)])
+                .split(split)
                 .border_style(state.theme.border_style)
                 .focus_border_style(state.theme.focus_border_style)
                 .split_focus(SplitContainerFocus::Bottom)
-                .to_boxed_any()
-                .into()
         })
 }
 
@@ -490,12 +1363,10 @@ fn issue_details(channel: &Channel<Message>) -> Widget<State, Message> {
 
     IssueDetails::default()
         .to_widget(tx.clone())
-        .on_update(|state: &State| {
+        .bind(|state: &State| {
             IssueDetailsProps::default()
                 .issue(state.preview.issue.clone())
                 .dim(state.theme.dim_no_focus)
-                .to_boxed_any()
-                .into()
         })
 }
 
@@ -504,13 +1375,23 @@ fn comment_tree(channel: &Channel<Message>) -> Widget<State, Message> {
 
     Tree::<State, Message, CommentItem, String>::default()
         .to_widget(tx.clone())
-        .on_event(|_, s, _| {
-            Some(Message::SelectComment {
+        .on_event(|key, s, _| match key {
+            Key::Char('z') => Some(Message::FoldPrefix),
+            Key::Char('a') => Some(Message::ToggleFold),
+            Key::Char('c') => Some(Message::Fold),
+            Key::Char('M') => Some(Message::FoldAll),
+            Key::Char('R') => Some(Message::UnfoldAll),
+            _ => Some(Message::SelectComment {
                 selected: s.and_then(|s| {
                     s.unwrap_tree()
-                        .map(|tree| tree.iter().map(|id| Oid::from_str(id).unwrap()).collect())
+                        // Summaries of capped replies are not comments.
+                        .map(|tree| {
+                            tree.iter()
+                                .filter_map(|id| Oid::from_str(id).ok())
+                                .collect()
+                        })
                 }),
-            })
+            }),
         })
         .on_update(|state| {
             let root = &state.preview.root_comments();
@@ -559,8 +1440,11 @@ fn comment(channel: &Channel<Message>) -> Widget<State, Message> {
                         })
                         .unwrap_or_default();
 
+                    let text = (!state.preview.raw).then(|| markdown(&body));
+
                     TextViewProps::default()
                         .state(Some(state.preview.comment.clone().content(body)))
+                        .text(text)
                         .footer(Some(reactions))
                         .show_scroll_progress(true)
                         .dim(state.theme.dim_no_focus)
@@ -634,7 +1518,7 @@ fn help_page(channel: &Channel<Message>) -> Widget<State, Message> {
 
     let shortcuts = Shortcuts::default().to_widget(tx.clone()).on_update(|_| {
         ShortcutsProps::default()
-            .shortcuts(&[("?", "close")])
+            .shortcuts([Settings::current().keys.shortcut(Action::Help, "close")])
             .to_boxed_any()
             .into()
     });
@@ -643,10 +1527,15 @@ fn help_page(channel: &Channel<Message>) -> Widget<State, Message> {
         .content(content)
         .shortcuts(shortcuts)
         .to_widget(tx.clone())
-        .on_event(|key, _, _| match key {
-            Key::Esc | Key::Ctrl('c') => Some(Message::Quit),
-            Key::Char('?') => Some(Message::LeavePage),
-            _ => None,
+        .on_event(|key, _, _| {
+            let keys = &Settings::current().keys;
+            match key {
+                key if keys.matches(Action::Quit, key) => Some(Message::Quit),
+                key if keys.matches(Action::Help, key) => Some(Message::LeavePage),
+                Key::AltLeft => Some(Message::PageBack),
+                Key::AltRight => Some(Message::PageForward),
+                _ => None,
+            }
         })
         .on_update(|_| PageProps::default().handle_keys(true).to_boxed_any().into())
 }
@@ -662,6 +1551,8 @@ fn help_text() -> String {
 `End`:      move cursor to the last line
 `Tab`:      focus next section
 `BackTab`:  focus previous section
+`M-←`:      Go back to the previous page
+`M-→`:      Go forward to the page gone back from
 `Esc`:      Quit / cancel
 
 # Specific keybindings
@@ -670,24 +1561,184 @@ fn help_text() -> String {
 `Enter`:    Show issue
 `e`:        Edit issue
 `p`:        Toggle issue preview
+`v`:        Toggle compact issue list
+`D`:        Toggle compact column spacing
+`o`:        Open issue or selected comment in `$PAGER`
+`r`:        Show who reacted to the selected comment
+`t`:        Toggle between rendered and raw markdown of the selected comment
+`a`:        Assign the selected issue to you, or unassign you
+`za`:       Toggle fold of comment thread
+`zc`:       Fold comment thread
+`zM`:       Fold all comment threads
+`zR`:       Unfold all comment threads
+`+`, `-`:   Grow / shrink issue details
 `/`:        Search
-`?`:        Show help
+`m`:        Toggle showing only issues authored by you
+`s`:        Cycle the state filter: open, closed, solved, all
+`^R`:       Reset the search to the filter given on start
+`L`:        Load more issues (if --limit is given)
+`:`:        Pick an operation from a palette
+`?,F1`:     Show help
 
 # Searching
 
 Pattern:    is:<state> | is:authored | is:assigned | authors:[<did>, ...] | assignees:[<did>, ...] | <search>
-Example:    is:solved is:authored alias"#
+            state=<state> | author=<did> | assignee=<did> | <key>=(<value> or <value> ...) | <key>=(<value> and <value> ...)
+            since:<time> | until:<time>, e.g. since:7d or until:2024-01-01
+            milestone:<name>, for issues labeled with the milestone prefix, e.g. milestone:v1.0
+Example:    is:solved is:authored alias
+Example:    state=(open or solved) assignee=(<did> and <did>)"#
         .into()
 }
 
+/// Adds the paths of the given comment and all its replies to `all`. Walks the
+/// tree iteratively, since comment threads can be arbitrarily deep.
 fn append_opened(all: &mut HashSet<Vec<String>>, path: Vec<String>, comment: CommentItem) {
-    all.insert([path.clone(), [comment.id.to_string()].to_vec()].concat());
+    let mut pending = vec![(path, comment)];
+
+    while let Some((path, comment)) = pending.pop() {
+        let path = [path, [comment.id.to_string()].to_vec()].concat();
+
+        for reply in comment.replies {
+            pending.push((path.clone(), reply));
+        }
+        all.insert(path);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::{HashMap, HashSet};
+    use std::str::FromStr;
+
+    use pretty_assertions::assert_eq;
+
+    use radicle::cob::Timestamp;
+    use radicle::git::Oid;
+
+    use crate::settings::{MaxWidths, TimestampFormat};
+    use crate::test::fixtures;
+    use crate::ui::items::{CommentItem, IssueItem};
+
+    use super::{Message, PreviewState, TextViewState};
+
+    fn id(index: usize) -> Oid {
+        Oid::from_str(&format!("{index:040x}")).unwrap()
+    }
+
+    fn comment(index: usize, reply_to: Option<usize>, replies: Vec<CommentItem>) -> CommentItem {
+        CommentItem {
+            id: id(index),
+            author: fixtures::author(),
+            body: String::new(),
+            reactions: vec![],
+            timestamp: Timestamp::from_secs(1709211909),
+            timestamp_format: TimestampFormat::default(),
+            reply_to: reply_to.map(id),
+            replies,
+        }
+    }
+
+    /// Returns the preview of an issue whose description `1` has the replies
+    /// `2` and `4`, where `2` has the reply `3`, with `3` selected.
+    fn preview() -> PreviewState {
+        let three = comment(3, Some(2), vec![]);
+        let two = comment(2, Some(1), vec![three.clone()]);
+        let four = comment(4, Some(1), vec![]);
+        let one = comment(1, None, vec![two.clone(), four.clone()]);
+
+        let issue = IssueItem {
+            id: id(100),
+            state: radicle::issue::State::Open,
+            title: String::from("Fold comments"),
+            author: one.author.clone(),
+            labels: vec![],
+            milestone: None,
+            assignees: vec![],
+            timestamp: one.timestamp,
+            timestamp_format: TimestampFormat::default(),
+            max_widths: MaxWidths::default(),
+            compact: false,
+            comments: vec![one, two, three, four],
+        };
+
+        PreviewState {
+            show: true,
+            selected_comments: HashMap::from([(issue.id, vec![id(1), id(2), id(3)])]),
+            issue: Some(issue),
+            folded_comments: HashMap::new(),
+            fold_pending: false,
+            comment: TextViewState::default(),
+            positions: HashMap::new(),
+            show_reactions: false,
+            raw: false,
+        }
+    }
+
+    fn path(indices: &[usize]) -> Vec<String> {
+        indices.iter().map(|index| id(*index).to_string()).collect()
+    }
+
+    #[test]
+    fn folding_a_reply_should_select_the_folded_ancestor() {
+        let mut preview = preview();
+
+        // `3` has no replies, so the subtree of its parent is folded.
+        preview.toggle_fold();
+
+        assert_eq!(preview.folded_comments(), HashSet::from([path(&[1, 2])]));
+        assert_eq!(preview.selected_comment_ids(), path(&[1, 2]));
+        assert!(!preview.opened_comments().contains(&path(&[1, 2])));
+
+        // `2` has replies, so its own subtree is unfolded again.
+        preview.toggle_fold();
+
+        assert!(preview.folded_comments().is_empty());
+        assert_eq!(preview.selected_comment_ids(), path(&[1, 2]));
+    }
+
+    #[test]
+    fn folding_all_should_select_the_root() {
+        let mut preview = preview();
 
-    for reply in comment.replies {
-        append_opened(
-            all,
-            [path.clone(), [comment.id.to_string()].to_vec()].concat(),
-            reply,
+        preview.fold_all();
+
+        assert_eq!(
+            preview.folded_comments(),
+            HashSet::from([path(&[1]), path(&[1, 2]), path(&[1, 2, 3]), path(&[1, 4])])
         );
+        assert_eq!(preview.selected_comment_ids(), path(&[1]));
+        assert!(preview.opened_comments().is_empty());
+
+        preview.unfold_all();
+
+        assert!(preview.folded_comments().is_empty());
+        assert_eq!(preview.opened_comments().len(), 4);
+    }
+
+    #[test]
+    fn fold_prefix_should_survive_background_messages() {
+        let mut preview = preview();
+        preview.fold_pending = true;
+
+        for message in [
+            Message::ItemsLoaded {
+                batch: vec![],
+                loaded: 1,
+                total: 2,
+            },
+            Message::LoadingFinished { remaining: 0 },
+            Message::Refresh { items: vec![] },
+        ] {
+            preview.take_fold_pending(&message);
+        }
+
+        assert!(preview.take_fold_pending(&Message::ToggleFold));
+        assert!(!preview.take_fold_pending(&Message::ToggleFold));
+
+        // Any other key cancels the prefix.
+        preview.fold_pending = true;
+        preview.take_fold_pending(&Message::ToggleReactions);
+        assert!(!preview.take_fold_pending(&Message::Fold));
     }
 }
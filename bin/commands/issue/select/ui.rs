@@ -23,16 +23,47 @@ use tui::ui::rm::widget::list::{Table, TableProps};
 use tui::ui::rm::widget::ViewProps;
 use tui::ui::rm::widget::{RenderProps, ToWidget, View};
 use tui::ui::span;
-use tui::ui::Column;
+use tui::ui::theme::Charset;
+use tui::ui::{Column, DynCells};
 
 use tui::BoxedAny;
 
-use crate::ui::items::{IssueItem, IssueItemFilter};
+use crate::ui;
+use crate::ui::items::{Filter, IssueItem, IssueItemFilter, ICON_WIDTH};
 
 use super::{Message, State};
 
 type Widget = widget::Widget<State, Message>;
 
+/// Returns the columns issues are listed in. The compact layout only keeps the
+/// first 5 columns, which is what compact issue rows are built of.
+pub fn columns<'a>(compact: bool) -> Vec<Column<'a>> {
+    let columns = [
+        Column::new("", Constraint::Length(ICON_WIDTH as u16)),
+        Column::new(" ● ", Constraint::Length(3)),
+        Column::new("ID", Constraint::Length(8)),
+        Column::new("Title", Constraint::Fill(5)),
+        Column::new("Author", Constraint::Length(16)).hide_small(),
+        Column::new("", Constraint::Length(16)).hide_medium(),
+        Column::new("Labels", Constraint::Fill(1)).hide_medium(),
+        Column::new("Milestone", Constraint::Length(12)).hide_medium(),
+        Column::new("Assignees", Constraint::Fill(1)).hide_medium(),
+        Column::new("Opened", Constraint::Length(16)).hide_small(),
+    ];
+
+    if compact {
+        columns[..5].to_vec()
+    } else {
+        columns.to_vec()
+    }
+}
+
+/// Frames of the spinner shown while issues are being loaded.
+const SPINNER: [&str; 8] = ["⣾", "⣽", "⣻", "⢿", "⡿", "⣟", "⣯", "⣷"];
+
+/// The width of the bar that shows how many issues are loaded already.
+const PROGRESS_WIDTH: usize = 12;
+
 #[derive(Clone, Default)]
 pub struct BrowserProps<'a> {
     /// Filtered issues.
@@ -47,6 +78,16 @@ pub struct BrowserProps<'a> {
     show_search: bool,
     /// Current search string.
     search: String,
+    /// If issues are still being loaded.
+    loading: bool,
+    /// Current frame of the loading spinner.
+    spinner: usize,
+    /// The number of issues loaded so far and the number of all issues.
+    progress: (usize, usize),
+    /// The number of issues that are not loaded yet, because of the limit.
+    remaining: usize,
+    /// The command the selected issue would be forwarded to.
+    command: Option<String>,
 }
 
 impl<'a> From<&State> for BrowserProps<'a> {
@@ -80,33 +121,44 @@ impl<'a> From<&State> for BrowserProps<'a> {
             ("Closed".to_string(), closed),
         ]);
 
+        let filter = state.browser.filter();
+        let chips = if filter.is_default() {
+            vec![]
+        } else {
+            filter.describe()
+        };
+
+        // The compact layout only keeps the first 5 columns, which is what
+        // compact issue rows are built of.
+        let detailed = if state.compact { 5 } else { 10 };
+
         Self {
             issues,
             stats,
             header: [
+                Column::new("", Constraint::Length(ICON_WIDTH as u16)),
                 Column::new(" ● ", Constraint::Length(3)),
                 Column::new("ID", Constraint::Length(8)),
-                Column::new("Title", Constraint::Fill(5)),
-                Column::new("Author", Constraint::Length(16)).hide_small(),
-                Column::new("", Constraint::Length(16)).hide_medium(),
-                Column::new("Labels", Constraint::Fill(1)).hide_medium(),
-                Column::new("Assignees", Constraint::Fill(1)).hide_medium(),
-                Column::new("Opened", Constraint::Length(16)).hide_small(),
-            ]
-            .to_vec(),
-            columns: [
-                Column::new(" ● ", Constraint::Length(3)),
-                Column::new("ID", Constraint::Length(8)),
-                Column::new("Title", Constraint::Fill(5)),
+                Column::new(
+                    ui::span::with_filter_chips("Title", &chips),
+                    Constraint::Fill(5),
+                ),
                 Column::new("Author", Constraint::Length(16)).hide_small(),
                 Column::new("", Constraint::Length(16)).hide_medium(),
                 Column::new("Labels", Constraint::Fill(1)).hide_medium(),
+                Column::new("Milestone", Constraint::Length(12)).hide_medium(),
                 Column::new("Assignees", Constraint::Fill(1)).hide_medium(),
                 Column::new("Opened", Constraint::Length(16)).hide_small(),
-            ]
-            .to_vec(),
+            ][..detailed]
+                .to_vec(),
+            columns: columns(state.compact),
             search: state.browser.read_search(),
             show_search: state.browser.is_search_shown(),
+            loading: state.loading,
+            spinner: state.spinner,
+            progress: state.progress,
+            remaining: state.remaining,
+            command: state.command_preview(),
         }
     }
 }
@@ -130,11 +182,12 @@ impl Browser {
                         .columns(props.header.clone())
                         .border_style(state.theme.border_style)
                         .focus_border_style(state.theme.focus_border_style)
+                        .density(state.theme.density)
                         .to_boxed_any()
                         .into()
                 }))
                 .content(
-                    Table::<State, Message, IssueItem, 8>::default()
+                    Table::<State, Message, IssueItem, DynCells>::default()
                         .to_widget(tx.clone())
                         .on_event(|_, s, _| {
                             let (selected, _) =
@@ -148,9 +201,20 @@ impl Browser {
 
                             TableProps::default()
                                 .columns(props.columns)
-                                .items(state.browser.items())
+                                .items(
+                                    state
+                                        .browser
+                                        .items()
+                                        .into_iter()
+                                        .map(|issue| issue.with_compact(state.compact))
+                                        .collect(),
+                                )
                                 .selected(state.browser.selected())
                                 .dim(state.theme.dim_no_focus)
+                                .density(state.theme.density)
+                                .highlight(
+                                    state.browser.highlight(state.theme.search_highlight_style),
+                                )
                                 .to_boxed_any()
                                 .into()
                         }),
@@ -197,6 +261,12 @@ impl View for Browser {
     type Message = Message;
     type State = State;
 
+    fn takes_text(&self, props: Option<&ViewProps>) -> bool {
+        props
+            .and_then(|props| props.inner_ref::<BrowserProps>())
+            .is_some_and(|props| props.show_search)
+    }
+
     fn handle_event(&mut self, props: Option<&ViewProps>, key: Key) -> Option<Self::Message> {
         let default = BrowserProps::default();
         let props = props
@@ -218,6 +288,7 @@ impl View for Browser {
         } else {
             match key {
                 Key::Char('/') => Some(Message::OpenSearch),
+                Key::Char('a') => Some(Message::ToggleAssignSelf),
                 _ => {
                     self.issues.handle_event(key);
                     None
@@ -262,6 +333,11 @@ fn browse_footer<'a>(props: &BrowserProps<'a>) -> Vec<Column<'a>> {
         span::default(" "),
         span::default(&props.search).gray().dim(),
     ]);
+    let command = props
+        .command
+        .as_ref()
+        .map(|command| Line::from(span::default(command).gray().dim()))
+        .unwrap_or_default();
 
     let open = Line::from(vec![
         span::positive(&props.stats.get("Open").unwrap_or(&0).to_string()).dim(),
@@ -279,10 +355,33 @@ fn browse_footer<'a>(props: &BrowserProps<'a>) -> Vec<Column<'a>> {
             .dim(),
         span::default(" Closed").dim(),
     ]);
-    let sum = Line::from(vec![
-        span::default("Σ ").dim(),
-        span::default(&props.issues.len().to_string()).dim(),
-    ]);
+    let sum = if props.loading {
+        let frame = SPINNER[props.spinner % SPINNER.len()];
+        let (loaded, total) = props.progress;
+        let filled = if total > 0 {
+            (loaded * PROGRESS_WIDTH / total).min(PROGRESS_WIDTH)
+        } else {
+            0
+        };
+        let bar = Charset::current().progress();
+        Line::from(vec![
+            span::default(&format!("{frame} ")).cyan().dim(),
+            span::default(&bar.repeat(filled)).cyan(),
+            span::default(&bar.repeat(PROGRESS_WIDTH - filled)).dim(),
+            span::default(" "),
+            span::step(loaded, total, false),
+        ])
+    } else {
+        let mut sum = Line::from(vec![
+            span::default("Σ ").dim(),
+            span::default(&props.issues.len().to_string()).dim(),
+        ]);
+        // Issues that are not loaded yet are not searched either.
+        if props.remaining > 0 {
+            sum.push_span(span::default(&format!(" ∙ {} not loaded", props.remaining)).dim());
+        }
+        sum
+    };
 
     match IssueItemFilter::from_str(&props.search)
         .unwrap_or_default()
@@ -301,6 +400,10 @@ fn browse_footer<'a>(props: &BrowserProps<'a>) -> Vec<Column<'a>> {
 
             [
                 Column::new(Text::from(search), Constraint::Fill(1)),
+                Column::new(
+                    Text::from(command.clone()),
+                    Constraint::Min(command.width() as u16),
+                ),
                 Column::new(
                     Text::from(block.clone()),
                     Constraint::Min(block.width() as u16),
@@ -311,6 +414,10 @@ fn browse_footer<'a>(props: &BrowserProps<'a>) -> Vec<Column<'a>> {
         }
         None => [
             Column::new(Text::from(search), Constraint::Fill(1)),
+            Column::new(
+                Text::from(command.clone()),
+                Constraint::Min(command.width() as u16),
+            ),
             Column::new(
                 Text::from(open.clone()),
                 Constraint::Min(open.width() as u16),
@@ -324,3 +431,28 @@ fn browse_footer<'a>(props: &BrowserProps<'a>) -> Vec<Column<'a>> {
         .to_vec(),
     }
 }
+
+#[cfg(test)]
+mod test {
+    use termion::event::Key;
+    use tokio::sync::mpsc;
+
+    use radicle_tui as tui;
+
+    use tui::ui::rm::widget::ToWidget;
+
+    use super::super::Message;
+    use super::Browser;
+
+    #[test]
+    fn paste_should_be_dropped_if_search_is_closed() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let mut browser = Browser::new(tx.clone()).to_widget(tx);
+
+        browser.handle_paste("abc");
+        assert!(rx.try_recv().is_err());
+
+        browser.handle_event(Key::Char('a'));
+        assert!(matches!(rx.try_recv(), Ok(Message::ToggleAssignSelf)));
+    }
+}
@@ -6,9 +6,12 @@ mod review;
 mod select;
 
 use std::ffi::OsString;
+use std::path::PathBuf;
 
 use anyhow::anyhow;
 
+use ratatui::Viewport;
+
 use radicle::cob::ObjectId;
 use radicle::identity::RepoId;
 use radicle::patch::{Patch, Revision, RevisionId, Status};
@@ -20,6 +23,10 @@ use radicle_cli::terminal::args::{string, Args, Error, Help};
 
 use crate::cob::patch;
 use crate::cob::patch::Filter;
+use crate::commands;
+use crate::output;
+use crate::ui::onboarding;
+use crate::ui::picker::AutoPick;
 
 pub const HELP: Help = Help {
     name: "patch",
@@ -33,6 +40,7 @@ Usage
 Select options
 
     --mode <MODE>           Set selection mode; see MODE below (default: operation)
+    --filter <search>       Start with the given search instead of the one applied last
     --all                   Show all patches, including merged and archived patches
     --archived              Show only archived patches
     --merged                Show only merged patches
@@ -41,6 +49,20 @@ Select options
     --authored              Show only patches that you have authored
     --author <did>          Show only patched where the given user is an author
                             (may be specified multiple times)
+    --inline <rows>         Render inline, using the given amount of rows (default: 20)
+    --fullscreen            Render in fullscreen
+    --watch                 Reload patches when they change in storage
+    --restore <json>        Select the patch of a previous selection again
+    --fuzzy                 Narrow patches down in a fuzzy finder and select a patch id
+    --select-first          Select the first (newest) matching patch without showing
+                            the interface
+    --select-last           Select the last (oldest) matching patch without showing
+                            the interface
+    --print                 Print the matching patches to stdout instead of showing the
+                            interface
+    --no-color              Print without colors, e.g. to pipe the output
+    --output-file <path>    Write the selection to the given file instead of stderr.
+                            Nothing is written if the interface is quit
 
     The MODE argument can be 'operation' or 'id'. 'operation' selects a patch id and
     an operation, whereas 'id' selects a patch id only.
@@ -72,6 +94,15 @@ pub enum OperationName {
 pub struct SelectOptions {
     mode: common::Mode,
     filter: patch::Filter,
+    search: Option<String>,
+    viewport: Option<Viewport>,
+    watch: bool,
+    restore: Option<select::Selection>,
+    fuzzy: bool,
+    pick: Option<AutoPick>,
+    print: bool,
+    no_color: bool,
+    output_file: Option<PathBuf>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -156,6 +187,43 @@ impl Args for Options {
                         .filter
                         .with_author(terminal::args::did(&parser.value()?)?);
                 }
+                Long("filter") if op == Some(OperationName::Select) => {
+                    let val = parser.value()?;
+                    select_opts.search = Some(terminal::args::string(&val));
+                }
+                Long(name @ ("inline" | "fullscreen")) if op == Some(OperationName::Select) => {
+                    // The name borrows the parser, which the value is read from.
+                    let name = name.to_owned();
+                    select_opts.viewport = Some(commands::viewport(&name, &mut parser)?);
+                }
+                Long("watch") if op == Some(OperationName::Select) => {
+                    select_opts.watch = true;
+                }
+                Long("restore") if op == Some(OperationName::Select) => {
+                    let val = parser.value()?;
+                    let selection = serde_json::from_str(&string(&val))
+                        .map_err(|err| anyhow!("invalid selection to restore: {err}"))?;
+
+                    select_opts.restore = Some(selection);
+                }
+                Long("fuzzy") if op == Some(OperationName::Select) => {
+                    select_opts.fuzzy = true;
+                }
+                Long("select-first") if op == Some(OperationName::Select) => {
+                    select_opts.pick = Some(AutoPick::First);
+                }
+                Long("select-last") if op == Some(OperationName::Select) => {
+                    select_opts.pick = Some(AutoPick::Last);
+                }
+                Long("print") if op == Some(OperationName::Select) => {
+                    select_opts.print = true;
+                }
+                Long("no-color") if op == Some(OperationName::Select) => {
+                    select_opts.no_color = true;
+                }
+                Long("output-file") if op == Some(OperationName::Select) => {
+                    select_opts.output_file = Some(PathBuf::from(parser.value()?));
+                }
                 Long("repo") => {
                     let val = parser.value()?;
                     let rid = terminal::args::rid(&val)?;
@@ -211,24 +279,53 @@ pub async fn run(options: Options, ctx: impl terminal::Context) -> anyhow::Resul
 
     match options.op {
         Operation::Select { opts } => {
-            let profile = ctx.profile()?;
+            let Some(profile) = onboarding::profile(&ctx, !opts.print).await? else {
+                return Ok(());
+            };
             let rid = options.repo.unwrap_or(rid);
 
-            // Run TUI with patch selection interface
-            let selection = interface::select(opts, profile, rid).await?;
-            let selection = selection
-                .map(|o| serde_json::to_string(&o).unwrap_or_default())
-                .unwrap_or_default();
+            if opts.print {
+                println!("{}", interface::print(opts, profile, rid)?);
+                return Ok(());
+            }
 
-            log::info!("About to print to `stderr`: {}", selection);
-            log::info!("Exiting patch selection interface..");
+            // Run TUI with patch selection interface. Reviews are run right away,
+            // after which the selection interface is shown again, with the
+            // reviewed patch selected.
+            let mut opts = opts;
+            let selection = loop {
+                let selection = interface::select(opts.clone(), profile.clone(), rid).await?;
+
+                match selection {
+                    Some(selection)
+                        if selection.operation
+                            == Some(common::PatchOperation::Review.to_string()) =>
+                    {
+                        let patch_id = *selection
+                            .ids
+                            .first()
+                            .ok_or_else(|| anyhow!("a patch must be selected"))?;
+                        let review_opts = ReviewOptions {
+                            patch_id: None,
+                            revision_id: None,
+                        };
+
+                        interface::review(review_opts, profile.clone(), rid, patch_id).await?;
+                        opts.restore = Some(selection);
+                    }
+                    selection => break selection,
+                }
+            };
 
-            eprint!("{selection}");
+            output::selection(selection, opts.output_file.as_deref())?;
+            log::info!("Exiting patch selection interface..");
         }
         Operation::Review { ref opts } => {
             log::info!("Starting patch review interface in project {rid}..");
 
-            let profile = ctx.profile()?;
+            let Some(profile) = onboarding::profile(&ctx, true).await? else {
+                return Ok(());
+            };
             let rid = options.repo.unwrap_or(rid);
             let repo = profile.storage.repository(rid).unwrap();
 
@@ -261,9 +358,12 @@ mod interface {
 
     use radicle_cli::terminal;
 
+    use ratatui::Viewport;
+
     use radicle_tui::Selection;
 
     use crate::cob::patch;
+    use crate::history::{self, Interface};
     use crate::tui_patch::review::builder::CommentBuilder;
     use crate::tui_patch::review::ReviewAction;
     use crate::tui_patch::select;
@@ -277,18 +377,45 @@ mod interface {
         profile: Profile,
         rid: RepoId,
     ) -> anyhow::Result<Option<Selection<ObjectId>>> {
+        log::info!("Starting patch selection interface in project {}..", rid);
+
+        select::App::new(context(opts, profile, rid), true)
+            .run()
+            .await
+    }
+
+    /// Renders the patches the selection interface would list as plain text.
+    pub fn print(opts: SelectOptions, profile: Profile, rid: RepoId) -> anyhow::Result<String> {
+        let color = !opts.no_color;
+
+        select::App::new(context(opts, profile, rid), true).print(color)
+    }
+
+    fn context(opts: SelectOptions, profile: Profile, rid: RepoId) -> select::Context {
         let repository = profile.storage.repository(rid).unwrap();
 
-        log::info!("Starting patch selection interface in project {}..", rid);
+        // The search applied last is restored if neither a search nor a filter is given.
+        let search = opts.search.clone().or_else(|| {
+            let interactive = !opts.print && opts.pick.is_none();
+            (interactive && opts.filter == patch::Filter::default())
+                .then(|| history::last_search(Some(&rid), Interface::Patches))
+                .flatten()
+        });
 
-        let context = select::Context {
+        select::Context {
             profile,
             repository,
             mode: opts.mode,
             filter: opts.filter.clone(),
-        };
-
-        select::App::new(context, true).run().await
+            search,
+            viewport: opts
+                .viewport
+                .unwrap_or(Viewport::Inline(radicle_tui::terminal::INLINE_HEIGHT)),
+            watch: opts.watch,
+            restore: opts.restore,
+            fuzzy: opts.fuzzy,
+            pick: opts.pick,
+        }
     }
 
     pub async fn review(
@@ -371,11 +498,17 @@ mod interface {
                             .ok_or_else(|| anyhow!("expected a hunk to comment on"))?;
 
                         let (old, new) = item.paths();
-                        let path = old.or(new);
-
-                        if let (Some(hunk), Some((path, _))) = (item.hunk(), path) {
-                            let builder = CommentBuilder::new(revision.head(), path.to_path_buf());
-                            let comments = builder.edit(hunk)?;
+                        let path = selection
+                            .path
+                            .clone()
+                            .or(old.or(new).map(|(path, _)| path.to_path_buf()));
+
+                        if let (Some(hunk), Some(path)) = (item.hunk(), path) {
+                            let builder = CommentBuilder::new(revision.head(), path);
+                            let comments = match selection.line {
+                                Some(line) => builder.edit_line(hunk, line)?,
+                                None => builder.edit(hunk)?,
+                            };
 
                             let signer = profile.signer()?;
                             patch.transaction("Review comments", &signer, |tx| {
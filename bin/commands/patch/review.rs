@@ -2,7 +2,9 @@
 pub mod builder;
 
 use std::fmt::Debug;
-use std::path::PathBuf;
+use std::fs;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::sync::Mutex;
 
@@ -10,12 +12,19 @@ use anyhow::Result;
 
 use termion::event::Key;
 
-use ratatui::layout::{Constraint, Position};
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+
+use ratatui::layout::{Constraint, Layout, Position, Rect};
 use ratatui::style::Stylize;
-use ratatui::text::Text;
+use ratatui::text::{Line, Text};
+use ratatui::widgets::Clear;
 use ratatui::{Frame, Viewport};
 
+use serde::{Deserialize, Serialize};
+
+use radicle::cob::Timestamp;
 use radicle::crypto::Signer;
+use radicle::git::Oid;
 use radicle::identity::RepoId;
 use radicle::patch::{PatchId, Review, Revision};
 use radicle::storage::git::Repository;
@@ -25,14 +34,18 @@ use radicle::Storage;
 use radicle_tui as tui;
 
 use tui::store;
-use tui::ui::im::widget::{PanesState, TableState, TextViewState, Window};
-use tui::ui::im::{Borders, Context, Show, Ui};
+use tui::task::{self, Process};
+use tui::ui::diff::{self, DiffLayout, DiffView, LineNumbering, LineNumbers};
+use tui::ui::im::widget::{PanesState, TableState, TextEditState, TextViewState, Window};
+use tui::ui::im::{Borders, Context, Show, ToastLevel, Ui};
+use tui::ui::keyhint::PrefixMap;
 use tui::ui::span;
 use tui::ui::Column;
 use tui::{Channel, Exit};
 
-use crate::git::HunkDiff;
+use crate::git::{self, BlameLine, HunkDiff};
 use crate::git::{HunkState, StatefulHunkDiff};
+use crate::settings;
 use crate::ui::format;
 use crate::ui::items::HunkItem;
 use crate::ui::layout;
@@ -41,6 +54,9 @@ use super::review::builder::DiffUtil;
 
 use self::builder::{Brain, FileReviewBuilder, Hunks};
 
+/// The width of the acceptance gauge in the context bar, not counting its label.
+const CONTEXT_BAR_GAUGE_WIDTH: u16 = 20;
+
 /// The actions that a user can carry out on a review item.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum ReviewAction {
@@ -54,6 +70,10 @@ pub struct Args(String);
 pub struct Selection {
     pub action: ReviewAction,
     pub hunk: Option<usize>,
+    /// The file of the hunk selected.
+    pub path: Option<PathBuf>,
+    /// The line the diff cursor was on, if any. Comments are anchored to it.
+    pub line: Option<LineNumbers>,
     pub args: Option<Args>,
 }
 
@@ -96,7 +116,13 @@ impl Tui {
         let viewport = Viewport::Fullscreen;
 
         let channel = Channel::default();
-        let state = App::new(
+        let (blames_tx, blames_rx) = mpsc::unbounded_channel();
+        let blamer = Blamer {
+            storage: self.storage.clone(),
+            rid: self.rid,
+            requests: blames_rx,
+        };
+        let mut state = App::new(
             self.storage,
             self.rid,
             self.signer,
@@ -106,6 +132,8 @@ impl Tui {
             self.review,
             self.hunks,
         )?;
+        state.blames = Some(blames_tx);
+        task::spawn(blamer, channel.tx.clone());
 
         tui::im(state, viewport, channel).await
     }
@@ -117,12 +145,30 @@ pub enum Message {
     PanesChanged { state: PanesState },
     HunkChanged { state: TableState },
     HunkViewChanged { state: DiffViewState },
+    ToggleDiffLayout,
+    ToggleLineNumbering,
+    ToggleWrap,
+    ExpandContext,
+    CollapseContext,
+    NextComment,
+    PreviousComment,
     ShowHelp,
     HelpChanged { state: TextViewState },
     Comment,
     Accept,
     Discard,
+    ExportAccepted { path: PathBuf },
+    OpenJump,
+    JumpChanged { state: TextEditState },
+    Jump,
+    CloseJump,
+    Blame,
+    BlameLoaded { blame: HunkBlame },
+    BlameFailed { error: String },
+    BlameChanged { state: TextViewState },
+    CloseBlame,
     Quit,
+    CancelQuit,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
@@ -134,6 +180,117 @@ pub enum AppPage {
 #[derive(Clone, Debug)]
 pub struct DiffViewState {
     cursor: Position,
+    /// The number of unchanged lines shown around changes.
+    context: usize,
+}
+
+impl Default for DiffViewState {
+    fn default() -> Self {
+        Self {
+            cursor: Position::new(0, 0),
+            context: diff::DEFAULT_CONTEXT,
+        }
+    }
+}
+
+/// What's restored of a review after a crash, see `store::Update::snapshot`.
+#[derive(Debug, Serialize, Deserialize)]
+struct Snapshot {
+    /// The hunk selected.
+    selected: Option<usize>,
+    /// The view states of all hunks.
+    views: Vec<ViewSnapshot>,
+    diff_layout: DiffLayout,
+    line_numbering: LineNumbering,
+    wrap: bool,
+}
+
+/// The view state of a single hunk in a `Snapshot`.
+#[derive(Debug, Serialize, Deserialize)]
+struct ViewSnapshot {
+    line: u16,
+    column: u16,
+    context: usize,
+}
+
+impl From<&DiffViewState> for ViewSnapshot {
+    fn from(state: &DiffViewState) -> Self {
+        Self {
+            line: state.cursor.x,
+            column: state.cursor.y,
+            context: state.context,
+        }
+    }
+}
+
+impl From<ViewSnapshot> for DiffViewState {
+    fn from(snapshot: ViewSnapshot) -> Self {
+        Self {
+            cursor: Position::new(snapshot.line, snapshot.column),
+            context: snapshot.context,
+        }
+    }
+}
+
+/// Who last changed the lines of the base revision a hunk changes.
+#[derive(Clone, Debug)]
+pub struct HunkBlame {
+    /// The file of the base revision the lines are in.
+    path: PathBuf,
+    /// The blamed lines, or `None` while they're blamed.
+    lines: Option<Vec<BlameLine>>,
+    /// State of the text view the lines are shown in.
+    view: TextViewState,
+}
+
+/// The lines of the base revision a hunk changes, which are blamed by the
+/// `Blamer`.
+#[derive(Clone, Debug)]
+pub struct BlameRequest {
+    path: PathBuf,
+    base: Oid,
+    lines: Range<u32>,
+}
+
+impl BlameRequest {
+    /// Blames the lines requested. This can take a while on large histories.
+    pub fn run(&self, repo: &Repository) -> Result<HunkBlame> {
+        let lines = git::blame(&repo.backend, self.base, &self.path, self.lines.clone())?;
+
+        Ok(HunkBlame {
+            path: self.path.clone(),
+            lines: Some(lines),
+            view: TextViewState::new(Position::default()),
+        })
+    }
+}
+
+/// A `Process` that blames hunks off the UI thread, whenever one is requested,
+/// see `Message::Blame`.
+struct Blamer {
+    storage: Storage,
+    rid: RepoId,
+    requests: UnboundedReceiver<BlameRequest>,
+}
+
+impl Process<Message> for Blamer {
+    async fn run(mut self, tx: UnboundedSender<Message>) -> anyhow::Result<()> {
+        while let Some(request) = self.requests.recv().await {
+            let (storage, rid) = (self.storage.clone(), self.rid);
+            let blamed =
+                tokio::task::spawn_blocking(move || request.run(&storage.repository(rid)?)).await?;
+            let message = match blamed {
+                Ok(blame) => Message::BlameLoaded { blame },
+                Err(err) => Message::BlameFailed {
+                    error: err.to_string(),
+                },
+            };
+            if tx.send(message).is_err() {
+                return Ok(());
+            }
+        }
+        Ok(())
+    }
 }
 
 pub struct HunkList<'a> {
@@ -176,6 +333,30 @@ impl<'a> HunkList<'a> {
     pub fn selected(&self) -> Option<usize> {
         self.table.selected()
     }
+
+    /// Returns the index of the first hunk with comments after the selected one.
+    pub fn next_commented(&self) -> Option<usize> {
+        let start = self.selected().map(|selected| selected + 1).unwrap_or(0);
+
+        self.items
+            .iter()
+            .enumerate()
+            .skip(start)
+            .find(|(_, item)| item.has_comments())
+            .map(|(idx, _)| idx)
+    }
+
+    /// Returns the index of the last hunk with comments before the selected one.
+    pub fn previous_commented(&self) -> Option<usize> {
+        let end = self
+            .selected()
+            .unwrap_or(self.items.len())
+            .min(self.items.len());
+
+        self.items[..end]
+            .iter()
+            .rposition(|item| item.has_comments())
+    }
 }
 
 #[derive(Clone)]
@@ -199,8 +380,28 @@ pub struct App<'a> {
     page: AppPage,
     /// State of panes widget on the main page.
     group: PanesState,
+    /// Layout the selected hunk is rendered in.
+    diff_layout: DiffLayout,
+    /// How the line numbers next to the selected hunk are shown.
+    line_numbering: LineNumbering,
+    /// If long lines of the selected hunk are wrapped.
+    wrap: bool,
+    /// The number of columns tabs in hunks are expanded to.
+    tab_width: usize,
     /// State of text view widget on the help page.
     help: TextViewState,
+    /// Toasts requested while handling messages, which are handed to the frontend.
+    toasts: Vec<(ToastLevel, String)>,
+    /// If quitting needs to be confirmed.
+    confirm_quit: bool,
+    /// If the quit confirmation is shown.
+    quitting: bool,
+    /// Input of the jump prompt, if it is shown.
+    jump: Option<TextEditState>,
+    /// Blame of the selected hunk, if it is shown.
+    blame: Option<HunkBlame>,
+    /// Requests to blame hunks, handled by the `Blamer`.
+    blames: Option<UnboundedSender<BlameRequest>>,
 }
 
 impl<'a> TryFrom<Tui> for App<'a> {
@@ -233,15 +434,17 @@ impl<'a> App<'a> {
         hunks: Hunks,
     ) -> Result<Self, anyhow::Error> {
         let repo = storage.repository(rid)?;
+        let settings = settings::Settings::current();
         let states = hunks
             .iter()
-            .map(|_| DiffViewState {
-                cursor: Position::new(0, 0),
-            })
+            .map(|_| DiffViewState::default())
             .collect::<Vec<_>>();
         let hunks = hunks
             .iter()
-            .map(|item| HunkItem::from((&repo, &review, StatefulHunkDiff::from(item))))
+            .map(|item| {
+                HunkItem::from((&repo, &review, StatefulHunkDiff::from(item)))
+                    .with_settings(settings)
+            })
             .collect::<Vec<_>>();
 
         let mut app = App {
@@ -258,7 +461,17 @@ impl<'a> App<'a> {
             ))),
             page: AppPage::Main,
             group: PanesState::new(2, Some(0)),
+            diff_layout: DiffLayout::default(),
+            line_numbering: LineNumbering::default(),
+            wrap: false,
+            tab_width: settings.diff_tab_width,
             help: TextViewState::new(Position::default()),
+            toasts: vec![],
+            confirm_quit: settings.confirm_quit.unwrap_or(true),
+            quitting: false,
+            jump: None,
+            blame: None,
+            blames: None,
         };
 
         app.reload_states()?;
@@ -351,6 +564,129 @@ impl<'a> App<'a> {
         Ok(())
     }
 
+    /// Writes all accepted hunks as a unified diff to the given path. The diff
+    /// is written to a temporary file first, which is then moved into place,
+    /// such that an existing file is never left half-written.
+    pub fn export_accepted(&self, path: &Path) -> Result<()> {
+        let diff = {
+            let hunks = self.hunks.lock().unwrap();
+            builder::accepted_diff(hunks.items.iter().map(|item| &item.inner))?
+        };
+        if diff.is_empty() {
+            anyhow::bail!("no hunks accepted");
+        }
+
+        let file_name = path
+            .file_name()
+            .ok_or_else(|| anyhow::anyhow!("invalid path {}", path.display()))?;
+        let mut tmp_name = std::ffi::OsString::from(".");
+        tmp_name.push(file_name);
+        tmp_name.push(".tmp");
+        let tmp = path.with_file_name(tmp_name);
+
+        fs::write(&tmp, diff)?;
+        if let Err(err) = fs::rename(&tmp, path) {
+            let _ = fs::remove_file(&tmp);
+            return Err(err.into());
+        }
+
+        Ok(())
+    }
+
+    pub fn export_path(&self) -> PathBuf {
+        PathBuf::from(format!("review-{}.patch", format::cob(&self.patch)))
+    }
+
+    /// Updates the number of unchanged lines shown around the changes of the
+    /// selected hunk.
+    pub fn update_context(&self, f: impl FnOnce(usize) -> usize) {
+        let mut hunks = self.hunks.lock().unwrap();
+
+        if let Some(selected) = hunks.selected() {
+            if let Some(state) = hunks.view_state(selected).cloned() {
+                let context = f(state.context);
+                hunks.update_view_state(selected, DiffViewState { context, ..state });
+            }
+        }
+    }
+
+    /// Requests a toast, which is shown once the frontend took it.
+    fn toast(&mut self, level: ToastLevel, message: impl ToString) {
+        self.toasts.push((level, message.to_string()));
+    }
+
+    /// Selects the hunk returned by `f`, or shows a toast if there is none.
+    pub fn select_commented(&mut self, f: impl FnOnce(&HunkList<'a>) -> Option<usize>) {
+        let selected = f(&self.hunks.lock().unwrap());
+
+        match selected {
+            Some(idx) => self
+                .hunks
+                .lock()
+                .unwrap()
+                .update_table(TableState::new(Some(idx))),
+            None => self.toast(ToastLevel::Info, "No more commented hunks"),
+        }
+    }
+
+    /// Selects the hunk, and optionally the line of its diff, given by `input`,
+    /// e.g. `37` or `37:12`. Both are counted from 1 and clamped to the hunks
+    /// and lines there are. Without a line, the diff cursor is reset.
+    pub fn jump_to(&mut self, input: &str) {
+        let Some((hunk, line)) = parse_jump(input) else {
+            self.toast(
+                ToastLevel::Warning,
+                format!("Expected hunk[:line], got '{}'", input.trim()),
+            );
+            return;
+        };
+        let mut hunks = self.hunks.lock().unwrap();
+        if hunks.items.is_empty() {
+            return;
+        }
+
+        let idx = hunk.clamp(1, hunks.items.len()) - 1;
+        let state = hunks.view_state(idx).cloned().unwrap_or_default();
+        let lines = hunks
+            .item(idx)
+            .and_then(|item| item.hunk_text(state.context))
+            .map(|text| text.lines.len())
+            .unwrap_or_default();
+        let row = line
+            .map(|line| line.clamp(1, lines.max(1)) - 1)
+            .unwrap_or_default();
+
+        hunks.update_table(TableState::new(Some(idx)));
+        hunks.update_view_state(
+            idx,
+            DiffViewState {
+                cursor: Position::new(row.min(u16::MAX as usize) as u16, 0),
+                ..state
+            },
+        );
+    }
+
+    /// Returns the lines of the base revision the selected hunk changes, which
+    /// are blamed. Returns `None` if it doesn't change any, e.g. because its file
+    /// was added by the patch.
+    pub fn blame_request(&self) -> Option<BlameRequest> {
+        let hunks = self.hunks.lock().unwrap();
+        let item = hunks.selected().and_then(|idx| hunks.item(idx))?;
+        let diff = item.inner.hunk();
+        let (Some((path, _)), Some(hunk)) = (diff.paths().0, diff.hunk()) else {
+            return None;
+        };
+        if hunk.old.is_empty() {
+            return None;
+        }
+
+        Some(BlameRequest {
+            path: path.to_path_buf(),
+            base: *self.revision.base(),
+            lines: hunk.old.clone(),
+        })
+    }
+
     pub fn selected_hunk_idx(&self) -> Option<usize> {
         self.hunks.lock().unwrap().selected()
     }
@@ -364,7 +700,7 @@ impl<'a> App<'a> {
     fn show_hunk_list(&self, ui: &mut Ui<Message>, frame: &mut Frame) {
         let header = [Column::new(" Hunks ", Constraint::Fill(1))].to_vec();
         let columns = [
-            Column::new("", Constraint::Length(2)),
+            Column::new("", Constraint::Length(3)),
             Column::new("", Constraint::Fill(1)),
             Column::new("", Constraint::Length(15)),
         ]
@@ -388,23 +724,35 @@ impl<'a> App<'a> {
         let hunk = selected.and_then(|selected| hunks.item(selected));
 
         if let Some(hunk) = hunk {
-            let empty_text = hunk
-                .hunk_text()
-                .unwrap_or(Text::raw("Nothing to show.").dark_gray());
-
-            let mut cursor = selected
+            let state = selected
                 .and_then(|selected| hunks.view_state(selected))
-                .map(|state| state.cursor)
+                .cloned()
                 .unwrap_or_default();
+            let mut cursor = state.cursor;
+
+            let empty_text = hunk
+                .hunk_text(state.context)
+                .unwrap_or(Text::raw("Nothing to show.").dark_gray());
 
             ui.composite(layout::container(), 1, |ui| {
                 ui.columns(frame, hunk.header(), Some(Borders::Top));
 
-                if let Some(text) = hunk.hunk_text() {
-                    let diff = ui.text_view(frame, text, &mut cursor, Some(Borders::BottomSides));
+                if let Some(text) = hunk.hunk_text(state.context) {
+                    let split = match self.diff_layout {
+                        DiffLayout::Split => hunk.split_diff(state.context),
+                        DiffLayout::Unified => None,
+                    };
+                    let numbers = hunk.row_line_numbers(state.context).unwrap_or_default();
+                    let diff = ui.add(
+                        frame,
+                        DiffView::new(text, split, &mut cursor, Some(Borders::BottomSides))
+                            .line_numbers(numbers, self.line_numbering)
+                            .tab_width(self.tab_width)
+                            .wrap(self.wrap),
+                    );
                     if diff.changed {
                         ui.send_message(Message::HunkViewChanged {
-                            state: DiffViewState { cursor },
+                            state: DiffViewState { cursor, ..state },
                         })
                     }
                 } else {
@@ -428,127 +776,311 @@ impl<'a> App<'a> {
             .len();
 
         let accepted_stats = format!(" Accepted {hunks_accepted}/{hunks_total} ");
-
-        ui.bar(
-            frame,
-            [
-                Column::new(
-                    span::default(" Review ").cyan().dim().reversed(),
-                    Constraint::Length(8),
-                ),
-                Column::new(
-                    span::default(&id)
-                        .style(ui.theme().bar_on_black_style)
-                        .magenta(),
-                    Constraint::Length(9),
-                ),
-                Column::new(
-                    span::default(title)
-                        .style(ui.theme().bar_on_black_style)
-                        .magenta()
-                        .dim(),
-                    Constraint::Length(title.chars().count() as u16),
-                ),
-                Column::new(
-                    span::default(" ")
-                        .into_left_aligned_line()
-                        .style(ui.theme().bar_on_black_style),
-                    Constraint::Fill(1),
-                ),
-                Column::new(
-                    span::default(&accepted_stats)
-                        .into_right_aligned_line()
-                        .cyan()
-                        .dim()
-                        .reversed(),
-                    Constraint::Length(accepted_stats.chars().count() as u16),
-                ),
-            ]
-            .to_vec(),
-            Some(Borders::None),
+        let accepted_ratio = if hunks_total > 0 {
+            hunks_accepted as f64 / hunks_total as f64
+        } else {
+            0.0
+        };
+        let gauge_width = accepted_stats.chars().count() as u16 + CONTEXT_BAR_GAUGE_WIDTH;
+
+        ui.layout(
+            Layout::horizontal([Constraint::Fill(1), Constraint::Length(gauge_width)]),
+            None,
+            |ui| {
+                ui.bar(
+                    frame,
+                    [
+                        Column::new(
+                            span::default(" Review ").cyan().dim().reversed(),
+                            Constraint::Length(8),
+                        ),
+                        Column::new(
+                            span::default(&id)
+                                .style(ui.theme().bar_on_black_style)
+                                .magenta(),
+                            Constraint::Length(9),
+                        ),
+                        Column::new(
+                            span::default(title)
+                                .style(ui.theme().bar_on_black_style)
+                                .magenta()
+                                .dim(),
+                            Constraint::Length(title.chars().count() as u16),
+                        ),
+                        Column::new(
+                            span::default(" ")
+                                .into_left_aligned_line()
+                                .style(ui.theme().bar_on_black_style),
+                            Constraint::Fill(1),
+                        ),
+                    ]
+                    .to_vec(),
+                    Some(Borders::None),
+                );
+                ui.gauge(
+                    frame,
+                    accepted_ratio,
+                    span::default(&accepted_stats).cyan().dim().reversed(),
+                );
+            },
         );
     }
-}
 
-impl<'a> Show<Message> for App<'a> {
-    fn show(&self, ctx: &Context<Message>, frame: &mut Frame) -> Result<(), anyhow::Error> {
-        Window::default().show(ctx, |ui| {
-            let mut page_focus = self.group.focus();
-
-            match self.page {
-                AppPage::Main => {
-                    ui.layout(layout::page(), Some(0), |ui| {
-                        let group = ui.panes(layout::list_item(), &mut page_focus, |ui| {
-                            self.show_hunk_list(ui, frame);
-                            self.show_hunk(ui, frame);
-                        });
-                        if group.response.changed {
-                            ui.send_message(Message::PanesChanged {
-                                state: PanesState::new(self.group.len(), page_focus),
-                            });
+    fn show_page(&self, ui: &mut Ui<Message>, frame: &mut Frame) {
+        let mut page_focus = self.group.focus();
+
+        match self.page {
+            AppPage::Main => {
+                ui.set_prefixes(prefixes());
+                ui.layout(layout::page(), Some(0), |ui| {
+                    // The pane without focus is rendered in a dimmed theme.
+                    let focus = page_focus;
+                    let theme = ui.theme().clone();
+                    let pane_theme = |index| {
+                        if focus == Some(index) {
+                            theme.clone()
+                        } else {
+                            theme.clone().dimmed()
                         }
+                    };
 
-                        self.show_context_bar(ui, frame);
+                    let group = ui.panes(layout::list_item(), &mut page_focus, |ui| {
+                        ui.scoped_theme(pane_theme(0), |ui| self.show_hunk_list(ui, frame));
+                        ui.scoped_theme(pane_theme(1), |ui| self.show_hunk(ui, frame));
+                    });
+                    if group.response.changed {
+                        ui.send_message(Message::PanesChanged {
+                            state: PanesState::new(self.group.len(), page_focus),
+                        });
+                    }
 
-                        ui.shortcuts(
+                    self.show_context_bar(ui, frame);
+
+                    ui.shortcuts(
+                        frame,
+                        &[
+                            ("c", "comment"),
+                            ("a", "accept"),
+                            ("d", "discard accepted"),
+                            ("x", "export"),
+                            ("s", "toggle split"),
+                            ("n", "toggle numbers"),
+                            ("w", "toggle wrap"),
+                            ("e", "expand"),
+                            (":", "jump"),
+                            ("b", "blame"),
+                            ("?", "help"),
+                            ("q", "quit"),
+                        ],
+                        '∙',
+                    );
+
+                    if ui.input_global(|key| key == Key::Char('?') || key == Key::F(1)) {
+                        ui.send_message(Message::ShowHelp);
+                    }
+                    if ui.input_global(|key| key == Key::Char('c')) {
+                        ui.send_message(Message::Comment);
+                    }
+                    if ui.input_global(|key| key == Key::Char('a')) {
+                        ui.send_message(Message::Accept);
+                    }
+                    if ui.input_global(|key| key == Key::Char('d')) {
+                        ui.send_message(Message::Discard);
+                    }
+                    if ui.input_global(|key| key == Key::Char('s')) {
+                        ui.send_message(Message::ToggleDiffLayout);
+                    }
+                    if ui.input_global(|key| key == Key::Char('n')) {
+                        ui.send_message(Message::ToggleLineNumbering);
+                    }
+                    if ui.input_global(|key| key == Key::Char('w')) {
+                        ui.send_message(Message::ToggleWrap);
+                    }
+                    if ui.input_global(|key| key == Key::Char('e')) {
+                        ui.send_message(Message::ExpandContext);
+                    }
+                    if ui.input_global(|key| key == Key::Char('E')) {
+                        ui.send_message(Message::CollapseContext);
+                    }
+                    if ui.input_global(|key| key == Key::Char('x')) {
+                        ui.send_message(Message::ExportAccepted {
+                            path: self.export_path(),
+                        });
+                    }
+                    if ui.input_global(|key| key == Key::Char(':')) {
+                        ui.send_message(Message::OpenJump);
+                    }
+                    if ui.input_global(|key| key == Key::Char('b')) {
+                        ui.send_message(Message::Blame);
+                    }
+                    if ui.input_chord(Key::Char(']'), Key::Char('c')) {
+                        ui.send_message(Message::NextComment);
+                    }
+                    if ui.input_chord(Key::Char('['), Key::Char('c')) {
+                        ui.send_message(Message::PreviousComment);
+                    }
+                });
+            }
+            AppPage::Help => {
+                ui.set_prefixes(PrefixMap::default());
+                ui.panes(layout::page(), &mut page_focus, |ui| {
+                    ui.composite(layout::container(), 1, |ui| {
+                        let header = [Column::new(" Help ", Constraint::Fill(1))].to_vec();
+                        let mut cursor = self.help.cursor();
+
+                        ui.columns(frame, header, Some(Borders::Top));
+                        let help = ui.text_view(
                             frame,
-                            &[
-                                ("c", "comment"),
-                                ("a", "accept"),
-                                ("d", "discard accepted"),
-                                ("?", "help"),
-                                ("q", "quit"),
-                            ],
-                            '∙',
+                            help_text().to_string(),
+                            &mut cursor,
+                            Some(Borders::BottomSides),
                         );
-
-                        if ui.input_global(|key| key == Key::Char('?')) {
-                            ui.send_message(Message::ShowHelp);
-                        }
-                        if ui.input_global(|key| key == Key::Char('c')) {
-                            ui.send_message(Message::Comment);
-                        }
-                        if ui.input_global(|key| key == Key::Char('a')) {
-                            ui.send_message(Message::Accept);
-                        }
-                        if ui.input_global(|key| key == Key::Char('d')) {
-                            ui.send_message(Message::Discard);
+                        if help.changed {
+                            ui.send_message(Message::HelpChanged {
+                                state: TextViewState::new(cursor),
+                            })
                         }
                     });
+
+                    self.show_context_bar(ui, frame);
+
+                    ui.shortcuts(frame, &[("?", "close"), ("q", "quit")], '∙');
+                });
+
+                if ui.input_global(|key| key == Key::Char('?') || key == Key::F(1)) {
+                    ui.send_message(Message::ShowMain);
                 }
-                AppPage::Help => {
-                    ui.panes(layout::page(), &mut page_focus, |ui| {
-                        ui.composite(layout::container(), 1, |ui| {
-                            let header = [Column::new(" Help ", Constraint::Fill(1))].to_vec();
-                            let mut cursor = self.help.cursor();
-
-                            ui.columns(frame, header, Some(Borders::Top));
-                            let help = ui.text_view(
-                                frame,
-                                help_text().to_string(),
-                                &mut cursor,
-                                Some(Borders::BottomSides),
-                            );
-                            if help.changed {
-                                ui.send_message(Message::HelpChanged {
-                                    state: TextViewState::new(cursor),
-                                })
-                            }
-                        });
+            }
+        }
 
-                        self.show_context_bar(ui, frame);
+        if ui.input_global(|key| key == Key::Char('q')) {
+            ui.send_message(Message::Quit);
+        }
+    }
+}
 
-                        ui.shortcuts(frame, &[("?", "close"), ("q", "quit")], '∙');
-                    });
+impl<'a> App<'a> {
+    /// Shows the jump prompt in the last two rows of the window, over the page.
+    fn show_jump(&self, ui: &mut Ui<Message>, frame: &mut Frame, state: &TextEditState) {
+        let area = ui.area();
+        let height = area.height.min(2);
+        let area = Rect {
+            y: area.bottom().saturating_sub(height),
+            height,
+            ..area
+        };
+        frame.render_widget(Clear, area);
 
-                    if ui.input_global(|key| key == Key::Char('?')) {
-                        ui.send_message(Message::ShowMain);
-                    }
+        let mut ui = ui
+            .child_ui(area, Layout::vertical([Constraint::Length(2)]))
+            .with_area_focus(Some(0))
+            .with_focus();
+        let (mut text, mut cursor) = (state.text.clone(), state.cursor);
+
+        let text_edit = ui.text_edit_labeled_singleline(
+            frame,
+            &mut text,
+            &mut cursor,
+            "hunk[:line]",
+            Some(Borders::None),
+        );
+        if text_edit.changed {
+            ui.send_message(Message::JumpChanged {
+                state: TextEditState { text, cursor },
+            });
+        }
+
+        if ui.input_global(|key| key == Key::Char('\n')) {
+            ui.send_message(Message::Jump);
+        }
+        if ui.input_global(|key| key == Key::Esc || key == Key::Ctrl('c')) {
+            ui.send_message(Message::CloseJump);
+        }
+    }
+}
+
+impl<'a> App<'a> {
+    /// Shows the blame of the selected hunk in a scrollable view, over the page.
+    /// It's shown while the lines are blamed already.
+    fn show_blame(&self, ui: &mut Ui<Message>, frame: &mut Frame, blame: &HunkBlame) {
+        let format = settings::Settings::current().timestamp_format;
+        let mut lines = blame
+            .lines
+            .iter()
+            .flatten()
+            .map(|line| {
+                let time = Timestamp::from_secs(line.time.max(0) as u64);
+                Line::from(
+                    [
+                        span::default(&format!("{:>4} ", line.line)).dark_gray(),
+                        span::default(&format::oid(line.commit)).yellow(),
+                        span::default(" "),
+                        span::default(&line.author).magenta(),
+                        span::default(" "),
+                        span::default(&format::timestamp(&time, format)).dark_gray(),
+                        span::default(" "),
+                        span::default(&line.summary),
+                    ]
+                    .to_vec(),
+                )
+            })
+            .collect::<Vec<_>>();
+        if blame.lines.is_none() {
+            lines.push(Line::from(span::default("Blaming…").dark_gray()));
+        }
+
+        ui.modal(frame, |ui, frame| {
+            ui.composite(layout::container(), 1, |ui| {
+                let title = format!(" Blame {} ", blame.path.display());
+                let header = [Column::new(title, Constraint::Fill(1))].to_vec();
+                let mut cursor = blame.view.cursor();
+
+                ui.columns(frame, header, Some(Borders::Top));
+                let view = ui.text_view(frame, lines, &mut cursor, Some(Borders::BottomSides));
+                if view.changed {
+                    ui.send_message(Message::BlameChanged {
+                        state: TextViewState::new(cursor),
+                    })
                 }
+            });
+
+            if ui.input_global(|key| matches!(key, Key::Esc | Key::Char('b'))) {
+                ui.send_message(Message::CloseBlame);
             }
+        });
+    }
+}
 
-            if ui.input_global(|key| key == Key::Char('q')) {
-                ui.send_message(Message::Quit);
+impl<'a> Show<Message> for App<'a> {
+    fn show(&self, ctx: &Context<Message>, frame: &mut Frame) -> Result<(), anyhow::Error> {
+        Window::default().show(ctx, |ui| {
+            // Popups take all input while they're shown. Modals only take it
+            // from the widgets below from the frame after the one they were
+            // shown first in.
+            if self.quitting {
+                ui.without_inputs(|ui| self.show_page(ui, frame));
+
+                let mut confirmed = None;
+                ui.confirm(
+                    frame,
+                    "Quit",
+                    "Quit the review? Accepted hunks are kept.",
+                    &mut confirmed,
+                );
+                match confirmed {
+                    Some(true) => ui.send_message(Message::Quit),
+                    Some(false) => ui.send_message(Message::CancelQuit),
+                    None => {}
+                }
+            } else if let Some(jump) = &self.jump {
+                // The prompt takes all input while it's shown.
+                ui.without_inputs(|ui| self.show_page(ui, frame));
+                self.show_jump(ui, frame, jump);
+            } else if let Some(blame) = &self.blame {
+                ui.without_inputs(|ui| self.show_page(ui, frame));
+                self.show_blame(ui, frame, blame);
+            } else {
+                self.show_page(ui, frame);
             }
         });
         Ok(())
@@ -586,16 +1118,61 @@ impl<'a> store::Update<Message> for App<'a> {
                 }
                 None
             }
+            Message::ToggleDiffLayout => {
+                self.diff_layout = self.diff_layout.toggle();
+                None
+            }
+            Message::ToggleLineNumbering => {
+                self.line_numbering = self.line_numbering.toggle();
+                None
+            }
+            Message::ToggleWrap => {
+                self.wrap = !self.wrap;
+                None
+            }
+            Message::ExpandContext => {
+                self.update_context(|context| context.saturating_add(diff::CONTEXT_STEP));
+                None
+            }
+            Message::CollapseContext => {
+                self.update_context(|_| diff::DEFAULT_CONTEXT);
+                None
+            }
+            Message::NextComment => {
+                self.select_commented(HunkList::next_commented);
+                None
+            }
+            Message::PreviousComment => {
+                self.select_commented(HunkList::previous_commented);
+                None
+            }
             Message::HelpChanged { state } => {
                 self.help = state;
                 None
             }
             Message::Comment => {
                 let hunks = self.hunks.lock().unwrap();
+                let selected = hunks.selected();
+                let item = selected.and_then(|idx| hunks.item(idx));
+                let state = selected
+                    .and_then(|idx| hunks.view_state(idx))
+                    .cloned()
+                    .unwrap_or_default();
+
+                let path = item.and_then(|item| {
+                    let (old, new) = item.inner.hunk().paths();
+                    old.or(new).map(|(path, _)| path.to_path_buf())
+                });
+                let line = item
+                    .and_then(|item| item.line_numbers_at(state.context, state.cursor.x.into()))
+                    .filter(|line| line.old.is_some() || line.new.is_some());
+
                 Some(Exit {
                     value: Some(Selection {
                         action: ReviewAction::Comment,
-                        hunk: hunks.selected(),
+                        hunk: selected,
+                        path,
+                        line,
                         args: None,
                     }),
                 })
@@ -603,24 +1180,221 @@ impl<'a> store::Update<Message> for App<'a> {
             Message::Accept => {
                 match self.accept_current_hunk() {
                     Ok(()) => log::info!("Hunk accepted."),
-                    Err(err) => log::info!("An error occured while accepting hunk: {}", err),
+                    Err(err) => {
+                        log::info!("An error occured while accepting hunk: {}", err);
+                        self.toast(ToastLevel::Error, format!("Could not accept hunk: {err}"));
+                    }
+                }
+                if let Err(err) = self.reload_states() {
+                    self.toast(
+                        ToastLevel::Error,
+                        format!("Could not reload hunk states: {err}"),
+                    );
                 }
-                let _ = self.reload_states();
                 None
             }
             Message::Discard => {
                 match self.discard_accepted_hunks() {
                     Ok(()) => log::info!("Discarded all hunks."),
-                    Err(err) => log::info!("An error occured while discarding hunks: {}", err),
+                    Err(err) => {
+                        log::info!("An error occured while discarding hunks: {}", err);
+                        self.toast(ToastLevel::Error, format!("Could not discard hunks: {err}"));
+                    }
+                }
+                if let Err(err) = self.reload_states() {
+                    self.toast(
+                        ToastLevel::Error,
+                        format!("Could not reload hunk states: {err}"),
+                    );
+                }
+                None
+            }
+            Message::ExportAccepted { path } => {
+                match self.export_accepted(&path) {
+                    Ok(()) => {
+                        log::info!("Exported accepted hunks to {}.", path.display());
+                        self.toast(
+                            ToastLevel::Info,
+                            format!("Exported accepted hunks to {}", path.display()),
+                        );
+                    }
+                    Err(err) => {
+                        log::info!("An error occured while exporting hunks: {}", err);
+                        self.toast(ToastLevel::Error, format!("Could not export hunks: {err}"));
+                    }
                 }
-                let _ = self.reload_states();
                 None
             }
-            Message::Quit => Some(Exit { value: None }),
+            Message::OpenJump => {
+                self.jump = Some(TextEditState {
+                    text: String::new(),
+                    cursor: 0,
+                });
+                None
+            }
+            Message::JumpChanged { state } => {
+                self.jump = Some(state);
+                None
+            }
+            Message::Jump => {
+                if let Some(jump) = self.jump.take() {
+                    self.jump_to(&jump.text);
+                }
+                None
+            }
+            Message::CloseJump => {
+                self.jump = None;
+                None
+            }
+            Message::Blame => {
+                let Some(request) = self.blame_request() else {
+                    self.toast(ToastLevel::Info, "No lines of the base revision to blame");
+                    return None;
+                };
+                let path = request.path.clone();
+                match self.blames.as_ref().map(|blames| blames.send(request)) {
+                    Some(Ok(())) => {
+                        self.blame = Some(HunkBlame {
+                            path,
+                            lines: None,
+                            view: TextViewState::new(Position::default()),
+                        });
+                    }
+                    _ => {
+                        self.toast(
+                            ToastLevel::Error,
+                            "Could not blame hunk: blaming is not available",
+                        );
+                    }
+                }
+                None
+            }
+            Message::BlameLoaded { blame } => {
+                // The blame is dropped if it was closed in the meantime.
+                if self
+                    .blame
+                    .as_ref()
+                    .is_some_and(|shown| shown.lines.is_none() && shown.path == blame.path)
+                {
+                    self.blame = Some(blame);
+                }
+                None
+            }
+            Message::BlameFailed { error } => {
+                log::info!("An error occured while blaming hunk: {}", error);
+                self.blame = None;
+                self.toast(ToastLevel::Error, format!("Could not blame hunk: {error}"));
+                None
+            }
+            Message::BlameChanged { state } => {
+                if let Some(blame) = self.blame.as_mut() {
+                    blame.view = state;
+                }
+                None
+            }
+            Message::CloseBlame => {
+                self.blame = None;
+                None
+            }
+            Message::Quit => {
+                if self.confirm_quit && !self.quitting {
+                    self.quitting = true;
+                    None
+                } else {
+                    Some(Exit { value: None })
+                }
+            }
+            Message::CancelQuit => {
+                self.quitting = false;
+                None
+            }
+        }
+    }
+
+    fn update_batch(&mut self, messages: Vec<Message>) -> Option<Exit<Self::Return>> {
+        store::update_pages(self, messages)
+    }
+
+    fn take_toasts(&mut self) -> Vec<(ToastLevel, String)> {
+        std::mem::take(&mut self.toasts)
+    }
+
+    fn snapshot(&self) -> Option<Vec<u8>> {
+        let hunks = self.hunks.lock().unwrap();
+        let snapshot = Snapshot {
+            selected: hunks.selected(),
+            views: hunks.views.iter().map(ViewSnapshot::from).collect(),
+            diff_layout: self.diff_layout,
+            line_numbering: self.line_numbering,
+            wrap: self.wrap,
+        };
+
+        serde_json::to_vec(&snapshot).ok()
+    }
+
+    fn snapshot_key(&self) -> Option<String> {
+        Some(format!("{}-{}", self.rid, self.revision.id()))
+    }
+
+    fn restore(&self, snapshot: &[u8]) -> Result<Option<Self>> {
+        let snapshot = serde_json::from_slice::<Snapshot>(snapshot)?;
+        let hunks = self.hunks.lock().unwrap();
+
+        // The hunks of a revision never change, but a snapshot of a different
+        // review would have a different number of them.
+        if snapshot.views.len() != hunks.items.len() {
+            return Ok(None);
         }
+        let selected = snapshot
+            .selected
+            .filter(|selected| *selected < hunks.items.len());
+
+        Ok(Some(Self {
+            hunks: Arc::new(Mutex::new(HunkList::new(
+                hunks.items.clone(),
+                snapshot.views.into_iter().map(DiffViewState::from),
+                TableState::new(selected),
+            ))),
+            diff_layout: snapshot.diff_layout,
+            line_numbering: snapshot.line_numbering,
+            wrap: snapshot.wrap,
+            ..self.clone()
+        }))
     }
 }
 
+impl<'a> store::Pages for App<'a> {
+    type Page = AppPage;
+
+    fn page(&self) -> Option<AppPage> {
+        Some(self.page.clone())
+    }
+
+    fn on_exit_page(&mut self, page: &AppPage) {
+        if *page == AppPage::Help {
+            self.help = TextViewState::new(Position::default());
+        }
+    }
+}
+
+/// Parses the input of the jump prompt, a hunk number optionally followed by a
+/// line number, e.g. `37` or `37:12`.
+fn parse_jump(input: &str) -> Option<(usize, Option<usize>)> {
+    let input = input.trim();
+    let (hunk, line) = match input.split_once(':') {
+        Some((hunk, line)) => (hunk, Some(line.trim().parse().ok()?)),
+        None => (input, None),
+    };
+
+    Some((hunk.trim().parse().ok()?, line))
+}
+
+fn prefixes() -> PrefixMap {
+    PrefixMap::default()
+        .prefix(Key::Char(']'), [("c", "next commented hunk")])
+        .prefix(Key::Char('['), [("c", "previous commented hunk")])
+}
+
 fn help_text() -> String {
     r#"# About
 
@@ -643,14 +1417,24 @@ review is done, it needs to be finalized via `rad patch review --accept | --reje
 `Tab`       Focus next pane
 `BackTab`   Focus previous pane
 
-`?`         toogle help
+`?,F1`      toogle help
 `q`         quit / cancel
 
 ## Specific keybindings
 
-`c`         comment on hunk
+`c`         comment on the line at the top of the diff
 `a`         accept hunk
-`d`         discard accepted hunks (reject all)"#
+`d`         discard accepted hunks (reject all)
+`s`         toggle split diff (side-by-side on wide terminals)
+`n`         toggle absolute and relative line numbers
+`w`         toggle wrapping long lines
+`e`         expand unchanged lines around changes
+`E`         collapse unchanged lines around changes
+`]c`        jump to the next hunk with comments
+`[c`        jump to the previous hunk with comments
+`:`         jump to a hunk (and line), e.g. `:37` or `:37:12`
+`b`         show who last changed the lines of the base the hunk changes
+`x`         export accepted hunks to `review-<id>.patch`"#
         .into()
 }
 
@@ -661,6 +1445,7 @@ mod test {
     use radicle::patch::Cache;
 
     use store::Update;
+    use tui::ui::im::TestDriver;
 
     use super::*;
     use crate::test;
@@ -815,6 +1600,263 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn diff_layout_can_be_toggled() -> Result<()> {
+        let alice = test::fixtures::node_with_repo();
+        let branch = test::fixtures::branch_with_main_emptied(&alice);
+
+        let mut patches = Cache::no_cache(&alice.repo.repo).unwrap();
+        let patch = test::fixtures::patch(&alice, &branch, &mut patches)?;
+
+        let mut app = fixtures::app(&alice, patch)?;
+        assert_eq!(app.diff_layout, DiffLayout::Unified);
+
+        app.update(Message::ToggleDiffLayout);
+        assert_eq!(app.diff_layout, DiffLayout::Split);
+
+        app.update(Message::ToggleDiffLayout);
+        assert_eq!(app.diff_layout, DiffLayout::Unified);
+
+        Ok(())
+    }
+
+    #[test]
+    fn line_numbering_can_be_toggled() -> Result<()> {
+        let alice = test::fixtures::node_with_repo();
+        let branch = test::fixtures::branch_with_main_emptied(&alice);
+
+        let mut patches = Cache::no_cache(&alice.repo.repo).unwrap();
+        let patch = test::fixtures::patch(&alice, &branch, &mut patches)?;
+
+        let mut app = fixtures::app(&alice, patch)?;
+        assert_eq!(app.line_numbering, LineNumbering::Absolute);
+
+        app.update(Message::ToggleLineNumbering);
+        assert_eq!(app.line_numbering, LineNumbering::Relative);
+
+        Ok(())
+    }
+
+    #[test]
+    fn wrapping_can_be_toggled() -> Result<()> {
+        let alice = test::fixtures::node_with_repo();
+        let branch = test::fixtures::branch_with_main_emptied(&alice);
+
+        let mut patches = Cache::no_cache(&alice.repo.repo).unwrap();
+        let patch = test::fixtures::patch(&alice, &branch, &mut patches)?;
+
+        let mut app = fixtures::app(&alice, patch)?;
+        assert!(!app.wrap);
+        assert_eq!(app.tab_width, diff::DEFAULT_TAB_WIDTH);
+
+        app.update(Message::ToggleWrap);
+        assert!(app.wrap);
+
+        Ok(())
+    }
+
+    #[test]
+    fn review_can_be_restored_from_snapshot() -> Result<()> {
+        let alice = test::fixtures::node_with_repo();
+        let branch = test::fixtures::branch_with_eof_removed(&alice);
+
+        let mut patches = Cache::no_cache(&alice.repo.repo).unwrap();
+        let patch = test::fixtures::patch(&alice, &branch, &mut patches)?;
+
+        let mut app = fixtures::app(&alice, patch)?;
+        let initial = app.snapshot().unwrap();
+
+        app.update(Message::HunkChanged {
+            state: TableState::new(Some(1)),
+        });
+        app.update(Message::ExpandContext);
+        app.update(Message::ToggleDiffLayout);
+        app.update(Message::ToggleWrap);
+        let snapshot = app.snapshot().unwrap();
+
+        let restored = app.restore(&initial)?.unwrap();
+        assert_eq!(restored.selected_hunk_idx(), Some(0));
+        assert_eq!(restored.diff_layout, DiffLayout::Unified);
+        assert!(!restored.wrap);
+
+        let restored = restored.restore(&snapshot)?.unwrap();
+        let context = restored
+            .hunks
+            .lock()
+            .unwrap()
+            .view_state(1)
+            .map(|state| state.context);
+        assert_eq!(restored.selected_hunk_idx(), Some(1));
+        assert_eq!(context, Some(diff::DEFAULT_CONTEXT + diff::CONTEXT_STEP));
+        assert_eq!(restored.diff_layout, DiffLayout::Split);
+        assert!(restored.wrap);
+
+        let other = serde_json::to_vec(&Snapshot {
+            selected: None,
+            views: vec![],
+            diff_layout: DiffLayout::Split,
+            line_numbering: LineNumbering::Absolute,
+            wrap: false,
+        })?;
+        assert!(app.restore(&other)?.is_none());
+        assert!(app.restore(b"{").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn quit_needs_to_be_confirmed() -> Result<()> {
+        let alice = test::fixtures::node_with_repo();
+        let branch = test::fixtures::branch_with_main_emptied(&alice);
+
+        let mut patches = Cache::no_cache(&alice.repo.repo).unwrap();
+        let patch = test::fixtures::patch(&alice, &branch, &mut patches)?;
+
+        let mut app = fixtures::app(&alice, patch)?;
+
+        assert!(app.update(Message::Quit).is_none());
+        assert!(app.quitting);
+
+        app.update(Message::CancelQuit);
+        assert!(!app.quitting);
+
+        assert!(app.update(Message::Quit).is_none());
+        assert!(app.update(Message::Quit).is_some());
+
+        Ok(())
+    }
+
+    #[test]
+    fn page_should_not_take_input_while_quit_is_confirmed() -> Result<()> {
+        let alice = test::fixtures::node_with_repo();
+        let branch = test::fixtures::branch_with_main_emptied(&alice);
+
+        let mut patches = Cache::no_cache(&alice.repo.repo).unwrap();
+        let patch = test::fixtures::patch(&alice, &branch, &mut patches)?;
+
+        let app = fixtures::app(&alice, patch)?;
+        let mut driver = TestDriver::new(app, 120, 30)?;
+        driver
+            .keys([Key::Char('q'), Key::Char('s'), Key::Char('y')])
+            .run(3)?;
+
+        assert!(matches!(driver.messages(), [Message::Quit, Message::Quit]));
+        assert_eq!(driver.state().diff_layout, DiffLayout::default());
+        assert!(driver.exit().is_some());
+
+        Ok(())
+    }
+
+    #[test]
+    fn blame_should_show_who_changed_base_lines() -> Result<()> {
+        let alice = test::fixtures::node_with_repo();
+        let branch = test::fixtures::branch_with_main_emptied(&alice);
+
+        let mut patches = Cache::no_cache(&alice.repo.repo).unwrap();
+        let patch = test::fixtures::patch(&alice, &branch, &mut patches)?;
+
+        let mut app = fixtures::app(&alice, patch)?;
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        app.blames = Some(tx);
+        app.update(Message::Blame);
+        assert!(app
+            .blame
+            .as_ref()
+            .is_some_and(|blame| blame.lines.is_none()));
+
+        let request = rx.try_recv()?;
+        app.update(Message::BlameLoaded {
+            blame: request.run(&app.repo()?)?,
+        });
+
+        let blame = app
+            .blame
+            .clone()
+            .ok_or_else(|| anyhow!("No blame shown."))?;
+        let lines = blame.lines.unwrap_or_default();
+        assert_eq!(blame.path, PathBuf::from("main.rs"));
+        assert_eq!(lines.len(), test::fixtures::MAIN_RS.lines().count());
+        assert!(lines
+            .iter()
+            .all(|line| line.author == "anonymous" && line.summary == "Second commit"));
+
+        app.update(Message::CloseBlame);
+        assert!(app.blame.is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn added_files_should_not_be_blamed() -> Result<()> {
+        let alice = test::fixtures::node_with_repo();
+        let branch = test::fixtures::branch_with_main_deleted_and_file_added(&alice);
+
+        let mut patches = Cache::no_cache(&alice.repo.repo).unwrap();
+        let patch = test::fixtures::patch(&alice, &branch, &mut patches)?;
+
+        let mut app = fixtures::app(&alice, patch)?;
+        let added = app
+            .hunks()
+            .iter()
+            .position(|item| matches!(item.inner.hunk(), HunkDiff::Added { .. }));
+        app.update(Message::HunkChanged {
+            state: TableState::new(added),
+        });
+        app.update(Message::Blame);
+
+        assert!(app.blame.is_none());
+        assert!(matches!(app.toasts.last(), Some((ToastLevel::Info, _))));
+
+        Ok(())
+    }
+
+    #[test]
+    fn help_can_be_opened_with_function_key() -> Result<()> {
+        let alice = test::fixtures::node_with_repo();
+        let branch = test::fixtures::branch_with_main_emptied(&alice);
+
+        let mut patches = Cache::no_cache(&alice.repo.repo).unwrap();
+        let patch = test::fixtures::patch(&alice, &branch, &mut patches)?;
+
+        let mut app = fixtures::app(&alice, patch)?;
+
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let ctx = Context::default()
+            .with_inputs([Key::F(1)].into())
+            .with_sender(tx);
+        tui::ui::im::render_once(80, 24, ctx, |ui, frame| app.show_page(ui, frame))?;
+
+        app.update_batch(std::iter::from_fn(|| rx.try_recv().ok()).collect());
+        assert_eq!(app.page, AppPage::Help);
+
+        Ok(())
+    }
+
+    #[test]
+    fn help_should_be_scrolled_to_top_when_left() -> Result<()> {
+        let alice = test::fixtures::node_with_repo();
+        let branch = test::fixtures::branch_with_main_emptied(&alice);
+
+        let mut patches = Cache::no_cache(&alice.repo.repo).unwrap();
+        let patch = test::fixtures::patch(&alice, &branch, &mut patches)?;
+
+        let mut app = fixtures::app(&alice, patch)?;
+
+        app.update_batch(vec![
+            Message::ShowHelp,
+            Message::HelpChanged {
+                state: TextViewState::new(Position::new(0, 12)),
+            },
+        ]);
+        assert_eq!(app.help.cursor(), Position::new(0, 12));
+
+        app.update_batch(vec![Message::ShowMain]);
+        assert_eq!(app.page, AppPage::Main);
+        assert_eq!(app.help.cursor(), Position::default());
+
+        Ok(())
+    }
+
     #[test]
     fn single_file_single_hunk_can_be_accepted() -> Result<()> {
         let alice = test::fixtures::node_with_repo();
@@ -909,4 +1951,162 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn context_is_expanded_per_hunk() -> Result<()> {
+        let alice = test::fixtures::node_with_repo();
+        let branch = test::fixtures::branch_with_main_changed(&alice);
+
+        let mut patches = Cache::no_cache(&alice.repo.repo).unwrap();
+        let patch = test::fixtures::patch(&alice, &branch, &mut patches)?;
+
+        let mut app = fixtures::app(&alice, patch)?;
+        app.update(Message::ExpandContext);
+        app.update(Message::ExpandContext);
+        app.update(Message::HunkChanged {
+            state: TableState::new(Some(1)),
+        });
+        app.update(Message::ExpandContext);
+        app.update(Message::CollapseContext);
+
+        let contexts = app
+            .hunks
+            .lock()
+            .unwrap()
+            .views
+            .iter()
+            .map(|view| view.context)
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            contexts,
+            [
+                diff::DEFAULT_CONTEXT + 2 * diff::CONTEXT_STEP,
+                diff::DEFAULT_CONTEXT
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn jumping_to_comments_keeps_selection_without_comments() -> Result<()> {
+        let alice = test::fixtures::node_with_repo();
+        let branch = test::fixtures::branch_with_main_changed(&alice);
+
+        let mut patches = Cache::no_cache(&alice.repo.repo).unwrap();
+        let patch = test::fixtures::patch(&alice, &branch, &mut patches)?;
+
+        let mut app = fixtures::app(&alice, patch)?;
+        app.update(Message::NextComment);
+        assert_eq!(app.selected_hunk_idx(), Some(0));
+
+        app.update(Message::HunkChanged {
+            state: TableState::new(Some(1)),
+        });
+        app.update(Message::PreviousComment);
+        assert_eq!(app.selected_hunk_idx(), Some(1));
+        assert!(matches!(app.toasts.last(), Some((ToastLevel::Info, _))));
+
+        Ok(())
+    }
+
+    #[test]
+    fn jump_input_can_be_parsed() {
+        assert_eq!(parse_jump("37"), Some((37, None)));
+        assert_eq!(parse_jump(" 37:12 "), Some((37, Some(12))));
+        assert_eq!(parse_jump("37:"), None);
+        assert_eq!(parse_jump("hunk"), None);
+    }
+
+    #[test]
+    fn jumping_to_hunk_is_clamped() -> Result<()> {
+        let alice = test::fixtures::node_with_repo();
+        let branch = test::fixtures::branch_with_main_changed(&alice);
+
+        let mut patches = Cache::no_cache(&alice.repo.repo).unwrap();
+        let patch = test::fixtures::patch(&alice, &branch, &mut patches)?;
+
+        let mut app = fixtures::app(&alice, patch)?;
+        app.update(Message::HunkViewChanged {
+            state: DiffViewState {
+                cursor: Position::new(3, 0),
+                ..Default::default()
+            },
+        });
+
+        app.update(Message::OpenJump);
+        app.update(Message::JumpChanged {
+            state: TextEditState {
+                text: "2".to_string(),
+                cursor: 1,
+            },
+        });
+        app.update(Message::Jump);
+        assert_eq!(app.selected_hunk_idx(), Some(1));
+        assert!(app.jump.is_none());
+
+        app.jump_to("0");
+        assert_eq!(app.selected_hunk_idx(), Some(0));
+        assert_eq!(
+            app.hunks
+                .lock()
+                .unwrap()
+                .view_state(0)
+                .map(|view| view.cursor),
+            Some(Position::new(0, 0))
+        );
+
+        app.jump_to("1:2");
+        assert_eq!(
+            app.hunks
+                .lock()
+                .unwrap()
+                .view_state(0)
+                .map(|view| view.cursor),
+            Some(Position::new(1, 0))
+        );
+
+        app.jump_to("99:1");
+        assert_eq!(app.selected_hunk_idx(), Some(1));
+        assert!(app.toasts.is_empty());
+
+        app.jump_to("next");
+        assert_eq!(app.selected_hunk_idx(), Some(1));
+        assert!(matches!(app.toasts.last(), Some((ToastLevel::Warning, _))));
+        assert_eq!(app.take_toasts().len(), 1);
+        assert!(app.toasts.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn accepted_hunks_can_be_exported() -> Result<()> {
+        let alice = test::fixtures::node_with_repo();
+        let branch = test::fixtures::branch_with_main_changed(&alice);
+
+        let mut patches = Cache::no_cache(&alice.repo.repo).unwrap();
+        let patch = test::fixtures::patch(&alice, &branch, &mut patches)?;
+
+        let mut app = fixtures::app(&alice, patch)?;
+        app.update(Message::HunkChanged {
+            state: TableState::new(Some(1)),
+        });
+        app.update(Message::Accept);
+
+        let checkout = &alice.repo.checkout().checkout;
+        let path = checkout.path().join("review.patch");
+        app.update(Message::ExportAccepted { path: path.clone() });
+
+        let diff = std::fs::read_to_string(&path)?;
+        assert!(diff.contains("+    println!(\"Hello again\");"));
+        assert!(!diff.contains("This is a new comment."));
+
+        let base = checkout.find_commit(*branch.base)?.tree()?;
+        let diff = radicle::git::raw::Diff::from_buffer(diff.as_bytes())?;
+
+        assert!(checkout.apply_to_tree(&base, &diff, None).is_ok());
+
+        Ok(())
+    }
 }
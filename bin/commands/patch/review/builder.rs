@@ -27,7 +27,11 @@ use radicle_cli::git::unified_diff::{self, FileHeader};
 use radicle_cli::git::unified_diff::{Encode, HunkHeader};
 use radicle_cli::terminal as term;
 
-use crate::git::HunkDiff;
+use ratatui::text::Line;
+
+use radicle_tui::ui::diff::{line_numbers, DiffLine, LineNumbers};
+
+use crate::git::{HunkDiff, HunkState, StatefulHunkDiff};
 
 /// Queue of items (usually hunks) left to review.
 #[derive(Clone, Default, Debug)]
@@ -233,6 +237,73 @@ impl FileReviewBuilder {
     }
 }
 
+/// Builds a unified diff that only contains the accepted items and applies to the
+/// base of the reviewed revision. New line numbers of accepted hunks are adjusted
+/// by the lines added or removed by hunks of the same file that were left out.
+///
+/// Changes of the newline at the end of a file are not exported, since they
+/// cannot be represented without the hunk they belong to.
+pub fn accepted_diff<'a>(
+    items: impl IntoIterator<Item = &'a StatefulHunkDiff>,
+) -> Result<String, Error> {
+    let mut buf = Vec::new();
+    let mut writer = unified_diff::Writer::new(&mut buf);
+
+    // The header of the current file, if it was written already and the delta
+    // of all hunks that were left out so far.
+    let mut file: Option<(FileHeader, bool, i32)> = None;
+
+    for item in items {
+        let hunk = item.hunk();
+        let header = hunk.file_header();
+
+        if file
+            .as_ref()
+            .map(|(current, ..)| *current != header)
+            .unwrap_or(true)
+        {
+            file = Some((header, false, 0));
+        }
+        let Some((header, written, delta)) = file.as_mut() else {
+            continue;
+        };
+
+        if *item.state() != HunkState::Accepted {
+            if let Some(h) = hunk.hunk_header() {
+                *delta += h.new_size as i32 - h.old_size as i32;
+            }
+            continue;
+        }
+        if let HunkDiff::EofChanged { .. } = hunk {
+            continue;
+        }
+
+        if !*written {
+            writer.encode(&*header)?;
+            *written = true;
+        }
+
+        if let (Some(h), Some(mut hunk_header)) = (hunk.hunk(), hunk.hunk_header()) {
+            hunk_header.new_line_no = (hunk_header.new_line_no as i32 - *delta) as u32;
+
+            let h = Hunk {
+                header: hunk_header
+                    .to_unified_string()?
+                    .as_bytes()
+                    .to_owned()
+                    .into(),
+                lines: h.lines.clone(),
+                old: h.old.clone(),
+                new: h.new.clone(),
+            };
+            writer.encode(&h)?;
+        }
+    }
+    drop(writer);
+
+    Ok(String::from_utf8_lossy(&buf).into_owned())
+}
+
 /// Represents the reviewer's brain, ie. what they have seen or not seen in terms
 /// of changes introduced by a patch.
 #[derive(Clone, Debug)]
@@ -511,6 +582,62 @@ impl CommentBuilder {
         Ok(self.comments())
     }
 
+    /// Like `edit`, but only quotes the line of the hunk given and anchors the
+    /// comment written below it to that line.
+    pub fn edit_line(
+        mut self,
+        hunk: &Hunk<Modification>,
+        line: LineNumbers,
+    ) -> Result<Vec<ReviewComment>, Error> {
+        let unified = hunk.to_unified_string()?;
+        let lines = unified
+            .lines()
+            .map(|line| DiffLine::parse(Line::raw(line)))
+            .collect::<Vec<_>>();
+
+        let mut input = String::new();
+        for (text, numbers) in unified.lines().zip(line_numbers(&lines)) {
+            if numbers == line {
+                writeln!(&mut input, "> {text}")?;
+            }
+        }
+
+        let output = term::Editor::comment()
+            .extension("diff")
+            .initial(input)?
+            .edit()?;
+
+        if let Some(output) = output {
+            let comment = output
+                .lines()
+                .filter(|line| !line.trim_start().starts_with('>'))
+                .collect::<Vec<_>>()
+                .join("\n");
+            self.add_line_comment(&comment, line);
+        }
+        Ok(self.comments())
+    }
+
+    pub fn add_line_comment(&mut self, comment: &str, line: LineNumbers) -> &mut Self {
+        if comment.trim().is_empty() {
+            return self;
+        }
+        let range = |line: usize| CodeRange::Lines {
+            range: line..line + 1,
+        };
+
+        self.comments.push(ReviewComment {
+            location: CodeLocation {
+                commit: self.commit,
+                path: self.path.clone(),
+                old: line.old.map(range),
+                new: line.new.map(range),
+            },
+            body: comment.trim().to_owned(),
+        });
+        self
+    }
+
     pub fn add_hunk(&mut self, hunk: HunkHeader, input: &str) -> &mut Self {
         let lines = input.trim().lines().map(|l| l.trim());
         let (mut old_line, mut new_line) = (hunk.old_line_no as usize, hunk.new_line_no as usize);
@@ -915,4 +1042,52 @@ Comment on a split hunk.
             assert_eq!(left, right);
         }
     }
+
+    #[test]
+    fn test_review_comments_on_line() {
+        let commit = Oid::from_str("a32c4b93e2573fd83b15ac1ad6bf1317dc8fd760").unwrap();
+        let path = PathBuf::from_str("main.rs").unwrap();
+
+        let mut builder = CommentBuilder::new(commit, path.clone());
+        builder
+            .add_line_comment(
+                "Comment on a context line.\n",
+                LineNumbers {
+                    old: Some(10),
+                    new: Some(12),
+                },
+            )
+            .add_line_comment(
+                "Comment on an addition.",
+                LineNumbers {
+                    old: None,
+                    new: Some(13),
+                },
+            )
+            .add_line_comment(" ", LineNumbers::default());
+
+        assert_eq!(
+            builder.comments(),
+            vec![
+                ReviewComment {
+                    location: CodeLocation {
+                        commit,
+                        path: path.clone(),
+                        old: Some(CodeRange::Lines { range: 10..11 }),
+                        new: Some(CodeRange::Lines { range: 12..13 }),
+                    },
+                    body: "Comment on a context line.".to_owned(),
+                },
+                ReviewComment {
+                    location: CodeLocation {
+                        commit,
+                        path,
+                        old: None,
+                        new: Some(CodeRange::Lines { range: 13..14 }),
+                    },
+                    body: "Comment on an addition.".to_owned(),
+                },
+            ]
+        );
+    }
 }
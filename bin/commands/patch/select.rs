@@ -9,6 +9,7 @@ use anyhow::Result;
 
 use ratatui::Viewport;
 use termion::event::Key;
+use tokio::sync::mpsc::UnboundedSender;
 
 use radicle_tui as tui;
 
@@ -17,35 +18,67 @@ use ratatui::style::Stylize;
 use ratatui::text::Text;
 
 use tui::store;
+use tui::task::watch::Watch;
+use tui::task::{self, Process};
+use tui::ui::print;
 use tui::ui::rm::widget::container::{Container, Footer, FooterProps, Header, HeaderProps};
 use tui::ui::rm::widget::input::{TextView, TextViewProps, TextViewState};
 use tui::ui::rm::widget::window::{
     Page, PageProps, Shortcuts, ShortcutsProps, Window, WindowProps,
 };
 use tui::ui::rm::widget::{ToWidget, Widget};
+use tui::ui::theme::Theme;
 use tui::ui::Column;
-use tui::ui::{span, BufferedValue};
+use tui::ui::{span, BufferedValue, Shortcut};
 
 use tui::{BoxedAny, Channel, Exit, PageStack};
 
-use radicle::patch::PatchId;
+use radicle::identity::RepoId;
+use radicle::patch::{Patch, PatchId};
 use radicle::storage::git::Repository;
+use radicle::storage::{ReadRepository, ReadStorage};
 use radicle::Profile;
 
 use self::rmui::{Browser, BrowserProps};
 use super::common::{Mode, PatchOperation};
 
 use crate::cob::patch;
-use crate::ui::items::{PatchItem, PatchItemFilter};
+use crate::history::{self, Interface};
+use crate::settings::{Action, Settings};
+use crate::ui::format;
+use crate::ui::items::{Filter, PatchItem, PatchItemFilter};
+use crate::ui::picker::{AutoPick, Picker, PickerItem};
 use crate::ui::rm::BrowserState;
+use crate::ui::TerminalInfo;
 
-type Selection = tui::Selection<PatchId>;
+pub type Selection = tui::Selection<PatchId>;
+
+/// The number of patches loaded at once while the interface is shown.
+const LOAD_BATCH_SIZE: usize = 50;
 
 pub struct Context {
     pub profile: Profile,
     pub repository: Repository,
     pub mode: Mode,
     pub filter: patch::Filter,
+    /// The search patches are filtered by on start, instead of the one of `filter`.
+    pub search: Option<String>,
+    pub viewport: Viewport,
+    pub watch: bool,
+    /// A previous selection whose patch should be selected again.
+    pub restore: Option<Selection>,
+    /// If the fuzzy picker is run instead of the browser.
+    pub fuzzy: bool,
+    /// The patch that is selected right away, without running the browser.
+    pub pick: Option<AutoPick>,
+}
+
+impl Context {
+    fn search(&self) -> String {
+        self.search
+            .clone()
+            .unwrap_or_else(|| self.filter.to_string())
+    }
 }
 
 pub struct App {
@@ -58,18 +91,65 @@ impl App {
         Self { context, im }
     }
 
+    /// Renders all patches that match the filter in the columns of the patch
+    /// list as plain text, instead of running the interface.
+    pub fn print(&self, color: bool) -> Result<String> {
+        let patches = load_filtered_items(&self.context)?;
+
+        Ok(print::table(
+            &patches,
+            &rmui::columns(),
+            print::terminal_width(),
+            color,
+        ))
+    }
+
     pub async fn run(&self) -> Result<Option<Selection>> {
-        let viewport = Viewport::Inline(20);
+        let viewport = self.context.viewport.clone();
+
+        if let Some(pick) = self.context.pick {
+            let operation = match self.context.mode {
+                Mode::Operation => Some(PatchOperation::Show.to_string()),
+                Mode::Id => None,
+            };
+            let patch = pick.pick(load_filtered_items(&self.context)?);
+
+            return Ok(patch.map(|patch| Selection {
+                operation,
+                ids: vec![patch.id],
+                args: vec![],
+            }));
+        }
+        if self.context.fuzzy {
+            return pick(&self.context).await;
+        }
 
         if self.im {
             let channel = Channel::default();
             let state = imui::App::try_from(&self.context)?;
+            let loader = Loader {
+                profile: self.context.profile.clone(),
+                rid: self.context.repository.id,
+            };
+            task::spawn(loader, channel.tx.clone());
+
+            if self.context.watch {
+                let watch = watch(&self.context, |items| imui::Message::Refresh { items });
+                task::spawn(watch, channel.tx.clone());
+            }
 
             tui::im(state, viewport, channel).await
         } else {
             let channel = Channel::default();
             let tx = channel.tx.clone();
             let state = State::try_from(&self.context)?;
+
+            if self.context.watch {
+                task::spawn(
+                    watch(&self.context, |items| Message::Refresh { items }),
+                    tx.clone(),
+                );
+            }
             let window = Window::default()
                 .page(AppPage::Browse, browser_page(&state, &channel))
                 .page(AppPage::Help, help_page(&state, &channel))
@@ -99,36 +179,41 @@ pub struct HelpState {
 
 #[derive(Clone, Debug)]
 pub struct State {
+    rid: RepoId,
     mode: Mode,
     pages: PageStack<AppPage>,
     browser: BrowserState<PatchItem, PatchItemFilter>,
+    /// The search of the filter given on start, which the search can be reset to.
+    default_search: String,
     help: HelpState,
+    theme: Theme,
 }
 
 impl TryFrom<&Context> for State {
     type Error = anyhow::Error;
 
     fn try_from(context: &Context) -> Result<Self, Self::Error> {
-        let patches = patch::all(&context.profile, &context.repository)?;
-        let search = BufferedValue::new(context.filter.to_string());
-        let filter = PatchItemFilter::from_str(&context.filter.to_string()).unwrap_or_default();
-
-        // Convert into UI items
-        let mut items = vec![];
-        for patch in patches {
-            if let Ok(item) = PatchItem::new(&context.profile, &context.repository, patch.clone()) {
-                items.push(item);
-            }
+        let items = load_items(&context.profile, &context.repository)?;
+        let search = BufferedValue::new(context.search());
+        let filter = PatchItemFilter::from_str(&search.read()).unwrap_or_default();
+
+        let mut browser = BrowserState::build(items.clone(), filter, search);
+        if let Some(restore) = &context.restore {
+            browser.select_item_by(|patch| restore.ids.contains(&patch.id));
         }
-        items.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
 
         Ok(Self {
+            rid: context.repository.id,
             mode: context.mode.clone(),
             pages: PageStack::new(vec![AppPage::Browse]),
-            browser: BrowserState::build(items.clone(), filter, search),
+            browser,
+            default_search: context.filter.to_string(),
             help: HelpState {
                 text: TextViewState::default().content(help_text()),
             },
+            theme: Settings::current().theme.theme(&TerminalInfo {
+                luma: terminal_light::luma().ok(),
+            }),
         })
     }
 }
@@ -143,9 +228,13 @@ pub enum Message {
     UpdateSearch { value: String },
     ApplySearch,
     CloseSearch,
+    ResetSearch,
+    ToggleAuthored,
+    CycleStateFilter,
     OpenHelp,
     LeavePage,
     ScrollHelp { state: TextViewState },
+    Refresh { items: Vec<PatchItem> },
 }
 
 impl store::Update<Message> for State {
@@ -191,6 +280,7 @@ impl store::Update<Message> for State {
             Message::ApplySearch => {
                 self.browser.hide_search();
                 self.browser.apply_search();
+                self.remember_search();
                 None
             }
             Message::CloseSearch => {
@@ -198,6 +288,25 @@ impl store::Update<Message> for State {
                 self.browser.reset_search();
                 None
             }
+            Message::ResetSearch => {
+                self.browser.update_search(self.default_search.clone());
+                self.browser.apply_search();
+                self.remember_search();
+                self.browser.select_first_item();
+                None
+            }
+            Message::ToggleAuthored => {
+                self.browser.toggle_search_word("is:authored");
+                self.remember_search();
+                self.browser.select_first_item();
+                None
+            }
+            Message::CycleStateFilter => {
+                self.browser.cycle_search_state(&PatchItemFilter::STATUSES);
+                self.remember_search();
+                self.browser.select_first_item();
+                None
+            }
             Message::OpenHelp => {
                 self.pages.push(AppPage::Help);
                 None
@@ -210,8 +319,155 @@ impl store::Update<Message> for State {
                 self.help.text = state;
                 None
             }
+            Message::Refresh { items } => {
+                self.browser.replace_items(items, |item| item.id);
+                None
+            }
         }
     }
+
+    fn update_batch(&mut self, messages: Vec<Message>) -> Option<Exit<Selection>> {
+        store::update_pages(self, messages)
+    }
+}
+
+impl store::Pages for State {
+    type Page = AppPage;
+
+    fn page(&self) -> Option<AppPage> {
+        self.pages.peek().ok().cloned()
+    }
+
+    fn on_exit_page(&mut self, page: &AppPage) {
+        if *page == AppPage::Help {
+            self.help.text = TextViewState::default().content(help_text());
+        }
+    }
+}
+
+impl State {
+    /// Remembers the applied search, such that the next session starts with it.
+    fn remember_search(&self) {
+        history::save_search(
+            Some(&self.rid),
+            Interface::Patches,
+            &self.browser.read_search(),
+        );
+    }
+}
+
+/// Loads all patches of the given repository and converts them into items,
+/// sorted by their timestamp.
+fn load_items(profile: &Profile, repository: &Repository) -> Result<Vec<PatchItem>> {
+    let patches = patch::all(profile, repository)?;
+
+    let mut items = patches
+        .into_iter()
+        .map(|patch| to_item(profile, repository, patch))
+        .collect::<Vec<_>>();
+    items.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+
+    Ok(items)
+}
+
+fn to_item(profile: &Profile, repository: &Repository, patch: (PatchId, Patch)) -> PatchItem {
+    PatchItem::new(profile, repository, patch).with_settings(Settings::current())
+}
+
+/// A `Process` that loads all patches of a repository in batches, such that the
+/// interface is shown right away and the loading progress can be shown. Used by
+/// the immediate mode interface, see `imui::Message::ItemsLoaded`.
+struct Loader {
+    profile: Profile,
+    rid: RepoId,
+}
+
+impl Process<imui::Message> for Loader {
+    async fn run(self, tx: UnboundedSender<imui::Message>) -> anyhow::Result<()> {
+        let Loader { profile, rid } = self;
+        let loader_tx = tx.clone();
+
+        let loaded =
+            tokio::task::spawn_blocking(move || load_batched(&profile, rid, &loader_tx)).await?;
+        if let Err(err) = loaded {
+            log::warn!("Failed to load patches: {}", err);
+        }
+        let _ = tx.send(imui::Message::LoadingFinished);
+
+        Ok(())
+    }
+}
+
+/// Loads all patches of the given repository and sends them in batches.
+fn load_batched(profile: &Profile, rid: RepoId, tx: &UnboundedSender<imui::Message>) -> Result<()> {
+    let repository = profile.storage.repository(rid)?;
+
+    let mut loaded = 0;
+    patch::batched(profile, &repository, LOAD_BATCH_SIZE, |batch, total| {
+        loaded += batch.len();
+        let items = batch
+            .into_iter()
+            .map(|patch| to_item(profile, &repository, patch))
+            .collect();
+        let _ = tx.send(imui::Message::ItemsLoaded {
+            items,
+            loaded,
+            total,
+        });
+    })
+}
+
+/// Loads all patches that match the filter given, newest first.
+fn load_filtered_items(context: &Context) -> Result<Vec<PatchItem>> {
+    let filter = PatchItemFilter::from_str(&context.search()).unwrap_or_default();
+
+    Ok(load_items(&context.profile, &context.repository)?
+        .into_iter()
+        .filter(|patch| filter.matches(patch))
+        .collect())
+}
+
+/// Runs the fuzzy picker on all patches that match the filter given and returns
+/// the id of the patch picked.
+async fn pick(context: &Context) -> Result<Option<Selection>> {
+    let items = load_filtered_items(context)?
+        .into_iter()
+        .map(|patch| {
+            PickerItem::new(
+                patch.id,
+                format!("{} {}", format::cob(&patch.id), patch.title),
+            )
+        })
+        .collect();
+
+    tui::im(Picker::new(items), Viewport::Fullscreen, Channel::default()).await
+}
+
+/// Builds a process that reloads all patches whenever the refs of the repository
+/// change, and sends them as the message built by `refresh`.
+fn watch<M>(context: &Context, refresh: fn(Vec<PatchItem>) -> M) -> impl Process<M>
+where
+    M: Send + 'static,
+{
+    let profile = context.profile.clone();
+    let rid = context.repository.id;
+
+    Watch::new(move || {
+        let items = profile
+            .storage
+            .repository(rid)
+            .map_err(anyhow::Error::from)
+            .and_then(|repository| load_items(&profile, &repository));
+
+        match items {
+            Ok(items) => Some(refresh(items)),
+            Err(err) => {
+                log::warn!("Failed to reload patches: {}", err);
+                None
+            }
+        }
+    })
+    .path(context.repository.path().join("refs"))
 }
 
 fn browser_page(_state: &State, channel: &Channel<Message>) -> Widget<State, Message> {
@@ -224,23 +480,31 @@ fn browser_page(_state: &State, channel: &Channel<Message>) -> Widget<State, Mes
     let shortcuts = Shortcuts::default()
         .to_widget(tx.clone())
         .on_update(|state: &State| {
-            let shortcuts = if state.browser.is_search_shown() {
-                vec![("esc", "cancel"), ("enter", "apply")]
+            let shortcuts: Vec<Shortcut> = if state.browser.is_search_shown() {
+                vec![("esc", "cancel").into(), ("enter", "apply").into()]
             } else {
                 match state.mode {
-                    Mode::Id => vec![("enter", "select"), ("/", "search")],
+                    Mode::Id => vec![
+                        ("enter", "select").into(),
+                        ("/", "search").into(),
+                        ("m", "mine").into(),
+                        ("s", "state").into(),
+                    ],
                     Mode::Operation => vec![
-                        ("enter", "show"),
-                        ("c", "checkout"),
-                        ("d", "diff"),
-                        ("/", "search"),
-                        ("?", "help"),
+                        ("enter", "show").into(),
+                        ("c", "checkout").into(),
+                        ("d", "diff").into(),
+                        ("r", "review").into(),
+                        ("/", "search").into(),
+                        ("m", "mine").into(),
+                        ("s", "state").into(),
+                        Settings::current().keys.shortcut(Action::Help, "help"),
                     ],
                 }
             };
 
             ShortcutsProps::default()
-                .shortcuts(&shortcuts)
+                .shortcuts(shortcuts)
                 .to_boxed_any()
                 .into()
         });
@@ -255,10 +519,14 @@ fn browser_page(_state: &State, channel: &Channel<Message>) -> Widget<State, Mes
                 .and_then(|props| props.inner_ref::<PageProps>())
                 .unwrap_or(&default);
 
+            let keys = &Settings::current().keys;
             if props.handle_keys {
                 match key {
-                    Key::Esc | Key::Ctrl('c') => Some(Message::Quit),
-                    Key::Char('?') => Some(Message::OpenHelp),
+                    key if keys.matches(Action::Quit, key) => Some(Message::Quit),
+                    key if keys.matches(Action::Help, key) => Some(Message::OpenHelp),
+                    Key::Char('m') => Some(Message::ToggleAuthored),
+                    Key::Char('s') => Some(Message::CycleStateFilter),
+                    Key::Ctrl('r') => Some(Message::ResetSearch),
                     Key::Char('\n') => Some(Message::ExitFromMode),
                     Key::Char('c') => Some(Message::Exit {
                         operation: Some(PatchOperation::Checkout),
@@ -266,6 +534,9 @@ fn browser_page(_state: &State, channel: &Channel<Message>) -> Widget<State, Mes
                     Key::Char('d') => Some(Message::Exit {
                         operation: Some(PatchOperation::Diff),
                     }),
+                    Key::Char('r') => Some(Message::Exit {
+                        operation: Some(PatchOperation::Review),
+                    }),
                     _ => None,
                 }
             } else {
@@ -328,7 +599,7 @@ fn help_page(_state: &State, channel: &Channel<Message>) -> Widget<State, Messag
 
     let shortcuts = Shortcuts::default().to_widget(tx.clone()).on_update(|_| {
         ShortcutsProps::default()
-            .shortcuts(&[("?", "close")])
+            .shortcuts([Settings::current().keys.shortcut(Action::Help, "close")])
             .to_boxed_any()
             .into()
     });
@@ -337,10 +608,13 @@ fn help_page(_state: &State, channel: &Channel<Message>) -> Widget<State, Messag
         .content(content)
         .shortcuts(shortcuts)
         .to_widget(tx.clone())
-        .on_event(|key, _, _| match key {
-            Key::Esc | Key::Ctrl('c') => Some(Message::Quit),
-            Key::Char('?') => Some(Message::LeavePage),
-            _ => None,
+        .on_event(|key, _, _| {
+            let keys = &Settings::current().keys;
+            match key {
+                key if keys.matches(Action::Quit, key) => Some(Message::Quit),
+                key if keys.matches(Action::Help, key) => Some(Message::LeavePage),
+                _ => None,
+            }
         })
         .on_update(|_| PageProps::default().handle_keys(true).to_boxed_any().into())
 }
@@ -362,12 +636,22 @@ fn help_text() -> String {
 `enter`:    Show patch
 `c`:        Checkout patch
 `d`:        Show patch diff
+`r`:        Review patch, then return to the list
 `/`:        Search
-`?`:        Show help
+`m`:        Toggle showing only patches authored by you
+`s`:        Cycle the status filter: open, draft, merged, archived, all
+`^R`:       Reset the search to the filter given on start
+`:`:        Pick an operation from a palette
+`?,F1`:     Show help
 
 # Searching
 
 Pattern:    is:<state> | is:authored | authors:[<did>, <did>] | <search>
-Example:    is:open is:authored improve"#
+            state=<state> | author=<did> | <key>=(<value> or <value> ...)
+            size:<xs|s|m|l|xl> | size=(<size> or <size>) | lines:<<n> | lines:><n>
+            since:<time> | until:<time>, e.g. since:7d or until:2024-01-01
+Example:    is:open is:authored improve
+Example:    state=(open or draft) improve
+Example:    is:open size:xs lines:<20"#
         .into()
 }
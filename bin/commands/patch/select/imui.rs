@@ -1,4 +1,5 @@
 use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use anyhow::Result;
 
@@ -9,18 +10,27 @@ use ratatui::style::Stylize;
 use ratatui::text::Span;
 use ratatui::Frame;
 
+use serde::{Deserialize, Serialize};
+
 use radicle_tui as tui;
 
 use tui::ui::im;
-use tui::ui::im::widget::{PanesState, TableState, TextEditState, TextViewState, Window};
+use tui::ui::im::widget::{
+    HeaderedTable, PanesState, TableState, TextEditState, TextViewState, Window,
+};
 use tui::ui::im::Borders;
 use tui::ui::im::Show;
-use tui::ui::{BufferedValue, Column};
+use tui::ui::utils::DateHistogram;
+use tui::ui::{BufferedValue, Column, Highlight, Shortcut};
 use tui::{store, Exit};
 
-use crate::cob::patch;
+use radicle::identity::RepoId;
+use radicle::patch::PatchId;
+
+use crate::history::{self, Interface};
+use crate::settings;
 use crate::tui_patch::common::{Mode, PatchOperation};
-use crate::ui::items::{Filter, PatchItem, PatchItemFilter};
+use crate::ui::items::{self, Filter, PatchItem, PatchItemFilter};
 
 use super::{Context, Selection};
 
@@ -32,6 +42,7 @@ const HELP: &str = r#"# Generic keybindings
 `PageDown`: move cursor one page down
 `Home`:     move cursor to the first line
 `End`:      move cursor to the last line
+`<n>↓,<n>j`: move cursor n lines down (and up, accordingly)
 `Esc`:      Quit / cancel
 
 # Specific keybindings
@@ -40,17 +51,27 @@ const HELP: &str = r#"# Generic keybindings
 `enter`:    Show patch
 `c`:        Checkout patch
 `d`:        Show patch diff
+`r`:        Review patch, then return to the list
 `/`:        Search
-`?`:        Show help
+`m`:        Toggle showing only patches authored by you
+`s`:        Cycle the status filter: open, draft, merged, archived, all
+`^R`:       Reset the search to the filter given on start
+`?,F1`:     Show help
 
 # Searching
 
 Pattern:    is:<state> | is:authored | authors:[<did>, <did>] | <search>
-Example:    is:open is:authored improve"#;
+            state=<state> | author=<did> | <key>=(<value> or <value> ...)
+            size:<xs|s|m|l|xl> | size=(<size> or <size>) | lines:<<n> | lines:><n>
+            since:<time> | until:<time>, e.g. since:7d or until:2024-01-01
+Example:    is:open is:authored improve
+Example:    state=(open or draft) improve
+Example:    is:open size:xs lines:<20"#;
 
 #[derive(Clone, Debug)]
 pub enum Message {
     Quit,
+    CancelQuit,
     Exit {
         operation: Option<PatchOperation>,
     },
@@ -68,20 +89,43 @@ pub enum Message {
         state: TextViewState,
     },
     ShowSearch,
+    ResetSearch,
+    ToggleAuthored,
+    CycleStateFilter,
     UpdateSearch {
         search: BufferedValue<TextEditState>,
     },
     HideSearch {
         apply: bool,
     },
+    Refresh {
+        items: Vec<PatchItem>,
+    },
+    /// A batch of patches was loaded, `loaded` of `total` patches are loaded
+    /// so far.
+    ItemsLoaded {
+        items: Vec<PatchItem>,
+        loaded: usize,
+        total: usize,
+    },
+    LoadingFinished,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub enum Page {
     Main,
     Help,
 }
 
+/// What's restored of the browser after a crash, see `store::Update::snapshot`.
+#[derive(Debug, Serialize, Deserialize)]
+struct Snapshot {
+    /// The search applied.
+    search: String,
+    /// The patch selected, which is selected again once it's loaded.
+    selected: Option<PatchId>,
+}
+
 #[derive(Clone, Debug)]
 pub struct Storage {
     patches: Vec<PatchItem>,
@@ -89,40 +133,40 @@ pub struct Storage {
 
 #[derive(Clone, Debug)]
 pub struct App {
+    rid: RepoId,
     storage: Storage,
     mode: Mode,
     page: Page,
     main_group: PanesState,
     patches: TableState,
     search: BufferedValue<TextEditState>,
+    /// The search of the filter given on start, which the search can be reset to.
+    default_search: String,
     show_search: bool,
     help: TextViewState,
     filter: PatchItemFilter,
+    confirm_quit: bool,
+    quitting: bool,
+    /// The number of patches loaded and the number of all patches, while they
+    /// are loaded.
+    loading: Option<(usize, usize)>,
+    /// Ids of a previous selection, which is restored once one of them is loaded.
+    restore: Vec<PatchId>,
+    /// The number of days the activity of the selected patch is shown for.
+    activity_days: usize,
 }
 
 impl TryFrom<&Context> for App {
     type Error = anyhow::Error;
 
     fn try_from(context: &Context) -> Result<Self, Self::Error> {
-        let patches = patch::all(&context.profile, &context.repository)?;
-        let search = {
-            let raw = context.filter.to_string();
-            raw.trim().to_string()
-        };
-        let filter = PatchItemFilter::from_str(&context.filter.to_string()).unwrap_or_default();
-
-        let mut items = vec![];
-        for patch in patches {
-            if let Ok(item) = PatchItem::new(&context.profile, &context.repository, patch.clone()) {
-                items.push(item);
-            }
-        }
-        items.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+        let search = context.search().trim().to_string();
+        let filter = PatchItemFilter::from_str(&search).unwrap_or_default();
 
+        // Patches are loaded asynchronously and added via `Message::ItemsLoaded`.
         Ok(App {
-            storage: Storage {
-                patches: items.clone(),
-            },
+            rid: context.repository.id,
+            storage: Storage { patches: vec![] },
             mode: context.mode.clone(),
             page: Page::Main,
             main_group: PanesState::new(3, Some(0)),
@@ -131,9 +175,21 @@ impl TryFrom<&Context> for App {
                 text: search.clone(),
                 cursor: search.len(),
             }),
+            default_search: context.filter.to_string().trim().to_string(),
             show_search: false,
             help: TextViewState::new(Position::default()),
             filter,
+            confirm_quit: settings::Settings::current()
+                .confirm_quit
+                .unwrap_or_default(),
+            quitting: false,
+            loading: Some((0, 0)),
+            restore: context
+                .restore
+                .as_ref()
+                .map(|restore| restore.ids.clone())
+                .unwrap_or_default(),
+            activity_days: settings::Settings::current().activity_days,
         })
     }
 }
@@ -145,7 +201,18 @@ impl store::Update<Message> for App {
         log::debug!("[State] Received message: {:?}", message);
 
         match message {
-            Message::Quit => Some(Exit { value: None }),
+            Message::Quit => {
+                if self.confirm_quit && !self.quitting {
+                    self.quitting = true;
+                    None
+                } else {
+                    Some(Exit { value: None })
+                }
+            }
+            Message::CancelQuit => {
+                self.quitting = false;
+                None
+            }
             Message::Exit { operation } => self.selected_patch().map(|issue| Exit {
                 value: Some(Selection {
                     operation: operation.map(|op| op.to_string()),
@@ -190,6 +257,7 @@ impl store::Update<Message> for App {
 
                 if apply {
                     self.search.apply();
+                    self.remember_search();
                 } else {
                     self.search.reset();
                 }
@@ -199,6 +267,21 @@ impl store::Update<Message> for App {
 
                 None
             }
+            Message::ResetSearch => {
+                self.apply_search(self.default_search.clone());
+                None
+            }
+            Message::ToggleAuthored => {
+                let text = items::toggle_search_word(&self.search.read().text, "is:authored");
+                self.apply_search(text);
+                None
+            }
+            Message::CycleStateFilter => {
+                let text =
+                    items::cycle_search_state(&self.search.read().text, &PatchItemFilter::STATUSES);
+                self.apply_search(text);
+                None
+            }
             Message::UpdateSearch { search } => {
                 self.search = search;
                 self.filter =
@@ -210,6 +293,79 @@ impl store::Update<Message> for App {
                 self.help = state;
                 None
             }
+            Message::Refresh { items } => {
+                self.set_patches(items);
+                None
+            }
+            Message::ItemsLoaded {
+                items,
+                loaded,
+                total,
+            } => {
+                let mut patches = std::mem::take(&mut self.storage.patches);
+                patches.extend(items);
+                patches.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+                self.set_patches(patches);
+                self.loading = Some((loaded, total));
+                None
+            }
+            Message::LoadingFinished => {
+                self.loading = None;
+                None
+            }
+        }
+    }
+
+    fn update_batch(&mut self, messages: Vec<Message>) -> Option<tui::Exit<Selection>> {
+        store::update_pages(self, messages)
+    }
+
+    fn snapshot(&self) -> Option<Vec<u8>> {
+        // While patches are loaded, a selection to restore is not selected yet.
+        let selected = self
+            .selected_patch()
+            .map(|patch| patch.id)
+            .or_else(|| self.restore.first().copied());
+        let snapshot = Snapshot {
+            search: self.search.read().text,
+            selected,
+        };
+
+        serde_json::to_vec(&snapshot).ok()
+    }
+
+    fn snapshot_key(&self) -> Option<String> {
+        Some(self.rid.to_string())
+    }
+
+    fn restore(&self, snapshot: &[u8]) -> Result<Option<Self>> {
+        let snapshot = serde_json::from_slice::<Snapshot>(snapshot)?;
+        let mut app = self.clone();
+
+        app.search = BufferedValue::new(TextEditState {
+            cursor: snapshot.search.chars().count(),
+            text: snapshot.search,
+        });
+        app.filter = PatchItemFilter::from_str(&app.search.read().text).unwrap_or_default();
+        // A selection given on start takes precedence.
+        if app.restore.is_empty() {
+            app.restore = snapshot.selected.into_iter().collect();
+        }
+
+        Ok(Some(app))
+    }
+}
+
+impl store::Pages for App {
+    type Page = Page;
+
+    fn page(&self) -> Option<Page> {
+        Some(self.page.clone())
+    }
+
+    fn on_exit_page(&mut self, page: &Page) {
+        if *page == Page::Help {
+            self.help = TextViewState::new(Position::default());
         }
     }
 }
@@ -217,170 +373,248 @@ impl store::Update<Message> for App {
 impl Show<Message> for App {
     fn show(&self, ctx: &im::Context<Message>, frame: &mut Frame) -> Result<()> {
         Window::default().show(ctx, |ui| {
-            match self.page {
-                Page::Main => {
-                    let show_search = self.show_search;
-                    let mut page_focus = if show_search { Some(1) } else { Some(0) };
-                    let mut group_focus = self.main_group.focus();
-
-                    ui.panes(
-                        Layout::vertical([Constraint::Fill(1), Constraint::Length(2)]),
-                        &mut page_focus,
-                        |ui| {
-                            let group = ui.panes(
-                                im::Layout::Expandable3 { left_only: true },
-                                &mut group_focus,
-                                |ui| {
-                                    self.show_patches(frame, ui);
-
-                                    ui.text_view(
-                                        frame,
-                                        String::new(),
-                                        &mut Position::default(),
-                                        Some(Borders::All),
-                                    );
-                                    ui.text_view(
-                                        frame,
-                                        String::new(),
-                                        &mut Position::default(),
-                                        Some(Borders::All),
-                                    );
-                                },
-                            );
-                            if group.response.changed {
-                                ui.send_message(Message::MainGroupChanged {
-                                    state: PanesState::new(3, group_focus),
-                                });
-                            }
-
-                            if show_search {
-                                self.show_search_text_edit(frame, ui);
-                            } else {
-                                ui.layout(Layout::vertical([1, 1]), None, |ui| {
-                                    ui.bar(
-                                        frame,
-                                        match group_focus {
-                                            Some(0) => browser_context(ui, self),
-                                            _ => default_context(ui),
-                                        },
-                                        Some(Borders::None),
-                                    );
-
-                                    ui.shortcuts(
-                                        frame,
-                                        &match self.mode {
-                                            Mode::Id => {
-                                                [("enter", "select"), ("/", "search")].to_vec()
-                                            }
-                                            Mode::Operation => [
-                                                ("enter", "show"),
-                                                ("c", "checkout"),
-                                                ("d", "diff"),
-                                                ("/", "search"),
-                                                ("?", "help"),
-                                            ]
-                                            .to_vec(),
-                                        },
-                                        '∙',
-                                    );
-                                });
+            ui.set_repeat_counts(true);
+
+            // The confirm dialog takes all input while it's shown. Modals only
+            // take it from the widgets below from the frame after the one they
+            // were shown first in.
+            if self.quitting {
+                ui.without_inputs(|ui| self.show_page(frame, ui));
+            } else {
+                self.show_page(frame, ui);
+            }
 
-                                if ui.input_global(|key| key == Key::Esc) {
-                                    ui.send_message(Message::Quit);
-                                }
-                                if ui.input_global(|key| key == Key::Char('?')) {
-                                    ui.send_message(Message::PageChanged { page: Page::Help });
-                                }
-                                if ui.input_global(|key| key == Key::Char('\n')) {
-                                    ui.send_message(Message::ExitFromMode);
-                                }
-                                if ui.input_global(|key| key == Key::Char('d')) {
-                                    ui.send_message(Message::Exit {
-                                        operation: Some(PatchOperation::Diff),
-                                    });
-                                }
-                                if ui.input_global(|key| key == Key::Char('c')) {
-                                    ui.send_message(Message::Exit {
-                                        operation: Some(PatchOperation::Checkout),
-                                    });
-                                }
-                            }
-                        },
-                    );
+            if self.quitting {
+                let mut confirmed = None;
+                ui.confirm(
+                    frame,
+                    "Quit",
+                    "Quit without selecting a patch?",
+                    &mut confirmed,
+                );
+                match confirmed {
+                    Some(true) => ui.send_message(Message::Quit),
+                    Some(false) => ui.send_message(Message::CancelQuit),
+                    None => {}
                 }
+            }
+        });
 
-                Page::Help => {
-                    let mut cursor = self.help.cursor();
-
-                    let layout = Layout::vertical([
-                        Constraint::Length(3),
-                        Constraint::Fill(1),
-                        Constraint::Length(1),
-                        Constraint::Length(1),
-                    ]);
-
-                    ui.composite(layout, 1, |ui| {
-                        ui.columns(
-                            frame,
-                            [Column::new(Span::raw(" Help ").bold(), Constraint::Fill(1))].to_vec(),
-                            Some(Borders::Top),
-                        );
+        Ok(())
+    }
+}
 
-                        let text_view = ui.text_view(
-                            frame,
-                            HELP.to_string(),
-                            &mut cursor,
-                            Some(Borders::BottomSides),
+impl App {
+    fn show_page(&self, frame: &mut Frame, ui: &mut im::Ui<Message>) {
+        match self.page {
+            Page::Main => {
+                let select = match self.mode {
+                    Mode::Id => Shortcut::new([Key::Char('\n')], "select"),
+                    Mode::Operation => Shortcut::new([Key::Char('\n')], "show"),
+                };
+                let checkout = Shortcut::new([Key::Char('c')], "checkout");
+                let diff = Shortcut::new([Key::Char('d')], "diff");
+                let review = Shortcut::new([Key::Char('r')], "review");
+                let search = Shortcut::new([Key::Char('/')], "search");
+                let mine = Shortcut::new([Key::Char('m')], "mine");
+                let state = Shortcut::new([Key::Char('s')], "state");
+                let help = Shortcut::new([Key::Char('?')], "help");
+
+                let show_search = self.show_search;
+                let mut page_focus = if show_search { Some(1) } else { Some(0) };
+                let mut group_focus = self.main_group.focus();
+
+                ui.panes(
+                    Layout::vertical([Constraint::Fill(1), Constraint::Length(2)]),
+                    &mut page_focus,
+                    |ui| {
+                        let group = ui.panes(
+                            im::Layout::Expandable3 { left_only: true },
+                            &mut group_focus,
+                            |ui| {
+                                self.show_patches(frame, ui);
+
+                                ui.text_view(
+                                    frame,
+                                    String::new(),
+                                    &mut Position::default(),
+                                    Some(Borders::All),
+                                );
+                                ui.text_view(
+                                    frame,
+                                    String::new(),
+                                    &mut Position::default(),
+                                    Some(Borders::All),
+                                );
+                            },
                         );
-                        if text_view.changed {
-                            ui.send_message(Message::HelpChanged {
-                                state: TextViewState::new(cursor),
-                            })
+                        if group.response.changed {
+                            ui.send_message(Message::MainGroupChanged {
+                                state: PanesState::new(3, group_focus),
+                            });
                         }
 
-                        ui.bar(
-                            frame,
-                            [
-                                Column::new(
-                                    Span::raw(" ".to_string())
-                                        .into_left_aligned_line()
-                                        .style(ui.theme().bar_on_black_style),
-                                    Constraint::Fill(1),
-                                ),
-                                Column::new(
-                                    Span::raw(" ")
-                                        .into_right_aligned_line()
-                                        .cyan()
-                                        .dim()
-                                        .reversed(),
-                                    Constraint::Length(6),
-                                ),
-                            ]
-                            .to_vec(),
-                            Some(Borders::None),
-                        );
+                        if show_search {
+                            self.show_search_text_edit(frame, ui);
+                        } else {
+                            ui.layout(Layout::vertical([1, 1]), None, |ui| {
+                                ui.layout(
+                                    Layout::horizontal([
+                                        Constraint::Fill(1),
+                                        Constraint::Length(self.activity_days as u16),
+                                    ]),
+                                    None,
+                                    |ui| {
+                                        ui.bar(
+                                            frame,
+                                            match group_focus {
+                                                Some(0) => browser_context(ui, self),
+                                                _ => default_context(ui),
+                                            },
+                                            Some(Borders::None),
+                                        );
+                                        match self.loading {
+                                            Some((loaded, total)) => {
+                                                ui.progress_bar(frame, loaded, total);
+                                            }
+                                            None => {
+                                                ui.sparkline(frame, &self.selected_activity());
+                                            }
+                                        }
+                                    },
+                                );
+
+                                ui.shortcuts(
+                                    frame,
+                                    match self.mode {
+                                        Mode::Id => {
+                                            vec![
+                                                select.clone(),
+                                                search.clone(),
+                                                mine.clone(),
+                                                state.clone(),
+                                            ]
+                                        }
+                                        Mode::Operation => vec![
+                                            select.clone(),
+                                            checkout.clone(),
+                                            diff.clone(),
+                                            review.clone(),
+                                            search.clone(),
+                                            mine.clone(),
+                                            state.clone(),
+                                            help.clone(),
+                                        ],
+                                    },
+                                    '∙',
+                                );
+                            });
+
+                            if ui.input_global(|key| key == Key::Esc) {
+                                ui.send_message(Message::Quit);
+                            }
+                            if ui.input_global(|key| help.matches(key) || key == Key::F(1)) {
+                                ui.send_message(Message::PageChanged { page: Page::Help });
+                            }
+                            if ui.input_global(|key| mine.matches(key)) {
+                                ui.send_message(Message::ToggleAuthored);
+                            }
+                            if ui.input_global(|key| state.matches(key)) {
+                                ui.send_message(Message::CycleStateFilter);
+                            }
+                            if ui.input_global(|key| key == Key::Ctrl('r')) {
+                                ui.send_message(Message::ResetSearch);
+                            }
+                            if ui.input_global(|key| select.matches(key)) {
+                                ui.send_message(Message::ExitFromMode);
+                            }
+                            if ui.input_global(|key| diff.matches(key)) {
+                                ui.send_message(Message::Exit {
+                                    operation: Some(PatchOperation::Diff),
+                                });
+                            }
+                            if ui.input_global(|key| checkout.matches(key)) {
+                                ui.send_message(Message::Exit {
+                                    operation: Some(PatchOperation::Checkout),
+                                });
+                            }
+                            if ui.input_global(|key| review.matches(key)) {
+                                ui.send_message(Message::Exit {
+                                    operation: Some(PatchOperation::Review),
+                                });
+                            }
+                        }
+                    },
+                );
+            }
 
-                        ui.shortcuts(frame, &[("?", "close")], '∙');
-                    });
+            Page::Help => {
+                let mut cursor = self.help.cursor();
 
-                    if ui.input_global(|key| key == Key::Char('?')) {
-                        ui.send_message(Message::PageChanged { page: Page::Main });
-                    }
-                    if ui.input_global(|key| key == Key::Esc) {
-                        ui.send_message(Message::Quit);
+                let layout = Layout::vertical([
+                    Constraint::Length(3),
+                    Constraint::Fill(1),
+                    Constraint::Length(1),
+                    Constraint::Length(1),
+                ]);
+
+                ui.composite(layout, 1, |ui| {
+                    ui.columns(
+                        frame,
+                        [Column::new(Span::raw(" Help ").bold(), Constraint::Fill(1))].to_vec(),
+                        Some(Borders::Top),
+                    );
+
+                    let text_view = ui.text_view(
+                        frame,
+                        HELP.to_string(),
+                        &mut cursor,
+                        Some(Borders::BottomSides),
+                    );
+                    if text_view.changed {
+                        ui.send_message(Message::HelpChanged {
+                            state: TextViewState::new(cursor),
+                        })
                     }
+
+                    ui.bar(
+                        frame,
+                        [
+                            Column::new(
+                                Span::raw(" ".to_string())
+                                    .into_left_aligned_line()
+                                    .style(ui.theme().bar_on_black_style),
+                                Constraint::Fill(1),
+                            ),
+                            Column::new(
+                                Span::raw(" ")
+                                    .into_right_aligned_line()
+                                    .cyan()
+                                    .dim()
+                                    .reversed(),
+                                Constraint::Length(6),
+                            ),
+                        ]
+                        .to_vec(),
+                        Some(Borders::None),
+                    );
+
+                    ui.shortcuts(frame, &[("?", "close")], '∙');
+                });
+
+                if ui.input_global(|key| key == Key::Char('?') || key == Key::F(1)) {
+                    ui.send_message(Message::PageChanged { page: Page::Main });
+                }
+                if ui.input_global(|key| key == Key::Esc) {
+                    ui.send_message(Message::Quit);
                 }
             }
-            if ui.input_global(|key| key == Key::Ctrl('c')) {
-                ui.send_message(Message::Quit);
-            }
-        });
-
-        Ok(())
+        }
+        if ui.input_global(|key| key == Key::Ctrl('c')) {
+            ui.send_message(Message::Quit);
+        }
     }
-}
 
-impl App {
     pub fn show_patches(&self, frame: &mut Frame, ui: &mut im::Ui<Message>) {
         let patches = self
             .storage
@@ -403,7 +637,15 @@ impl App {
             Column::new(Span::raw("Updated").bold(), Constraint::Length(16)).hide_small(),
         ];
 
-        let table = ui.headered_table(frame, &mut selected, &patches, header.clone(), header);
+        let highlight = self
+            .filter
+            .search()
+            .map(|search| Highlight::new(search, ui.theme().search_highlight_style));
+        let table = ui.add(
+            frame,
+            HeaderedTable::new(&mut selected, &patches, header.clone(), header)
+                .highlight(highlight),
+        );
         if table.changed {
             ui.send_message(Message::PatchesChanged {
                 state: TableState::new(selected),
@@ -449,6 +691,37 @@ impl App {
 }
 
 impl App {
+    /// Replaces all patches and keeps the selected one selected. A previous
+    /// selection is restored instead, once one of its patches is there. Ids of
+    /// one that are gone or filtered out are ignored.
+    fn set_patches(&mut self, items: Vec<PatchItem>) {
+        let selected = self.selected_patch().map(|patch| patch.id);
+        self.storage.patches = items;
+
+        let patches = self
+            .storage
+            .patches
+            .iter()
+            .filter(|patch| self.filter.matches(patch))
+            .collect::<Vec<_>>();
+        let restored = patches
+            .iter()
+            .position(|patch| self.restore.contains(&patch.id));
+        let position = restored
+            .or_else(|| selected.and_then(|id| patches.iter().position(|patch| patch.id == id)))
+            .or_else(|| {
+                self.patches
+                    .selected()
+                    .map(|s| s.min(patches.len().saturating_sub(1)))
+            })
+            .filter(|_| !patches.is_empty());
+
+        if restored.is_some() {
+            self.restore.clear();
+        }
+        self.patches = TableState::new(position);
+    }
+
     pub fn selected_patch(&self) -> Option<&PatchItem> {
         let patches = self
             .storage
@@ -462,6 +735,45 @@ impl App {
             .and_then(|selected| patches.get(selected))
             .copied()
     }
+
+    /// Returns the number of revisions and comments per day of the selected patch,
+    /// within the last `activity_days`.
+    pub fn selected_activity(&self) -> Vec<u64> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+
+        self.selected_patch()
+            .map(|patch| {
+                DateHistogram::new(self.activity_days)
+                    .counts(now, patch.activity.iter().map(|time| time.as_secs()))
+            })
+            .unwrap_or_default()
+    }
+
+    /// Applies and remembers the given search, then selects the first patch
+    /// that matches it.
+    fn apply_search(&mut self, text: String) {
+        self.search.write(TextEditState {
+            cursor: text.chars().count(),
+            text,
+        });
+        self.search.apply();
+        self.remember_search();
+
+        self.filter = PatchItemFilter::from_str(&self.search.read().text).unwrap_or_default();
+        self.patches.select_first();
+    }
+
+    /// Remembers the applied search, such that the next session starts with it.
+    fn remember_search(&self) {
+        history::save_search(
+            Some(&self.rid),
+            Interface::Patches,
+            &self.search.read().text,
+        );
+    }
 }
 
 fn browser_context<'a>(ui: &im::Ui<Message>, app: &'a App) -> Vec<Column<'a>> {
@@ -602,6 +914,14 @@ fn browser_context<'a>(ui: &im::Ui<Message>, app: &'a App) -> Vec<Column<'a>> {
             ]
             .to_vec()
         } else {
+            // The status filtered by, e.g. when cycled through with `s`.
+            let status = match app.filter.status() {
+                Some(radicle::patch::Status::Draft) => " draft ",
+                Some(radicle::patch::Status::Open) => " open ",
+                Some(radicle::patch::Status::Merged) => " merged ",
+                Some(radicle::patch::Status::Archived) => " archived ",
+                None => "",
+            };
             [
                 Column::new(
                     Span::raw(" Search ".to_string()).cyan().dim().reversed(),
@@ -613,6 +933,10 @@ fn browser_context<'a>(ui: &im::Ui<Message>, app: &'a App) -> Vec<Column<'a>> {
                         .style(ui.theme().bar_on_black_style),
                     Constraint::Fill(1),
                 ),
+                Column::new(
+                    Span::raw(status).style(ui.theme().bar_on_black_style).dim(),
+                    Constraint::Length(status.chars().count() as u16),
+                ),
                 Column::new(
                     Span::raw(filtered_counts.clone())
                         .into_right_aligned_line()
@@ -25,16 +25,33 @@ use tui::ui::rm::widget::list::{Table, TableProps};
 use tui::ui::rm::widget::ViewProps;
 use tui::ui::rm::widget::{RenderProps, ToWidget, View};
 use tui::ui::span;
-use tui::ui::Column;
+use tui::ui::{Cells, Column};
 
 use tui::BoxedAny;
 
-use crate::ui::items::{PatchItem, PatchItemFilter};
+use crate::ui;
+use crate::ui::items::{Filter, PatchItem, PatchItemFilter};
 
 use super::{Message, State};
 
 type Widget = widget::Widget<State, Message>;
 
+/// Returns the columns patches are listed in.
+pub fn columns<'a>() -> Vec<Column<'a>> {
+    [
+        Column::new(" ● ", Constraint::Length(3)),
+        Column::new("ID", Constraint::Length(8)),
+        Column::new("Title", Constraint::Fill(1)),
+        Column::new("Author", Constraint::Length(16)).hide_small(),
+        Column::new("", Constraint::Length(16)).hide_medium(),
+        Column::new("Head", Constraint::Length(8)).hide_small(),
+        Column::new("+", Constraint::Length(6)).hide_small(),
+        Column::new("-", Constraint::Length(6)).hide_small(),
+        Column::new("Updated", Constraint::Length(16)).hide_small(),
+    ]
+    .to_vec()
+}
+
 #[derive(Clone, Default)]
 pub struct BrowserProps<'a> {
     /// Filtered patches.
@@ -79,25 +96,23 @@ impl<'a> From<&State> for BrowserProps<'a> {
             ("Merged".to_string(), merged),
         ]);
 
+        let filter = state.browser.filter();
+        let chips = if filter.is_default() {
+            vec![]
+        } else {
+            filter.describe()
+        };
+
         Self {
             patches,
             stats,
             header: [
                 Column::new(" ● ", Constraint::Length(3)),
                 Column::new("ID", Constraint::Length(8)),
-                Column::new("Title", Constraint::Fill(1)),
-                Column::new("Author", Constraint::Length(16)).hide_small(),
-                Column::new("", Constraint::Length(16)).hide_medium(),
-                Column::new("Head", Constraint::Length(8)).hide_small(),
-                Column::new("+", Constraint::Length(6)).hide_small(),
-                Column::new("-", Constraint::Length(6)).hide_small(),
-                Column::new("Updated", Constraint::Length(16)).hide_small(),
-            ]
-            .to_vec(),
-            columns: [
-                Column::new(" ● ", Constraint::Length(3)),
-                Column::new("ID", Constraint::Length(8)),
-                Column::new("Title", Constraint::Fill(1)),
+                Column::new(
+                    ui::span::with_filter_chips("Title", &chips),
+                    Constraint::Fill(1),
+                ),
                 Column::new("Author", Constraint::Length(16)).hide_small(),
                 Column::new("", Constraint::Length(16)).hide_medium(),
                 Column::new("Head", Constraint::Length(8)).hide_small(),
@@ -106,6 +121,7 @@ impl<'a> From<&State> for BrowserProps<'a> {
                 Column::new("Updated", Constraint::Length(16)).hide_small(),
             ]
             .to_vec(),
+            columns: columns(),
             show_search: state.browser.is_search_shown(),
             search: state.browser.read_search(),
         }
@@ -132,7 +148,7 @@ impl Browser {
                         .into()
                 }))
                 .content(
-                    Table::<State, Message, PatchItem, 9>::default()
+                    Table::<State, Message, PatchItem, Cells<9>>::default()
                         .to_widget(tx.clone())
                         .on_event(|_, s, _| {
                             let (selected, _) =
@@ -148,6 +164,9 @@ impl Browser {
                                 .columns(props.columns)
                                 .items(state.browser.items())
                                 .selected(state.browser.selected())
+                                .highlight(
+                                    state.browser.highlight(state.theme.search_highlight_style),
+                                )
                                 .to_boxed_any()
                                 .into()
                         }),
@@ -191,6 +210,12 @@ impl View for Browser {
     type Message = Message;
     type State = State;
 
+    fn takes_text(&self, props: Option<&ViewProps>) -> bool {
+        props
+            .and_then(|props| props.inner_ref::<BrowserProps>())
+            .is_some_and(|props| props.show_search)
+    }
+
     fn handle_event(&mut self, props: Option<&ViewProps>, key: Key) -> Option<Self::Message> {
         let default = BrowserProps::default();
         let props = props
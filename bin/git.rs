@@ -1,5 +1,7 @@
+use std::collections::HashMap;
 use std::fmt;
 use std::fmt::Debug;
+use std::ops::Range;
 use std::path::Path;
 use std::{fs, path::PathBuf};
 
@@ -34,6 +36,65 @@ pub fn diff_stats(
     diff.stats()
 }
 
+/// Who last changed a line of a file, as returned by [`blame`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BlameLine {
+    /// The line number, counted from 1.
+    pub line: usize,
+    /// The commit that last changed the line.
+    pub commit: Oid,
+    /// The name of the commit author.
+    pub author: String,
+    /// The time the commit was authored, in seconds since the epoch.
+    pub time: i64,
+    /// The first line of the commit message.
+    pub summary: String,
+}
+
+/// Get who last changed the given lines of the file at `path`, as of commit
+/// `rev`. Lines are counted from 1 and the range excludes its end, like the
+/// ranges of a hunk. Should match the output of `git blame -L <lines> <rev> -- <path>`.
+pub fn blame(
+    repo: &git::raw::Repository,
+    rev: Oid,
+    path: &Path,
+    lines: Range<u32>,
+) -> Result<Vec<BlameLine>, git::raw::Error> {
+    if lines.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let mut opts = git::raw::BlameOptions::new();
+    opts.newest_commit(*rev)
+        .min_line(lines.start as usize)
+        .max_line(lines.end as usize - 1);
+    let blame = repo.blame_file(path, Some(&mut opts))?;
+
+    let mut summaries = HashMap::new();
+    let mut result = vec![];
+    for line in lines.start as usize..lines.end as usize {
+        let Some(hunk) = blame.get_line(line) else {
+            continue;
+        };
+        let commit = hunk.final_commit_id();
+        if !summaries.contains_key(&commit) {
+            let summary = repo.find_commit(commit)?.summary().map(str::to_string);
+            summaries.insert(commit, summary.unwrap_or_default());
+        }
+        let signature = hunk.final_signature();
+
+        result.push(BlameLine {
+            line,
+            commit: commit.into(),
+            author: signature.name().unwrap_or_default().to_string(),
+            time: signature.when().seconds(),
+            summary: summaries[&commit].clone(),
+        });
+    }
+
+    Ok(result)
+}
+
 /// A repository of Git blobs.
 pub trait Repo {
     /// Lookup a blob from the repo.
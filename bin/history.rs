@@ -0,0 +1,244 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::OnceLock;
+use std::{fs, process};
+
+use anyhow::Context as _;
+
+use serde::{Deserialize, Serialize};
+
+use radicle::identity::RepoId;
+
+use radicle_tui as tui;
+
+/// The history file, relative to the user's state directory.
+const FILE: &str = "radicle-tui/history.json";
+
+/// The key searches are kept under if they're not applied to a single repository,
+/// e.g. in the inbox of all repositories.
+const ALL_REPOS: &str = "*";
+
+/// The path of the history file, see `init`.
+static PATH: OnceLock<PathBuf> = OnceLock::new();
+
+/// Counts the history files written by this process, see `History::save`.
+static SAVES: AtomicUsize = AtomicUsize::new(0);
+
+/// The interfaces searches are remembered for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Interface {
+    Issues,
+    Patches,
+    Inbox,
+}
+
+impl Interface {
+    fn name(&self) -> &'static str {
+        match self {
+            Interface::Issues => "issue",
+            Interface::Patches => "patch",
+            Interface::Inbox => "inbox",
+        }
+    }
+}
+
+/// State kept across sessions. It's read when an interface is started and
+/// written whenever it changes.
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct History {
+    /// The search last applied, per repository and interface, e.g.
+    /// `{ "rad:z3gq…": { "issue": "is:open is:authored" } }`.
+    searches: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+impl History {
+    /// Reads the history file at the given path. A missing file is an empty
+    /// history.
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read history file {}", path.display()))?;
+
+        serde_json::from_str(&content)
+            .with_context(|| format!("Malformed history file {}", path.display()))
+    }
+
+    /// Reads the history file at the given path like `load`, but starts over
+    /// with an empty history if it can't be read. A malformed file is moved
+    /// aside such that it can be inspected.
+    pub fn load_or_default(path: &Path) -> Self {
+        match Self::load(path) {
+            Ok(history) => history,
+            Err(err) => {
+                let backup = path.with_extension("json.corrupt");
+                log::warn!(
+                    "Could not read history: {:#}, moving it to {}",
+                    err,
+                    backup.display()
+                );
+                if let Err(err) = fs::rename(path, &backup) {
+                    log::warn!("Could not move history {}: {}", path.display(), err);
+                }
+                Self::default()
+            }
+        }
+    }
+
+    /// Writes the history to a temporary file first, which is then moved into
+    /// place, such that concurrent sessions never read a half-written file.
+    /// The temporary file is named after the process and the number of saves
+    /// made by it, such that sessions never write to the same one.
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let tmp = path.with_extension(format!(
+            "{}.{}.tmp",
+            process::id(),
+            SAVES.fetch_add(1, Ordering::Relaxed)
+        ));
+
+        fs::write(&tmp, serde_json::to_string_pretty(self)?)?;
+        fs::rename(&tmp, path)?;
+
+        Ok(())
+    }
+
+    pub fn search(&self, repo: Option<&RepoId>, interface: Interface) -> Option<&str> {
+        self.searches
+            .get(&key(repo))
+            .and_then(|searches| searches.get(interface.name()))
+            .map(String::as_str)
+    }
+
+    pub fn set_search(&mut self, repo: Option<&RepoId>, interface: Interface, search: &str) {
+        self.searches
+            .entry(key(repo))
+            .or_default()
+            .insert(interface.name().to_string(), search.to_string());
+    }
+}
+
+fn key(repo: Option<&RepoId>) -> String {
+    repo.map(|rid| rid.to_string())
+        .unwrap_or_else(|| ALL_REPOS.to_string())
+}
+
+/// Returns the path of the history file: `$XDG_STATE_HOME/radicle-tui/history.json`,
+/// or `$HOME/.local/state/radicle-tui/history.json` if `XDG_STATE_HOME` is not set.
+pub fn default_path() -> Option<PathBuf> {
+    Some(tui::xdg_home("XDG_STATE_HOME", ".local/state")?.join(FILE))
+}
+
+/// Enables the history, which is kept in the given file. Without it, e.g. in
+/// tests, nothing is remembered.
+pub fn init(path: PathBuf) {
+    let _ = PATH.set(path);
+}
+
+/// Returns the search last applied in the given interface, if the history is
+/// enabled and has one.
+pub fn last_search(repo: Option<&RepoId>, interface: Interface) -> Option<String> {
+    let path = PATH.get()?;
+
+    match History::load(path) {
+        Ok(history) => history.search(repo, interface).map(str::to_string),
+        Err(err) => {
+            log::warn!("Could not read history: {:#}", err);
+            None
+        }
+    }
+}
+
+/// Remembers the search applied in the given interface, if the history is
+/// enabled. Errors are logged, since they shouldn't interrupt the interface.
+/// A history that can't be read is replaced.
+pub fn save_search(repo: Option<&RepoId>, interface: Interface, search: &str) {
+    let Some(path) = PATH.get() else {
+        return;
+    };
+
+    let mut history = History::load_or_default(path);
+    history.set_search(repo, interface, search);
+
+    if let Err(err) = history.save(path) {
+        log::warn!("Could not write history: {:#}", err);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+    use std::str::FromStr;
+
+    use pretty_assertions::assert_eq;
+
+    use radicle::identity::RepoId;
+
+    use crate::ui::items::{IssueItemFilter, NotificationItemFilter, PatchItemFilter};
+
+    use super::{History, Interface};
+
+    #[test]
+    fn persisted_searches_should_round_trip() -> anyhow::Result<()> {
+        let rid = RepoId::from_str("rad:z3gqcJUoA1n9HaHKufZs5FCSGazv5")?;
+        let issues = "is:solved is:authored since:2024-01-01 crash";
+        let patches = "is:merged is:authored until:2024-06-01";
+        let inbox = "is:unseen is:issue repo:heartwood";
+
+        let mut history = History::default();
+        history.set_search(Some(&rid), Interface::Issues, issues);
+        history.set_search(Some(&rid), Interface::Patches, patches);
+        history.set_search(None, Interface::Inbox, inbox);
+
+        let restored: History = serde_json::from_str(&serde_json::to_string(&history)?)?;
+        assert_eq!(restored, history);
+        assert_eq!(restored.search(None, Interface::Issues), None);
+
+        let search = |interface| restored.search(Some(&rid), interface).unwrap_or_default();
+        assert_eq!(
+            IssueItemFilter::from_str(search(Interface::Issues))?,
+            IssueItemFilter::from_str(issues)?
+        );
+        assert_eq!(
+            PatchItemFilter::from_str(search(Interface::Patches))?,
+            PatchItemFilter::from_str(patches)?
+        );
+        assert_eq!(
+            NotificationItemFilter::from_str(restored.search(None, Interface::Inbox).unwrap())?,
+            NotificationItemFilter::from_str(inbox)?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn malformed_history_should_be_replaced() -> anyhow::Result<()> {
+        let dir =
+            std::env::temp_dir().join(format!("radicle-tui-test-{}-history", std::process::id()));
+        let path = dir.join("history.json");
+        fs::create_dir_all(&dir)?;
+        fs::write(&path, "{ \"searches\": ")?;
+
+        assert!(History::load(&path).is_err());
+
+        let mut history = History::load_or_default(&path);
+        assert_eq!(history, History::default());
+        assert!(dir.join("history.json.corrupt").exists());
+
+        history.set_search(None, Interface::Inbox, "is:unseen");
+        history.save(&path)?;
+        history.save(&path)?;
+
+        assert_eq!(History::load(&path)?, history);
+        assert_eq!(fs::read_dir(&dir)?.count(), 2);
+
+        fs::remove_dir_all(&dir)?;
+
+        Ok(())
+    }
+}
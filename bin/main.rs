@@ -1,7 +1,9 @@
 mod cob;
 mod commands;
 mod git;
+mod history;
 mod log;
+mod output;
 mod settings;
 #[cfg(test)]
 mod test;
@@ -10,6 +12,7 @@ mod ui;
 use std::ffi::OsString;
 use std::io;
 use std::io::Write;
+use std::path::PathBuf;
 use std::{iter, process};
 
 use anyhow::anyhow;
@@ -40,8 +43,25 @@ enum Command {
     Version,
 }
 
+/// Options that apply to all commands.
+#[derive(Debug, Default)]
+struct Options {
+    /// The settings file to read instead of the default one.
+    config: Option<PathBuf>,
+}
+
 fn main() {
-    match parse_args().map_err(Some).and_then(run) {
+    let result = parse_args().map_err(Some).and_then(|(command, options)| {
+        if let Command::Other(_) = command {
+            settings::init(options.config.as_deref()).map_err(Some)?;
+            if let Some(path) = history::default_path() {
+                history::init(path);
+            }
+        }
+        run(command)
+    });
+
+    match result {
         Ok(_) => process::exit(0),
         Err(err) => {
             if let Some(err) = err {
@@ -52,11 +72,12 @@ fn main() {
     }
 }
 
-fn parse_args() -> anyhow::Result<Command> {
+fn parse_args() -> anyhow::Result<(Command, Options)> {
     use lexopt::prelude::*;
 
     let mut parser = lexopt::Parser::from_env();
     let mut command = None;
+    let mut options = Options::default();
 
     while let Some(arg) = parser.next()? {
         match arg {
@@ -66,10 +87,16 @@ fn parse_args() -> anyhow::Result<Command> {
             Long("version") => {
                 command = Some(Command::Version);
             }
+            Long("config") if command.is_none() => {
+                options.config = Some(PathBuf::from(parser.value()?));
+            }
             Value(val) if command.is_none() => {
-                let args = iter::once(val)
+                let mut args = iter::once(val)
                     .chain(iter::from_fn(|| parser.value().ok()))
                     .collect();
+                if let Some(config) = take_config(&mut args)? {
+                    options.config = Some(config);
+                }
 
                 command = Some(Command::Other(args))
             }
@@ -77,7 +104,40 @@ fn parse_args() -> anyhow::Result<Command> {
         }
     }
 
-    Ok(command.unwrap_or_else(|| Command::Other(vec![])))
+    Ok((command.unwrap_or_else(|| Command::Other(vec![])), options))
+}
+
+/// Removes `--config <path>` and `--config=<path>` from the arguments of a
+/// command and returns the last path given, such that the settings file can
+/// also be given after the command, e.g. `rad-tui issue select --config <path>`.
+/// Arguments after `--` are kept as they are.
+fn take_config(args: &mut Vec<OsString>) -> anyhow::Result<Option<PathBuf>> {
+    let mut config = None;
+    let mut idx = 0;
+
+    while idx < args.len() {
+        let arg = args[idx].to_str().map(str::to_owned);
+        match arg.as_deref() {
+            Some("--") => break,
+            Some("--config") => {
+                args.remove(idx);
+                if idx == args.len() {
+                    return Err(anyhow!("missing argument for option '--config'"));
+                }
+                config = Some(PathBuf::from(args.remove(idx)));
+            }
+            Some(arg) => match arg.strip_prefix("--config=") {
+                Some(path) => {
+                    config = Some(PathBuf::from(path));
+                    args.remove(idx);
+                }
+                None => idx += 1,
+            },
+            None => idx += 1,
+        }
+    }
+
+    Ok(config)
 }
 
 fn print_help() -> anyhow::Result<()> {
@@ -142,3 +202,37 @@ fn run_other(exe: &str, args: &[OsString]) -> Result<(), Option<anyhow::Error>>
         ))),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::OsString;
+    use std::path::PathBuf;
+
+    use pretty_assertions::assert_eq;
+
+    use super::take_config;
+
+    #[test]
+    fn config_should_be_taken_from_command_args() -> anyhow::Result<()> {
+        let mut args = [
+            "issue",
+            "select",
+            "--config",
+            "a.toml",
+            "--config=b.toml",
+            "--",
+            "--config",
+        ]
+        .map(OsString::from)
+        .to_vec();
+
+        assert_eq!(take_config(&mut args)?, Some(PathBuf::from("b.toml")));
+        assert_eq!(
+            args,
+            ["issue", "select", "--", "--config"].map(OsString::from)
+        );
+        assert!(take_config(&mut vec![OsString::from("--config")]).is_err());
+
+        Ok(())
+    }
+}
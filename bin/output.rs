@@ -0,0 +1,74 @@
+use std::fs;
+use std::path::Path;
+
+use anyhow::Context as _;
+
+use serde::Serialize;
+
+/// Writes the selection of an interface as JSON to the given file, or to `stderr`
+/// if none is given. Nothing is written if nothing was selected, e.g. because the
+/// interface was quit, such that scripts can tell both apart.
+pub fn selection(selection: Option<impl Serialize>, file: Option<&Path>) -> anyhow::Result<()> {
+    let Some(selection) = selection else {
+        log::info!("Nothing selected, not writing any output");
+        return Ok(());
+    };
+    let json = serde_json::to_string(&selection)?;
+
+    match file {
+        Some(path) => {
+            log::info!("About to write to `{}`: {}", path.display(), json);
+            write(path, &json)
+                .with_context(|| format!("Failed to write selection to {}", path.display()))
+        }
+        None => {
+            log::info!("About to print to `stderr`: {}", json);
+            eprint!("{json}");
+            Ok(())
+        }
+    }
+}
+
+/// Writes to a temporary file next to `path` first, which is then moved into
+/// place, such that a script never reads a half-written selection.
+fn write(path: &Path, content: &str) -> anyhow::Result<()> {
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+
+    fs::write(&tmp, content)?;
+    fs::rename(&tmp, path)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use pretty_assertions::assert_eq;
+
+    use radicle_tui as tui;
+
+    #[test]
+    fn selection_should_only_be_written_if_given() -> anyhow::Result<()> {
+        let dir =
+            std::env::temp_dir().join(format!("radicle-tui-test-{}-output", std::process::id()));
+        fs::create_dir_all(&dir)?;
+        let path = dir.join("selection.json");
+        let selection = tui::Selection::<u64>::default()
+            .with_operation("show".to_string())
+            .with_id(42);
+
+        super::selection(None::<tui::Selection<u64>>, Some(&path))?;
+        assert!(!path.exists());
+
+        super::selection(Some(&selection), Some(&path))?;
+        let written: tui::Selection<u64> = serde_json::from_str(&fs::read_to_string(&path)?)?;
+        assert_eq!(written, selection);
+        assert_eq!(fs::read_dir(&dir)?.count(), 1);
+
+        fs::remove_dir_all(&dir)?;
+
+        Ok(())
+    }
+}
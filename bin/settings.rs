@@ -1,9 +1,36 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+use anyhow::{anyhow, Context as _};
+
+use serde::Deserialize;
+
+use radicle_cli::terminal;
+
+use ratatui::style::{Color, Style};
+
+use termion::event::Key;
 
 use radicle_tui as tui;
-use tui::ui::theme::Theme;
+use tui::ui::diff;
+use tui::ui::theme::{style, Density, Theme};
+use tui::ui::{key_name, parse_key, Priority, Shortcut};
 
-static THEME_RADICLE: &str = "Radicle";
+use crate::ui::items::{IconRule, IssueItemFilter};
+use crate::ui::TerminalInfo;
+
+static THEME_RADICLE: &str = "radicle";
+static THEME_HIGH_CONTRAST: &str = "high-contrast";
+
+/// The settings file that is read if none is given, relative to the user's
+/// configuration directory.
+const FILE: &str = "radicle-tui/config.toml";
+
+/// The settings resolved once at startup, see `init`.
+static SETTINGS: OnceLock<Settings> = OnceLock::new();
 
 pub type ThemeBundleId = String;
 
@@ -35,6 +62,199 @@ impl Default for ThemeBundle {
     }
 }
 
+impl ThemeBundle {
+    /// A bundle that doesn't rely on dimmed text or subtle shades, in light and
+    /// dark mode alike, see `Theme::high_contrast`.
+    pub fn high_contrast() -> Self {
+        Self {
+            light: Theme::high_contrast(),
+            dark: Theme::high_contrast(),
+        }
+    }
+}
+
+/// `TimestampFormat` defines how timestamps are rendered, e.g. in the
+/// `Updated` column of a patch or next to a comment.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TimestampFormat {
+    /// Time passed since, e.g. `3 hours ago`.
+    #[default]
+    Relative,
+    /// Date and time in UTC, e.g. `2024-06-01T12:30:00Z`.
+    Iso8601,
+    /// Date in UTC, e.g. `2024-06-01`.
+    Short,
+}
+
+/// `DidFormat` defines how the DIDs of authors are rendered, e.g. if they don't
+/// have an alias.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DidFormat {
+    /// The start and end of the node id, e.g. `z6MkgEz…KYwBmjb`.
+    #[default]
+    Short,
+    /// The full DID, which can be copied and pasted, e.g. `did:key:z6MkgEz…`.
+    Full,
+    /// The given number of characters of the start and end of the node id.
+    Truncated(usize),
+}
+
+/// `MaxWidths` defines how many characters of free-form fields are rendered in
+/// table rows at most. Longer values are cut in the middle, such that they don't
+/// push other columns out of view.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+#[serde(default, deny_unknown_fields)]
+pub struct MaxWidths {
+    /// Aliases and project names.
+    pub name: usize,
+    /// Issue and patch titles, as well as notification summaries.
+    pub title: usize,
+}
+
+impl Default for MaxWidths {
+    fn default() -> Self {
+        Self {
+            name: 32,
+            title: 120,
+        }
+    }
+}
+
+/// Keys the focused widget navigates with, e.g. a table moving its cursor.
+/// Widgets handle keys before the page they're shown on, so these can't be
+/// bound to actions.
+const NAVIGATION_KEYS: [Key; 14] = [
+    Key::Up,
+    Key::Down,
+    Key::Left,
+    Key::Right,
+    Key::Char('k'),
+    Key::Char('j'),
+    Key::Char('h'),
+    Key::Char('l'),
+    Key::PageUp,
+    Key::PageDown,
+    Key::Home,
+    Key::End,
+    Key::Char('\t'),
+    Key::BackTab,
+];
+
+/// `Action` is a page-level action whose keys can be remapped in the `[keys]`
+/// table of a settings file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Action {
+    Quit,
+    Help,
+    Preview,
+    Edit,
+}
+
+impl Action {
+    pub const ALL: [Action; 4] = [Action::Quit, Action::Help, Action::Preview, Action::Edit];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Action::Quit => "quit",
+            Action::Help => "help",
+            Action::Preview => "preview",
+            Action::Edit => "edit",
+        }
+    }
+}
+
+impl FromStr for Action {
+    type Err = anyhow::Error;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Action::ALL
+            .into_iter()
+            .find(|action| action.name() == name)
+            .ok_or_else(|| anyhow!("Unknown action `{name}`, expected quit, help, preview or edit"))
+    }
+}
+
+/// `KeyBindings` maps page-level actions to the keys that trigger them.
+///
+/// Pages receive a key after the focused widget handled it. Navigation keys,
+/// e.g. `j` or `↑`, are therefore always handled by the widget and can't be
+/// bound. Bound keys take precedence over all other keys of a page, e.g. `v`
+/// toggles the preview and not the compact mode if it's bound to `preview`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeyBindings {
+    bindings: HashMap<Action, Vec<Key>>,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self {
+            bindings: HashMap::from([
+                (Action::Quit, vec![Key::Esc, Key::Ctrl('c')]),
+                (Action::Help, vec![Key::Char('?'), Key::F(1)]),
+                (Action::Preview, vec![Key::Char('p')]),
+                (Action::Edit, vec![Key::Char('e')]),
+            ]),
+        }
+    }
+}
+
+impl KeyBindings {
+    /// Returns the keys bound to the given action.
+    pub fn keys(&self, action: Action) -> &[Key] {
+        self.bindings
+            .get(&action)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Returns `true` if the given key triggers the given action.
+    pub fn matches(&self, action: Action, key: Key) -> bool {
+        self.keys(action).contains(&key)
+    }
+
+    /// Returns a shortcut hint for the given action, showing its first key.
+    /// Shortcuts to quit or show the help are essential.
+    pub fn shortcut(&self, action: Action, label: &str) -> Shortcut {
+        let shortcut = Shortcut::new(self.keys(action).iter().take(1).copied(), label);
+        match action {
+            Action::Quit | Action::Help => shortcut.with_priority(Priority::Essential),
+            Action::Preview | Action::Edit => shortcut,
+        }
+    }
+
+    /// Binds the given keys to the given action, replacing its keys. Fails if
+    /// one of them is a navigation key or bound to another action already, or
+    /// if no key is given for an essential action, i.e. `quit` or `help`.
+    pub fn bind(&mut self, action: Action, keys: Vec<Key>) -> anyhow::Result<()> {
+        if keys.is_empty() && matches!(action, Action::Quit | Action::Help) {
+            return Err(anyhow!("`{}` needs at least one key", action.name()));
+        }
+        for key in &keys {
+            if NAVIGATION_KEYS.contains(key) {
+                return Err(anyhow!(
+                    "Key `{}` of `{}` is used for navigation",
+                    key_name(key),
+                    action.name()
+                ));
+            }
+            if let Some(other) = Action::ALL
+                .into_iter()
+                .find(|other| *other != action && self.matches(*other, *key))
+            {
+                return Err(anyhow!(
+                    "Key `{}` is bound to both `{}` and `{}`",
+                    key_name(key),
+                    other.name(),
+                    action.name()
+                ));
+            }
+        }
+        self.bindings.insert(action, keys);
+
+        Ok(())
+    }
+}
+
 #[derive(Debug)]
 pub struct ThemeSettings {
     /// Set light or dark mode, or detect terminal background luma and
@@ -54,11 +274,53 @@ impl ThemeSettings {
     pub fn active_bundle(&self) -> Option<&ThemeBundle> {
         self.bundles.get(&self.active_bundle)
     }
+
+    /// Returns the theme of the active bundle for the mode set. In `auto` mode,
+    /// the dark theme is picked if the terminal is dark.
+    pub fn theme(&self, terminal_info: &TerminalInfo) -> Theme {
+        let default_bundle = ThemeBundle::default();
+        let bundle = self.active_bundle().unwrap_or(&default_bundle);
+
+        match self.mode {
+            ThemeMode::Auto if terminal_info.is_dark() => bundle.dark.clone(),
+            ThemeMode::Auto | ThemeMode::Light => bundle.light.clone(),
+            ThemeMode::Dark => bundle.dark.clone(),
+        }
+    }
 }
 
 #[derive(Debug)]
 pub struct Settings {
     pub theme: ThemeSettings,
+    /// Render an identicon next to author aliases.
+    pub identicons: bool,
+    /// The format timestamps are rendered in.
+    pub timestamp_format: TimestampFormat,
+    /// The format DIDs are rendered in.
+    pub did_format: DidFormat,
+    /// The maximum widths of aliases, project names and titles in table rows.
+    pub max_widths: MaxWidths,
+    /// How densely tables are laid out. It can be toggled in interfaces that
+    /// support it.
+    pub density: Density,
+    /// The rules the icons in front of issues are picked by. The first rule
+    /// whose search matches an issue is applied.
+    pub issue_icons: Vec<IconRule<IssueItemFilter>>,
+    /// Ask for confirmation before quitting. If not set, every interface decides
+    /// on its own, e.g. the review asks since quitting ends it. Only immediate
+    /// mode interfaces (`patch review` and `patch select`) ask, retained mode
+    /// ones (`issue select` and `inbox select`) always quit right away.
+    pub confirm_quit: Option<bool>,
+    /// The number of columns tabs in diffs are expanded to.
+    pub diff_tab_width: usize,
+    /// The number of days the activity of the selected patch in `patch select`
+    /// is shown for.
+    pub activity_days: usize,
+    /// The keys that trigger page-level actions, e.g. showing the help.
+    pub keys: KeyBindings,
+    /// Labels that start with this prefix name the milestone of an issue, e.g.
+    /// `milestone:v1.0`. If empty, no label does.
+    pub milestone_prefix: String,
 }
 
 impl Default for Settings {
@@ -67,8 +329,419 @@ impl Default for Settings {
             theme: ThemeSettings {
                 mode: ThemeMode::Auto,
                 active_bundle: THEME_RADICLE.into(),
-                bundles: HashMap::from([(THEME_RADICLE.to_string(), ThemeBundle::default())]),
+                bundles: HashMap::from([
+                    (THEME_RADICLE.to_string(), ThemeBundle::default()),
+                    (
+                        THEME_HIGH_CONTRAST.to_string(),
+                        ThemeBundle::high_contrast(),
+                    ),
+                ]),
             },
+            identicons: false,
+            timestamp_format: TimestampFormat::default(),
+            did_format: DidFormat::default(),
+            max_widths: MaxWidths::default(),
+            density: Density::default(),
+            issue_icons: [("is:assigned", "🔒"), ("is:authored", "⭐")]
+                .into_iter()
+                .map(|(search, glyph)| {
+                    let predicate = IssueItemFilter::from_str(search)
+                        .expect("Settings: built-in icon searches are valid");
+                    IconRule::new(predicate, glyph, style::yellow())
+                })
+                .collect(),
+            confirm_quit: None,
+            diff_tab_width: diff::DEFAULT_TAB_WIDTH,
+            activity_days: 14,
+            keys: KeyBindings::default(),
+            milestone_prefix: "milestone:".to_string(),
+        }
+    }
+}
+
+impl Settings {
+    /// Returns the settings resolved by `init`, or the default settings if
+    /// none were resolved, e.g. in tests.
+    pub fn current() -> &'static Settings {
+        SETTINGS.get_or_init(Settings::default)
+    }
+}
+
+/// The settings as written in a settings file. Settings that are not given
+/// keep their default, keys that are not known are ignored.
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct Raw {
+    /// `auto`, `light` or `dark`.
+    pub theme: Option<String>,
+    /// `radicle` or `high-contrast`.
+    pub theme_bundle: Option<String>,
+    /// `comfortable` or `compact`.
+    pub density: Option<String>,
+    pub identicons: Option<bool>,
+    /// `relative`, `iso8601` or `short`.
+    pub timestamp_format: Option<String>,
+    /// `short`, `full` or `truncated:<n>`.
+    pub did_format: Option<String>,
+    /// The widths of `name` and `title`, e.g. `max-widths = { title = 80 }`.
+    pub max_widths: Option<MaxWidths>,
+    /// Replaces the built-in rules if given, e.g.
+    /// `issue-icons = [{ search = "milestone:v1.0", glyph = "🚩", color = "red" }]`.
+    pub issue_icons: Option<Vec<RawIconRule>>,
+    /// Only applies to immediate mode interfaces, see `Settings::confirm_quit`.
+    pub confirm_quit: Option<bool>,
+    pub diff_tab_width: Option<usize>,
+    pub activity_days: Option<usize>,
+    pub milestone_prefix: Option<String>,
+    /// Action names mapped to key names, e.g. `help = ["H", "F1"]`.
+    pub keys: BTreeMap<String, Vec<String>>,
+    /// Keys that are not known, e.g. because of a typo.
+    #[serde(flatten)]
+    unknown: BTreeMap<String, toml::Value>,
+}
+
+/// An icon rule as written in a settings file, see `IconRule`. The icon is
+/// yellow if no color is given.
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct RawIconRule {
+    /// The issue search that picks the issues marked, e.g. `is:assigned`.
+    pub search: String,
+    pub glyph: String,
+    /// A color name, e.g. `red`, or a hex color, e.g. `#ff0000`.
+    pub color: Option<String>,
+}
+
+impl Raw {
+    /// Reads the settings file at the given path.
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read settings file {}", path.display()))?;
+
+        let raw = Self::from_str(&content)
+            .with_context(|| format!("Malformed settings file {}", path.display()))?;
+        for key in raw.unknown_keys() {
+            terminal::warning(format!(
+                "Ignoring unknown key `{key}` in settings file {}",
+                path.display()
+            ));
+        }
+
+        Ok(raw)
+    }
+
+    /// Returns the keys of the settings file that are not known, e.g. because
+    /// of a typo.
+    pub fn unknown_keys(&self) -> impl Iterator<Item = &str> {
+        self.unknown.keys().map(String::as_str)
+    }
+}
+
+impl FromStr for Raw {
+    type Err = anyhow::Error;
+
+    fn from_str(content: &str) -> Result<Self, Self::Err> {
+        Ok(toml::from_str(content)?)
+    }
+}
+
+impl TryFrom<Raw> for Settings {
+    type Error = anyhow::Error;
+
+    fn try_from(raw: Raw) -> Result<Self, Self::Error> {
+        let mut settings = Settings::default();
+
+        if let Some(theme) = raw.theme {
+            settings.theme.mode = match theme.as_str() {
+                "auto" => ThemeMode::Auto,
+                "light" => ThemeMode::Light,
+                "dark" => ThemeMode::Dark,
+                _ => return Err(invalid("theme", &theme, "auto, light or dark")),
+            };
+        }
+        if let Some(bundle) = raw.theme_bundle {
+            if !settings.theme.bundles.contains_key(&bundle) {
+                return Err(invalid(
+                    "theme-bundle",
+                    &bundle,
+                    &format!("{THEME_RADICLE} or {THEME_HIGH_CONTRAST}"),
+                ));
+            }
+            settings.theme.active_bundle = bundle;
+        }
+        if let Some(density) = raw.density {
+            settings.density = match density.as_str() {
+                "comfortable" => Density::Comfortable,
+                "compact" => Density::Compact,
+                _ => return Err(invalid("density", &density, "comfortable or compact")),
+            };
+        }
+        if let Some(format) = raw.timestamp_format {
+            settings.timestamp_format = match format.as_str() {
+                "relative" => TimestampFormat::Relative,
+                "iso8601" => TimestampFormat::Iso8601,
+                "short" => TimestampFormat::Short,
+                _ => {
+                    return Err(invalid(
+                        "timestamp-format",
+                        &format,
+                        "relative, iso8601 or short",
+                    ))
+                }
+            };
+        }
+        if let Some(format) = raw.did_format {
+            settings.did_format = match format.as_str() {
+                "short" => DidFormat::Short,
+                "full" => DidFormat::Full,
+                other => other
+                    .strip_prefix("truncated:")
+                    .and_then(|len| len.parse().ok())
+                    .map(DidFormat::Truncated)
+                    .ok_or_else(|| {
+                        invalid("did-format", &format, "short, full or truncated:<n>")
+                    })?,
+            };
+        }
+        if let Some(max_widths) = raw.max_widths {
+            if max_widths.name == 0 || max_widths.title == 0 {
+                return Err(anyhow!(
+                    "Invalid value `0` in `max-widths`, expected a positive width"
+                ));
+            }
+            settings.max_widths = max_widths;
+        }
+        if let Some(rules) = raw.issue_icons {
+            settings.issue_icons = rules
+                .into_iter()
+                .map(|rule| {
+                    let predicate = IssueItemFilter::from_str(&rule.search).with_context(|| {
+                        format!("Invalid search `{}` in `issue-icons`", rule.search)
+                    })?;
+                    let style = match rule.color {
+                        Some(color) => Color::from_str(&color)
+                            .map(|color| Style::default().fg(color))
+                            .map_err(|_| invalid("issue-icons.color", &color, "a color"))?,
+                        None => style::yellow(),
+                    };
+                    Ok(IconRule::new(predicate, rule.glyph, style))
+                })
+                .collect::<anyhow::Result<_>>()?;
         }
+        if let Some(days) = raw.activity_days {
+            if !(1..=365).contains(&days) {
+                return Err(invalid(
+                    "activity-days",
+                    &days.to_string(),
+                    "a number of days from 1 to 365",
+                ));
+            }
+            settings.activity_days = days;
+        }
+        if let Some(width) = raw.diff_tab_width {
+            if !(1..=16).contains(&width) {
+                return Err(invalid(
+                    "diff-tab-width",
+                    &width.to_string(),
+                    "a number of columns from 1 to 16",
+                ));
+            }
+            settings.diff_tab_width = width;
+        }
+        settings.identicons = raw.identicons.unwrap_or(settings.identicons);
+        settings.confirm_quit = raw.confirm_quit.or(settings.confirm_quit);
+        settings.milestone_prefix = raw.milestone_prefix.unwrap_or(settings.milestone_prefix);
+
+        // Remapped actions are unbound first, such that their keys can be bound
+        // to other actions, e.g. when swapping keys.
+        let mut bindings = vec![];
+        for (name, key_names) in raw.keys {
+            let action = Action::from_str(&name)?;
+            let keys = key_names
+                .iter()
+                .map(|key| {
+                    parse_key(key)
+                        .ok_or_else(|| invalid(&format!("keys.{name}"), key, "a key name"))
+                })
+                .collect::<anyhow::Result<Vec<_>>>()?;
+            settings.keys.bindings.remove(&action);
+            bindings.push((action, keys));
+        }
+        for (action, keys) in bindings {
+            settings.keys.bind(action, keys)?;
+        }
+
+        Ok(settings)
+    }
+}
+
+fn invalid(key: &str, value: &str, expected: &str) -> anyhow::Error {
+    anyhow!("Invalid value `{value}` for `{key}`, expected {expected}")
+}
+
+/// Returns the path of the settings file that is read if none is given:
+/// `$XDG_CONFIG_HOME/radicle-tui/config.toml`, or `$HOME/.config/radicle-tui/config.toml`
+/// if `XDG_CONFIG_HOME` is not set.
+pub fn default_path() -> Option<PathBuf> {
+    Some(tui::xdg_home("XDG_CONFIG_HOME", ".config")?.join(FILE))
+}
+
+/// Resolves the settings all interfaces read, see `Settings::current`. They are
+/// read from the given settings file, or from the default one if it exists.
+/// Interfaces use the default settings if there is no settings file.
+pub fn init(path: Option<&Path>) -> anyhow::Result<()> {
+    let raw = match path {
+        Some(path) => Raw::load(path)?,
+        None => match default_path().filter(|path| path.exists()) {
+            Some(path) => Raw::load(&path)?,
+            None => Raw::default(),
+        },
+    };
+    let settings = Settings::try_from(raw)?;
+
+    SETTINGS
+        .set(settings)
+        .map_err(|_| anyhow!("Settings were resolved already"))
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use pretty_assertions::assert_eq;
+
+    use radicle_tui as tui;
+
+    use ratatui::style::{Color, Style};
+
+    use termion::event::Key;
+
+    use tui::ui::theme::{Density, Theme};
+
+    use super::{
+        Action, DidFormat, KeyBindings, MaxWidths, Raw, Settings, ThemeMode, TimestampFormat,
+    };
+
+    #[test]
+    fn raw_settings_should_be_evaluated() -> anyhow::Result<()> {
+        let raw = Raw::from_str(
+            r#"
+theme = "dark"
+theme-bundle = "high-contrast"
+density = "compact"
+timestamp-format = "iso8601"
+did-format = "truncated:4"
+max-widths = { title = 80 }
+issue-icons = [{ search = "is:open", glyph = "!", color = "red" }]
+diff-tab-width = 8
+activity-days = 30
+milestone-prefix = "iteration/"
+"#,
+        )?;
+        let settings = Settings::try_from(raw)?;
+
+        assert_eq!(settings.theme.mode(), &ThemeMode::Dark);
+        assert_eq!(
+            settings
+                .theme
+                .active_bundle()
+                .map(|bundle| bundle.dark.search_highlight_style),
+            Some(Theme::high_contrast().search_highlight_style)
+        );
+        assert_eq!(settings.density, Density::Compact);
+        assert_eq!(settings.timestamp_format, TimestampFormat::Iso8601);
+        assert_eq!(settings.did_format, DidFormat::Truncated(4));
+        assert_eq!(
+            settings.max_widths,
+            MaxWidths {
+                name: 32,
+                title: 80
+            }
+        );
+        assert_eq!(settings.issue_icons.len(), 1);
+        assert_eq!(settings.issue_icons[0].glyph, "!");
+        assert_eq!(
+            settings.issue_icons[0].style,
+            Style::default().fg(Color::Red)
+        );
+        assert_eq!(settings.diff_tab_width, 8);
+        assert_eq!(settings.activity_days, 30);
+        assert_eq!(settings.milestone_prefix, "iteration/");
+        assert!(!settings.identicons);
+
+        Ok(())
+    }
+
+    #[test]
+    fn malformed_settings_should_fail() {
+        assert!(Raw::from_str("theme = ").is_err());
+        assert!(Settings::try_from(Raw::from_str("theme = \"blue\"").unwrap()).is_err());
+        assert!(Settings::try_from(Raw::from_str("theme-bundle = \"neon\"").unwrap()).is_err());
+        assert!(Settings::try_from(Raw::from_str("did-format = \"truncated:\"").unwrap()).is_err());
+        assert!(Settings::try_from(Raw::from_str("max-widths = { name = 0 }").unwrap()).is_err());
+        assert!(Settings::try_from(Raw::from_str("activity-days = 0").unwrap()).is_err());
+        assert!(Settings::try_from(Raw::from_str("diff-tab-width = 0").unwrap()).is_err());
+        assert!(Settings::try_from(Raw::from_str("diff-tab-width = 1000000").unwrap()).is_err());
+        assert!(Settings::try_from(
+            Raw::from_str(r#"issue-icons = [{ search = "since:soon", glyph = "!" }]"#).unwrap()
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn unknown_settings_should_be_ignored() -> anyhow::Result<()> {
+        let raw = Raw::from_str("colour = \"red\"\ntheme = \"light\"\n[extra]\nkey = 1")?;
+
+        assert_eq!(raw.unknown_keys().collect::<Vec<_>>(), ["colour", "extra"]);
+        assert_eq!(Settings::try_from(raw)?.theme.mode(), &ThemeMode::Light);
+
+        Ok(())
+    }
+
+    #[test]
+    fn default_key_bindings_should_match_built_in_keys() {
+        let keys = KeyBindings::default();
+
+        assert!(keys.matches(Action::Quit, Key::Esc));
+        assert!(keys.matches(Action::Quit, Key::Ctrl('c')));
+        assert!(keys.matches(Action::Help, Key::F(1)));
+        assert!(keys.matches(Action::Preview, Key::Char('p')));
+        assert!(keys.matches(Action::Edit, Key::Char('e')));
+        assert_eq!(keys.shortcut(Action::Help, "help").hint(), "?");
+    }
+
+    #[test]
+    fn keys_should_be_remapped() -> anyhow::Result<()> {
+        let raw = Raw::from_str(
+            r#"
+[keys]
+help = ["H", "F1"]
+preview = ["e"]
+edit = ["p"]
+"#,
+        )?;
+        let keys = Settings::try_from(raw)?.keys;
+
+        assert_eq!(keys.keys(Action::Help), &[Key::Char('H'), Key::F(1)]);
+        assert_eq!(keys.keys(Action::Preview), &[Key::Char('e')]);
+        assert_eq!(keys.keys(Action::Edit), &[Key::Char('p')]);
+        assert_eq!(keys.keys(Action::Quit), &[Key::Esc, Key::Ctrl('c')]);
+        assert!(!keys.matches(Action::Help, Key::Char('?')));
+
+        Ok(())
+    }
+
+    #[test]
+    fn conflicting_keys_should_fail() {
+        let settings = |content: &str| Settings::try_from(Raw::from_str(content).unwrap());
+
+        assert!(settings("[keys]\npreview = [\"esc\"]").is_err());
+        assert!(settings("[keys]\npreview = [\"x\"]\nedit = [\"x\"]").is_err());
+        assert!(settings("[keys]\nhelp = [\"j\"]").is_err());
+        assert!(settings("[keys]\nhelp = [\"nope\"]").is_err());
+        assert!(settings("[keys]\nsave = [\"s\"]").is_err());
+        assert!(settings("[keys]\nquit = []").is_err());
+        assert!(settings("[keys]\nhelp = []").is_err());
+        assert!(settings("[keys]\npreview = []").is_ok());
     }
 }
@@ -123,9 +123,11 @@ pub mod fixtures {
     use anyhow::Result;
 
     use radicle::cob::cache::NoCache;
+    use radicle::cob::Timestamp;
     use radicle::crypto::{Signer, Verified};
     use radicle::git;
     use radicle::identity::{RepoId, Visibility};
+    use radicle::node::notifications::NotificationId;
     use radicle::patch::{Cache, MergeTarget, PatchMut, Patches};
     use radicle::rad;
     use radicle::storage::git::Repository;
@@ -134,6 +136,9 @@ pub mod fixtures {
     use radicle::test::setup::{BranchWith, Node};
     use radicle::Storage;
 
+    use crate::settings::{MaxWidths, TimestampFormat};
+    use crate::ui::items::{AuthorItem, NotificationItem, NotificationKindItem};
+
     use super::setup::{NodeRepo, NodeRepoCheckout, NodeWithRepo};
 
     /// The birth of the radicle project, January 1st, 2018.
@@ -280,4 +285,32 @@ fn main() {
 
         (repo, oid)
     }
+
+    /// An author without node id and alias, as if they were unknown.
+    pub fn author() -> AuthorItem {
+        AuthorItem {
+            nid: None,
+            human_nid: None,
+            alias: None,
+            you: false,
+            identicon: false,
+        }
+    }
+
+    /// A notification of an unknown kind in `radicle-tui` by an unknown author.
+    pub fn notification(id: NotificationId) -> NotificationItem {
+        NotificationItem {
+            id,
+            project: "radicle-tui".to_string(),
+            seen: false,
+            kind: NotificationKindItem::Unknown {
+                refname: "refs/heads/master".to_string(),
+            },
+            author: author(),
+            timestamp: Timestamp::from_secs(1709211909),
+            timestamp_format: TimestampFormat::default(),
+            max_widths: MaxWidths::default(),
+            group: None,
+        }
+    }
 }
@@ -2,6 +2,8 @@ pub mod format;
 pub mod im;
 pub mod items;
 pub mod layout;
+pub mod onboarding;
+pub mod picker;
 pub mod rm;
 pub mod span;
 
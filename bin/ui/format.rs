@@ -1,14 +1,21 @@
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use radicle::cob::Label;
 use radicle::cob::{ObjectId, Timestamp};
 use radicle::crypto::PublicKey;
 use radicle::issue;
-use radicle::node::Alias;
+use radicle::node::{Alias, NodeId};
 use radicle::patch;
 use radicle::prelude::Did;
 use ratatui::style::Color;
 
+use radicle_tui::ui::theme::BadgeKind;
+
+use crate::settings::{DidFormat, TimestampFormat};
+
 /// Format a git Oid.
 pub fn oid(oid: impl Into<radicle::git::Oid>) -> String {
     format!("{:.7}", oid.into())
@@ -20,37 +27,262 @@ pub fn cob(id: &ObjectId) -> String {
 }
 
 /// Format a DID.
-pub fn did(did: &Did) -> String {
+pub fn did(did: &Did, format: DidFormat) -> String {
     let nid = did.as_key().to_human();
-    format!("{}…{}", &nid[..7], &nid[nid.len() - 7..])
+    let len = match format {
+        DidFormat::Short => 7,
+        DidFormat::Full => return did.to_string(),
+        DidFormat::Truncated(len) => len,
+    };
+
+    if nid.len() <= len * 2 {
+        nid
+    } else {
+        format!("{}…{}", &nid[..len], &nid[nid.len() - len..])
+    }
+}
+
+/// Colors authors are rendered in. Only the basic ANSI colors are used, since
+/// terminals adapt them to their own palette and keep them readable on light and
+/// dark backgrounds.
+const AUTHOR_COLORS: [Color; 6] = [
+    Color::Red,
+    Color::Green,
+    Color::Yellow,
+    Color::Blue,
+    Color::Magenta,
+    Color::Cyan,
+];
+
+/// FNV-1a, since it is stable across platforms and releases.
+fn fnv1a(content: &str) -> u64 {
+    content.bytes().fold(0xcbf29ce484222325_u64, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// Pick a deterministic color for an author, such that all rows of the same
+/// author are rendered in the same color.
+pub fn author_color(nid: &NodeId) -> Color {
+    AUTHOR_COLORS[(fnv1a(&nid.to_string()) % AUTHOR_COLORS.len() as u64) as usize]
+}
+
+/// Build a deterministic identicon for a DID. The identicon is a grid of 2x3
+/// cells, rendered as 3 half-block characters, where the left and right column
+/// mirror each other. Returns the identicon and the authors' color.
+pub fn identicon(did: &Did) -> (String, Color) {
+    let hash = fnv1a(&did.to_string());
+
+    let cell = |bits: u64| match bits & 0b11 {
+        0b01 => '▀',
+        0b10 => '▄',
+        0b11 => '█',
+        _ => ' ',
+    };
+    let outer = cell(hash);
+    let inner = match cell(hash >> 2) {
+        ' ' if outer == ' ' => '█',
+        inner => inner,
+    };
+
+    (
+        [outer, inner, outer].iter().collect(),
+        author_color(did.as_key()),
+    )
+}
+
+/// Format a timestamp. Relative timestamps are computed against the
+/// current system time.
+pub fn timestamp(time: &Timestamp, format: TimestampFormat) -> String {
+    timestamp_at(time, format, SystemTime::now())
+}
+
+/// Format a timestamp. Relative timestamps are computed against `now`.
+pub fn timestamp_at(time: &Timestamp, format: TimestampFormat, now: SystemTime) -> String {
+    match format {
+        TimestampFormat::Relative => {
+            let now = now.duration_since(UNIX_EPOCH).unwrap_or_default();
+            let duration = Duration::from_secs(now.as_secs().saturating_sub(time.as_secs()));
+
+            timeago::Formatter::new().convert(duration)
+        }
+        TimestampFormat::Iso8601 => {
+            let (year, month, day) = civil_date(time.as_secs() / 86400);
+            let secs = time.as_secs() % 86400;
+
+            format!(
+                "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+                secs / 3600,
+                secs % 3600 / 60,
+                secs % 60
+            )
+        }
+        TimestampFormat::Short => {
+            let (year, month, day) = civil_date(time.as_secs() / 86400);
+
+            format!("{year:04}-{month:02}-{day:02}")
+        }
+    }
 }
 
-/// Format a timestamp.
-pub fn timestamp(time: &Timestamp) -> String {
-    let fmt = timeago::Formatter::new();
-    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
-    let now = Timestamp::from_secs(now.as_secs());
-    let duration = std::time::Duration::from_secs(now.as_secs() - time.as_secs());
+/// Convert days since the unix epoch into a proleptic gregorian
+/// `(year, month, day)`, see <http://howardhinnant.github.io/date_algorithms.html>.
+fn civil_date(days: u64) -> (u64, u64, u64) {
+    let days = days + 719468;
+    let era = days / 146097;
+    let doe = days - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
 
-    fmt.convert(duration)
+    (year, month, day)
+}
+
+/// Convert a proleptic gregorian `(year, month, day)` into days since the unix
+/// epoch, the inverse of `civil_date`.
+fn days_from_civil(year: u64, month: u64, day: u64) -> u64 {
+    let year = year - u64::from(month <= 2);
+    let era = year / 400;
+    let yoe = year - era * 400;
+    let mp = if month > 2 { month - 3 } else { month + 9 };
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+
+    (era * 146097 + doe).saturating_sub(719468)
+}
+
+/// A point in time that timestamps are compared against, e.g. by search filters.
+/// It's either relative to the current time, e.g. `7d`, or a date, e.g.
+/// `2024-01-01`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TimeBound {
+    /// The given amount of time before now. Written in weeks (`w`), days (`d`)
+    /// or hours (`h`).
+    Ago(Duration),
+    /// The start of a day in UTC.
+    Date { year: u64, month: u64, day: u64 },
+}
+
+impl TimeBound {
+    const UNITS: [(char, u64); 3] = [('w', 7 * 86400), ('d', 86400), ('h', 3600)];
+
+    /// The years dates can be in, such that they're written in four digits.
+    const YEARS: RangeInclusive<u64> = 1970..=9999;
+
+    /// Returns the seconds since the unix epoch this bound refers to at `now`.
+    pub fn secs_at(&self, now: SystemTime) -> u64 {
+        match self {
+            TimeBound::Ago(duration) => now
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .saturating_sub(*duration)
+                .as_secs(),
+            TimeBound::Date { year, month, day } => {
+                days_from_civil(*year, *month, *day).saturating_mul(86400)
+            }
+        }
+    }
+
+    /// Returns the seconds since the unix epoch at which the period this bound
+    /// refers to ends: the end of the day for dates, such that a date includes
+    /// all of its day.
+    pub fn end_secs_at(&self, now: SystemTime) -> u64 {
+        match self {
+            TimeBound::Ago(_) => self.secs_at(now),
+            TimeBound::Date { .. } => self.secs_at(now).saturating_add(86400),
+        }
+    }
+}
+
+impl fmt::Display for TimeBound {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimeBound::Ago(duration) => {
+                let secs = duration.as_secs();
+                let (unit, length) = Self::UNITS
+                    .into_iter()
+                    .find(|(_, length)| secs % length == 0)
+                    .unwrap_or(('h', 3600));
+                write!(f, "{}{unit}", secs / length)
+            }
+            TimeBound::Date { year, month, day } => write!(f, "{year:04}-{month:02}-{day:02}"),
+        }
+    }
+}
+
+impl FromStr for TimeBound {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid =
+            || anyhow::anyhow!("invalid time '{value}', expected e.g. '7d' or '2024-01-01'");
+
+        if let Some((unit, length)) = Self::UNITS
+            .into_iter()
+            .find(|(unit, _)| value.ends_with(*unit))
+        {
+            let amount = value
+                .trim_end_matches(unit)
+                .parse::<u64>()
+                .map_err(|_| invalid())?;
+            return Ok(TimeBound::Ago(Duration::from_secs(
+                amount.checked_mul(length).ok_or_else(invalid)?,
+            )));
+        }
+
+        let mut parts = value.splitn(3, '-').map(|part| part.parse::<u64>().ok());
+        let (Some(Some(year)), Some(Some(month)), Some(Some(day))) =
+            (parts.next(), parts.next(), parts.next())
+        else {
+            return Err(invalid());
+        };
+        // Dates that don't exist, e.g. `2023-02-30`, don't convert back into
+        // themselves.
+        if !Self::YEARS.contains(&year)
+            || !(1..=12).contains(&month)
+            || !(1..=31).contains(&day)
+            || civil_date(days_from_civil(year, month, day)) != (year, month, day)
+        {
+            return Err(invalid());
+        }
+
+        Ok(TimeBound::Date { year, month, day })
+    }
 }
 
-pub fn issue_state(state: &issue::State) -> (String, Color) {
+pub fn issue_state(state: &issue::State) -> (String, BadgeKind) {
     match state {
-        issue::State::Open => (" ● ".into(), Color::Green),
-        issue::State::Closed { reason: _ } => (" ● ".into(), Color::Red),
+        issue::State::Open => ("●".into(), BadgeKind::Open),
+        issue::State::Closed { reason: _ } => ("●".into(), BadgeKind::Closed),
     }
 }
 
-pub fn patch_state(state: &patch::State) -> (String, Color) {
+pub fn patch_state(state: &patch::State) -> (String, BadgeKind) {
     match state {
-        patch::State::Open { conflicts: _ } => (" ● ".into(), Color::Green),
-        patch::State::Archived => (" ● ".into(), Color::Yellow),
-        patch::State::Draft => (" ● ".into(), Color::Gray),
+        patch::State::Open { conflicts: _ } => ("●".into(), BadgeKind::Open),
+        patch::State::Archived => ("●".into(), BadgeKind::Archived),
+        patch::State::Draft => ("●".into(), BadgeKind::Draft),
         patch::State::Merged {
             revision: _,
             commit: _,
-        } => (" ✔ ".into(), Color::Magenta),
+        } => ("✔".into(), BadgeKind::Merged),
+    }
+}
+
+/// Returns the kind of badge a notification status, e.g. `merged`, is rendered
+/// as.
+pub fn notification_status(status: &str) -> BadgeKind {
+    match status {
+        "open" | "created" => BadgeKind::Open,
+        "closed" => BadgeKind::Closed,
+        "merged" => BadgeKind::Merged,
+        "draft" => BadgeKind::Draft,
+        "archived" => BadgeKind::Archived,
+        "updated" => BadgeKind::Updated,
+        _ => BadgeKind::Neutral,
     }
 }
 
@@ -71,7 +303,7 @@ pub fn labels(labels: &[Label]) -> String {
 pub fn author(did: &Did, alias: &Option<Alias>, is_you: bool) -> String {
     let author = match alias {
         Some(alias) => format!("{alias}"),
-        None => self::did(did),
+        None => self::did(did, DidFormat::default()),
     };
 
     if is_you {
@@ -96,3 +328,205 @@ pub fn assignees(assignees: &[(Option<PublicKey>, Option<Alias>, bool)]) -> Stri
     }
     output
 }
+
+/// Where the ellipsis goes if a string needs to be truncated.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Ellipsis {
+    /// Keeps the end, e.g. `…/heads/main`.
+    Start,
+    /// Keeps the start and the end, e.g. `refs/…/main`.
+    Middle,
+    /// Keeps the start, e.g. `refs/heads…`.
+    #[default]
+    End,
+}
+
+/// Truncates the given string to at most `max` characters, replacing the part
+/// cut off with an ellipsis. Strings are cut at character boundaries only.
+pub fn truncate(s: &str, max: usize, ellipsis: Ellipsis) -> String {
+    let len = s.chars().count();
+    if len <= max {
+        return s.to_string();
+    }
+    if max == 0 {
+        return String::new();
+    }
+
+    let keep = max - 1;
+    let head = |n: usize| s.chars().take(n).collect::<String>();
+    let tail = |n: usize| s.chars().skip(len - n).collect::<String>();
+
+    match ellipsis {
+        Ellipsis::Start => format!("…{}", tail(keep)),
+        Ellipsis::Middle => format!("{}…{}", head(keep / 2), tail(keep - keep / 2)),
+        Ellipsis::End => format!("{}…", head(keep)),
+    }
+}
+
+/// Truncates the given string to at most `max` characters, keeping its start
+/// and end, e.g. `refs/heads/feature/very-long-name` becomes `refs/hea…long-name`
+/// if `max` is 18.
+pub fn truncate_middle(s: &str, max: usize) -> String {
+    truncate(s, max, Ellipsis::Middle)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use anyhow::Result;
+
+    use super::*;
+
+    #[test]
+    fn states_should_share_badge_kinds_across_views() {
+        assert_eq!(
+            patch_state(&patch::State::Draft),
+            ("●".to_string(), notification_status("draft"))
+        );
+        assert_eq!(
+            issue_state(&issue::State::Open).1,
+            notification_status("created")
+        );
+        assert_eq!(notification_status("unknown"), BadgeKind::Neutral);
+    }
+
+    #[test]
+    fn did_formats_should_succeed() -> Result<()> {
+        let alice = Did::from_str("did:key:z6MkkpTPzcq1ybmjQyQpyre15JUeMvZY6toxoZVpLZ8YarsB")?;
+
+        assert_eq!(did(&alice, DidFormat::Short), "z6MkkpT…Z8YarsB");
+        assert_eq!(did(&alice, DidFormat::Truncated(4)), "z6Mk…arsB");
+        assert_eq!(
+            did(&alice, DidFormat::Full),
+            "did:key:z6MkkpTPzcq1ybmjQyQpyre15JUeMvZY6toxoZVpLZ8YarsB"
+        );
+        assert_eq!(
+            did(&alice, DidFormat::Truncated(100)),
+            "z6MkkpTPzcq1ybmjQyQpyre15JUeMvZY6toxoZVpLZ8YarsB"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn identicon_is_deterministic_and_fixed_width() -> Result<()> {
+        let alice = Did::from_str("did:key:z6MkkpTPzcq1ybmjQyQpyre15JUeMvZY6toxoZVpLZ8YarsB")?;
+        let bob = Did::from_str("did:key:z6Mku8hpprWTmCv3BqkssCYDfr2feUdyLSUnycVajFo9XVAx")?;
+
+        assert_eq!(identicon(&alice), identicon(&alice));
+        assert_eq!(identicon(&alice).0.chars().count(), 3);
+        assert_eq!(identicon(&bob).0.chars().count(), 3);
+
+        Ok(())
+    }
+
+    #[test]
+    fn author_color_is_deterministic_and_matches_identicon() -> Result<()> {
+        let alice = Did::from_str("did:key:z6MkkpTPzcq1ybmjQyQpyre15JUeMvZY6toxoZVpLZ8YarsB")?;
+
+        assert_eq!(author_color(alice.as_key()), author_color(alice.as_key()));
+        assert!(AUTHOR_COLORS.contains(&author_color(alice.as_key())));
+        assert_eq!(identicon(&alice).1, author_color(alice.as_key()));
+
+        Ok(())
+    }
+
+    #[test]
+    fn timestamp_formats_should_succeed() {
+        // 2024-02-29T13:05:09Z
+        let time = Timestamp::from_secs(1709211909);
+        let now = UNIX_EPOCH + Duration::from_secs(1709211909 + 3 * 3600);
+
+        assert_eq!(
+            timestamp_at(&time, TimestampFormat::Relative, now),
+            "3 hours ago"
+        );
+        assert_eq!(
+            timestamp_at(&time, TimestampFormat::Iso8601, now),
+            "2024-02-29T13:05:09Z"
+        );
+        assert_eq!(
+            timestamp_at(&time, TimestampFormat::Short, now),
+            "2024-02-29"
+        );
+    }
+
+    #[test]
+    fn truncate_should_keep_short_strings() {
+        assert_eq!(truncate_middle("refs/heads/main", 15), "refs/heads/main");
+        assert_eq!(truncate("main", 4, Ellipsis::End), "main");
+    }
+
+    #[test]
+    fn truncate_should_place_ellipsis() {
+        let refname = "refs/heads/feature/long-name";
+
+        assert_eq!(truncate(refname, 12, Ellipsis::Start), "…e/long-name");
+        assert_eq!(truncate(refname, 12, Ellipsis::Middle), "refs/…g-name");
+        assert_eq!(truncate(refname, 12, Ellipsis::End), "refs/heads/…");
+        assert_eq!(truncate(refname, 1, Ellipsis::Middle), "…");
+        assert_eq!(truncate(refname, 0, Ellipsis::Middle), "");
+    }
+
+    #[test]
+    fn truncate_should_not_split_multi_byte_characters() {
+        let refname = "refs/heads/föö-bär-🚀-änderung";
+
+        for max in 0..refname.chars().count() {
+            for ellipsis in [Ellipsis::Start, Ellipsis::Middle, Ellipsis::End] {
+                let truncated = truncate(refname, max, ellipsis);
+                assert_eq!(truncated.chars().count(), max);
+            }
+        }
+        assert_eq!(truncate_middle(refname, 9), "refs…rung");
+        assert_eq!(truncate_middle("🚀🚀🚀🚀🚀", 4), "🚀…🚀🚀");
+    }
+
+    #[test]
+    fn relative_time_bounds_should_round_trip() -> Result<()> {
+        let now = UNIX_EPOCH + Duration::from_secs(30 * 86400);
+
+        assert_eq!(
+            TimeBound::from_str("7d")?,
+            TimeBound::Ago(Duration::from_secs(7 * 86400))
+        );
+        assert_eq!(TimeBound::from_str("7d")?.to_string(), "1w");
+        assert_eq!(TimeBound::from_str("36h")?.to_string(), "36h");
+        assert_eq!(TimeBound::from_str("2w")?.secs_at(now), 16 * 86400);
+        assert!(TimeBound::from_str("d").is_err());
+        assert!(TimeBound::from_str("7m").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn absolute_time_bounds_should_round_trip() -> Result<()> {
+        let date = TimeBound::from_str("2024-01-01")?;
+
+        assert_eq!(date.to_string(), "2024-01-01");
+        assert_eq!(date.secs_at(SystemTime::now()), 1704067200);
+        assert_eq!(date.end_secs_at(SystemTime::now()), 1704067200 + 86400);
+        assert_eq!(TimeBound::from_str("2024-02-29")?.to_string(), "2024-02-29");
+        assert!(TimeBound::from_str("2023-02-29").is_err());
+        assert!(TimeBound::from_str("2024-13-01").is_err());
+        assert!(TimeBound::from_str("yesterday").is_err());
+        assert!(TimeBound::from_str("1969-12-31").is_err());
+        assert!(TimeBound::from_str("10000-01-01").is_err());
+        assert!(TimeBound::from_str("99999999999999999-01-01").is_err());
+        assert_eq!(
+            TimeBound::from_str("9999-12-31")?.end_secs_at(SystemTime::now()),
+            253402300800
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn timestamp_in_the_future_should_not_panic() {
+        let time = Timestamp::from_secs(1709211909);
+        let now = UNIX_EPOCH + Duration::from_secs(1709211000);
+
+        assert_eq!(timestamp_at(&time, TimestampFormat::Relative, now), "now");
+    }
+}
@@ -1,3 +1,5 @@
+use std::marker::PhantomData;
+
 use termion::event::Key;
 
 use ratatui::layout::{Constraint, Layout};
@@ -7,7 +9,7 @@ use radicle_tui as tui;
 
 use tui::ui::im::widget::{TableState, TextEditState, Widget};
 use tui::ui::im::{Borders, Response, Ui};
-use tui::ui::{BufferedValue, Column, ToRow};
+use tui::ui::{BufferedValue, Column, TableRow};
 
 pub struct UiExt<'a, M>(&'a mut Ui<M>);
 
@@ -29,7 +31,7 @@ where
     M: Clone,
 {
     #[allow(clippy::too_many_arguments)]
-    pub fn browser<R, const W: usize>(
+    pub fn browser<R, A>(
         &mut self,
         frame: &mut Frame,
         selected: &'a mut Option<usize>,
@@ -40,9 +42,9 @@ where
         search: &'a mut BufferedValue<TextEditState>,
     ) -> Response
     where
-        R: ToRow<W> + Clone,
+        R: TableRow<A> + Clone,
     {
-        Browser::<R, W>::new(selected, items, header, footer, show_search, search).ui(self.0, frame)
+        Browser::<R, A>::new(selected, items, header, footer, show_search, search).ui(self.0, frame)
     }
 }
 
@@ -69,17 +71,18 @@ impl BrowserState {
     }
 }
 
-pub struct Browser<'a, R, const W: usize> {
+pub struct Browser<'a, R, A> {
     items: &'a Vec<R>,
     selected: &'a mut Option<usize>,
     header: Vec<Column<'a>>,
     footer: Vec<Column<'a>>,
     show_search: &'a mut bool,
     search: &'a mut BufferedValue<TextEditState>,
+    phantom: PhantomData<A>,
 }
 
 #[allow(dead_code)]
-impl<'a, R, const W: usize> Browser<'a, R, W> {
+impl<'a, R, A> Browser<'a, R, A> {
     pub fn new(
         selected: &'a mut Option<usize>,
         items: &'a Vec<R>,
@@ -95,6 +98,7 @@ impl<'a, R, const W: usize> Browser<'a, R, W> {
             footer: footer.into_iter().collect(),
             show_search,
             search,
+            phantom: PhantomData,
         }
     }
 
@@ -105,9 +109,9 @@ impl<'a, R, const W: usize> Browser<'a, R, W> {
 
 /// TODO(erikli): Implement `show` that returns an `InnerResponse` such that it can
 /// used like a group.
-impl<'a, R, const W: usize> Widget for Browser<'a, R, W>
+impl<'a, R, A> Widget for Browser<'a, R, A>
 where
-    R: ToRow<W> + Clone,
+    R: TableRow<A> + Clone,
 {
     fn ui<M>(self, ui: &mut Ui<M>, frame: &mut Frame) -> Response
     where
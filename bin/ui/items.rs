@@ -1,11 +1,16 @@
-use std::collections::HashMap;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
 use std::fmt::Debug;
 use std::str::FromStr;
-
-use nom::bytes::complete::{tag, take};
-use nom::multi::separated_list0;
-use nom::sequence::{delimited, preceded};
+use std::time::SystemTime;
+
+use nom::branch::alt;
+use nom::bytes::complete::{tag, take, take_till1};
+use nom::character::complete::{alpha1, char, multispace0, multispace1};
+use nom::combinator::{consumed, map};
+use nom::multi::{many0, separated_list0};
+use nom::sequence::{delimited, preceded, separated_pair, tuple};
 use nom::{IResult, Parser};
 
 use ansi_to_tui::IntoText;
@@ -36,21 +41,275 @@ use ratatui::widgets::Cell;
 
 use tui_tree_widget::TreeItem;
 
+use unicode_width::UnicodeWidthStr;
+
 use radicle_tui as tui;
 
-use tui::ui::theme::style;
+use tui::ui::diff::{
+    collapse, fold, hidden_line, line_at_row, line_numbers, DiffLine, Fold, LineNumbers, SplitDiff,
+};
+use tui::ui::theme::{style, Charset};
 use tui::ui::utils::LineMerger;
 use tui::ui::{span, Column};
-use tui::ui::{ToRow, ToTree};
+use tui::ui::{Highlight, ToRow, ToRowDyn, ToTree};
 
+use crate::cob::inbox::SortBy;
 use crate::git::{Blobs, DiffStats, HunkDiff, HunkStats, StatefulHunkDiff};
+use crate::settings::{DidFormat, MaxWidths, Settings, TimestampFormat};
 use crate::ui;
 
 use super::super::git;
 use super::format;
+use super::format::{Ellipsis, TimeBound};
 
 pub trait Filter<T> {
     fn matches(&self, item: &T) -> bool;
+
+    /// Returns a short, human-readable description of each active filter
+    /// criterion, e.g. `open` or `authored by you`.
+    fn describe(&self) -> Vec<String>;
+
+    /// Returns the free-text search of this filter, if any.
+    fn search(&self) -> Option<&str> {
+        None
+    }
+}
+
+/// The width of the leading icon column of a row.
+pub const ICON_WIDTH: usize = 2;
+
+/// A rule that marks each item matching its predicate with an icon, which is
+/// rendered in the leading column of its row, e.g. a ⭐ for issues you authored.
+#[derive(Clone, Debug)]
+pub struct IconRule<F> {
+    pub predicate: F,
+    pub glyph: String,
+    pub style: Style,
+}
+
+impl<F> IconRule<F> {
+    pub fn new(predicate: F, glyph: impl ToString, style: Style) -> Self {
+        Self {
+            predicate,
+            glyph: glyph.to_string(),
+            style,
+        }
+    }
+}
+
+/// Returns the icon of the first rule that matches the item, cut to
+/// `ICON_WIDTH` columns such that wide glyphs don't shift other columns. Items
+/// that no rule matches get a blank icon.
+pub fn icon<'a, T, F>(rules: &[IconRule<F>], item: &T) -> Span<'a>
+where
+    F: Filter<T>,
+{
+    let Some(rule) = rules.iter().find(|rule| rule.predicate.matches(item)) else {
+        return span::blank();
+    };
+
+    let mut glyph = String::new();
+    for c in rule.glyph.chars() {
+        let mut next = glyph.clone();
+        next.push(c);
+        if next.width() > ICON_WIDTH {
+            break;
+        }
+        glyph = next;
+    }
+
+    Span::styled(glyph, rule.style)
+}
+
+/// A filter on a single property of an item. It matches either a single value,
+/// or multiple values that are combined with `or` or `and`, e.g. the expression
+/// `state=(open or closed)`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ValueFilter<T> {
+    Single(T),
+    Or(Vec<T>),
+    And(Vec<T>),
+}
+
+impl<T> ValueFilter<T> {
+    /// Returns `true` if the predicate is satisfied by the value of this filter,
+    /// by any of its values if combined with `or` or by all of its values if
+    /// combined with `and`.
+    pub fn matches(&self, predicate: impl Fn(&T) -> bool) -> bool {
+        match self {
+            ValueFilter::Single(value) => predicate(value),
+            ValueFilter::Or(values) => values.iter().any(predicate),
+            ValueFilter::And(values) => values.iter().all(predicate),
+        }
+    }
+
+    /// Returns the value if this filter consists of a single value only.
+    pub fn single(&self) -> Option<&T> {
+        match self {
+            ValueFilter::Single(value) => Some(value),
+            ValueFilter::Or(values) | ValueFilter::And(values) if values.len() == 1 => {
+                values.first()
+            }
+            _ => None,
+        }
+    }
+
+    pub fn try_map<U, E>(self, f: impl Fn(T) -> Result<U, E>) -> Result<ValueFilter<U>, E> {
+        Ok(match self {
+            ValueFilter::Single(value) => ValueFilter::Single(f(value)?),
+            ValueFilter::Or(values) => {
+                ValueFilter::Or(values.into_iter().map(f).collect::<Result<_, _>>()?)
+            }
+            ValueFilter::And(values) => {
+                ValueFilter::And(values.into_iter().map(f).collect::<Result<_, _>>()?)
+            }
+        })
+    }
+
+    /// Formats all values of this filter and joins them with their operator.
+    pub fn describe(&self, f: impl Fn(&T) -> String) -> String {
+        match self {
+            ValueFilter::Single(value) => f(value),
+            ValueFilter::Or(values) => values.iter().map(f).collect::<Vec<_>>().join(" or "),
+            ValueFilter::And(values) => values.iter().map(f).collect::<Vec<_>>().join(" and "),
+        }
+    }
+}
+
+/// A token of a search string. It is either an expression, e.g. `state=open` or
+/// `author=(<did> or <did>)`, or a single word.
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum Token<'a> {
+    Expr {
+        raw: &'a str,
+        key: &'a str,
+        value: ValueFilter<&'a str>,
+    },
+    Word(&'a str),
+}
+
+/// Splits a search string into tokens. Words that do not form a valid expression,
+/// e.g. an expression that is not completely typed yet, are returned as single
+/// words.
+fn tokens(input: &str) -> IResult<&str, Vec<Token<'_>>> {
+    preceded(multispace0, separated_list0(multispace1, token))(input)
+}
+
+fn token(input: &str) -> IResult<&str, Token<'_>> {
+    alt((
+        map(
+            consumed(separated_pair(alpha1, char('='), value_filter)),
+            |(raw, (key, value))| Token::Expr { raw, key, value },
+        ),
+        map(take_till1(char::is_whitespace), Token::Word),
+    ))(input)
+}
+
+fn value_filter(input: &str) -> IResult<&str, ValueFilter<&str>> {
+    alt((
+        delimited(
+            tuple((char('('), multispace0)),
+            value_group,
+            tuple((multispace0, char(')'))),
+        ),
+        map(value, ValueFilter::Single),
+    ))(input)
+}
+
+/// Parses values combined with either `or` or `and`. Mixing both is not supported
+/// and fails the whole search string.
+fn value_group(input: &str) -> IResult<&str, ValueFilter<&str>> {
+    let operator = delimited(multispace1, alt((tag("or"), tag("and"))), multispace1);
+    let (rest, (first, others)) = tuple((value, many0(tuple((operator, value)))))(input)?;
+
+    let mut values = vec![first];
+    values.extend(others.iter().map(|(_, value)| *value));
+
+    let filter = match others.first().map(|(operator, _)| *operator) {
+        None => ValueFilter::Single(first),
+        Some(operator) if others.iter().any(|(other, _)| *other != operator) => {
+            return Err(nom::Err::Failure(nom::error::Error::new(
+                input,
+                nom::error::ErrorKind::Verify,
+            )));
+        }
+        Some("and") => ValueFilter::And(values),
+        Some(_) => ValueFilter::Or(values),
+    };
+
+    Ok((rest, filter))
+}
+
+fn value(input: &str) -> IResult<&str, &str> {
+    take_till1(|c: char| c.is_whitespace() || c == '(' || c == ')')(input)
+}
+
+/// Returns `true` if the timestamp lies within the given bounds, e.g. those of
+/// `since:7d` and `until:2024-01-01`. Dates include all of their day.
+fn matches_time(time: &Timestamp, since: Option<&TimeBound>, until: Option<&TimeBound>) -> bool {
+    let now = SystemTime::now();
+
+    since.map_or(true, |since| time.as_secs() >= since.secs_at(now))
+        && until.map_or(true, |until| time.as_secs() < until.end_secs_at(now))
+}
+
+/// Appends the given word to a search string if it does not contain it yet, and
+/// removes all of its occurrences otherwise. Used to toggle single-word filters
+/// like `is:authored` without the search string and the filter getting out of sync.
+pub fn toggle_search_word(search: &str, word: &str) -> String {
+    let words = search.split_whitespace().collect::<Vec<_>>();
+
+    if words.contains(&word) {
+        words
+            .into_iter()
+            .filter(|other| *other != word)
+            .collect::<Vec<_>>()
+            .join(" ")
+    } else {
+        [words, vec![word]].concat().join(" ")
+    }
+}
+
+/// Replaces the state filter of a search string with the one that follows it in
+/// `states`, which are words like `is:open`. The last one is followed by no
+/// state filter, such that items of all states are shown, and that by the first
+/// one again. A `state=` expression is replaced by the first one.
+pub fn cycle_search_state(search: &str, states: &[&str]) -> String {
+    let words = match tokens(search) {
+        Ok((_, tokens)) => tokens
+            .into_iter()
+            .map(|token| match token {
+                Token::Expr {
+                    raw, key: "state", ..
+                } => (raw, true),
+                Token::Expr { raw, .. } => (raw, false),
+                Token::Word(word) => (word, states.contains(&word)),
+            })
+            .collect::<Vec<_>>(),
+        Err(_) => search
+            .split_whitespace()
+            .map(|word| (word, states.contains(&word)))
+            .collect(),
+    };
+
+    // Like when the search is parsed, the last state filter wins.
+    let current = words
+        .iter()
+        .rev()
+        .find(|(_, is_state)| *is_state)
+        .map(|(word, _)| states.iter().position(|state| state == word));
+    let next = match current {
+        Some(Some(index)) => states.get(index + 1),
+        Some(None) | None => states.first(),
+    };
+
+    words
+        .into_iter()
+        .filter(|(_, is_state)| !is_state)
+        .map(|(word, _)| word)
+        .chain(next.copied())
+        .collect::<Vec<_>>()
+        .join(" ")
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -59,6 +318,8 @@ pub struct AuthorItem {
     pub human_nid: Option<String>,
     pub alias: Option<Alias>,
     pub you: bool,
+    /// If an identicon should be rendered next to the alias.
+    pub identicon: bool,
 }
 
 impl AuthorItem {
@@ -68,13 +329,63 @@ impl AuthorItem {
             None => None,
         };
         let you = nid.map(|nid| nid == *profile.id()).unwrap_or_default();
-        let human_nid = nid.map(|nid| format::did(&Did::from(nid)));
+        let human_nid = nid.map(|nid| format::did(&Did::from(nid), DidFormat::default()));
 
         Self {
             nid,
             human_nid,
             alias,
             you,
+            identicon: false,
+        }
+    }
+
+    pub fn with_identicon(mut self, identicon: bool) -> Self {
+        self.identicon = identicon;
+        self
+    }
+
+    /// Renders the node id of this author in the given format if they don't
+    /// have an alias.
+    pub fn with_did_format(mut self, format: DidFormat) -> Self {
+        self.human_nid = self.nid.map(|nid| format::did(&Did::from(nid), format));
+        self
+    }
+
+    /// Renders the alias of this author in a color that is stable per author, or
+    /// their human-readable node id dimmed if they don't have one, cut in the
+    /// middle if longer than `max`. The local author is rendered bold.
+    fn colored_alias(&self, max: usize) -> Span<'static> {
+        match &self.alias {
+            Some(alias) => {
+                let alias = if self.you {
+                    span::alias(&format::truncate_middle(&format!("{} (you)", alias), max)).bold()
+                } else {
+                    span::alias(&format::truncate_middle(alias, max))
+                };
+                match self.nid {
+                    Some(nid) => alias.fg(format::author_color(&nid)),
+                    None => alias,
+                }
+            }
+            None => match &self.human_nid {
+                Some(nid) => span::alias(&format::truncate_middle(nid, max)).dim(),
+                None => span::blank(),
+            },
+        }
+    }
+
+    /// Prepends this authors' identicon to the given alias, if enabled. The
+    /// identicon has a fixed width and is left blank if the NID is unknown.
+    fn with_identicon_span<'a>(&self, alias: Span<'a>) -> Line<'a> {
+        if self.identicon {
+            let identicon = match self.nid {
+                Some(nid) => ui::span::identicon(&Did::from(nid)),
+                None => span::default("   "),
+            };
+            Line::from([identicon, span::default(" "), alias].to_vec())
+        } else {
+            Line::from(alias)
         }
     }
 }
@@ -214,6 +525,26 @@ pub struct NotificationItem {
     pub author: AuthorItem,
     /// Time the update has happened.
     pub timestamp: Timestamp,
+    /// The format the timestamp is rendered in.
+    pub timestamp_format: TimestampFormat,
+    /// The maximum widths of the project name, summary and author alias.
+    pub max_widths: MaxWidths,
+    /// The group this is shown in, if notifications are grouped.
+    pub group: Option<NotificationGroup>,
+}
+
+/// The row a notification is shown in if notifications of the same COB or
+/// branch are grouped.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum NotificationGroup {
+    /// The latest notification of a group with the given count, whose other
+    /// notifications are hidden.
+    Collapsed(usize),
+    /// The latest notification of a group with the given count, whose other
+    /// notifications are shown below.
+    Expanded(usize),
+    /// Any other notification of an expanded group.
+    Member,
 }
 
 impl NotificationItem {
@@ -241,10 +572,128 @@ impl NotificationItem {
             kind: kind.unwrap(),
             author: AuthorItem::new(notification.remote, profile),
             timestamp: notification.timestamp.into(),
+            timestamp_format: TimestampFormat::default(),
+            max_widths: MaxWidths::default(),
+            group: None,
         }))
     }
+
+    /// Applies the formats and widths of the given settings.
+    pub fn with_settings(self, settings: &Settings) -> Self {
+        self.with_timestamp_format(settings.timestamp_format)
+            .with_did_format(settings.did_format)
+            .with_max_widths(settings.max_widths)
+    }
+
+    pub fn with_timestamp_format(mut self, format: TimestampFormat) -> Self {
+        self.timestamp_format = format;
+        self
+    }
+
+    pub fn with_did_format(mut self, format: DidFormat) -> Self {
+        self.author = self.author.with_did_format(format);
+        self
+    }
+
+    pub fn with_max_widths(mut self, max_widths: MaxWidths) -> Self {
+        self.max_widths = max_widths;
+        self
+    }
+
+    /// Returns the key notifications are grouped by: the COB or branch of the
+    /// project they refer to. Notifications of unknown kind are never grouped.
+    pub fn group_key(&self) -> String {
+        match &self.kind {
+            NotificationKindItem::Branch { name, .. } => {
+                format!("{}/branch/{}", self.project, name)
+            }
+            NotificationKindItem::Cob {
+                type_name,
+                id: Some(id),
+                ..
+            } => format!("{}/{}/{}", self.project, type_name, id),
+            _ => self.id.to_string(),
+        }
+    }
+
+    /// Compares two notifications by the given field. Notifications of the same
+    /// project are ordered by timestamp, newest first, regardless of the direction
+    /// projects are sorted in.
+    pub fn compare(&self, other: &Self, sort_by: &SortBy) -> Ordering {
+        let ordering = match sort_by.field {
+            "timestamp" => self.timestamp.cmp(&other.timestamp),
+            "id" => self.id.cmp(&other.id),
+            "project" => self.project.cmp(&other.project),
+            _ => Ordering::Equal,
+        };
+        let ordering = if sort_by.reverse {
+            ordering.reverse()
+        } else {
+            ordering
+        };
+
+        match sort_by.field {
+            "project" => ordering.then_with(|| other.timestamp.cmp(&self.timestamp)),
+            _ => ordering,
+        }
+    }
+}
+
+/// Groups the given notifications by COB or branch, see [`NotificationItem::group_key`].
+/// A group is shown where its first notification is listed, as the latest one
+/// of the group. Its other notifications follow only if the group's key is
+/// `expanded`. Groups of a single notification are shown as is.
+pub fn group_notifications(
+    items: &[NotificationItem],
+    expanded: &HashSet<String>,
+) -> Vec<NotificationItem> {
+    // Groups are kept in the order of their first notification.
+    let mut groups: Vec<(String, Vec<&NotificationItem>)> = vec![];
+    let mut index: HashMap<String, usize> = HashMap::new();
+    for item in items {
+        let key = item.group_key();
+        match index.get(&key) {
+            Some(&position) => groups[position].1.push(item),
+            None => {
+                index.insert(key.clone(), groups.len());
+                groups.push((key, vec![item]));
+            }
+        }
+    }
+
+    let mut notifications = vec![];
+    for (key, group) in groups {
+        let Some(latest) = group.iter().max_by_key(|item| item.timestamp) else {
+            continue;
+        };
+        if group.len() == 1 {
+            notifications.push((*latest).clone());
+            continue;
+        }
+
+        let mut latest = (*latest).clone();
+        if expanded.contains(&key) {
+            latest.group = Some(NotificationGroup::Expanded(group.len()));
+            notifications.push(latest.clone());
+            notifications.extend(group.into_iter().filter(|item| item.id != latest.id).map(
+                |item| NotificationItem {
+                    group: Some(NotificationGroup::Member),
+                    ..item.clone()
+                },
+            ));
+        } else {
+            latest.group = Some(NotificationGroup::Collapsed(group.len()));
+            notifications.push(latest);
+        }
+    }
+
+    notifications
 }
 
+/// The maximum width of refnames in notification rows. The end of a refname is
+/// usually more meaningful than its prefix, so longer ones are cut in the middle.
+const REFNAME_MAX_WIDTH: usize = 32;
+
 impl ToRow<9> for NotificationItem {
     fn to_row(&self) -> [Cell; 9] {
         let (type_name, summary, status, kind_id) = match &self.kind {
@@ -274,7 +723,7 @@ impl ToRow<9> for NotificationItem {
                 )
             }
             NotificationKindItem::Unknown { refname } => (
-                refname.to_string(),
+                format::truncate_middle(refname, REFNAME_MAX_WIDTH),
                 String::new(),
                 String::new(),
                 String::new(),
@@ -287,33 +736,57 @@ impl ToRow<9> for NotificationItem {
         } else {
             span::primary(" ● ")
         };
-        let kind_id = span::primary(&kind_id);
-        let summary = span::default(&summary);
+        let kind_id =
+            ui::span::truncated(&kind_id, REFNAME_MAX_WIDTH, Ellipsis::Middle).style(style::cyan());
+        let summary = span::default(&format::truncate_middle(&summary, self.max_widths.title));
+        let summary = match self.group {
+            Some(NotificationGroup::Collapsed(count)) => {
+                let marker = Charset::current().fold_marker(false);
+                Line::from(vec![
+                    span::default(&format!("{marker} {count} ")).dim(),
+                    summary,
+                ])
+            }
+            Some(NotificationGroup::Expanded(count)) => {
+                let marker = Charset::current().fold_marker(true);
+                Line::from(vec![
+                    span::default(&format!("{marker} {count} ")).dim(),
+                    summary,
+                ])
+            }
+            Some(NotificationGroup::Member) => {
+                let branch = Charset::current().branch();
+                Line::from(vec![span::default(&format!("  {branch} ")).dim(), summary])
+            }
+            None => Line::from(summary),
+        };
         let type_name = span::notification_type(&type_name);
-        let name = span::default(&self.project.clone()).style(style::gray().dim());
+        let name = span::default(&format::truncate_middle(
+            &self.project,
+            self.max_widths.name,
+        ))
+        .style(style::gray().dim());
 
-        let status = match status.as_str() {
-            "archived" => span::default(&status).yellow(),
-            "draft" => span::default(&status).gray().dim(),
-            "updated" => span::primary(&status),
-            "open" | "created" => span::positive(&status),
-            "closed" | "merged" => span::ternary(&status),
-            _ => span::default(&status),
+        let status = if status.is_empty() {
+            span::blank()
+        } else {
+            span::badge(&status, format::notification_status(&status))
         };
+        let max = self.max_widths.name;
         let author = match &self.author.alias {
             Some(alias) => {
                 if self.author.you {
-                    span::alias(&format!("{} (you)", alias))
+                    span::alias(&format::truncate_middle(&format!("{} (you)", alias), max))
                 } else {
-                    span::alias(alias)
+                    span::alias(&format::truncate_middle(alias, max))
                 }
             }
             None => match &self.author.human_nid {
-                Some(nid) => span::alias(nid).dim(),
+                Some(nid) => span::alias(&format::truncate_middle(nid, max)).dim(),
                 None => span::blank(),
             },
         };
-        let timestamp = span::timestamp(&format::timestamp(&self.timestamp));
+        let timestamp = span::timestamp(&format::timestamp(&self.timestamp, self.timestamp_format));
 
         [
             id.into(),
@@ -347,6 +820,9 @@ pub struct NotificationItemFilter {
     state: Option<NotificationState>,
     type_name: Option<NotificationType>,
     authors: Vec<Did>,
+    repo: Option<String>,
+    since: Option<TimeBound>,
+    until: Option<TimeBound>,
     search: Option<String>,
 }
 
@@ -354,6 +830,10 @@ impl NotificationItemFilter {
     pub fn state(&self) -> Option<NotificationState> {
         self.state.clone()
     }
+
+    pub fn is_default(&self) -> bool {
+        *self == NotificationItemFilter::default()
+    }
 }
 
 impl Filter<NotificationItem> for NotificationItemFilter {
@@ -396,6 +876,13 @@ impl Filter<NotificationItem> for NotificationItemFilter {
             })
             .unwrap_or(true);
 
+        let matches_repo = match &self.repo {
+            Some(repo) => notif.project == *repo,
+            None => true,
+        };
+
+        let matches_time = matches_time(&notif.timestamp, self.since.as_ref(), self.until.as_ref());
+
         let matches_search = match &self.search {
             Some(search) => {
                 let summary = match &notif.kind {
@@ -421,7 +908,52 @@ impl Filter<NotificationItem> for NotificationItemFilter {
             None => true,
         };
 
-        matches_state && matches_type && matches_authors && matches_search
+        matches_state
+            && matches_type
+            && matches_authors
+            && matches_repo
+            && matches_time
+            && matches_search
+    }
+
+    fn describe(&self) -> Vec<String> {
+        let mut chips = vec![];
+
+        match self.state {
+            Some(NotificationState::Seen) => chips.push("seen".to_string()),
+            Some(NotificationState::Unseen) => chips.push("unseen".to_string()),
+            None => {}
+        }
+        match self.type_name {
+            Some(NotificationType::Patch) => chips.push("patch".to_string()),
+            Some(NotificationType::Issue) => chips.push("issue".to_string()),
+            Some(NotificationType::Branch) => chips.push("branch".to_string()),
+            None => {}
+        }
+        for author in &self.authors {
+            chips.push(format!(
+                "author: {}",
+                format::did(author, DidFormat::default())
+            ));
+        }
+        if let Some(repo) = &self.repo {
+            chips.push(format!("repo: {repo}"));
+        }
+        if let Some(since) = &self.since {
+            chips.push(format!("since: {since}"));
+        }
+        if let Some(until) = &self.until {
+            chips.push(format!("until: {until}"));
+        }
+        if let Some(search) = &self.search {
+            chips.push(format!("\"{search}\""));
+        }
+
+        chips
+    }
+
+    fn search(&self) -> Option<&str> {
+        self.search.as_deref()
     }
 }
 
@@ -433,6 +965,9 @@ impl FromStr for NotificationItemFilter {
         let mut type_name = None;
         let mut search = String::new();
         let mut authors = vec![];
+        let mut repo = None;
+        let mut since = None;
+        let mut until = None;
 
         let mut authors_parser = |input| -> IResult<&str, Vec<&str>> {
             preceded(
@@ -453,6 +988,16 @@ impl FromStr for NotificationItemFilter {
                 "is:patch" => type_name = Some(NotificationType::Patch),
                 "is:issue" => type_name = Some(NotificationType::Issue),
                 "is:branch" => type_name = Some(NotificationType::Branch),
+                other if other.starts_with("repo:") => {
+                    let name = other.trim_start_matches("repo:");
+                    repo = (!name.is_empty()).then(|| name.to_string());
+                }
+                other if other.starts_with("since:") => {
+                    since = Some(TimeBound::from_str(other.trim_start_matches("since:"))?);
+                }
+                other if other.starts_with("until:") => {
+                    until = Some(TimeBound::from_str(other.trim_start_matches("until:"))?);
+                }
                 other => {
                     if let Ok((_, dids)) = authors_parser.parse(other) {
                         for did in dids {
@@ -465,11 +1010,20 @@ impl FromStr for NotificationItemFilter {
             }
         }
 
+        let search = if search.is_empty() {
+            None
+        } else {
+            Some(search)
+        };
+
         Ok(Self {
             state,
             type_name,
             authors,
-            search: Some(search),
+            repo,
+            since,
+            until,
+            search,
         })
     }
 }
@@ -484,12 +1038,20 @@ pub struct IssueItem {
     pub title: String,
     /// Issue author.
     pub author: AuthorItem,
-    /// Issue labels.
+    /// Issue labels, without the milestone label.
     pub labels: Vec<Label>,
+    /// The issue milestone, see `with_milestone_prefix`.
+    pub milestone: Option<String>,
     /// Issue assignees.
     pub assignees: Vec<AuthorItem>,
     /// Time when issue was opened.
     pub timestamp: Timestamp,
+    /// The format the timestamps are rendered in.
+    pub timestamp_format: TimestampFormat,
+    /// The maximum widths of the title and author alias.
+    pub max_widths: MaxWidths,
+    /// If only the icon, state, id, title and author are rendered.
+    pub compact: bool,
     /// Comment timeline
     pub comments: Vec<CommentItem>,
 }
@@ -504,11 +1066,15 @@ impl IssueItem {
             title: issue.title().into(),
             author: AuthorItem::new(Some(*issue.author().id), profile),
             labels: issue.labels().cloned().collect(),
+            milestone: None,
             assignees: issue
                 .assignees()
                 .map(|did| AuthorItem::new(Some(**did), profile))
                 .collect::<Vec<_>>(),
             timestamp: issue.timestamp(),
+            timestamp_format: TimestampFormat::default(),
+            max_widths: MaxWidths::default(),
+            compact: false,
             comments: issue
                 .comments()
                 .map(|(comment_id, comment)| {
@@ -518,6 +1084,87 @@ impl IssueItem {
         })
     }
 
+    /// Adds the given author to the assignees of this issue if `assigned` is true,
+    /// and removes them otherwise.
+    pub fn assign(&mut self, assignee: AuthorItem, assigned: bool) {
+        self.assignees.retain(|author| author.nid != assignee.nid);
+        if assigned {
+            self.assignees
+                .push(assignee.with_identicon(self.author.identicon));
+        }
+    }
+
+    /// Applies the identicons, formats, widths and milestone prefix of the given
+    /// settings.
+    pub fn with_settings(self, settings: &Settings) -> Self {
+        self.with_identicons(settings.identicons)
+            .with_timestamp_format(settings.timestamp_format)
+            .with_did_format(settings.did_format)
+            .with_max_widths(settings.max_widths)
+            .with_milestone_prefix(&settings.milestone_prefix)
+    }
+
+    pub fn with_identicons(mut self, identicons: bool) -> Self {
+        self.author = self.author.with_identicon(identicons);
+        self
+    }
+
+    pub fn with_max_widths(mut self, max_widths: MaxWidths) -> Self {
+        self.max_widths = max_widths;
+        self
+    }
+
+    pub fn with_compact(mut self, compact: bool) -> Self {
+        self.compact = compact;
+        self
+    }
+
+    /// Takes the milestone from the labels that start with the given prefix, e.g.
+    /// `milestone:v1.0` for the prefix `milestone:`. These labels are not listed
+    /// with the others, and if there are several, the first one is the milestone.
+    /// An empty prefix doesn't mark any label as milestone.
+    pub fn with_milestone_prefix(mut self, prefix: &str) -> Self {
+        if prefix.is_empty() {
+            return self;
+        }
+        let (milestones, labels): (Vec<_>, Vec<_>) = self
+            .labels
+            .into_iter()
+            .partition(|label| label.name().starts_with(prefix));
+
+        self.labels = labels;
+        self.milestone = milestones
+            .first()
+            .map(|label| label.name()[prefix.len()..].to_string())
+            .or(self.milestone);
+        self
+    }
+
+    pub fn with_timestamp_format(mut self, format: TimestampFormat) -> Self {
+        self.timestamp_format = format;
+        self.comments = self
+            .comments
+            .into_iter()
+            .map(|comment| comment.with_timestamp_format(format))
+            .collect();
+        self
+    }
+
+    pub fn with_did_format(mut self, format: DidFormat) -> Self {
+        self.author = self.author.with_did_format(format);
+        self.assignees = self
+            .assignees
+            .into_iter()
+            .map(|assignee| assignee.with_did_format(format))
+            .collect();
+        self.comments = self
+            .comments
+            .into_iter()
+            .map(|comment| comment.with_did_format(format))
+            .collect();
+        self
+    }
+
     pub fn root_comments(&self) -> Vec<CommentItem> {
         self.comments
             .iter()
@@ -527,47 +1174,69 @@ impl IssueItem {
     }
 }
 
-impl ToRow<8> for IssueItem {
-    fn to_row(&self) -> [Cell; 8] {
-        let (state, state_color) = format::issue_state(&self.state);
+/// Issues are rendered in 10 columns, or in 5 if compact: icon, state, id, title
+/// and author. Icons are picked by the rules in `Settings::issue_icons`.
+impl ToRowDyn for IssueItem {
+    fn to_dyn_row(&self) -> Vec<Cell> {
+        self.row(&Settings::current().issue_icons, None)
+    }
 
-        let state = span::default(&state).style(Style::default().fg(state_color));
-        let id = span::primary(&format::cob(&self.id));
-        let title = span::default(&self.title.clone());
+    fn to_highlighted_dyn_row(&self, highlight: &Highlight) -> Vec<Cell> {
+        self.row(&Settings::current().issue_icons, Some(highlight))
+    }
+}
 
-        let author = match &self.author.alias {
-            Some(alias) => {
-                if self.author.you {
-                    span::alias(&format!("{} (you)", alias))
-                } else {
-                    span::alias(alias)
-                }
-            }
-            None => match &self.author.human_nid {
-                Some(nid) => span::alias(nid).dim(),
-                None => span::blank(),
-            },
+impl IssueItem {
+    /// Returns the cells of this issue, with the icon of the first of the given
+    /// rules that matches it.
+    fn row(&self, icons: &[IconRule<IssueItemFilter>], highlight: Option<&Highlight>) -> Vec<Cell> {
+        let (state, kind) = format::issue_state(&self.state);
+
+        let icon = icon(icons, self);
+        let state = span::badge(&state, kind);
+        let id = span::primary(&format::cob(&self.id));
+        let title = format::truncate_middle(&self.title, self.max_widths.title);
+        let title = match highlight {
+            Some(highlight) => highlight.line(&title, Style::default()),
+            None => span::default(&title).into(),
         };
+
+        let author = self
+            .author
+            .with_identicon_span(self.author.colored_alias(self.max_widths.name));
+        if self.compact {
+            return vec![
+                icon.into(),
+                state.into(),
+                id.into(),
+                title.into(),
+                author.into(),
+            ];
+        }
+
         let did = match &self.author.human_nid {
             Some(nid) => span::alias(nid).dim(),
             None => span::blank(),
         };
         let labels = span::labels(&format::labels(&self.labels));
+        let milestone = span::labels(self.milestone.as_deref().unwrap_or_default()).bold();
         let assignees = self
             .assignees
             .iter()
             .map(|author| (author.nid, author.alias.clone(), author.you))
             .collect::<Vec<_>>();
         let assignees = span::alias(&format::assignees(&assignees));
-        let opened = span::timestamp(&format::timestamp(&self.timestamp));
+        let opened = span::timestamp(&format::timestamp(&self.timestamp, self.timestamp_format));
 
-        [
+        vec![
+            icon.into(),
             state.into(),
             id.into(),
             title.into(),
             author.into(),
             did.into(),
             labels.into(),
+            milestone.into(),
             assignees.into(),
             opened.into(),
         ]
@@ -576,17 +1245,30 @@ impl ToRow<8> for IssueItem {
 
 #[derive(Clone, Default, Debug, Eq, PartialEq)]
 pub struct IssueItemFilter {
-    state: Option<issue::State>,
+    state: Option<ValueFilter<issue::State>>,
     authored: bool,
-    authors: Vec<Did>,
+    authors: Option<ValueFilter<Did>>,
     assigned: bool,
-    assignees: Vec<Did>,
+    assignees: Option<ValueFilter<Did>>,
+    milestone: Option<String>,
+    since: Option<TimeBound>,
+    until: Option<TimeBound>,
     search: Option<String>,
 }
 
 impl IssueItemFilter {
+    /// The states cycled through by `cycle_search_state`.
+    pub const STATES: [&'static str; 3] = ["is:open", "is:closed", "is:solved"];
+
     pub fn state(&self) -> Option<issue::State> {
         self.state
+            .as_ref()
+            .and_then(|state| state.single())
+            .copied()
+    }
+
+    pub fn is_default(&self) -> bool {
+        *self == IssueItemFilter::default()
     }
 }
 
@@ -597,13 +1279,18 @@ impl Filter<IssueItem> for IssueItemFilter {
 
         let matcher = SkimMatcherV2::default();
 
-        let matches_state = match self.state {
-            Some(issue::State::Closed {
-                reason: CloseReason::Other,
-            }) => matches!(issue.state, issue::State::Closed { .. }),
-            Some(state) => issue.state == state,
-            None => true,
-        };
+        let matches_state = self
+            .state
+            .as_ref()
+            .map(|state| {
+                state.matches(|state| match state {
+                    issue::State::Closed {
+                        reason: CloseReason::Other,
+                    } => matches!(issue.state, issue::State::Closed { .. }),
+                    state => issue.state == *state,
+                })
+            })
+            .unwrap_or(true);
 
         let matches_authored = if self.authored {
             issue.author.you
@@ -611,12 +1298,10 @@ impl Filter<IssueItem> for IssueItemFilter {
             true
         };
 
-        let matches_authors = (!self.authors.is_empty())
-            .then(|| {
-                self.authors
-                    .iter()
-                    .any(|other| issue.author.nid == Some(**other))
-            })
+        let matches_authors = self
+            .authors
+            .as_ref()
+            .map(|authors| authors.matches(|other| issue.author.nid == Some(**other)))
             .unwrap_or(true);
 
         let matches_assigned = self
@@ -624,19 +1309,27 @@ impl Filter<IssueItem> for IssueItemFilter {
             .then(|| issue.assignees.iter().any(|assignee| assignee.you))
             .unwrap_or(true);
 
-        let matches_assignees = (!self.assignees.is_empty())
-            .then(|| {
-                self.assignees.iter().any(|other| {
+        let matches_assignees = self
+            .assignees
+            .as_ref()
+            .map(|assignees| {
+                assignees.matches(|other| {
                     issue
                         .assignees
                         .iter()
-                        .filter_map(|author| author.nid)
-                        .collect::<Vec<_>>()
-                        .contains(other)
+                        .any(|assignee| assignee.nid == Some(**other))
                 })
             })
             .unwrap_or(true);
 
+        let matches_milestone = self
+            .milestone
+            .as_ref()
+            .map(|milestone| issue.milestone.as_ref() == Some(milestone))
+            .unwrap_or(true);
+
+        let matches_time = matches_time(&issue.timestamp, self.since.as_ref(), self.until.as_ref());
+
         let matches_search = match &self.search {
             Some(search) => match matcher.fuzzy_match(&issue.title, search) {
                 Some(score) => score == 0 || score > 60,
@@ -650,8 +1343,56 @@ impl Filter<IssueItem> for IssueItemFilter {
             && matches_authors
             && matches_assigned
             && matches_assignees
+            && matches_milestone
+            && matches_time
             && matches_search
     }
+
+    fn describe(&self) -> Vec<String> {
+        let mut chips = vec![];
+
+        if let Some(state) = &self.state {
+            chips.push(state.describe(|state| match state {
+                issue::State::Open => "open".to_string(),
+                issue::State::Closed {
+                    reason: CloseReason::Solved,
+                } => "solved".to_string(),
+                issue::State::Closed {
+                    reason: CloseReason::Other,
+                } => "closed".to_string(),
+            }));
+        }
+        if self.authored {
+            chips.push("authored by you".to_string());
+        }
+        if let Some(authors) = &self.authors {
+            chips.push(format!("author: {}", authors.describe(format::did)));
+        }
+        if self.assigned {
+            chips.push("assigned to you".to_string());
+        }
+        if let Some(assignees) = &self.assignees {
+            chips.push(format!("assignee: {}", assignees.describe(format::did)));
+        }
+        if let Some(milestone) = &self.milestone {
+            chips.push(format!("milestone: {milestone}"));
+        }
+        if let Some(since) = &self.since {
+            chips.push(format!("since: {since}"));
+        }
+        if let Some(until) = &self.until {
+            chips.push(format!("until: {until}"));
+        }
+        if let Some(search) = &self.search {
+            chips.push(format!("\"{search}\""));
+        }
+
+        chips
+    }
+
+    fn search(&self) -> Option<&str> {
+        self.search.as_deref()
+    }
 }
 
 impl FromStr for IssueItemFilter {
@@ -661,9 +1402,12 @@ impl FromStr for IssueItemFilter {
         let mut state = None;
         let mut search = String::new();
         let mut authored = false;
-        let mut authors = vec![];
+        let mut authors = None;
         let mut assigned = false;
-        let mut assignees = vec![];
+        let mut assignees = None;
+        let mut milestone = None;
+        let mut since = None;
+        let mut until = None;
 
         let mut authors_parser = |input| -> IResult<&str, Vec<&str>> {
             preceded(
@@ -687,31 +1431,70 @@ impl FromStr for IssueItemFilter {
             )(input)
         };
 
-        let parts = value.split(' ');
-        for part in parts {
-            match part {
-                "is:open" => state = Some(issue::State::Open),
-                "is:closed" => {
-                    state = Some(issue::State::Closed {
+        let parse_state = |value: &str| -> Result<issue::State, anyhow::Error> {
+            match value {
+                "open" => Ok(issue::State::Open),
+                "closed" => Ok(issue::State::Closed {
+                    reason: issue::CloseReason::Other,
+                }),
+                "solved" => Ok(issue::State::Closed {
+                    reason: issue::CloseReason::Solved,
+                }),
+                other => Err(anyhow::anyhow!("unknown issue state '{other}'")),
+            }
+        };
+
+        let (_, tokens) = tokens(value).map_err(|err| anyhow::anyhow!("{err}"))?;
+        for token in tokens {
+            match token {
+                Token::Word("is:open") => state = Some(ValueFilter::Single(issue::State::Open)),
+                Token::Word("is:closed") => {
+                    state = Some(ValueFilter::Single(issue::State::Closed {
                         reason: issue::CloseReason::Other,
-                    })
+                    }))
                 }
-                "is:solved" => {
-                    state = Some(issue::State::Closed {
+                Token::Word("is:solved") => {
+                    state = Some(ValueFilter::Single(issue::State::Closed {
                         reason: issue::CloseReason::Solved,
-                    })
+                    }))
                 }
-                "is:authored" => authored = true,
-                "is:assigned" => assigned = true,
-                other => {
+                Token::Word("is:authored") => authored = true,
+                Token::Word("is:assigned") => assigned = true,
+                Token::Expr {
+                    key: "state",
+                    value,
+                    ..
+                } => state = Some(value.try_map(parse_state)?),
+                Token::Expr {
+                    key: "author",
+                    value,
+                    ..
+                } => authors = Some(value.try_map(Did::from_str)?),
+                Token::Expr {
+                    key: "assignee",
+                    value,
+                    ..
+                } => assignees = Some(value.try_map(Did::from_str)?),
+                Token::Expr { raw, .. } => search.push_str(raw),
+                Token::Word(other) => {
                     if let Ok((_, dids)) = assignees_parser.parse(other) {
-                        for did in dids {
-                            assignees.push(Did::from_str(did)?);
-                        }
+                        let dids = dids
+                            .into_iter()
+                            .map(Did::from_str)
+                            .collect::<Result<Vec<_>, _>>()?;
+                        assignees = Some(ValueFilter::Or(dids));
+                    } else if let Some(value) = other.strip_prefix("milestone:") {
+                        milestone = Some(value.to_string());
+                    } else if let Some(value) = other.strip_prefix("since:") {
+                        since = Some(TimeBound::from_str(value)?);
+                    } else if let Some(value) = other.strip_prefix("until:") {
+                        until = Some(TimeBound::from_str(value)?);
                     } else if let Ok((_, dids)) = authors_parser.parse(other) {
-                        for did in dids {
-                            authors.push(Did::from_str(did)?);
-                        }
+                        let dids = dids
+                            .into_iter()
+                            .map(Did::from_str)
+                            .collect::<Result<Vec<_>, _>>()?;
+                        authors = Some(ValueFilter::Or(dids));
                     } else {
                         search.push_str(other);
                     }
@@ -719,13 +1502,22 @@ impl FromStr for IssueItemFilter {
             }
         }
 
+        let search = if search.is_empty() {
+            None
+        } else {
+            Some(search)
+        };
+
         Ok(Self {
             state,
             authored,
             authors,
             assigned,
             assignees,
-            search: Some(search),
+            milestone,
+            since,
+            until,
+            search,
         })
     }
 }
@@ -746,64 +1538,143 @@ pub struct PatchItem {
     pub added: u16,
     /// Lines removed by the latest revision.
     pub removed: u16,
+    /// If the lines added and removed could not be computed, e.g. because the
+    /// revision range could not be diffed. Both are zero in that case.
+    pub unknown_stats: bool,
     /// Time when patch was opened.
     pub timestamp: Timestamp,
+    /// The format the timestamp is rendered in.
+    pub timestamp_format: TimestampFormat,
+    /// The maximum widths of the title and author alias.
+    pub max_widths: MaxWidths,
+    /// Times when revisions were pushed or comments were made on them.
+    pub activity: Vec<Timestamp>,
 }
 
 impl PatchItem {
-    pub fn new(
-        profile: &Profile,
-        repository: &Repository,
-        patch: (PatchId, Patch),
-    ) -> Result<Self, anyhow::Error> {
+    pub fn new(profile: &Profile, repository: &Repository, patch: (PatchId, Patch)) -> Self {
+        let author = AuthorItem::new(Some(*patch.1.author().id), profile);
+        Self::build(author, repository, patch)
+    }
+
+    /// Builds the item for a patch written by the given author. If the diff
+    /// stats of the latest revision cannot be computed, the failure is logged
+    /// and the patch is still built, but marked as having unknown stats.
+    fn build(author: AuthorItem, repository: &Repository, patch: (PatchId, Patch)) -> Self {
         let (id, patch) = patch;
         let (_, revision) = patch.latest();
         let (from, to) = revision.range();
-        let stats = git::diff_stats(repository.raw(), &from, &to)?;
 
-        Ok(Self {
+        let (added, removed, unknown_stats) = match git::diff_stats(repository.raw(), &from, &to) {
+            Ok(stats) => (stats.insertions() as u16, stats.deletions() as u16, false),
+            Err(err) => {
+                log::warn!("Could not compute diff stats of patch {id} ({from}..{to}): {err}");
+                (0, 0, true)
+            }
+        };
+
+        let activity = patch
+            .revisions()
+            .flat_map(|(_, revision)| {
+                std::iter::once(revision.timestamp()).chain(
+                    revision
+                        .discussion()
+                        .comments()
+                        .map(|(_, comment)| comment.timestamp()),
+                )
+            })
+            .collect();
+
+        Self {
             id,
             state: patch.state().clone(),
             title: patch.title().into(),
-            author: AuthorItem::new(Some(*patch.author().id), profile),
+            author,
             head: revision.head(),
-            added: stats.insertions() as u16,
-            removed: stats.deletions() as u16,
+            added,
+            removed,
+            unknown_stats,
             timestamp: patch.updated_at(),
-        })
+            timestamp_format: TimestampFormat::default(),
+            max_widths: MaxWidths::default(),
+            activity,
+        }
+    }
+
+    /// Applies the identicons, formats and widths of the given settings.
+    pub fn with_settings(self, settings: &Settings) -> Self {
+        self.with_identicons(settings.identicons)
+            .with_timestamp_format(settings.timestamp_format)
+            .with_did_format(settings.did_format)
+            .with_max_widths(settings.max_widths)
+    }
+
+    pub fn with_identicons(mut self, identicons: bool) -> Self {
+        self.author = self.author.with_identicon(identicons);
+        self
+    }
+
+    pub fn with_timestamp_format(mut self, format: TimestampFormat) -> Self {
+        self.timestamp_format = format;
+        self
+    }
+
+    pub fn with_did_format(mut self, format: DidFormat) -> Self {
+        self.author = self.author.with_did_format(format);
+        self
+    }
+
+    pub fn with_max_widths(mut self, max_widths: MaxWidths) -> Self {
+        self.max_widths = max_widths;
+        self
+    }
+
+    /// Returns the amount of lines added and removed by the latest revision.
+    pub fn lines_changed(&self) -> usize {
+        self.added as usize + self.removed as usize
     }
 }
 
 impl ToRow<9> for PatchItem {
     fn to_row(&self) -> [Cell; 9] {
-        let (state, color) = format::patch_state(&self.state);
+        self.row(None)
+    }
 
-        let state = span::default(&state).style(Style::default().fg(color));
-        let id = span::primary(&format::cob(&self.id));
-        let title = span::default(&self.title.clone());
+    fn to_highlighted_row(&self, highlight: &Highlight) -> [Cell; 9] {
+        self.row(Some(highlight))
+    }
+}
 
-        let author = match &self.author.alias {
-            Some(alias) => {
-                if self.author.you {
-                    span::alias(&format!("{} (you)", alias))
-                } else {
-                    span::alias(alias)
-                }
-            }
-            None => match &self.author.human_nid {
-                Some(nid) => span::alias(nid).dim(),
-                None => span::blank(),
-            },
+impl PatchItem {
+    fn row(&self, highlight: Option<&Highlight>) -> [Cell; 9] {
+        let (state, kind) = format::patch_state(&self.state);
+
+        let state = span::badge(&state, kind);
+        let id = span::primary(&format::cob(&self.id));
+        let title = format::truncate_middle(&self.title, self.max_widths.title);
+        let title = match highlight {
+            Some(highlight) => highlight.line(&title, Style::default()),
+            None => span::default(&title).into(),
         };
+
+        let author = self
+            .author
+            .with_identicon_span(self.author.colored_alias(self.max_widths.name));
         let did = match &self.author.human_nid {
             Some(nid) => span::alias(nid).dim(),
             None => span::blank(),
         };
 
         let head = span::ternary(&format::oid(self.head));
-        let added = span::positive(&format!("+{}", self.added));
-        let removed = span::negative(&format!("-{}", self.removed));
-        let updated = span::timestamp(&format::timestamp(&self.timestamp));
+        let (added, removed) = if self.unknown_stats {
+            (span::positive("+?").dim(), span::negative("-?").dim())
+        } else {
+            (
+                span::positive(&format!("+{}", self.added)),
+                span::negative(&format!("-{}", self.removed)),
+            )
+        };
+        let updated = span::timestamp(&format::timestamp(&self.timestamp, self.timestamp_format));
 
         [
             state.into(),
@@ -819,17 +1690,122 @@ impl ToRow<9> for PatchItem {
     }
 }
 
+/// The size of a patch, bucketed by the amount of lines it changes.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PatchSize {
+    /// Less than 10 lines changed.
+    ExtraSmall,
+    /// Less than 50 lines changed.
+    Small,
+    /// Less than 250 lines changed.
+    Medium,
+    /// Less than 1000 lines changed.
+    Large,
+    /// 1000 or more lines changed.
+    ExtraLarge,
+}
+
+impl PatchSize {
+    /// Returns the bucket the given amount of changed lines falls into.
+    pub fn of(lines: usize) -> Self {
+        match lines {
+            0..=9 => PatchSize::ExtraSmall,
+            10..=49 => PatchSize::Small,
+            50..=249 => PatchSize::Medium,
+            250..=999 => PatchSize::Large,
+            _ => PatchSize::ExtraLarge,
+        }
+    }
+}
+
+impl fmt::Display for PatchSize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PatchSize::ExtraSmall => write!(f, "xs"),
+            PatchSize::Small => write!(f, "s"),
+            PatchSize::Medium => write!(f, "m"),
+            PatchSize::Large => write!(f, "l"),
+            PatchSize::ExtraLarge => write!(f, "xl"),
+        }
+    }
+}
+
+impl FromStr for PatchSize {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "xs" => Ok(PatchSize::ExtraSmall),
+            "s" => Ok(PatchSize::Small),
+            "m" => Ok(PatchSize::Medium),
+            "l" => Ok(PatchSize::Large),
+            "xl" => Ok(PatchSize::ExtraLarge),
+            other => Err(anyhow::anyhow!("unknown patch size '{other}'")),
+        }
+    }
+}
+
+/// A comparison against the amount of lines a patch changes, e.g. `<100`
+/// or `>500`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LinesFilter {
+    LessThan(usize),
+    GreaterThan(usize),
+}
+
+impl LinesFilter {
+    pub fn matches(&self, lines: usize) -> bool {
+        match self {
+            LinesFilter::LessThan(max) => lines < *max,
+            LinesFilter::GreaterThan(min) => lines > *min,
+        }
+    }
+}
+
+impl fmt::Display for LinesFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LinesFilter::LessThan(max) => write!(f, "<{max}"),
+            LinesFilter::GreaterThan(min) => write!(f, ">{min}"),
+        }
+    }
+}
+
+impl FromStr for LinesFilter {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if let Some(max) = value.strip_prefix('<') {
+            Ok(LinesFilter::LessThan(max.parse()?))
+        } else if let Some(min) = value.strip_prefix('>') {
+            Ok(LinesFilter::GreaterThan(min.parse()?))
+        } else {
+            Err(anyhow::anyhow!("invalid lines comparison '{value}'"))
+        }
+    }
+}
+
 #[derive(Clone, Default, Debug, Eq, PartialEq)]
 pub struct PatchItemFilter {
-    status: Option<patch::Status>,
+    status: Option<ValueFilter<patch::Status>>,
     authored: bool,
-    authors: Vec<Did>,
+    authors: Option<ValueFilter<Did>>,
+    size: Option<ValueFilter<PatchSize>>,
+    lines: Vec<LinesFilter>,
+    since: Option<TimeBound>,
+    until: Option<TimeBound>,
     search: Option<String>,
 }
 
 impl PatchItemFilter {
+    /// The statuses cycled through by `cycle_search_state`.
+    pub const STATUSES: [&'static str; 4] = ["is:open", "is:draft", "is:merged", "is:archived"];
+
     pub fn status(&self) -> Option<patch::Status> {
         self.status
+            .as_ref()
+            .and_then(|status| status.single())
+            .copied()
     }
 
     pub fn is_default(&self) -> bool {
@@ -844,13 +1820,18 @@ impl Filter<PatchItem> for PatchItemFilter {
 
         let matcher = SkimMatcherV2::default();
 
-        let matches_state = match self.status {
-            Some(patch::Status::Draft) => matches!(patch.state, patch::State::Draft),
-            Some(patch::Status::Open) => matches!(patch.state, patch::State::Open { .. }),
-            Some(patch::Status::Merged) => matches!(patch.state, patch::State::Merged { .. }),
-            Some(patch::Status::Archived) => matches!(patch.state, patch::State::Archived),
-            None => true,
-        };
+        let matches_state = self
+            .status
+            .as_ref()
+            .map(|status| {
+                status.matches(|status| match status {
+                    patch::Status::Draft => matches!(patch.state, patch::State::Draft),
+                    patch::Status::Open => matches!(patch.state, patch::State::Open { .. }),
+                    patch::Status::Merged => matches!(patch.state, patch::State::Merged { .. }),
+                    patch::Status::Archived => matches!(patch.state, patch::State::Archived),
+                })
+            })
+            .unwrap_or(true);
 
         let matches_authored = if self.authored {
             patch.author.you
@@ -858,14 +1839,28 @@ impl Filter<PatchItem> for PatchItemFilter {
             true
         };
 
-        let matches_authors = (!self.authors.is_empty())
-            .then(|| {
-                self.authors
-                    .iter()
-                    .any(|other| patch.author.nid == Some(**other))
+        let matches_authors = self
+            .authors
+            .as_ref()
+            .map(|authors| authors.matches(|other| patch.author.nid == Some(**other)))
+            .unwrap_or(true);
+
+        let matches_size = self
+            .size
+            .as_ref()
+            .map(|size| {
+                !patch.unknown_stats
+                    && size.matches(|size| PatchSize::of(patch.lines_changed()) == *size)
             })
             .unwrap_or(true);
 
+        let matches_lines = self
+            .lines
+            .iter()
+            .all(|lines| !patch.unknown_stats && lines.matches(patch.lines_changed()));
+
+        let matches_time = matches_time(&patch.timestamp, self.since.as_ref(), self.until.as_ref());
+
         let matches_search = match &self.search {
             Some(search) => match matcher.fuzzy_match(&patch.title, search) {
                 Some(score) => score == 0 || score > 60,
@@ -874,7 +1869,53 @@ impl Filter<PatchItem> for PatchItemFilter {
             None => true,
         };
 
-        matches_state && matches_authored && matches_authors && matches_search
+        matches_state
+            && matches_authored
+            && matches_authors
+            && matches_size
+            && matches_lines
+            && matches_time
+            && matches_search
+    }
+
+    fn describe(&self) -> Vec<String> {
+        let mut chips = vec![];
+
+        if let Some(status) = &self.status {
+            chips.push(status.describe(|status| match status {
+                patch::Status::Draft => "draft".to_string(),
+                patch::Status::Open => "open".to_string(),
+                patch::Status::Merged => "merged".to_string(),
+                patch::Status::Archived => "archived".to_string(),
+            }));
+        }
+        if self.authored {
+            chips.push("authored by you".to_string());
+        }
+        if let Some(authors) = &self.authors {
+            chips.push(format!("author: {}", authors.describe(format::did)));
+        }
+        if let Some(size) = &self.size {
+            chips.push(format!("size: {}", size.describe(PatchSize::to_string)));
+        }
+        for lines in &self.lines {
+            chips.push(format!("lines {lines}"));
+        }
+        if let Some(since) = &self.since {
+            chips.push(format!("since: {since}"));
+        }
+        if let Some(until) = &self.until {
+            chips.push(format!("until: {until}"));
+        }
+        if let Some(search) = &self.search {
+            chips.push(format!("\"{search}\""));
+        }
+
+        chips
+    }
+
+    fn search(&self) -> Option<&str> {
+        self.search.as_deref()
     }
 }
 
@@ -885,7 +1926,11 @@ impl FromStr for PatchItemFilter {
         let mut status = None;
         let mut search = String::new();
         let mut authored = false;
-        let mut authors = vec![];
+        let mut authors = None;
+        let mut size = None;
+        let mut lines = vec![];
+        let mut since = None;
+        let mut until = None;
 
         let mut authors_parser = |input| -> IResult<&str, Vec<&str>> {
             preceded(
@@ -898,22 +1943,61 @@ impl FromStr for PatchItemFilter {
             )(input)
         };
 
-        let parts = value.split(' ');
-        for part in parts {
-            match part {
-                "is:open" => status = Some(patch::Status::Open),
-                "is:merged" => status = Some(patch::Status::Merged),
-                "is:archived" => status = Some(patch::Status::Archived),
-                "is:draft" => status = Some(patch::Status::Draft),
-                "is:authored" => authored = true,
-                other => match authors_parser.parse(other) {
-                    Ok((_, dids)) => {
-                        for did in dids {
-                            authors.push(Did::from_str(did)?);
-                        }
+        let parse_status = |value: &str| -> Result<patch::Status, anyhow::Error> {
+            match value {
+                "draft" => Ok(patch::Status::Draft),
+                "open" => Ok(patch::Status::Open),
+                "merged" => Ok(patch::Status::Merged),
+                "archived" => Ok(patch::Status::Archived),
+                other => Err(anyhow::anyhow!("unknown patch state '{other}'")),
+            }
+        };
+
+        let (_, tokens) = tokens(value).map_err(|err| anyhow::anyhow!("{err}"))?;
+        for token in tokens {
+            match token {
+                Token::Word("is:open") => status = Some(ValueFilter::Single(patch::Status::Open)),
+                Token::Word("is:merged") => {
+                    status = Some(ValueFilter::Single(patch::Status::Merged))
+                }
+                Token::Word("is:archived") => {
+                    status = Some(ValueFilter::Single(patch::Status::Archived))
+                }
+                Token::Word("is:draft") => status = Some(ValueFilter::Single(patch::Status::Draft)),
+                Token::Word("is:authored") => authored = true,
+                Token::Expr {
+                    key: "state",
+                    value,
+                    ..
+                } => status = Some(value.try_map(parse_status)?),
+                Token::Expr {
+                    key: "author",
+                    value,
+                    ..
+                } => authors = Some(value.try_map(Did::from_str)?),
+                Token::Expr {
+                    key: "size", value, ..
+                } => size = Some(value.try_map(PatchSize::from_str)?),
+                Token::Expr { raw, .. } => search.push_str(raw),
+                Token::Word(other) => {
+                    if let Some(value) = other.strip_prefix("size:") {
+                        size = Some(ValueFilter::Single(PatchSize::from_str(value)?));
+                    } else if let Some(value) = other.strip_prefix("lines:") {
+                        lines.push(LinesFilter::from_str(value)?);
+                    } else if let Some(value) = other.strip_prefix("since:") {
+                        since = Some(TimeBound::from_str(value)?);
+                    } else if let Some(value) = other.strip_prefix("until:") {
+                        until = Some(TimeBound::from_str(value)?);
+                    } else if let Ok((_, dids)) = authors_parser.parse(other) {
+                        let dids = dids
+                            .into_iter()
+                            .map(Did::from_str)
+                            .collect::<Result<Vec<_>, _>>()?;
+                        authors = Some(ValueFilter::Or(dids));
+                    } else {
+                        search.push_str(other);
                     }
-                    _ => search.push_str(other),
-                },
+                }
             }
         }
 
@@ -927,11 +2011,43 @@ impl FromStr for PatchItemFilter {
             status,
             authored,
             authors,
+            size,
+            lines,
+            since,
+            until,
             search,
         })
     }
 }
 
+/// A reaction to a comment and everyone who reacted with it.
+#[derive(Clone, Debug)]
+pub struct ReactionItem {
+    /// The reaction emoji.
+    pub emoji: char,
+    /// Authors who reacted with this emoji.
+    pub authors: Vec<AuthorItem>,
+}
+
+impl ReactionItem {
+    /// Renders the emoji followed by the aliases of all authors who reacted with it.
+    pub fn to_line(&self) -> Line<'static> {
+        let mut spans = vec![span::default(&format!("{} ", self.emoji))];
+        for (idx, author) in self.authors.iter().enumerate() {
+            if idx > 0 {
+                spans.push(span::default(", "));
+            }
+            spans.push(author.colored_alias(MaxWidths::default().name));
+        }
+
+        Line::from(spans)
+    }
+}
+
+/// The depth up to which replies to comments are rendered. Replies below are
+/// summarized in a single row.
+pub const MAX_COMMENT_DEPTH: usize = 16;
+
 /// A `CommentItem` represents a comment COB and is constructed from an `Issue` and
 /// a `Comment`.
 #[derive(Clone, Debug)]
@@ -943,9 +2059,11 @@ pub struct CommentItem {
     /// The content of this comment.
     pub body: String,
     /// Reactions to this comment.
-    pub reactions: Vec<char>,
+    pub reactions: Vec<ReactionItem>,
     /// Time when patch was opened.
     pub timestamp: Timestamp,
+    /// The format the timestamp is rendered in.
+    pub timestamp_format: TimestampFormat,
     /// The parent OID if this is a reply.
     pub reply_to: Option<CommentId>,
     /// Replies to this comment.
@@ -961,8 +2079,19 @@ impl CommentItem {
             id: comment_id,
             author: AuthorItem::new(Some(NodeId::from(*comment.author().0)), profile),
             body: comment.body().to_string(),
-            reactions: comment.reactions().iter().map(|r| r.0.emoji()).collect(),
+            reactions: comment
+                .reactions()
+                .iter()
+                .map(|(reaction, authors)| ReactionItem {
+                    emoji: reaction.emoji(),
+                    authors: authors
+                        .iter()
+                        .map(|author| AuthorItem::new(Some(**author), profile))
+                        .collect(),
+                })
+                .collect(),
             timestamp: comment.timestamp(),
+            timestamp_format: TimestampFormat::default(),
             reply_to: comment.reply_to(),
             replies: issue
                 .thread()
@@ -978,15 +2107,46 @@ impl CommentItem {
         }
     }
 
+    pub fn with_timestamp_format(mut self, format: TimestampFormat) -> Self {
+        self.timestamp_format = format;
+        self.replies = self
+            .replies
+            .into_iter()
+            .map(|reply| reply.with_timestamp_format(format))
+            .collect();
+        self
+    }
+
+    pub fn with_did_format(mut self, format: DidFormat) -> Self {
+        self.author = self.author.with_did_format(format);
+        self.replies = self
+            .replies
+            .into_iter()
+            .map(|reply| reply.with_did_format(format))
+            .collect();
+        self
+    }
+
+    /// Returns the number of all replies below this comment, including replies
+    /// to replies.
+    pub fn reply_count(&self) -> usize {
+        let mut count = 0;
+        let mut pending = self.replies.iter().collect::<Vec<_>>();
+
+        while let Some(reply) = pending.pop() {
+            count += 1;
+            pending.extend(reply.replies.iter());
+        }
+
+        count
+    }
+
     pub fn accumulated_reactions(&self) -> Vec<(char, usize)> {
         let mut accumulated: HashMap<char, usize> = HashMap::new();
 
         for reaction in &self.reactions {
-            if let Some(count) = accumulated.get_mut(reaction) {
-                *count = count.saturating_add(1);
-            } else {
-                accumulated.insert(*reaction, 1_usize);
-            }
+            let count = accumulated.entry(reaction.emoji).or_default();
+            *count = count.saturating_add(reaction.authors.len());
         }
 
         let mut sorted = accumulated.into_iter().collect::<Vec<_>>();
@@ -996,12 +2156,22 @@ impl CommentItem {
     }
 }
 
-impl ToTree<String> for CommentItem {
-    fn rows(&self) -> Vec<TreeItem<'_, String>> {
-        let mut children = vec![];
-        for comment in &self.replies {
-            children.extend(comment.rows());
-        }
+impl CommentItem {
+    fn tree_item(&self, depth: usize) -> TreeItem<'_, String> {
+        let children = if self.replies.is_empty() {
+            vec![]
+        } else if depth.saturating_add(1) < MAX_COMMENT_DEPTH {
+            self.replies
+                .iter()
+                .map(|reply| reply.tree_item(depth.saturating_add(1)))
+                .collect()
+        } else {
+            let count = self.reply_count();
+            let noun = if count == 1 { "reply" } else { "replies" };
+            let text = Text::from(span::default(&format!("… {count} more {noun}")).dim());
+
+            vec![TreeItem::new_leaf(String::from("more"), text)]
+        };
 
         let author = match &self.author.alias {
             Some(alias) => {
@@ -1021,15 +2191,18 @@ impl ToTree<String> for CommentItem {
         } else {
             "commented"
         };
-        let timestamp = span::timestamp(&format::timestamp(&self.timestamp));
+        let timestamp = span::timestamp(&format::timestamp(&self.timestamp, self.timestamp_format));
 
         let text = Text::from(Line::from(
             [author, " ".into(), action.into(), " ".into(), timestamp].to_vec(),
         ));
-        let item = TreeItem::new(self.id.to_string(), text, children)
-            .expect("Identifiers need to be unique");
+        TreeItem::new(self.id.to_string(), text, children).expect("Identifiers need to be unique")
+    }
+}
 
-        vec![item]
+impl ToTree<String> for CommentItem {
+    fn rows(&self) -> Vec<TreeItem<'_, String>> {
+        vec![self.tree_item(0)]
     }
 }
 
@@ -1101,6 +2274,10 @@ pub struct HunkItem<'a> {
     pub lines: Blobs<Vec<Line<'a>>>,
     /// A hunks' comments, indexed by line.
     pub comments: HunkComments,
+    /// The format comment timestamps are rendered in.
+    pub timestamp_format: TimestampFormat,
+    /// The format comment authors are rendered in.
+    pub did_format: DidFormat,
 }
 
 impl<'a> From<(&Repository, &Review, StatefulHunkDiff)> for HunkItem<'a> {
@@ -1135,6 +2312,8 @@ impl<'a> From<(&Repository, &Review, StatefulHunkDiff)> for HunkItem<'a> {
             inner: item.clone(),
             lines,
             comments: HunkComments::from(comments),
+            timestamp_format: TimestampFormat::default(),
+            did_format: DidFormat::default(),
         }
     }
 }
@@ -1189,9 +2368,7 @@ impl<'a> ToRow<3> for HunkItem<'a> {
                 .concat();
 
                 [
-                    Line::from(ui::span::hunk_state(self.inner.state()))
-                        .right_aligned()
-                        .into(),
+                    self.state_cell(),
                     Line::from(ui::span::pretty_path(path, false, false)).into(),
                     Line::from(stats_cell).right_aligned().into(),
                 ]
@@ -1212,9 +2389,7 @@ impl<'a> ToRow<3> for HunkItem<'a> {
                 .concat();
 
                 [
-                    Line::from(ui::span::hunk_state(self.inner.state()))
-                        .right_aligned()
-                        .into(),
+                    self.state_cell(),
                     Line::from(ui::span::pretty_path(path, false, false)).into(),
                     Line::from(stats_cell).right_aligned().into(),
                 ]
@@ -1234,9 +2409,7 @@ impl<'a> ToRow<3> for HunkItem<'a> {
                 .concat();
 
                 [
-                    Line::from(ui::span::hunk_state(self.inner.state()))
-                        .right_aligned()
-                        .into(),
+                    self.state_cell(),
                     Line::from(ui::span::pretty_path(path, false, false)).into(),
                     Line::from(stats_cell).right_aligned().into(),
                 ]
@@ -1250,9 +2423,7 @@ impl<'a> ToRow<3> for HunkItem<'a> {
                 .concat();
 
                 [
-                    Line::from(ui::span::hunk_state(self.inner.state()))
-                        .right_aligned()
-                        .into(),
+                    self.state_cell(),
                     Line::from(ui::span::pretty_path(&copied.new_path, false, false)).into(),
                     Line::from(stats_cell).right_aligned().into(),
                 ]
@@ -1266,9 +2437,7 @@ impl<'a> ToRow<3> for HunkItem<'a> {
                 .concat();
 
                 [
-                    Line::from(ui::span::hunk_state(self.inner.state()))
-                        .right_aligned()
-                        .into(),
+                    self.state_cell(),
                     Line::from(ui::span::pretty_path(&moved.new_path, false, false)).into(),
                     Line::from(stats_cell).right_aligned().into(),
                 ]
@@ -1280,9 +2449,7 @@ impl<'a> ToRow<3> for HunkItem<'a> {
                 new: _,
                 _eof: _,
             } => [
-                Line::from(ui::span::hunk_state(self.inner.state()))
-                    .right_aligned()
-                    .into(),
+                self.state_cell(),
                 Line::from(ui::span::pretty_path(path, false, false)).into(),
                 Line::from(span::default("EOF ").light_blue())
                     .right_aligned()
@@ -1294,9 +2461,7 @@ impl<'a> ToRow<3> for HunkItem<'a> {
                 old: _,
                 new: _,
             } => [
-                Line::from(ui::span::hunk_state(self.inner.state()))
-                    .right_aligned()
-                    .into(),
+                self.state_cell(),
                 Line::from(ui::span::pretty_path(path, false, false)).into(),
                 Line::from(span::default("FM ").light_blue())
                     .right_aligned()
@@ -1307,6 +2472,44 @@ impl<'a> ToRow<3> for HunkItem<'a> {
 }
 
 impl<'a> HunkItem<'a> {
+    /// Applies the formats of the given settings.
+    pub fn with_settings(self, settings: &Settings) -> Self {
+        self.with_timestamp_format(settings.timestamp_format)
+            .with_did_format(settings.did_format)
+    }
+
+    pub fn with_timestamp_format(mut self, format: TimestampFormat) -> Self {
+        self.timestamp_format = format;
+        self
+    }
+
+    pub fn with_did_format(mut self, format: DidFormat) -> Self {
+        self.did_format = format;
+        self
+    }
+
+    pub fn has_comments(&self) -> bool {
+        !self.comments.is_empty()
+    }
+
+    /// Returns the state of the hunk, preceded by a marker if it has comments,
+    /// such that commented hunks can be spotted before jumping to them.
+    fn state_cell(&self) -> Cell<'a> {
+        let marker = if self.has_comments() {
+            span::default(Charset::current().list_bullet()).yellow()
+        } else {
+            span::default(" ")
+        };
+
+        Line::from(vec![
+            marker,
+            span::default(" "),
+            ui::span::hunk_state(self.inner.state()),
+        ])
+        .right_aligned()
+        .into()
+    }
+
     pub fn header(&self) -> Vec<Column<'a>> {
         let comment_tag = if !self.comments.is_empty() {
             let count = self.comments.len();
@@ -1503,80 +2706,184 @@ impl<'a> HunkItem<'a> {
         }
     }
 
-    pub fn hunk_text(&'a self) -> Option<Text<'a>> {
+    /// Returns the unified diff of this hunk, including its comments. Unchanged
+    /// lines that are more than `context` lines away from a change or a comment
+    /// are collapsed.
+    pub fn hunk_text(&'a self, context: usize) -> Option<Text<'a>> {
+        let rows = self.merged_rows()?;
+        let diff_lines = self.diff_lines().unwrap_or_default();
+        if diff_lines.len() != rows.len() {
+            return Some(Text::from(rows.concat()));
+        }
+
+        let numbers = line_numbers(&diff_lines);
+        let has_comments = |index: usize| rows[index].len() > 1;
+
+        let folded = fold(&diff_lines, context, has_comments)
+            .into_iter()
+            .flat_map(|row| match row {
+                Fold::Line(index) => rows[index].clone(),
+                Fold::Hidden(hidden) => vec![hidden_line(&numbers, &hidden)],
+            })
+            .collect::<Vec<_>>();
+
+        Some(Text::from(folded))
+    }
+
+    /// Returns the line numbers of the diff line shown in `row` of `hunk_text`,
+    /// e.g. the one the diff cursor is on. The rows of comments belong to the
+    /// line they were made on.
+    pub fn line_numbers_at(&'a self, context: usize, row: usize) -> Option<LineNumbers> {
+        let rows = self.merged_rows()?;
+        let diff_lines = self.diff_lines()?;
+        if diff_lines.len() != rows.len() {
+            return None;
+        }
+
+        let folds = fold(&diff_lines, context, |index| rows[index].len() > 1);
+        let index = line_at_row(&folds, |index| rows[index].len(), row)?;
+
+        line_numbers(&diff_lines).get(index).copied()
+    }
+
+    /// Returns the line numbers of each row of `hunk_text`, e.g. for a gutter.
+    /// Rows of comments and collapsed lines have none.
+    pub fn row_line_numbers(&'a self, context: usize) -> Option<Vec<LineNumbers>> {
+        let rows = self.merged_rows()?;
+        let diff_lines = self.diff_lines()?;
+        if diff_lines.len() != rows.len() {
+            return None;
+        }
+
+        let numbers = line_numbers(&diff_lines);
+        let folded = fold(&diff_lines, context, |index| rows[index].len() > 1)
+            .into_iter()
+            .flat_map(|row| match row {
+                Fold::Line(index) => {
+                    let mut row = vec![LineNumbers::default(); rows[index].len()];
+                    row[0] = numbers[index];
+                    row
+                }
+                Fold::Hidden(_) => vec![LineNumbers::default()],
+            })
+            .collect();
+
+        Some(folded)
+    }
+
+    /// Returns the old and new side of this hunk, collapsed like `hunk_text`
+    /// does, but without comments.
+    pub fn split_diff(&'a self, context: usize) -> Option<SplitDiff<'a>> {
+        self.diff_lines()
+            .map(|lines| SplitDiff::new(collapse(lines, context)))
+    }
+
+    /// Returns the rendered lines of this hunk, one row per diff line that
+    /// holds the line itself followed by the comments made on it.
+    fn merged_rows(&'a self) -> Option<Vec<Vec<Line<'a>>>> {
         match &self.inner.hunk() {
             HunkDiff::Added { hunk, .. }
             | HunkDiff::Modified { hunk, .. }
             | HunkDiff::Deleted { hunk, .. } => {
-                let mut lines = hunk
-                    .as_ref()
-                    .map(|hunk| Text::from(hunk.to_text(&self.lines)));
-                let start = hunk
-                    .as_ref()
-                    .map(|hunk| hunk.new.start as usize)
-                    .unwrap_or_default();
-
-                lines = lines.map(|lines| {
-                    let mut mixins = HashMap::new();
-
-                    let divider = span::default(&"─".to_string().repeat(500)).gray().dim();
-
-                    for (line, comments) in self.comments.all() {
-                        mixins.insert(
-                            *line,
-                            comments
-                                .iter()
-                                .enumerate()
-                                .map(|(idx, comment)| {
-                                    // let body = span::default(comment.1.body()).gray();
-                                    let timestamp =
-                                        span::timestamp(&format::timestamp(&comment.1.timestamp()));
-                                    let author =
-                                        span::alias(&format::did(&Did::from(comment.1.author())));
-
-                                    let mut rendered = vec![];
-
-                                    // Only add top divider for the first comment
-                                    if idx == 0 {
-                                        rendered.push(Line::from([divider.clone()].to_vec()));
-                                    }
-
-                                    // Add comment body
-                                    rendered.extend(
-                                        comment
-                                            .1
-                                            .body()
-                                            .lines()
-                                            .map(|line| {
-                                                Line::from([span::default(line).gray()].to_vec())
-                                            })
-                                            .collect::<Vec<_>>(),
-                                    );
-
-                                    // Add metadata
-                                    rendered.push(
-                                        Line::from(
-                                            [timestamp, span::default(" by ").dim(), author]
-                                                .to_vec(),
-                                        )
-                                        .right_aligned(),
-                                    );
-
-                                    // Add bottom divider
+                let hunk = hunk.as_ref()?;
+                let lines = Text::from(hunk.to_text(&self.lines));
+                let start = hunk.new.start as usize;
+
+                let mut mixins = HashMap::new();
+
+                let divider = span::default(&"─".to_string().repeat(500)).gray().dim();
+
+                for (line, comments) in self.comments.all() {
+                    mixins.insert(
+                        *line,
+                        comments
+                            .iter()
+                            .enumerate()
+                            .map(|(idx, comment)| {
+                                // let body = span::default(comment.1.body()).gray();
+                                let timestamp = span::timestamp(&format::timestamp(
+                                    &comment.1.timestamp(),
+                                    self.timestamp_format,
+                                ));
+                                let author = span::alias(&format::did(
+                                    &Did::from(comment.1.author()),
+                                    self.did_format,
+                                ));
+
+                                let mut rendered = vec![];
+
+                                // Only add top divider for the first comment
+                                if idx == 0 {
                                     rendered.push(Line::from([divider.clone()].to_vec()));
+                                }
+
+                                // Add comment body
+                                rendered.extend(
+                                    comment
+                                        .1
+                                        .body()
+                                        .lines()
+                                        .map(|line| {
+                                            Line::from([span::default(line).gray()].to_vec())
+                                        })
+                                        .collect::<Vec<_>>(),
+                                );
+
+                                // Add metadata
+                                rendered.push(
+                                    Line::from(
+                                        [timestamp, span::default(" by ").dim(), author].to_vec(),
+                                    )
+                                    .right_aligned(),
+                                );
+
+                                // Add bottom divider
+                                rendered.push(Line::from([divider.clone()].to_vec()));
+
+                                rendered
+                            })
+                            .collect(),
+                    );
+                }
+                Some(LineMerger::rows(lines.lines, mixins, start))
+            }
+            _ => None,
+        }
+    }
 
-                                    rendered
-                                })
-                                .collect(),
-                        );
-                    }
-                    let merged = LineMerger::merge(lines.lines.clone(), mixins, start);
+    /// Returns the lines of this hunk. Lines of the old side do not show new
+    /// line numbers and vice versa.
+    fn diff_lines(&'a self) -> Option<Vec<DiffLine<'a>>> {
+        let without_span = |mut line: Line<'a>, index: usize| {
+            if index < line.spans.len() {
+                line.spans.remove(index);
+            }
+            line
+        };
 
-                    Text::from(merged)
-                });
+        match &self.inner.hunk() {
+            HunkDiff::Added { hunk, .. }
+            | HunkDiff::Modified { hunk, .. }
+            | HunkDiff::Deleted { hunk, .. } => hunk.as_ref().map(|hunk| {
+                let lines = hunk.to_text(&self.lines);
+                let offset = lines.len().saturating_sub(hunk.lines.len());
 
-                lines
-            }
+                let header = lines.iter().take(offset).cloned().map(DiffLine::Header);
+                let body = hunk
+                    .lines
+                    .iter()
+                    .zip(lines.iter().skip(offset).cloned())
+                    .map(|(modification, line)| match modification {
+                        Modification::Addition(_) => DiffLine::Addition(without_span(line, 0)),
+                        Modification::Deletion(_) => DiffLine::Deletion(without_span(line, 1)),
+                        Modification::Context { .. } => DiffLine::Context {
+                            old: without_span(line.clone(), 1),
+                            new: without_span(line, 0),
+                        },
+                    });
+
+                header.chain(body).collect()
+            }),
             _ => None,
         }
     }
@@ -1789,6 +3096,8 @@ impl<'a> ToText<'a> for Hunk<Modification> {
 mod tests {
     use anyhow::Result;
 
+    use crate::test::fixtures;
+
     use super::*;
 
     #[test]
@@ -1797,13 +3106,14 @@ mod tests {
         let actual = PatchItemFilter::from_str(search)?;
 
         let expected = PatchItemFilter {
-            status: Some(patch::Status::Open),
+            status: Some(ValueFilter::Single(patch::Status::Open)),
             authored: true,
-            authors: vec![
+            authors: Some(ValueFilter::Or(vec![
                 Did::from_str("did:key:z6MkkpTPzcq1ybmjQyQpyre15JUeMvZY6toxoZVpLZ8YarsB")?,
                 Did::from_str("did:key:z6Mku8hpprWTmCv3BqkssCYDfr2feUdyLSUnycVajFo9XVAx")?,
-            ],
+            ])),
             search: Some("cli".to_string()),
+            ..Default::default()
         };
 
         assert_eq!(expected, actual);
@@ -1811,30 +3121,368 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn patch_item_filter_with_expressions_from_str_should_succeed() -> Result<()> {
+        let search = r#"state=(open or draft) author=(did:key:z6MkkpTPzcq1ybmjQyQpyre15JUeMvZY6toxoZVpLZ8YarsB or did:key:z6Mku8hpprWTmCv3BqkssCYDfr2feUdyLSUnycVajFo9XVAx) cli"#;
+        let actual = PatchItemFilter::from_str(search)?;
+
+        let expected = PatchItemFilter {
+            status: Some(ValueFilter::Or(vec![
+                patch::Status::Open,
+                patch::Status::Draft,
+            ])),
+            authored: false,
+            authors: Some(ValueFilter::Or(vec![
+                Did::from_str("did:key:z6MkkpTPzcq1ybmjQyQpyre15JUeMvZY6toxoZVpLZ8YarsB")?,
+                Did::from_str("did:key:z6Mku8hpprWTmCv3BqkssCYDfr2feUdyLSUnycVajFo9XVAx")?,
+            ])),
+            search: Some("cli".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(expected, actual);
+        assert_eq!(actual.status(), None);
+        assert_eq!(
+            PatchItemFilter::from_str("state=merged")?.status(),
+            Some(patch::Status::Merged)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn patch_item_filter_with_size_from_str_should_succeed() -> Result<()> {
+        let actual = PatchItemFilter::from_str("is:open size:xs lines:>5 lines:<100 cli")?;
+
+        let expected = PatchItemFilter {
+            status: Some(ValueFilter::Single(patch::Status::Open)),
+            size: Some(ValueFilter::Single(PatchSize::ExtraSmall)),
+            lines: vec![LinesFilter::GreaterThan(5), LinesFilter::LessThan(100)],
+            search: Some("cli".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(expected, actual);
+        assert_eq!(
+            PatchItemFilter::from_str("size=(s or m)")?.size,
+            Some(ValueFilter::Or(vec![PatchSize::Small, PatchSize::Medium]))
+        );
+        assert!(PatchItemFilter::from_str("size:huge").is_err());
+        assert!(PatchItemFilter::from_str("lines:=100").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn patch_item_should_be_filtered_by_time() -> Result<()> {
+        use radicle::crypto::Signer;
+        use radicle::patch::Cache;
+
+        let alice = fixtures::node_with_repo();
+        let branch = fixtures::branch_with_main_changed(&alice);
+
+        let mut patches = Cache::no_cache(&alice.repo.repo)?;
+        let patch = fixtures::patch(&alice, &branch, &mut patches)?;
+
+        let author = AuthorItem {
+            nid: Some(*alice.signer.public_key()),
+            you: true,
+            ..fixtures::author()
+        };
+        let item = PatchItem::build(author, &alice.repo.repo, (*patch.id(), (*patch).clone()));
+
+        assert!(PatchItemFilter::from_str("since:1d")?.matches(&item));
+        assert!(PatchItemFilter::from_str("since:2024-01-01")?.matches(&item));
+        assert!(!PatchItemFilter::from_str("until:2024-01-01")?.matches(&item));
+        assert!(!PatchItemFilter::from_str("until:1d")?.matches(&item));
+
+        Ok(())
+    }
+
+    #[test]
+    fn notifications_of_deleted_cobs_should_be_skipped() -> Result<()> {
+        use radicle::git;
+        use radicle::node::notifications::NotificationStatus;
+        use radicle::node::LocalTime;
+        use radicle::patch::Cache;
+
+        let alice = fixtures::node_with_repo();
+        let branch = fixtures::branch_with_main_changed(&alice);
+
+        let mut patches = Cache::no_cache(&alice.repo.repo)?;
+        let patch = fixtures::patch(&alice, &branch, &mut patches)?;
+
+        let notification = |typed_id: TypedId| Notification {
+            id: 1,
+            repo: alice.repo.repo.id,
+            remote: None,
+            qualified: git::Qualified::from_refstr(git::refname!("refs/heads/master")).unwrap(),
+            update: RefUpdate::Created {
+                name: git::refname!("refs/heads/master"),
+                oid: branch.oid,
+            },
+            kind: NotificationKind::Cob { typed_id },
+            status: NotificationStatus::Unread,
+            timestamp: LocalTime::from_secs(1709211909),
+        };
+
+        // The issue does not exist (anymore).
+        let deleted = notification(TypedId {
+            id: ObjectId::from_str("ffffffffffffffffffffffffffffffffffffffff")?,
+            type_name: issue::TYPENAME.clone(),
+        });
+        assert!(NotificationKindItem::new(&alice.repo.repo, &deleted)?.is_none());
+
+        let existing = notification(TypedId {
+            id: *patch.id(),
+            type_name: patch::TYPENAME.clone(),
+        });
+        let Some(NotificationKindItem::Cob { type_name, id, .. }) =
+            NotificationKindItem::new(&alice.repo.repo, &existing)?
+        else {
+            panic!("Notification of an existing patch should be listed");
+        };
+        assert_eq!(type_name, "patch");
+        assert_eq!(id, Some(*patch.id()));
+
+        Ok(())
+    }
+
+    #[test]
+    fn time_filters_from_str_should_succeed() -> Result<()> {
+        let actual = IssueItemFilter::from_str("is:open since:7d until:2024-06-30 cli")?;
+
+        assert_eq!(actual.since, Some(TimeBound::from_str("1w")?));
+        assert_eq!(
+            actual.describe(),
+            ["open", "since: 1w", "until: 2024-06-30", "\"cli\""]
+        );
+        assert_eq!(
+            NotificationItemFilter::from_str("since:2024-01-01")?.describe(),
+            ["since: 2024-01-01"]
+        );
+        assert_eq!(
+            PatchItemFilter::from_str("until:12h")?.describe(),
+            ["until: 12h"]
+        );
+        assert!(PatchItemFilter::from_str("since:lately").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn patch_size_and_lines_should_round_trip() -> Result<()> {
+        for size in ["xs", "s", "m", "l", "xl"] {
+            assert_eq!(PatchSize::from_str(size)?.to_string(), size);
+        }
+        for lines in ["<100", ">500"] {
+            assert_eq!(LinesFilter::from_str(lines)?.to_string(), lines);
+        }
+
+        assert_eq!(PatchSize::of(0), PatchSize::ExtraSmall);
+        assert_eq!(PatchSize::of(49), PatchSize::Small);
+        assert_eq!(PatchSize::of(50), PatchSize::Medium);
+        assert_eq!(PatchSize::of(1000), PatchSize::ExtraLarge);
+        assert!(LinesFilter::LessThan(100).matches(99));
+        assert!(!LinesFilter::GreaterThan(500).matches(500));
+
+        Ok(())
+    }
+
+    #[test]
+    fn patch_item_with_undiffable_range_should_be_listed() -> Result<()> {
+        use radicle::crypto::Signer;
+        use radicle::patch::Cache;
+        use radicle::test::setup::BranchWith;
+
+        use crate::ui::rm::BrowserState;
+
+        let alice = fixtures::node_with_repo();
+        let branch = fixtures::branch_with_main_changed(&alice);
+        let branch = BranchWith {
+            base: Oid::from_str("ffffffffffffffffffffffffffffffffffffffff")?,
+            oid: branch.oid,
+        };
+
+        let mut patches = Cache::no_cache(&alice.repo.repo)?;
+        let patch = fixtures::patch(&alice, &branch, &mut patches)?;
+
+        let author = AuthorItem {
+            nid: Some(*alice.signer.public_key()),
+            you: true,
+            ..fixtures::author()
+        };
+        let item = PatchItem::build(author, &alice.repo.repo, (*patch.id(), (*patch).clone()));
+
+        assert!(item.unknown_stats);
+        assert_eq!((item.added, item.removed), (0, 0));
+
+        let browser = BrowserState::build(
+            vec![item],
+            PatchItemFilter::default(),
+            tui::ui::BufferedValue::new(String::new()),
+        );
+        let items = browser.items();
+
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].id, *patch.id());
+
+        Ok(())
+    }
+
+    #[test]
+    fn search_word_should_be_toggled() -> Result<()> {
+        let search = toggle_search_word("is:open cli", "is:authored");
+        assert_eq!(search, "is:open cli is:authored");
+        assert!(PatchItemFilter::from_str(&search)?.authored);
+
+        let search = toggle_search_word(&search, "is:authored");
+        assert_eq!(search, "is:open cli");
+        assert!(!IssueItemFilter::from_str(&search)?.authored);
+
+        assert_eq!(toggle_search_word("", "is:authored"), "is:authored");
+
+        Ok(())
+    }
+
+    #[test]
+    fn search_state_should_be_cycled() -> Result<()> {
+        let cycle = |search: &str| cycle_search_state(search, &IssueItemFilter::STATES);
+
+        let search = cycle("is:authored cli");
+        assert_eq!(search, "is:authored cli is:open");
+        let search = cycle(&search);
+        assert_eq!(search, "is:authored cli is:closed");
+        let search = cycle(&search);
+        assert_eq!(search, "is:authored cli is:solved");
+        assert_eq!(
+            IssueItemFilter::from_str(&search)?.state(),
+            Some(issue::State::Closed {
+                reason: CloseReason::Solved
+            })
+        );
+
+        // After the last state, items of all states are shown.
+        let search = cycle(&search);
+        assert_eq!(search, "is:authored cli");
+        assert_eq!(IssueItemFilter::from_str(&search)?.state(), None);
+        assert_eq!(cycle(&search), "is:authored cli is:open");
+
+        assert_eq!(cycle("is:closed state=(open or solved) cli"), "cli is:open");
+
+        let search = cycle_search_state("is:merged", &PatchItemFilter::STATUSES);
+        assert_eq!(search, "is:archived");
+        assert_eq!(
+            PatchItemFilter::from_str(&search)?.status(),
+            Some(patch::Status::Archived)
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn issue_item_filter_from_str_should_succeed() -> Result<()> {
         let search = r#"is:open is:assigned assignees:[did:key:z6MkkpTPzcq1ybmjQyQpyre15JUeMvZY6toxoZVpLZ8YarsB,did:key:z6Mku8hpprWTmCv3BqkssCYDfr2feUdyLSUnycVajFo9XVAx] is:authored authors:[did:key:z6Mku8hpprWTmCv3BqkssCYDfr2feUdyLSUnycVajFo9XVAx] cli"#;
         let actual = IssueItemFilter::from_str(search)?;
 
         let expected = IssueItemFilter {
-            state: Some(issue::State::Open),
-            authors: vec![Did::from_str(
+            state: Some(ValueFilter::Single(issue::State::Open)),
+            authors: Some(ValueFilter::Or(vec![Did::from_str(
                 "did:key:z6Mku8hpprWTmCv3BqkssCYDfr2feUdyLSUnycVajFo9XVAx",
-            )?],
+            )?])),
             authored: true,
             assigned: true,
-            assignees: vec![
+            assignees: Some(ValueFilter::Or(vec![
                 Did::from_str("did:key:z6MkkpTPzcq1ybmjQyQpyre15JUeMvZY6toxoZVpLZ8YarsB")?,
                 Did::from_str("did:key:z6Mku8hpprWTmCv3BqkssCYDfr2feUdyLSUnycVajFo9XVAx")?,
-            ],
+            ])),
+            milestone: None,
+            since: None,
+            until: None,
+            search: Some("cli".to_string()),
+        };
+
+        assert_eq!(expected, actual);
+
+        Ok(())
+    }
+
+    #[test]
+    fn issue_item_filter_with_expressions_from_str_should_succeed() -> Result<()> {
+        let search = r#"state=( open or solved ) assignee=(did:key:z6MkkpTPzcq1ybmjQyQpyre15JUeMvZY6toxoZVpLZ8YarsB and did:key:z6Mku8hpprWTmCv3BqkssCYDfr2feUdyLSUnycVajFo9XVAx) author=did:key:z6Mku8hpprWTmCv3BqkssCYDfr2feUdyLSUnycVajFo9XVAx cli"#;
+        let actual = IssueItemFilter::from_str(search)?;
+
+        let expected = IssueItemFilter {
+            state: Some(ValueFilter::Or(vec![
+                issue::State::Open,
+                issue::State::Closed {
+                    reason: CloseReason::Solved,
+                },
+            ])),
+            authors: Some(ValueFilter::Single(Did::from_str(
+                "did:key:z6Mku8hpprWTmCv3BqkssCYDfr2feUdyLSUnycVajFo9XVAx",
+            )?)),
+            authored: false,
+            assigned: false,
+            assignees: Some(ValueFilter::And(vec![
+                Did::from_str("did:key:z6MkkpTPzcq1ybmjQyQpyre15JUeMvZY6toxoZVpLZ8YarsB")?,
+                Did::from_str("did:key:z6Mku8hpprWTmCv3BqkssCYDfr2feUdyLSUnycVajFo9XVAx")?,
+            ])),
+            milestone: None,
+            since: None,
+            until: None,
             search: Some("cli".to_string()),
         };
 
+        assert_eq!(expected, actual);
+        assert_eq!(
+            actual.describe().first(),
+            Some(&"open or solved".to_string())
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn issue_item_filter_with_invalid_expressions_should_fail() {
+        assert!(IssueItemFilter::from_str("state=(open or closed and solved)").is_err());
+        assert!(IssueItemFilter::from_str("state=unknown").is_err());
+        assert!(IssueItemFilter::from_str("author=(did:key:invalid)").is_err());
+    }
+
+    #[test]
+    fn issue_item_filter_with_incomplete_expressions_should_be_searched() -> Result<()> {
+        let actual = IssueItemFilter::from_str("state=(open or")?;
+
+        let expected = IssueItemFilter {
+            search: Some("state=(openor".to_string()),
+            ..Default::default()
+        };
+
         assert_eq!(expected, actual);
 
         Ok(())
     }
 
+    #[test]
+    fn issue_item_filter_describe_should_succeed() -> Result<()> {
+        let search = r#"is:solved is:authored assignees:[did:key:z6MkkpTPzcq1ybmjQyQpyre15JUeMvZY6toxoZVpLZ8YarsB] cli"#;
+        let actual = IssueItemFilter::from_str(search)?.describe();
+
+        let expected = vec![
+            "solved".to_string(),
+            "authored by you".to_string(),
+            "assignee: z6MkkpT…Z8YarsB".to_string(),
+            "\"cli\"".to_string(),
+        ];
+
+        assert_eq!(expected, actual);
+        assert!(IssueItemFilter::from_str("")?.is_default());
+        assert!(IssueItemFilter::from_str("")?.describe().is_empty());
+
+        Ok(())
+    }
+
     #[test]
     fn notification_item_filter_from_str_should_succeed() -> Result<()> {
         let search = r#"is:seen is:patch authors:[did:key:z6MkkpTPzcq1ybmjQyQpyre15JUeMvZY6toxoZVpLZ8YarsB,did:key:z6Mku8hpprWTmCv3BqkssCYDfr2feUdyLSUnycVajFo9XVAx] cli"#;
@@ -1847,6 +3495,9 @@ mod tests {
                 Did::from_str("did:key:z6MkkpTPzcq1ybmjQyQpyre15JUeMvZY6toxoZVpLZ8YarsB")?,
                 Did::from_str("did:key:z6Mku8hpprWTmCv3BqkssCYDfr2feUdyLSUnycVajFo9XVAx")?,
             ],
+            repo: None,
+            since: None,
+            until: None,
             search: Some("cli".to_string()),
         };
 
@@ -1854,4 +3505,377 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn notification_item_filter_with_repo_from_str_should_succeed() -> Result<()> {
+        let search = r#"is:unseen repo:heartwood cli"#;
+        let actual = NotificationItemFilter::from_str(search)?;
+
+        let expected = NotificationItemFilter {
+            state: Some(NotificationState::Unseen),
+            repo: Some("heartwood".to_string()),
+            search: Some("cli".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(expected, actual);
+
+        Ok(())
+    }
+
+    #[test]
+    fn notification_items_should_be_sorted_by_project_then_timestamp() -> Result<()> {
+        let notification = |id, project: &str, secs| NotificationItem {
+            project: project.to_string(),
+            timestamp: Timestamp::from_secs(secs),
+            ..fixtures::notification(id)
+        };
+
+        let mut items = vec![
+            notification(1, "radicle-tui", 1709211909),
+            notification(2, "heartwood", 1709211909),
+            notification(3, "radicle-tui", 1709211999),
+            notification(4, "heartwood", 1709211000),
+            notification(5, "heartwood", 1709211999),
+            notification(6, "heartwood", 1709211999),
+        ];
+        items.sort_by(|a, b| a.compare(b, &SortBy::with_field("project")));
+
+        let actual = items
+            .iter()
+            .map(|item| (item.project.as_str(), item.id))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            actual,
+            [
+                ("heartwood", 5),
+                ("heartwood", 6),
+                ("heartwood", 2),
+                ("heartwood", 4),
+                ("radicle-tui", 3),
+                ("radicle-tui", 1),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn notification_items_should_be_grouped_by_branch() -> Result<()> {
+        let notification = |id, kind: NotificationKindItem, secs| NotificationItem {
+            kind,
+            timestamp: Timestamp::from_secs(secs),
+            ..fixtures::notification(id)
+        };
+        let branch = |name: &str| NotificationKindItem::Branch {
+            name: name.to_string(),
+            summary: String::new(),
+            status: String::new(),
+            id: None,
+        };
+        let unknown = || NotificationKindItem::Unknown {
+            refname: "refs/heads/master".to_string(),
+        };
+
+        let items = vec![
+            notification(1, branch("main"), 1709211909),
+            notification(2, unknown(), 1709211909),
+            notification(3, branch("main"), 1709211999),
+            notification(4, branch("feature"), 1709211000),
+            notification(5, unknown(), 1709211909),
+            notification(6, branch("main"), 1709211000),
+        ];
+        let rows = |expanded: &HashSet<String>| {
+            group_notifications(&items, expanded)
+                .iter()
+                .map(|item| (item.id, item.group))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            rows(&HashSet::new()),
+            [
+                (3, Some(NotificationGroup::Collapsed(3))),
+                (2, None),
+                (4, None),
+                (5, None),
+            ]
+        );
+        assert_eq!(
+            rows(&HashSet::from([items[0].group_key()])),
+            [
+                (3, Some(NotificationGroup::Expanded(3))),
+                (1, Some(NotificationGroup::Member)),
+                (6, Some(NotificationGroup::Member)),
+                (2, None),
+                (4, None),
+                (5, None),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn notification_row_should_be_cut_to_max_widths() -> Result<()> {
+        use ratatui::widgets::{Row, Table};
+
+        let item = NotificationItem {
+            project: "radicle-terminal-user-interface".to_string(),
+            max_widths: MaxWidths { name: 9, title: 9 },
+            ..fixtures::notification(1)
+        };
+
+        let area = Rect::new(0, 0, 200, 1);
+        let mut buffer = Buffer::empty(area);
+        let table = Table::new([Row::new(item.to_row())], [Constraint::Length(40); 9]);
+        Widget::render(table, area, &mut buffer);
+
+        let line = buffer
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect::<String>();
+
+        assert!(line.contains("radi…face"));
+        assert!(!line.contains("radicle-terminal"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn long_aliases_should_be_cut_to_max_width() -> Result<()> {
+        let author = AuthorItem {
+            human_nid: Some("z".repeat(500)),
+            ..fixtures::author()
+        };
+        let max = MaxWidths::default().name;
+        let alias = author.colored_alias(max);
+
+        assert_eq!(alias.width(), max);
+        assert!(alias.content.starts_with("zzz"));
+        assert!(alias.content.ends_with("zzz"));
+        assert!(alias.content.contains('…'));
+
+        Ok(())
+    }
+
+    #[test]
+    fn assigned_issue_item_should_match_assigned_filter() -> Result<()> {
+        let you = AuthorItem {
+            nid: Some(NodeId::from_str(
+                "z6MkkpTPzcq1ybmjQyQpyre15JUeMvZY6toxoZVpLZ8YarsB",
+            )?),
+            you: true,
+            ..fixtures::author()
+        };
+        let mut item = IssueItem {
+            id: IssueId::from_str("e8c676b9e3b42308dc9d218b70faa5408f8e58ca")?,
+            state: issue::State::Open,
+            title: "Issue".to_string(),
+            author: you.clone(),
+            labels: vec![],
+            milestone: None,
+            assignees: vec![],
+            timestamp: Timestamp::from_secs(1709211909),
+            timestamp_format: TimestampFormat::default(),
+            max_widths: MaxWidths::default(),
+            compact: false,
+            comments: vec![],
+        };
+        let filter = IssueItemFilter::from_str("is:assigned")?;
+
+        assert!(!filter.matches(&item));
+
+        item.assign(you.clone(), true);
+        item.assign(you.clone(), true);
+        assert_eq!(item.assignees.len(), 1);
+        assert!(filter.matches(&item));
+
+        item.assign(you, false);
+        assert!(item.assignees.is_empty());
+        assert!(!filter.matches(&item));
+
+        Ok(())
+    }
+
+    #[test]
+    fn issue_icon_should_be_picked_by_first_matching_rule() -> Result<()> {
+        let you = AuthorItem {
+            nid: Some(NodeId::from_str(
+                "z6MkkpTPzcq1ybmjQyQpyre15JUeMvZY6toxoZVpLZ8YarsB",
+            )?),
+            you: true,
+            ..fixtures::author()
+        };
+        let mut item = IssueItem {
+            id: IssueId::from_str("e8c676b9e3b42308dc9d218b70faa5408f8e58ca")?,
+            state: issue::State::Open,
+            title: "Issue".to_string(),
+            author: you.clone(),
+            labels: vec![],
+            milestone: None,
+            assignees: vec![],
+            timestamp: Timestamp::from_secs(1709211909),
+            timestamp_format: TimestampFormat::default(),
+            max_widths: MaxWidths::default(),
+            compact: false,
+            comments: vec![],
+        };
+        let rules = vec![
+            IconRule::new(
+                IssueItemFilter::from_str("is:assigned")?,
+                "🔒",
+                Style::default(),
+            ),
+            IconRule::new(
+                IssueItemFilter::from_str("is:authored")?,
+                "⭐⭐",
+                Style::default(),
+            ),
+        ];
+
+        assert_eq!(icon(&rules, &item).content, "⭐");
+
+        item.assign(you, true);
+        assert_eq!(icon(&rules, &item).content, "🔒");
+
+        item.author.you = false;
+        item.assignees.clear();
+        assert_eq!(icon(&rules, &item).width(), 0);
+        assert_eq!(icon::<IssueItem, IssueItemFilter>(&[], &item).width(), 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn compact_issue_item_should_render_fewer_cells() -> Result<()> {
+        let item = IssueItem {
+            id: IssueId::from_str("e8c676b9e3b42308dc9d218b70faa5408f8e58ca")?,
+            state: issue::State::Open,
+            title: "Issue".to_string(),
+            author: fixtures::author(),
+            labels: vec![],
+            milestone: None,
+            assignees: vec![],
+            timestamp: Timestamp::from_secs(1709211909),
+            timestamp_format: TimestampFormat::default(),
+            max_widths: MaxWidths::default(),
+            compact: false,
+            comments: vec![],
+        };
+
+        assert_eq!(item.to_dyn_row().len(), 10);
+        assert_eq!(item.with_compact(true).to_dyn_row().len(), 5);
+
+        Ok(())
+    }
+
+    #[test]
+    fn milestone_labels_should_be_matched_by_milestone_filter() -> Result<()> {
+        let item = |labels: &[&str]| -> Result<IssueItem> {
+            Ok(IssueItem {
+                id: IssueId::from_str("e8c676b9e3b42308dc9d218b70faa5408f8e58ca")?,
+                state: issue::State::Open,
+                title: "Issue".to_string(),
+                author: fixtures::author(),
+                labels: labels
+                    .iter()
+                    .map(|name| Label::new(*name))
+                    .collect::<Result<Vec<_>, _>>()?,
+                milestone: None,
+                assignees: vec![],
+                timestamp: Timestamp::from_secs(1709211909),
+                timestamp_format: TimestampFormat::default(),
+                max_widths: MaxWidths::default(),
+                compact: false,
+                comments: vec![],
+            })
+        };
+        let filter = IssueItemFilter::from_str("is:open milestone:v1.0")?;
+
+        let v1 = item(&["bug", "milestone:v1.0"])?.with_milestone_prefix("milestone:");
+        assert_eq!(v1.milestone.as_deref(), Some("v1.0"));
+        assert_eq!(format::labels(&v1.labels), "bug");
+        assert!(filter.matches(&v1));
+
+        let v2 = item(&["milestone:v2.0"])?.with_milestone_prefix("milestone:");
+        assert!(!filter.matches(&v2));
+        assert!(!filter.matches(&item(&["milestone:v1.0"])?.with_milestone_prefix("")));
+
+        let iteration = item(&["iteration/v1.0"])?.with_milestone_prefix("iteration/");
+        assert!(filter.matches(&iteration));
+        assert_eq!(filter.describe(), vec!["open", "milestone: v1.0"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn comment_item_reactions_should_be_accumulated_per_author() -> Result<()> {
+        let author = |alias: &str| AuthorItem {
+            alias: Some(Alias::from_str(alias).unwrap()),
+            ..fixtures::author()
+        };
+        let comment = CommentItem {
+            id: Oid::from_str("e8c676b9e3b42308dc9d218b70faa5408f8e58ca")?,
+            author: author("alice"),
+            body: String::new(),
+            reactions: vec![
+                ReactionItem {
+                    emoji: '🚀',
+                    authors: vec![author("bob")],
+                },
+                ReactionItem {
+                    emoji: '👍',
+                    authors: vec![author("alice"), author("bob")],
+                },
+            ],
+            timestamp: Timestamp::from_secs(1709211909),
+            timestamp_format: TimestampFormat::default(),
+            reply_to: None,
+            replies: vec![],
+        };
+
+        assert_eq!(comment.accumulated_reactions(), vec![('👍', 2), ('🚀', 1)]);
+        assert_eq!(comment.reactions[1].to_line().to_string(), "👍 alice, bob");
+
+        Ok(())
+    }
+
+    #[test]
+    fn deep_comment_trees_should_be_capped() -> Result<()> {
+        let depth = 1_000;
+        let comment = |index: usize, replies: Vec<CommentItem>| -> Result<CommentItem> {
+            Ok(CommentItem {
+                id: Oid::from_str(&format!("{:040x}", index + 1))?,
+                author: fixtures::author(),
+                body: String::new(),
+                reactions: vec![],
+                timestamp: Timestamp::from_secs(1709211909),
+                timestamp_format: TimestampFormat::default(),
+                reply_to: None,
+                replies,
+            })
+        };
+
+        let mut root = comment(depth, vec![])?;
+        for index in (0..depth).rev() {
+            root = comment(index, vec![root])?;
+        }
+        assert_eq!(root.reply_count(), depth);
+
+        let rows = root.rows();
+        let mut item = &rows[0];
+        let mut rendered = 1;
+        while let Some(child) = item.children().first() {
+            item = child;
+            rendered += 1;
+        }
+
+        assert_eq!(rendered, MAX_COMMENT_DEPTH + 1);
+        assert_eq!(item.identifier(), "more");
+
+        Ok(())
+    }
 }
@@ -0,0 +1,160 @@
+use anyhow::Result;
+
+use termion::event::Key;
+
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::Stylize;
+use ratatui::text::{Line, Text};
+use ratatui::{Frame, Viewport};
+
+use radicle::Profile;
+
+use radicle_cli::terminal;
+use radicle_cli::terminal::args::Error;
+
+use radicle_tui as tui;
+
+use tui::ui::im;
+use tui::ui::im::widget::Window;
+use tui::ui::im::{Borders, Show};
+use tui::{store, Channel, Exit};
+
+#[derive(Clone, Debug)]
+pub enum Message {
+    Quit,
+}
+
+/// A page that is shown instead of an interface if no Radicle profile can be
+/// loaded, e.g. because `rad auth` was never run. It explains why and how to
+/// create one.
+#[derive(Clone, Debug)]
+pub struct Onboarding {
+    /// Why the profile could not be loaded.
+    error: String,
+    /// How the error can be resolved, if known.
+    hint: Option<String>,
+}
+
+impl Onboarding {
+    pub fn new(error: &anyhow::Error) -> Self {
+        match error.downcast_ref() {
+            Some(Error::WithHint { err, hint }) => Self {
+                error: err.to_string(),
+                hint: Some(hint.to_string()),
+            },
+            _ => Self {
+                error: error.to_string(),
+                hint: None,
+            },
+        }
+    }
+
+    fn text(&self) -> Text<'static> {
+        let mut lines = vec![
+            Line::from("Welcome to Radicle!").bold(),
+            Line::default(),
+            Line::from(self.error.clone()).yellow(),
+        ];
+        if let Some(hint) = &self.hint {
+            lines.push(Line::from(hint.clone()).yellow());
+        }
+        lines.extend([
+            Line::default(),
+            Line::from("A Radicle profile is needed to browse and review repositories."),
+            Line::from(vec![
+                "To create one, quit and run ".into(),
+                "rad auth".cyan().bold(),
+                " in your terminal.".into(),
+            ]),
+        ]);
+
+        Text::from(lines).centered()
+    }
+}
+
+impl store::Update<Message> for Onboarding {
+    type Return = ();
+
+    fn update(&mut self, message: Message) -> Option<Exit<Self::Return>> {
+        match message {
+            Message::Quit => Some(Exit { value: None }),
+        }
+    }
+}
+
+impl Show<Message> for Onboarding {
+    fn show(&self, ctx: &im::Context<Message>, frame: &mut Frame) -> Result<()> {
+        Window::default().show(ctx, |ui| {
+            let layout = Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]);
+
+            ui.layout(layout, None, |ui| {
+                ui.centered_text_view(frame, self.text(), Some(Borders::All));
+                ui.shortcuts(frame, &[("q", "quit")], '∙');
+            });
+
+            if ui.input_global(|key| matches!(key, Key::Char('q') | Key::Esc | Key::Ctrl('c'))) {
+                ui.send_message(Message::Quit);
+            }
+        });
+
+        Ok(())
+    }
+}
+
+/// Returns the profile of the given context. If it can't be loaded, the
+/// onboarding page is shown instead and `None` is returned once it's quit, such
+/// that commands can exit without printing an error. If not `interactive`, e.g.
+/// because the output is printed, the error is returned instead.
+pub async fn profile(ctx: &impl terminal::Context, interactive: bool) -> Result<Option<Profile>> {
+    match ctx.profile() {
+        Ok(profile) => Ok(Some(profile)),
+        Err(err) if !interactive => Err(err),
+        Err(err) => {
+            log::warn!("Failed to load profile: {}", err);
+
+            tui::im(
+                Onboarding::new(&err),
+                Viewport::Fullscreen,
+                Channel::default(),
+            )
+            .await?;
+            Ok(None)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use radicle_cli::terminal::args::Error;
+
+    use radicle_tui as tui;
+
+    use tui::store::Update;
+
+    use super::{Message, Onboarding};
+
+    #[test]
+    fn onboarding_should_show_hint_and_quit() {
+        let error = anyhow::Error::from(Error::WithHint {
+            err: anyhow::anyhow!("Could not load radicle profile"),
+            hint: "To setup your radicle profile, run `rad auth`.",
+        });
+        let mut onboarding = Onboarding::new(&error);
+
+        assert_eq!(onboarding.error, "Could not load radicle profile");
+        assert_eq!(
+            onboarding.hint.as_deref(),
+            Some("To setup your radicle profile, run `rad auth`.")
+        );
+        assert!(onboarding
+            .text()
+            .lines
+            .iter()
+            .any(|line| line.to_string().contains("rad auth")));
+        assert!(onboarding
+            .update(Message::Quit)
+            .is_some_and(|exit| exit.value.is_none()));
+    }
+}
@@ -0,0 +1,374 @@
+use anyhow::Result;
+
+use fuzzy_matcher::skim::SkimMatcherV2;
+use fuzzy_matcher::FuzzyMatcher;
+
+use termion::event::Key;
+
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Style, Stylize};
+use ratatui::text::Span;
+use ratatui::widgets::Cell;
+use ratatui::Frame;
+
+use radicle_tui as tui;
+
+use tui::ui::im;
+use tui::ui::im::widget::{TextEditState, Window};
+use tui::ui::im::{Borders, Show};
+use tui::ui::{Column, Highlight, ToRow};
+use tui::{store, Exit, Selection};
+
+/// An item of the picker: the id that is returned if it's picked and the text
+/// it's narrowed down by.
+#[derive(Clone, Debug)]
+pub struct PickerItem<Id> {
+    pub id: Id,
+    pub text: String,
+}
+
+impl<Id> PickerItem<Id> {
+    pub fn new(id: Id, text: impl ToString) -> Self {
+        Self {
+            id,
+            text: text.to_string(),
+        }
+    }
+
+    fn row(&self, highlight: Option<&Highlight>) -> [Cell; 1] {
+        let text = match highlight {
+            Some(highlight) => highlight.line(&self.text, Style::default()),
+            None => Span::raw(self.text.clone()).into(),
+        };
+
+        [text.into()]
+    }
+}
+
+impl<Id> ToRow<1> for PickerItem<Id> {
+    fn to_row(&self) -> [Cell; 1] {
+        self.row(None)
+    }
+
+    fn to_highlighted_row(&self, highlight: &Highlight) -> [Cell; 1] {
+        self.row(Some(highlight))
+    }
+}
+
+/// Which of the listed items is selected right away, without showing an
+/// interface, e.g. for scripts that always want the newest item.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AutoPick {
+    First,
+    Last,
+}
+
+impl AutoPick {
+    /// Returns the item picked of all items given, in the order they are listed.
+    pub fn pick<T>(&self, items: impl IntoIterator<Item = T>) -> Option<T> {
+        match self {
+            AutoPick::First => items.into_iter().next(),
+            AutoPick::Last => items.into_iter().last(),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum Message {
+    Quit,
+    Pick,
+    SearchChanged { search: TextEditState },
+    SelectionChanged { selected: Option<usize> },
+}
+
+/// A full-screen fuzzy finder: all items that match what's typed into the
+/// input line at the bottom are listed above it, best matches first. Picking an
+/// item returns its id as the single id of a `Selection`.
+#[derive(Clone, Debug)]
+pub struct Picker<Id> {
+    items: Vec<PickerItem<Id>>,
+    search: TextEditState,
+    selected: Option<usize>,
+}
+
+impl<Id> Picker<Id>
+where
+    Id: Clone,
+{
+    pub fn new(items: Vec<PickerItem<Id>>) -> Self {
+        Self {
+            selected: if items.is_empty() { None } else { Some(0) },
+            items,
+            search: TextEditState {
+                text: String::new(),
+                cursor: 0,
+            },
+        }
+    }
+
+    /// Returns all items that match the current search, ranked by their score.
+    /// Items with the same score keep their original order, which is also the
+    /// order of all items if nothing was typed yet.
+    pub fn matches(&self) -> Vec<PickerItem<Id>> {
+        let search = self.search.text.trim();
+        if search.is_empty() {
+            return self.items.clone();
+        }
+
+        let matcher = SkimMatcherV2::default();
+        let mut matches = self
+            .items
+            .iter()
+            .filter_map(|item| {
+                matcher
+                    .fuzzy_match(&item.text, search)
+                    .map(|score| (score, item))
+            })
+            .collect::<Vec<_>>();
+        matches.sort_by(|a, b| b.0.cmp(&a.0));
+
+        matches.into_iter().map(|(_, item)| item.clone()).collect()
+    }
+
+    fn move_selection(&self, len: usize, up: bool) -> Option<usize> {
+        match self.selected {
+            _ if len == 0 => None,
+            Some(selected) if up => Some(selected.saturating_sub(1)),
+            Some(selected) => Some(selected.saturating_add(1).min(len - 1)),
+            None => Some(0),
+        }
+    }
+}
+
+impl<Id> store::Update<Message> for Picker<Id>
+where
+    Id: Clone + ToString,
+{
+    type Return = Selection<Id>;
+
+    fn update(&mut self, message: Message) -> Option<Exit<Self::Return>> {
+        match message {
+            Message::Quit => Some(Exit { value: None }),
+            Message::Pick => self
+                .selected
+                .and_then(|selected| self.matches().get(selected).cloned())
+                .map(|item| Exit {
+                    value: Some(Selection {
+                        operation: None,
+                        ids: vec![item.id],
+                        args: vec![],
+                    }),
+                }),
+            Message::SearchChanged { search } => {
+                self.search = search;
+                // The best match is always selected while narrowing.
+                self.selected = if self.matches().is_empty() {
+                    None
+                } else {
+                    Some(0)
+                };
+                None
+            }
+            Message::SelectionChanged { selected } => {
+                self.selected = selected;
+                None
+            }
+        }
+    }
+}
+
+impl<Id> Show<Message> for Picker<Id>
+where
+    Id: Clone,
+{
+    fn show(&self, ctx: &im::Context<Message>, frame: &mut Frame) -> Result<()> {
+        Window::default().show(ctx, |ui| {
+            let matches = self.matches();
+            let mut selected = self.selected;
+            let (mut text, mut cursor) = (self.search.text.clone(), self.search.cursor);
+
+            let layout = Layout::vertical([
+                Constraint::Fill(1),
+                Constraint::Length(1),
+                Constraint::Length(1),
+            ]);
+
+            // Only the input line has focus, such that all chars are typed into
+            // it instead of navigating the list.
+            ui.layout(layout, Some(2), |ui| {
+                let highlight = Highlight::new(&text, ui.theme().search_highlight_style);
+                ui.add(
+                    frame,
+                    im::widget::Table::new(
+                        &mut selected,
+                        &matches,
+                        [Column::new("", Constraint::Fill(1))].to_vec(),
+                        Some(Borders::None),
+                    )
+                    .highlight(Some(highlight)),
+                );
+
+                let count = format!(" {}/{} ", matches.len(), self.items.len());
+                ui.bar(
+                    frame,
+                    [
+                        Column::new(
+                            Span::raw(" ".to_string())
+                                .into_left_aligned_line()
+                                .style(ui.theme().bar_on_black_style),
+                            Constraint::Fill(1),
+                        ),
+                        Column::new(
+                            Span::raw(count.clone())
+                                .into_right_aligned_line()
+                                .cyan()
+                                .dim()
+                                .reversed(),
+                            Constraint::Length(count.chars().count() as u16),
+                        ),
+                    ]
+                    .to_vec(),
+                    Some(Borders::None),
+                );
+
+                let text_edit = ui.text_edit_labeled_singleline(
+                    frame,
+                    &mut text,
+                    &mut cursor,
+                    ">",
+                    Some(Borders::None),
+                );
+                if text_edit.changed {
+                    ui.send_message(Message::SearchChanged {
+                        search: TextEditState { text, cursor },
+                    });
+                }
+            });
+
+            if ui.input_global(|key| matches!(key, Key::Up | Key::Ctrl('p') | Key::Ctrl('k'))) {
+                ui.send_message(Message::SelectionChanged {
+                    selected: self.move_selection(matches.len(), true),
+                });
+            }
+            if ui.input_global(|key| matches!(key, Key::Down | Key::Ctrl('n') | Key::Ctrl('j'))) {
+                ui.send_message(Message::SelectionChanged {
+                    selected: self.move_selection(matches.len(), false),
+                });
+            }
+            if ui.input_global(|key| key == Key::Char('\n')) {
+                ui.send_message(Message::Pick);
+            }
+            if ui.input_global(|key| key == Key::Esc || key == Key::Ctrl('c')) {
+                ui.send_message(Message::Quit);
+            }
+        });
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use termion::event::Key;
+
+    use radicle_tui as tui;
+
+    use tui::store::Update;
+    use tui::ui::im::widget::TextEditState;
+    use tui::ui::im::TestDriver;
+
+    use super::{AutoPick, Message, Picker, PickerItem};
+
+    fn picker() -> Picker<usize> {
+        Picker::new(vec![
+            PickerItem::new(0, "Reload all nodes kindly"),
+            PickerItem::new(1, "Fix crash on resize"),
+            PickerItem::new(2, "Picker: rank by score"),
+        ])
+    }
+
+    fn search(text: &str) -> Message {
+        Message::SearchChanged {
+            search: TextEditState {
+                text: text.to_string(),
+                cursor: text.chars().count(),
+            },
+        }
+    }
+
+    #[test]
+    fn matches_should_be_narrowed_and_ranked() {
+        let mut picker = picker();
+        assert_eq!(picker.matches().len(), 3);
+
+        picker.update(search("rank"));
+        let ids = picker
+            .matches()
+            .iter()
+            .map(|item| item.id)
+            .collect::<Vec<_>>();
+        assert_eq!(ids, vec![2, 0]);
+
+        picker.update(search("nothing like this"));
+        assert!(picker.matches().is_empty());
+        assert_eq!(picker.selected, None);
+    }
+
+    #[test]
+    fn auto_pick_should_return_first_or_last_item() {
+        assert_eq!(AutoPick::First.pick([1, 2, 3]), Some(1));
+        assert_eq!(AutoPick::Last.pick([1, 2, 3]), Some(3));
+        assert_eq!(AutoPick::Last.pick(Vec::<usize>::new()), None);
+    }
+
+    #[test]
+    fn picker_should_be_navigated_and_picked_by_keys() -> anyhow::Result<()> {
+        let mut driver = TestDriver::new(picker(), 80, 10)?;
+
+        driver
+            .keys([Key::Down, Key::Down, Key::Down, Key::Up, Key::Char('\n')])
+            .run(10)?;
+
+        assert!(matches!(
+            driver.messages(),
+            [
+                Message::SelectionChanged { selected: Some(1) },
+                Message::SelectionChanged { selected: Some(2) },
+                Message::SelectionChanged { selected: Some(2) },
+                Message::SelectionChanged { selected: Some(1) },
+                Message::Pick,
+            ]
+        ));
+        let exit = driver.exit().and_then(|exit| exit.value.as_ref());
+        assert_eq!(exit.map(|selection| selection.ids.clone()), Some(vec![1]));
+
+        Ok(())
+    }
+
+    #[test]
+    fn picker_should_narrow_typed_search() -> anyhow::Result<()> {
+        let mut driver = TestDriver::new(picker(), 80, 10)?;
+
+        driver.keys("rank".chars().map(Key::Char)).run(4)?;
+        assert_eq!(driver.state().search.text, "rank");
+        assert!(format!("{:?}", driver.buffer()).contains("2/3"));
+
+        driver.keys([Key::Char('\n')]).run(1)?;
+        let exit = driver.exit().and_then(|exit| exit.value.as_ref());
+        assert_eq!(exit.map(|selection| selection.ids.clone()), Some(vec![2]));
+
+        Ok(())
+    }
+
+    #[test]
+    fn picking_should_return_the_selected_match() {
+        let mut picker = picker();
+
+        picker.update(search("crash"));
+        let exit = picker.update(Message::Pick).and_then(|exit| exit.value);
+
+        assert_eq!(exit.map(|selection| selection.ids), Some(vec![1]));
+    }
+}
@@ -3,7 +3,7 @@ use std::str::FromStr;
 
 use radicle::issue::{self, CloseReason};
 use ratatui::layout::{Constraint, Layout};
-use ratatui::style::Stylize;
+use ratatui::style::{Style, Stylize};
 use ratatui::text::{Line, Span, Text};
 use ratatui::widgets::Row;
 use ratatui::Frame;
@@ -12,9 +12,10 @@ use radicle_tui as tui;
 
 use tui::ui::rm::widget::{RenderProps, View, ViewProps};
 use tui::ui::theme::style;
-use tui::ui::{layout, span, BufferedValue};
+use tui::ui::{layout, span, BufferedValue, Highlight};
 
 use super::format;
+use super::items;
 use super::items::IssueItem;
 
 use crate::ui::items::Filter;
@@ -69,6 +70,70 @@ where
         self.items_ref().into_iter().cloned().collect()
     }
 
+    /// Appends the given items and sorts all items afterwards. The sort is stable,
+    /// so items comparing equal keep the order they were added in. The item
+    /// selected before, identified by `key`, stays selected even if it moved.
+    /// Selects the first item if nothing was selected before.
+    pub fn extend_sorted<C, K, T>(&mut self, items: impl IntoIterator<Item = I>, compare: C, key: K)
+    where
+        C: FnMut(&I, &I) -> std::cmp::Ordering,
+        K: Fn(&I) -> T,
+        T: PartialEq,
+    {
+        let selected = self.selected_item().map(&key);
+
+        self.items.extend(items);
+        self.items.sort_by(compare);
+
+        let position = selected.and_then(|selected| {
+            self.items_ref()
+                .iter()
+                .position(|item| key(item) == selected)
+        });
+
+        if position.is_some() {
+            self.selected = position;
+        } else if self.selected.is_none() && !self.items_ref().is_empty() {
+            self.selected = Some(0);
+        }
+    }
+
+    /// Replaces all items, e.g. after they were reloaded. The item selected before
+    /// stays selected if it's still part of the filtered items, which also keeps the
+    /// scroll position of the table rendering them. Otherwise the selection is moved
+    /// to the closest item that still exists.
+    pub fn replace_items<K, T>(&mut self, items: Vec<I>, key: K)
+    where
+        K: Fn(&I) -> T,
+        T: PartialEq,
+    {
+        let selected = self.selected_item().map(&key);
+
+        self.items = items;
+
+        let position = selected.and_then(|selected| {
+            self.items_ref()
+                .iter()
+                .position(|item| key(item) == selected)
+        });
+
+        match position {
+            Some(position) => self.selected = Some(position),
+            None => self.clamp_selection(),
+        }
+    }
+
+    /// Moves the selection to the last item if it's out of bounds, e.g. because
+    /// items changed and don't match the filter anymore.
+    pub fn clamp_selection(&mut self) {
+        let len = self.items_ref().len();
+
+        self.selected = self
+            .selected
+            .map(|selected| selected.min(len.saturating_sub(1)))
+            .filter(|_| len > 0);
+    }
+
     pub fn items_ref(&self) -> Vec<&I> {
         self.items
             .iter()
@@ -80,16 +145,45 @@ where
         self.selected
     }
 
+    pub fn filter(&self) -> &F {
+        &self.filter
+    }
+
+    /// Returns the highlight for search matches of the current filter, if it
+    /// has a free-text search.
+    pub fn highlight(&self, style: Style) -> Option<Highlight> {
+        self.filter
+            .search()
+            .map(|search| Highlight::new(search, style))
+    }
+
     pub fn selected_item(&self) -> Option<&I> {
         self.selected
             .and_then(|selected| self.items_ref().get(selected).copied())
     }
 
+    pub fn selected_item_mut(&mut self) -> Option<&mut I> {
+        let selected = self.selected?;
+        let filter = &self.filter;
+
+        self.items
+            .iter_mut()
+            .filter(|item| filter.matches(item))
+            .nth(selected)
+    }
+
     pub fn select_item(&mut self, selected: Option<usize>) -> Option<&I> {
         self.selected = selected;
         self.selected_item()
     }
 
+    /// Selects the first of the filtered items the predicate is satisfied by.
+    /// The selection is kept if there's no such item.
+    pub fn select_item_by(&mut self, f: impl Fn(&I) -> bool) -> Option<&I> {
+        let position = self.items_ref().iter().position(|item| f(item))?;
+        self.select_item(Some(position))
+    }
+
     pub fn select_first_item(&mut self) -> Option<&I> {
         self.selected.and_then(|selected| {
             if selected > self.items_ref().len() {
@@ -122,6 +216,25 @@ where
         self.search.apply();
     }
 
+    /// Adds the given word to the search if it does not contain it yet, and
+    /// removes it otherwise. The search is applied and items are filtered right away.
+    pub fn toggle_search_word(&mut self, word: &str) {
+        self.search
+            .write(items::toggle_search_word(&self.search.read(), word));
+        self.search.apply();
+        self.filter_items();
+    }
+
+    /// Replaces the state filter of the search with the next one of `states`, see
+    /// `items::cycle_search_state`. Like with `toggle_search_word`, the search
+    /// is applied right away.
+    pub fn cycle_search_state(&mut self, states: &[&str]) {
+        self.search
+            .write(items::cycle_search_state(&self.search.read(), states));
+        self.search.apply();
+        self.filter_items();
+    }
+
     pub fn reset_search(&mut self) {
         self.search.reset();
         self.filter_items();
@@ -226,25 +339,31 @@ impl<S, M> View for IssueDetails<S, M> {
                 },
             };
 
-            let table = ratatui::widgets::Table::new(
-                [
-                    Row::new([
-                        Text::raw("Title").cyan(),
-                        Text::raw(issue.title.clone()).bold(),
-                    ]),
-                    Row::new([
-                        Text::raw("Issue").cyan(),
-                        Text::raw(issue.id.to_string()).bold(),
-                    ]),
-                    Row::new([
-                        Text::raw("Author").cyan(),
-                        Line::from([author, " ".into(), did].to_vec()).into(),
-                    ]),
-                    Row::new([Text::raw("Labels").cyan(), Text::from(labels).blue()]),
-                    Row::new([Text::raw("Status").cyan(), status]),
-                ],
-                [Constraint::Length(8), Constraint::Fill(1)],
-            );
+            let mut rows = vec![
+                Row::new([
+                    Text::raw("Title").cyan(),
+                    Text::raw(issue.title.clone()).bold(),
+                ]),
+                Row::new([
+                    Text::raw("Issue").cyan(),
+                    Text::raw(issue.id.to_string()).bold(),
+                ]),
+                Row::new([
+                    Text::raw("Author").cyan(),
+                    Line::from([author, " ".into(), did].to_vec()).into(),
+                ]),
+                Row::new([Text::raw("Labels").cyan(), Text::from(labels).blue()]),
+            ];
+            if let Some(milestone) = &issue.milestone {
+                rows.push(Row::new([
+                    Text::raw("Milestone").cyan(),
+                    Text::raw(milestone.clone()).blue().bold(),
+                ]));
+            }
+            rows.push(Row::new([Text::raw("Status").cyan(), status]));
+
+            let table =
+                ratatui::widgets::Table::new(rows, [Constraint::Length(10), Constraint::Fill(1)]);
 
             let table = if !render.focus && props.dim {
                 table.dim()
@@ -264,3 +383,106 @@ impl<S, M> View for IssueDetails<S, M> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+    use std::str::FromStr;
+
+    use pretty_assertions::assert_eq;
+
+    use radicle_tui::ui::BufferedValue;
+
+    use crate::ui::items::Filter;
+
+    use super::BrowserState;
+
+    /// Matches even numbers only, if the search is `even`.
+    #[derive(Clone, Debug, Default)]
+    struct EvenFilter(bool);
+
+    impl Filter<u32> for EvenFilter {
+        fn matches(&self, item: &u32) -> bool {
+            !self.0 || item % 2 == 0
+        }
+
+        fn describe(&self) -> Vec<String> {
+            vec![]
+        }
+    }
+
+    impl FromStr for EvenFilter {
+        type Err = Infallible;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            Ok(Self(s == "even"))
+        }
+    }
+
+    #[test]
+    fn replaced_items_should_keep_selected_item() {
+        let mut browser = BrowserState::build(
+            vec![2, 4, 6, 8],
+            EvenFilter(true),
+            BufferedValue::new("even".to_string()),
+        );
+        browser.select_item(Some(2));
+
+        browser.replace_items(vec![0, 1, 2, 3, 6, 8], |item| *item);
+
+        assert_eq!(browser.selected(), Some(2));
+        assert_eq!(browser.selected_item(), Some(&6));
+    }
+
+    #[test]
+    fn extended_items_should_keep_selected_item() {
+        let mut browser = BrowserState::build(
+            vec![8, 4],
+            EvenFilter(true),
+            BufferedValue::new("even".to_string()),
+        );
+        browser.select_item(Some(1));
+
+        browser.extend_sorted(vec![6, 3, 2], |a, b| b.cmp(a), |item| *item);
+        assert_eq!(browser.selected_item(), Some(&4));
+
+        let mut browser = BrowserState::build(
+            vec![],
+            EvenFilter(false),
+            BufferedValue::new("".to_string()),
+        );
+        browser.extend_sorted(vec![1, 2], |a, b| a.cmp(b), |item| *item);
+        assert_eq!(browser.selected_item(), Some(&1));
+    }
+
+    #[test]
+    fn item_should_be_selected_by_predicate() {
+        let mut browser = BrowserState::build(
+            vec![1, 2, 3, 4],
+            EvenFilter(true),
+            BufferedValue::new("even".to_string()),
+        );
+
+        assert_eq!(browser.select_item_by(|item| *item == 4), Some(&4));
+        assert_eq!(browser.selected(), Some(1));
+
+        assert_eq!(browser.select_item_by(|item| *item == 3), None);
+        assert_eq!(browser.selected(), Some(1));
+    }
+
+    #[test]
+    fn replaced_items_should_move_selection_if_selected_item_is_gone() {
+        let mut browser = BrowserState::build(
+            vec![2, 4, 6, 8],
+            EvenFilter(false),
+            BufferedValue::new("".to_string()),
+        );
+        browser.select_item(Some(3));
+
+        browser.replace_items(vec![2, 4], |item| *item);
+        assert_eq!(browser.selected_item(), Some(&4));
+
+        browser.replace_items(vec![], |item| *item);
+        assert_eq!(browser.selected(), None);
+    }
+}
@@ -1,9 +1,13 @@
 use std::path::Path;
 
 use ratatui::prelude::Stylize;
-use ratatui::text::Span;
+use ratatui::text::{Line, Span};
+
+use radicle::prelude::Did;
 
 use crate::git::HunkState;
+use crate::ui::format;
+use crate::ui::format::Ellipsis;
 
 use radicle_tui as tui;
 
@@ -17,6 +21,27 @@ pub fn hunk_state(state: &HunkState) -> Span<'static> {
     }
 }
 
+/// Renders the given string, truncated to at most `max` characters.
+pub fn truncated(s: &str, max: usize, ellipsis: Ellipsis) -> Span<'static> {
+    span::default(&format::truncate(s, max, ellipsis))
+}
+
+pub fn identicon(did: &Did) -> Span<'static> {
+    let (identicon, color) = format::identicon(did);
+    span::default(&identicon).fg(color)
+}
+
+/// Renders the given title followed by a chip for each active filter criterion.
+pub fn with_filter_chips(title: &str, chips: &[String]) -> Line<'static> {
+    let mut spans = vec![span::default(title)];
+    for chip in chips {
+        spans.push(span::default(" "));
+        spans.push(span::default(&format!(" {chip} ")).cyan().dim().reversed());
+    }
+
+    Line::from(spans)
+}
+
 pub fn pretty_path(path: &Path, crossed_out: bool, show_path: bool) -> Vec<Span<'static>> {
     let file = path.file_name().unwrap_or_default();
     let path = if path.iter().count() > 1 {
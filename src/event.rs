@@ -1,5 +1,37 @@
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Debug)]
 pub enum Event {
     Key(termion::event::Key),
+    /// Text that was pasted at once, e.g. while bracketed paste is enabled.
+    Paste(String),
     Resize,
+    /// The terminal window got the focus, e.g. because the user switched back.
+    FocusGained,
+    /// The terminal window lost the focus.
+    FocusLost,
+}
+
+/// Returns the pasted text given as a single line. Line breaks and tabs are
+/// replaced by spaces, such that pasting does not submit an input, and other
+/// control characters are removed.
+pub fn single_line(text: &str) -> String {
+    text.replace("\r\n", " ")
+        .chars()
+        .filter_map(|c| match c {
+            '\n' | '\r' | '\t' => Some(' '),
+            c if c.is_control() => None,
+            c => Some(c),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::single_line;
+
+    #[test]
+    fn pasted_lines_should_be_joined() {
+        assert_eq!(single_line("foo\r\nbar\tbaz\n\x07"), "foo bar baz ");
+    }
 }
@@ -5,11 +5,18 @@ pub mod terminal;
 pub mod ui;
 
 use std::any::Any;
-use std::fmt::Debug;
+use std::env;
+use std::fmt::{Debug, Display};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use homedir::my_home;
 
 use ratatui::Viewport;
+use tokio::sync::broadcast;
 use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
 
+use serde::de::{self, Deserialize, Deserializer};
 use serde::ser::{Serialize, SerializeStruct, Serializer};
 
 use anyhow::Result;
@@ -51,10 +58,29 @@ where
         self
     }
 
+    pub fn with_ids(mut self, ids: impl IntoIterator<Item = I>) -> Self {
+        self.ids.extend(ids);
+        self
+    }
+
     pub fn with_args(mut self, arg: String) -> Self {
         self.args.push(arg);
         self
     }
+
+    /// Returns the command this selection is forwarded to, e.g. `rad issue edit <id>`
+    /// for the prefix `rad issue`.
+    pub fn to_command_preview(&self, prefix: &str) -> String {
+        let prefix = (!prefix.is_empty()).then(|| prefix.to_string());
+
+        prefix
+            .into_iter()
+            .chain(self.operation.clone())
+            .chain(self.ids.iter().map(|id| id.to_string()))
+            .chain(self.args.iter().cloned())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
 }
 
 impl<I> Serialize for Selection<I>
@@ -76,6 +102,106 @@ where
     }
 }
 
+/// Selections are deserialized from the output of their `Serialize` implementation,
+/// e.g. to restore a previous selection. Ids that can't be parsed fail the
+/// deserialization.
+impl<'de, I> Deserialize<'de> for Selection<I>
+where
+    I: ToString + FromStr,
+    I::Err: Display,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Default, serde::Deserialize)]
+        #[serde(default)]
+        struct Raw {
+            operation: Option<String>,
+            ids: Vec<String>,
+            args: Vec<String>,
+        }
+
+        let raw = Raw::deserialize(deserializer)?;
+        let ids = raw
+            .ids
+            .iter()
+            .map(|id| {
+                I::from_str(id)
+                    .map_err(|err| de::Error::custom(format!("invalid id '{id}': {err}")))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            operation: raw.operation,
+            ids,
+            args: raw.args,
+        })
+    }
+}
+
+/// Several selections that are returned at once, e.g. to run different operations
+/// on different items. A batch is serialized as a JSON array of its selections.
+#[derive(Clone, Default, Debug, Eq, PartialEq)]
+pub struct Batch<I>
+where
+    I: ToString,
+{
+    pub selections: Vec<Selection<I>>,
+}
+
+impl<I> Batch<I>
+where
+    I: ToString,
+{
+    pub fn with_selection(mut self, selection: Selection<I>) -> Self {
+        self.selections.push(selection);
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.selections.is_empty()
+    }
+}
+
+impl<I> From<Selection<I>> for Batch<I>
+where
+    I: ToString,
+{
+    fn from(selection: Selection<I>) -> Self {
+        Self {
+            selections: vec![selection],
+        }
+    }
+}
+
+impl<I> Serialize for Batch<I>
+where
+    I: ToString,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.selections.serialize(serializer)
+    }
+}
+
+impl<'de, I> Deserialize<'de> for Batch<I>
+where
+    I: ToString + FromStr,
+    I::Err: Display,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(Self {
+            selections: Vec::<Selection<I>>::deserialize(deserializer)?,
+        })
+    }
+}
+
 /// Provide implementations for conversions to and from `Box<dyn Any>`.
 pub trait BoxedAny {
     fn from_boxed_any(any: Box<dyn Any>) -> Option<Self>
@@ -107,19 +233,54 @@ where
 }
 
 /// A 'PageStack' for applications. Page identifier can be pushed to and
-/// popped from the stack.
+/// popped from the stack. Like in a browser, pages that were gone back from
+/// can be gone forward to again, until a new page is pushed.
 #[derive(Clone, Default, Debug)]
 pub struct PageStack<T> {
     pages: Vec<T>,
+    forward: Vec<T>,
 }
 
 impl<T> PageStack<T> {
     pub fn new(pages: Vec<T>) -> Self {
-        Self { pages }
+        Self {
+            pages,
+            forward: vec![],
+        }
     }
 
+    /// Pushes a new page and clears the pages that could be gone forward to.
     pub fn push(&mut self, page: T) {
         self.pages.push(page);
+        self.forward.clear();
+    }
+
+    /// Goes back to the previous page and remembers the page left, such that
+    /// `forward` returns to it. The first page is never left. Returns `true`
+    /// if there was a page to go back to.
+    pub fn back(&mut self) -> bool {
+        if self.pages.len() < 2 {
+            return false;
+        }
+        match self.pages.pop() {
+            Some(page) => {
+                self.forward.push(page);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns to the page last gone back from. Returns `true` if there was
+    /// such a page.
+    pub fn forward(&mut self) -> bool {
+        match self.forward.pop() {
+            Some(page) => {
+                self.pages.push(page);
+                true
+            }
+            None => false,
+        }
     }
 
     pub fn pop(&mut self) -> Option<T> {
@@ -136,16 +297,48 @@ impl<T> PageStack<T> {
     }
 }
 
-/// A multi-producer, single-consumer message channel.
+/// The number of handled messages subscribers can lag behind, see
+/// `Channel::subscribe`.
+const HANDLED_CAPACITY: usize = 256;
+
+/// A multi-producer, single-consumer message channel. The messages handled by
+/// the application are published to subscribers.
 pub struct Channel<M> {
     pub tx: UnboundedSender<M>,
     pub rx: UnboundedReceiver<M>,
+    handled_tx: broadcast::Sender<M>,
+}
+
+impl<M> Channel<M> {
+    /// Returns a receiver for the messages the application handles from now on,
+    /// e.g. to pass them on to an external program.
+    pub fn subscribe(&self) -> broadcast::Receiver<M> {
+        self.handled_tx.subscribe()
+    }
 }
 
-impl<A> Default for Channel<A> {
+impl<A> Default for Channel<A>
+where
+    A: Clone,
+{
     fn default() -> Self {
         let (tx, rx) = mpsc::unbounded_channel();
-        Self { tx: tx.clone(), rx }
+        let (handled_tx, _) = broadcast::channel(HANDLED_CAPACITY);
+        Self {
+            tx: tx.clone(),
+            rx,
+            handled_tx,
+        }
+    }
+}
+
+/// Returns the base directory the given XDG variable points to, e.g. the one of
+/// `XDG_STATE_HOME`, or `fallback` in the home directory if it's not set, e.g.
+/// `.local/state`. Returns `None` if there is no home directory.
+pub fn xdg_home(var: &str, fallback: impl AsRef<Path>) -> Option<PathBuf> {
+    match env::var_os(var).filter(|dir| !dir.is_empty()) {
+        Some(dir) => Some(PathBuf::from(dir)),
+        None => Some(my_home().ok().flatten()?.join(fallback)),
     }
 }
 
@@ -160,17 +353,36 @@ pub async fn rm<S, M, P>(
 ) -> Result<Option<P>>
 where
     S: Update<M, Return = P> + Clone + Debug + Send + Sync + 'static,
-    M: Debug + Send + Sync + 'static,
+    M: Clone + Debug + Send + Sync + 'static,
     P: Clone + Debug + Send + Sync + 'static,
 {
     let (terminator, mut interrupt_rx) = task::create_termination();
 
     let (store, state_rx) = store::Store::<S, M, P>::new();
+    let store = store
+        .snapshot_path(store::snapshot_path::<S, M>(&state))
+        .publish(channel.handled_tx);
+    let (external_tx, external_rx) = mpsc::unbounded_channel();
+    let (toast_tx, toast_rx) = mpsc::unbounded_channel();
     let frontend = rm::Frontend::default();
 
     tokio::try_join!(
-        store.run(state, terminator, channel.rx, interrupt_rx.resubscribe()),
-        frontend.run(root, state_rx, interrupt_rx.resubscribe(), viewport),
+        store.run(
+            state,
+            terminator,
+            channel.rx,
+            interrupt_rx.resubscribe(),
+            external_tx,
+            toast_tx
+        ),
+        frontend.run(
+            root,
+            state_rx,
+            interrupt_rx.resubscribe(),
+            external_rx,
+            toast_rx,
+            viewport
+        ),
     )?;
 
     if let Ok(reason) = interrupt_rx.recv().await {
@@ -196,11 +408,30 @@ where
 
     let state_tx = channel.tx.clone();
     let (store, state_rx) = store::Store::<S, M, P>::new();
+    let store = store
+        .snapshot_path(store::snapshot_path::<S, M>(&state))
+        .publish(channel.handled_tx);
+    let (external_tx, external_rx) = mpsc::unbounded_channel();
+    let (toast_tx, toast_rx) = mpsc::unbounded_channel();
     let frontend = im::Frontend::default();
 
     tokio::try_join!(
-        store.run(state, terminator, channel.rx, interrupt_rx.resubscribe()),
-        frontend.run(state_tx, state_rx, interrupt_rx.resubscribe(), viewport),
+        store.run(
+            state,
+            terminator,
+            channel.rx,
+            interrupt_rx.resubscribe(),
+            external_tx,
+            toast_tx
+        ),
+        frontend.run(
+            state_tx,
+            state_rx,
+            interrupt_rx.resubscribe(),
+            external_rx,
+            toast_rx,
+            viewport
+        ),
     )?;
 
     if let Ok(reason) = interrupt_rx.recv().await {
@@ -212,3 +443,129 @@ where
         anyhow::bail!("exited because of an unexpected error");
     }
 }
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::{xdg_home, Batch, PageStack, Selection};
+
+    #[test]
+    fn pages_should_be_traversed_back_and_forward() -> anyhow::Result<()> {
+        let mut pages = PageStack::new(vec!["browser"]);
+        pages.push("details");
+        pages.push("help");
+
+        assert!(pages.back());
+        assert!(pages.back());
+        assert_eq!(*pages.peek()?, "browser");
+        // The first page is never left.
+        assert!(!pages.back());
+
+        assert!(pages.forward());
+        assert_eq!(*pages.peek()?, "details");
+        assert!(pages.forward());
+        assert_eq!(*pages.peek()?, "help");
+        assert!(!pages.forward());
+
+        Ok(())
+    }
+
+    #[test]
+    fn pushing_a_page_should_clear_forward_history() -> anyhow::Result<()> {
+        let mut pages = PageStack::new(vec!["browser"]);
+        pages.push("help");
+        assert!(pages.back());
+
+        pages.push("details");
+        assert!(!pages.forward());
+        assert_eq!(*pages.peek()?, "details");
+
+        assert!(pages.back());
+        assert_eq!(*pages.peek()?, "browser");
+        assert!(pages.forward());
+        assert_eq!(*pages.peek()?, "details");
+
+        Ok(())
+    }
+
+    #[test]
+    fn command_preview_should_contain_operation_ids_and_args() {
+        let selection = Selection::default()
+            .with_operation("edit".to_string())
+            .with_id(42)
+            .with_args("--verbose".to_string());
+
+        assert_eq!(
+            selection.to_command_preview("rad issue"),
+            "rad issue edit 42 --verbose"
+        );
+        assert_eq!(
+            Selection::<u32>::default()
+                .with_id(7)
+                .to_command_preview(""),
+            "7"
+        );
+    }
+
+    #[test]
+    fn selection_should_round_trip_through_json() -> anyhow::Result<()> {
+        let selection = Selection::default()
+            .with_operation("show".to_string())
+            .with_id(42_u32);
+
+        let json = serde_json::to_string(&selection)?;
+        assert_eq!(serde_json::from_str::<Selection<u32>>(&json)?, selection);
+
+        assert_eq!(
+            serde_json::from_str::<Selection<u32>>(r#"{"ids":["7"]}"#)?,
+            Selection::default().with_id(7)
+        );
+        assert!(serde_json::from_str::<Selection<u32>>(r#"{"ids":["foo"]}"#).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn batch_should_be_serialized_as_array() -> anyhow::Result<()> {
+        let batch = Batch::default()
+            .with_selection(
+                Selection::default()
+                    .with_operation("archive".to_string())
+                    .with_id(1_u32)
+                    .with_id(2),
+            )
+            .with_selection(
+                Selection::default()
+                    .with_operation("show".to_string())
+                    .with_id(3),
+            );
+
+        let json = serde_json::to_string(&batch)?;
+        assert_eq!(
+            json,
+            r#"[{"operation":"archive","ids":["1","2"],"args":[]},{"operation":"show","ids":["3"],"args":[]}]"#
+        );
+        assert_eq!(serde_json::from_str::<Batch<u32>>(&json)?, batch);
+        assert!(serde_json::from_str::<Batch<u32>>("[]")?.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn xdg_home_should_fall_back_to_home_directory() {
+        const VAR: &str = "RADICLE_TUI_TEST_XDG_HOME";
+
+        std::env::set_var(VAR, "/tmp/state");
+        assert_eq!(xdg_home(VAR, ".local/state"), Some("/tmp/state".into()));
+
+        std::env::set_var(VAR, "");
+        if let Some(home) = homedir::my_home().ok().flatten() {
+            assert_eq!(
+                xdg_home(VAR, ".local/state"),
+                Some(home.join(".local/state"))
+            );
+        }
+        std::env::remove_var(VAR);
+    }
+}
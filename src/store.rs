@@ -1,10 +1,14 @@
 use std::fmt::Debug;
+use std::fs;
 use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
 use tokio::sync::broadcast;
 use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
 
+use crate::terminal::External;
+use crate::ui::toast::ToastLevel;
 use crate::Exit;
 
 use super::task::{Interrupted, Terminator};
@@ -20,8 +24,190 @@ pub trait Update<M> {
     /// in case the received message requested the application to also quit.
     fn update(&mut self, message: M) -> Option<Exit<Self::Return>>;
 
+    /// Handle all messages that were queued since the last update at once, such
+    /// that only one new state is emitted for them. Messages are handled in order
+    /// and handling stops at the first one that requested the application to quit.
+    /// States with pages can use `update_pages` here to get their page hooks
+    /// called.
+    fn update_batch(&mut self, messages: Vec<M>) -> Option<Exit<Self::Return>> {
+        for message in messages {
+            if let Some(exit) = self.update(message) {
+                return Some(exit);
+            }
+        }
+        None
+    }
+
     /// Handle recurring tick.
     fn tick(&mut self) {}
+
+    /// Take an external program, e.g. a pager, that was requested while handling
+    /// a message. It is run by the frontend while the terminal is suspended.
+    fn take_external(&mut self) -> Option<External> {
+        None
+    }
+
+    /// Take the toasts that were requested while handling messages, e.g. because
+    /// an action failed. They're shown by the frontend until they expire.
+    fn take_toasts(&mut self) -> Vec<(ToastLevel, String)> {
+        vec![]
+    }
+
+    /// Returns a snapshot of this state that is persisted periodically, such that
+    /// it can be restored if the application crashed. States without a snapshot
+    /// are not persisted.
+    fn snapshot(&self) -> Option<Vec<u8>> {
+        None
+    }
+
+    /// Returns what snapshots of this state are kept apart by, e.g. the id of the
+    /// repository it shows, such that applications running on different
+    /// repositories neither restore nor remove each other's snapshot.
+    fn snapshot_key(&self) -> Option<String> {
+        None
+    }
+
+    /// Restores a state from the snapshot left behind by a crashed application.
+    /// It's called on the initial state, which can be used to fill in what's not
+    /// part of the snapshot, or to reject snapshots that belong to a different
+    /// context, e.g. another repository, by returning `None`. Snapshots that
+    /// can't be decoded are an error.
+    fn restore(&self, _snapshot: &[u8]) -> anyhow::Result<Option<Self>>
+    where
+        Self: Sized,
+    {
+        Ok(None)
+    }
+}
+
+/// Lifecycle hooks of the pages of an application, which states can opt into.
+/// They're called by `update_pages`, which needs to handle the messages of the
+/// state in `Update::update_batch`.
+pub trait Pages {
+    /// The pages of the application.
+    type Page: PartialEq;
+
+    /// Returns the page that is currently shown, e.g. the top of a `PageStack`.
+    /// Whenever it changes, `on_exit_page` is called with the page left and
+    /// `on_enter_page` with the page shown. The page shown first isn't entered.
+    fn page(&self) -> Option<Self::Page>;
+
+    /// Called after the given page is shown, e.g. to start a watcher it needs.
+    fn on_enter_page(&mut self, _page: &Self::Page) {}
+
+    /// Called after the given page was left, e.g. to clear a draft.
+    fn on_exit_page(&mut self, _page: &Self::Page) {}
+}
+
+/// Handles the given messages like the default `Update::update_batch` and calls
+/// the page hooks of the state if the page shown changed in the meantime.
+pub fn update_pages<S, M>(state: &mut S, messages: Vec<M>) -> Option<Exit<S::Return>>
+where
+    S: Update<M> + Pages,
+{
+    let left = state.page();
+    let mut exit = None;
+    for message in messages {
+        exit = state.update(message);
+        if exit.is_some() {
+            break;
+        }
+    }
+    let shown = state.page();
+
+    if shown != left {
+        if let Some(page) = &left {
+            state.on_exit_page(page);
+        }
+        if let Some(page) = &shown {
+            state.on_enter_page(page);
+        }
+    }
+
+    exit
+}
+
+/// Returns the default path snapshots of the given state are persisted at:
+/// `$XDG_STATE_HOME/radicle-tui/snapshots`, or `$HOME/.local/state/radicle-tui/snapshots`
+/// if `XDG_STATE_HOME` is not set. Returns `None` if there is no home directory.
+pub fn snapshot_path<S, M>(state: &S) -> Option<PathBuf>
+where
+    S: Update<M>,
+{
+    let dir = crate::xdg_home("XDG_STATE_HOME", ".local/state")?;
+
+    Some(
+        dir.join("radicle-tui")
+            .join("snapshots")
+            .join(snapshot_name(state)),
+    )
+}
+
+/// Returns the file name of snapshots of the given state, made of its type and
+/// its `snapshot_key`.
+fn snapshot_name<S, M>(state: &S) -> String
+where
+    S: Update<M>,
+{
+    let name = match state.snapshot_key() {
+        Some(key) => format!("{}-{key}", std::any::type_name::<S>()),
+        None => std::any::type_name::<S>().to_string(),
+    };
+    let name = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect::<String>();
+
+    format!("{name}.snapshot")
+}
+
+/// Writes a snapshot to a temporary file first, which is then moved into place,
+/// such that a crash never leaves a half-written snapshot behind.
+fn write_snapshot(path: &Path, snapshot: &[u8]) -> anyhow::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let tmp = path.with_extension("tmp");
+
+    fs::write(&tmp, snapshot)?;
+    fs::rename(&tmp, path)?;
+
+    Ok(())
+}
+
+/// Restores the given state from the snapshot at `path`, if there is one. A
+/// snapshot that can't be decoded, e.g. because it's corrupt, is moved aside
+/// such that it can be inspected, and the initial state is used instead. So is
+/// a snapshot the state rejected, but it's left in place.
+fn read_snapshot<S, M>(path: &Path, state: &S) -> Option<S>
+where
+    S: Update<M>,
+{
+    let snapshot = fs::read(path).ok()?;
+
+    match state.restore(&snapshot) {
+        Ok(Some(restored)) => {
+            log::info!("Restored state from snapshot {}", path.display());
+            Some(restored)
+        }
+        Ok(None) => {
+            log::info!("Snapshot {} doesn't apply, not restored", path.display());
+            None
+        }
+        Err(err) => {
+            let backup = path.with_extension("corrupt");
+            log::warn!(
+                "Could not restore state from snapshot {}, moved to {}: {}",
+                path.display(),
+                backup.display(),
+                err
+            );
+            if let Err(err) = fs::rename(path, &backup) {
+                log::warn!("Could not move snapshot {}: {}", path.display(), err);
+            }
+            None
+        }
+    }
 }
 
 /// The `Store` updates the applications' state concurrently. It handles
@@ -32,7 +218,11 @@ where
     P: Clone + Debug + Send + Sync,
 {
     state_tx: UnboundedSender<S>,
-    _phantom: PhantomData<(M, P)>,
+    /// Where snapshots of the state are persisted, if at all.
+    snapshot_path: Option<PathBuf>,
+    /// Where handled messages are published to, if at all.
+    handled_tx: Option<broadcast::Sender<M>>,
+    _phantom: PhantomData<P>,
 }
 
 impl<S, M, P> Store<S, M, P>
@@ -46,16 +236,52 @@ where
         (
             Store {
                 state_tx,
+                snapshot_path: None,
+                handled_tx: None,
                 _phantom: PhantomData,
             },
             state_rx,
         )
     }
+
+    /// Persists snapshots of the state at the given path. A snapshot that is
+    /// still there when the store is run again was left behind by a crash and
+    /// is restored. It's removed once the application exits normally.
+    pub fn snapshot_path(mut self, path: Option<PathBuf>) -> Self {
+        self.snapshot_path = path;
+        self
+    }
+
+    /// Publishes every message to the given channel before the state handles it,
+    /// such that processes can react to them, see `Channel::subscribe`.
+    pub fn publish(mut self, handled_tx: broadcast::Sender<M>) -> Self {
+        self.handled_tx = Some(handled_tx);
+        self
+    }
+
+    /// Persists a snapshot of the given state, unless it didn't change since the
+    /// last one.
+    fn persist(&self, state: &S, last_snapshot: &mut Option<Vec<u8>>) {
+        let Some(path) = &self.snapshot_path else {
+            return;
+        };
+
+        match state.snapshot() {
+            Some(snapshot) if last_snapshot.as_ref() != Some(&snapshot) => {
+                if let Err(err) = write_snapshot(path, &snapshot) {
+                    log::warn!("Could not write snapshot {}: {}", path.display(), err);
+                }
+                *last_snapshot = Some(snapshot);
+            }
+            _ => {}
+        }
+    }
 }
 
 impl<S, M, P> Store<S, M, P>
 where
     S: Update<M, Return = P> + Clone + Send + Sync + 'static,
+    M: Clone,
     P: Clone + Debug + Send + Sync + 'static,
 {
     /// By calling `main_loop`, the store will wait for new messages coming
@@ -68,7 +294,16 @@ where
         mut terminator: Terminator<P>,
         mut message_rx: UnboundedReceiver<M>,
         mut interrupt_rx: broadcast::Receiver<Interrupted<P>>,
+        external_tx: UnboundedSender<External>,
+        toast_tx: UnboundedSender<(ToastLevel, String)>,
     ) -> anyhow::Result<Interrupted<P>> {
+        if let Some(path) = &self.snapshot_path {
+            if let Some(restored) = read_snapshot(path, &state) {
+                state = restored;
+            }
+        }
+        let mut last_snapshot = None;
+
         // Send the initial state once
         self.state_tx.send(state.clone())?;
 
@@ -77,9 +312,17 @@ where
         let result = loop {
             tokio::select! {
                 // Handle the messages coming from the frontend
-                // and process them to do async operations
+                // and process them to do async operations. Messages queued
+                // in the meantime, e.g. while a key is held, are handled along
+                // with it, so that only one state is sent for all of them.
                 Some(message) = message_rx.recv() => {
-                    if let Some(exit) = state.update(message) {
+                    let mut messages = vec![message];
+                    while let Ok(message) = message_rx.try_recv() {
+                        messages.push(message);
+                    }
+                    self.publish_handled(&messages);
+
+                    if let Some(exit) = state.update_batch(messages) {
                         let interrupted = Interrupted::User { payload: exit.value };
                         let _ = terminator.terminate(interrupted.clone());
 
@@ -89,6 +332,7 @@ where
                 // Tick to terminate the select every N milliseconds
                 _ = ticker.tick() => {
                     state.tick();
+                    self.persist(&state, &mut last_snapshot);
                 },
                 // Catch and handle interrupt signal to gracefully shutdown
                 Ok(interrupted) = interrupt_rx.recv() => {
@@ -96,9 +340,315 @@ where
                 }
             }
 
+            if let Some(external) = state.take_external() {
+                external_tx.send(external)?;
+            }
+            // Frontends that don't show toasts drop their receiver.
+            for toast in state.take_toasts() {
+                let _ = toast_tx.send(toast);
+            }
             self.state_tx.send(state.clone())?;
         };
 
+        // The application exits normally, so there's nothing to restore.
+        if let Some(path) = &self.snapshot_path {
+            let _ = fs::remove_file(path);
+        }
+
         Ok(result)
     }
+
+    /// Publishes the given messages, if anyone subscribed to them.
+    fn publish_handled(&self, messages: &[M]) {
+        let Some(handled_tx) = &self.handled_tx else {
+            return;
+        };
+        if handled_tx.receiver_count() > 0 {
+            for message in messages {
+                let _ = handled_tx.send(message.clone());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use tokio::sync::{broadcast, mpsc};
+
+    use crate::task::{Interrupted, Terminator};
+    use crate::{Exit, PageStack};
+
+    use super::{read_snapshot, snapshot_name, update_pages, Pages, Store, Update};
+
+    #[derive(Clone, Debug, Default)]
+    struct Counter {
+        count: usize,
+        repo: Option<&'static str>,
+    }
+
+    #[derive(Clone, Debug)]
+    enum Message {
+        Increment,
+        Quit,
+    }
+
+    impl Update<Message> for Counter {
+        type Return = usize;
+
+        fn update(&mut self, message: Message) -> Option<Exit<usize>> {
+            match message {
+                Message::Increment => {
+                    self.count += 1;
+                    None
+                }
+                Message::Quit => Some(Exit {
+                    value: Some(self.count),
+                }),
+            }
+        }
+
+        fn snapshot(&self) -> Option<Vec<u8>> {
+            Some(format!("{}:{}", self.repo.unwrap_or_default(), self.count).into_bytes())
+        }
+
+        fn snapshot_key(&self) -> Option<String> {
+            self.repo.map(String::from)
+        }
+
+        fn restore(&self, snapshot: &[u8]) -> anyhow::Result<Option<Self>> {
+            let (repo, count) = std::str::from_utf8(snapshot)?
+                .rsplit_once(':')
+                .ok_or_else(|| anyhow::anyhow!("missing count"))?;
+            let count = count.parse()?;
+            if repo != self.repo.unwrap_or_default() {
+                return Ok(None);
+            }
+
+            Ok(Some(Self {
+                count,
+                ..self.clone()
+            }))
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    struct PageState {
+        pages: PageStack<&'static str>,
+        hooks: Vec<String>,
+    }
+
+    #[derive(Debug)]
+    enum PageMessage {
+        Open(&'static str),
+        Back,
+        Stay,
+    }
+
+    impl Update<PageMessage> for PageState {
+        type Return = ();
+
+        fn update(&mut self, message: PageMessage) -> Option<Exit<()>> {
+            match message {
+                PageMessage::Open(page) => self.pages.push(page),
+                PageMessage::Back => {
+                    self.pages.back();
+                }
+                PageMessage::Stay => {}
+            }
+            None
+        }
+
+        fn update_batch(&mut self, messages: Vec<PageMessage>) -> Option<Exit<()>> {
+            update_pages(self, messages)
+        }
+    }
+
+    impl Pages for PageState {
+        type Page = &'static str;
+
+        fn page(&self) -> Option<&'static str> {
+            self.pages.peek().ok().copied()
+        }
+
+        fn on_enter_page(&mut self, page: &&'static str) {
+            self.hooks.push(format!("enter {page}"));
+        }
+
+        fn on_exit_page(&mut self, page: &&'static str) {
+            self.hooks.push(format!("exit {page}"));
+        }
+    }
+
+    #[test]
+    fn page_hooks_should_be_called_if_page_changed() {
+        let mut state = PageState {
+            pages: PageStack::new(vec!["browse"]),
+            hooks: vec![],
+        };
+
+        state.update_batch(vec![PageMessage::Stay]);
+        assert!(state.hooks.is_empty());
+
+        state.update_batch(vec![PageMessage::Open("help")]);
+        state.update_batch(vec![PageMessage::Back]);
+        // Pages that are only passed within a batch are neither entered nor left.
+        state.update_batch(vec![PageMessage::Open("help"), PageMessage::Back]);
+
+        assert_eq!(
+            state.hooks,
+            ["exit browse", "enter help", "exit help", "enter browse"]
+        );
+    }
+
+    #[test]
+    fn batch_update_should_stop_at_first_exit() {
+        let mut counter = Counter::default();
+        let exit =
+            counter.update_batch(vec![Message::Increment, Message::Quit, Message::Increment]);
+
+        assert_eq!(exit.and_then(|exit| exit.value), Some(1));
+        assert_eq!(counter.count, 1);
+    }
+
+    #[tokio::test]
+    async fn queued_messages_should_be_sent_as_single_state() -> anyhow::Result<()> {
+        let (store, mut state_rx) = Store::<Counter, Message, usize>::new();
+        let (message_tx, message_rx) = mpsc::unbounded_channel();
+        let (external_tx, _external_rx) = mpsc::unbounded_channel();
+        let (toast_tx, _toast_rx) = mpsc::unbounded_channel();
+        let (interrupt_tx, interrupt_rx) = broadcast::channel(1);
+        let terminator = Terminator::new(interrupt_tx);
+
+        for _ in 0..3 {
+            message_tx.send(Message::Increment)?;
+        }
+
+        let store = tokio::spawn(store.run(
+            Counter::default(),
+            terminator,
+            message_rx,
+            interrupt_rx,
+            external_tx,
+            toast_tx,
+        ));
+
+        // Ticks might send unchanged states in between.
+        let mut counts = vec![];
+        while let Some(state) = state_rx.recv().await {
+            counts.push(state.count);
+            if state.count == 3 {
+                break;
+            }
+        }
+        counts.dedup();
+        assert_eq!(counts, vec![0, 3]);
+
+        message_tx.send(Message::Quit)?;
+
+        match store.await?? {
+            Interrupted::User { payload } => assert_eq!(payload, Some(3)),
+            Interrupted::OsSignal => panic!("store should be interrupted by the user"),
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn snapshot_should_be_restored_and_removed_on_exit() -> anyhow::Result<()> {
+        let path = std::env::temp_dir().join(format!(
+            "radicle-tui-test-{}-{}",
+            std::process::id(),
+            snapshot_name::<Counter, Message>(&Counter::default())
+        ));
+        std::fs::write(&path, ":5")?;
+
+        let (store, mut state_rx) = Store::<Counter, Message, usize>::new();
+        let store = store.snapshot_path(Some(path.clone()));
+        let (message_tx, message_rx) = mpsc::unbounded_channel();
+        let (external_tx, _external_rx) = mpsc::unbounded_channel();
+        let (toast_tx, _toast_rx) = mpsc::unbounded_channel();
+        let (interrupt_tx, interrupt_rx) = broadcast::channel(1);
+        let terminator = Terminator::new(interrupt_tx);
+
+        let store = tokio::spawn(store.run(
+            Counter::default(),
+            terminator,
+            message_rx,
+            interrupt_rx,
+            external_tx,
+            toast_tx,
+        ));
+        assert_eq!(state_rx.recv().await.map(|state| state.count), Some(5));
+
+        message_tx.send(Message::Quit)?;
+        store.await??;
+
+        assert!(!path.exists());
+
+        Ok(())
+    }
+
+    #[test]
+    fn snapshots_should_be_kept_apart_by_key() {
+        let name = |repo| {
+            snapshot_name::<Counter, Message>(&Counter {
+                repo,
+                ..Default::default()
+            })
+        };
+
+        assert_ne!(name(Some("rad:z3gq")), name(Some("rad:z4V1")));
+        assert!(name(Some("rad:z3gq")).ends_with("Counter-rad-z3gq.snapshot"));
+        assert!(name(None).ends_with("Counter.snapshot"));
+    }
+
+    #[test]
+    fn corrupt_snapshot_should_be_moved_aside() -> anyhow::Result<()> {
+        let path = std::env::temp_dir().join(format!(
+            "radicle-tui-test-{}-corrupt.snapshot",
+            std::process::id()
+        ));
+        let backup = path.with_extension("corrupt");
+        // A snapshot that was cut off while being written.
+        std::fs::write(&path, r#"{"count":"#)?;
+
+        let restored = read_snapshot::<Counter, Message>(&path, &Counter::default());
+
+        assert!(restored.is_none());
+        assert!(!path.exists());
+        assert_eq!(std::fs::read_to_string(&backup)?, r#"{"count":"#);
+        assert!(read_snapshot::<Counter, Message>(&path, &Counter::default()).is_none());
+
+        std::fs::remove_file(&backup)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn rejected_snapshot_should_be_left_in_place() -> anyhow::Result<()> {
+        let path = std::env::temp_dir().join(format!(
+            "radicle-tui-test-{}-rejected.snapshot",
+            std::process::id()
+        ));
+        let state = Counter {
+            repo: Some("rad:z3gq"),
+            ..Default::default()
+        };
+        std::fs::write(&path, "rad:z4V1:5")?;
+
+        assert!(read_snapshot::<Counter, Message>(&path, &state).is_none());
+        assert!(path.exists());
+        assert!(!path.with_extension("corrupt").exists());
+
+        std::fs::write(&path, "rad:z3gq:5")?;
+        let restored = read_snapshot::<Counter, Message>(&path, &state);
+
+        assert_eq!(restored.map(|state| state.count), Some(5));
+
+        std::fs::remove_file(&path)?;
+
+        Ok(())
+    }
 }
@@ -1,8 +1,19 @@
+pub mod desktop;
+pub mod interval;
+#[cfg(any(test, feature = "script"))]
+pub mod script;
+pub mod watch;
+
 use std::fmt::Debug;
+use std::future::Future;
 
 #[cfg(unix)]
 use tokio::signal::unix::signal;
 use tokio::sync::broadcast;
+use tokio::sync::mpsc::UnboundedSender;
+use tokio::task::JoinHandle;
+
+use crate::terminal;
 
 /// An `Interrupt` message that is produced by either an OS signal (e.g. kill)
 /// or the user by requesting the application to close.
@@ -50,7 +61,15 @@ where
     let mut interrupt_signal = signal(tokio::signal::unix::SignalKind::interrupt())
         .expect("failed to create interrupt signal stream");
 
-    interrupt_signal.recv().await;
+    // Interrupts that happen while the terminal is suspended belong to the
+    // external program that is currently run.
+    loop {
+        interrupt_signal.recv().await;
+
+        if !terminal::is_suspended() {
+            break;
+        }
+    }
 
     terminator
         .terminate(Interrupted::OsSignal)
@@ -70,3 +89,53 @@ where
 
     (terminator, rx)
 }
+
+/// A `Process` runs concurrently to an application and emits messages into it,
+/// e.g. in response to external events.
+pub trait Process<M>: Send + 'static
+where
+    M: Send + 'static,
+{
+    /// Run until the process is done or the receiving end of the application's
+    /// message channel is closed.
+    fn run(self, tx: UnboundedSender<M>) -> impl Future<Output = anyhow::Result<()>> + Send;
+}
+
+/// Builds the next message with `message` and sends it, if there is one. It's
+/// built on a thread where blocking is acceptable, since building it may take a
+/// while, e.g. if it reloads data, which must not stall other tasks. Returns the
+/// builder, such that it can be called again, or `None` if the receiving end of
+/// the channel is closed.
+pub(crate) async fn send_blocking<M, F>(
+    message: F,
+    tx: &UnboundedSender<M>,
+) -> anyhow::Result<Option<F>>
+where
+    M: Send + 'static,
+    F: FnMut() -> Option<M> + Send + 'static,
+{
+    let (next, message) = tokio::task::spawn_blocking(move || {
+        let mut message = message;
+        (message(), message)
+    })
+    .await?;
+
+    match next {
+        Some(next) if tx.send(next).is_err() => Ok(None),
+        _ => Ok(Some(message)),
+    }
+}
+
+/// Spawn a task that runs the given process and sends its messages to the
+/// channel given. Errors returned by the process are logged.
+pub fn spawn<M, P>(process: P, tx: UnboundedSender<M>) -> JoinHandle<()>
+where
+    M: Send + 'static,
+    P: Process<M>,
+{
+    tokio::spawn(async move {
+        if let Err(err) = process.run(tx).await {
+            log::warn!("Process failed: {}", err);
+        }
+    })
+}
@@ -0,0 +1,157 @@
+use std::time::Duration;
+
+use tokio::sync::mpsc::{self, UnboundedSender};
+use tokio::time::{self, Instant};
+
+use crate::terminal;
+
+use super::Process;
+
+/// The default time summaries are collected for before they're posted at once.
+pub const DEBOUNCE: Duration = Duration::from_secs(5);
+
+/// A `Process` that runs the given process and passes its messages on to the
+/// application. Each message is passed to `summary` as well, which returns a
+/// summary if the message is worth a desktop notification, e.g. because new items
+/// arrived. Summaries built within the debounce period are posted at once.
+///
+/// Notifications are posted as OSC 9 escape sequences, which most terminal
+/// emulators turn into desktop notifications and all others ignore. They're
+/// written by the frontend, see `terminal::notify`.
+pub struct DesktopNotify<P, F> {
+    title: String,
+    process: P,
+    summary: F,
+    debounce: Duration,
+}
+
+impl<P, F> DesktopNotify<P, F> {
+    pub fn new(title: impl ToString, process: P, summary: F) -> Self {
+        Self {
+            title: title.to_string(),
+            process,
+            summary,
+            debounce: DEBOUNCE,
+        }
+    }
+
+    pub fn debounce(mut self, debounce: Duration) -> Self {
+        self.debounce = debounce;
+        self
+    }
+}
+
+impl<M, P, F> Process<M> for DesktopNotify<P, F>
+where
+    M: Send + 'static,
+    P: Process<M>,
+    F: FnMut(&M) -> Option<String> + Send + 'static,
+{
+    async fn run(mut self, tx: UnboundedSender<M>) -> anyhow::Result<()> {
+        let (inner_tx, mut inner_rx) = mpsc::unbounded_channel();
+        let inner = super::spawn(self.process, inner_tx);
+
+        let mut summaries = vec![];
+        let mut deadline = None;
+
+        loop {
+            tokio::select! {
+                message = inner_rx.recv() => {
+                    let Some(message) = message else {
+                        break;
+                    };
+                    if let Some(summary) = (self.summary)(&message) {
+                        deadline.get_or_insert_with(|| Instant::now() + self.debounce);
+                        summaries.push(summary);
+                    }
+                    if tx.send(message).is_err() {
+                        break;
+                    }
+                }
+                _ = time::sleep_until(deadline.unwrap_or_else(Instant::now)), if deadline.is_some() => {
+                    terminal::notify(notification(&self.title, &summaries));
+
+                    summaries.clear();
+                    deadline = None;
+                }
+                _ = tx.closed() => break,
+            }
+        }
+        inner.abort();
+
+        Ok(())
+    }
+}
+
+/// Builds the escape sequence of a notification that shows the first summary
+/// and how many more there are. Control characters are left out, since they
+/// would end the sequence early.
+fn notification(title: &str, summaries: &[String]) -> String {
+    let body = match summaries {
+        [] => String::new(),
+        [summary] => summary.clone(),
+        [summary, more @ ..] => format!("{summary} (and {} more)", more.len()),
+    };
+    let text = format!("{title}: {body}")
+        .chars()
+        .filter(|c| !c.is_control())
+        .collect::<String>();
+
+    format!("\x1b]9;{text}\x07")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use pretty_assertions::assert_eq;
+    use tokio::sync::mpsc::{self, UnboundedSender};
+
+    use crate::task::{self, Process};
+
+    use super::{notification, DesktopNotify};
+
+    struct Numbers(Vec<usize>);
+
+    impl Process<usize> for Numbers {
+        async fn run(self, tx: UnboundedSender<usize>) -> anyhow::Result<()> {
+            for number in self.0 {
+                tx.send(number)?;
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn notifications_should_summarize_and_strip_control_characters() {
+        assert_eq!(
+            notification("Radicle", &["1 new\x07 notification".to_string()]),
+            "\x1b]9;Radicle: 1 new notification\x07"
+        );
+        assert_eq!(
+            notification(
+                "Radicle",
+                &["a".to_string(), "b".to_string(), "c".to_string()]
+            ),
+            "\x1b]9;Radicle: a (and 2 more)\x07"
+        );
+    }
+
+    #[tokio::test]
+    async fn messages_should_be_passed_on() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        // Nothing is posted, since the process ends before the debounce period.
+        let process = DesktopNotify::new("Test", Numbers(vec![1, 2, 3]), |number: &usize| {
+            (*number > 1).then(|| format!("{number} arrived"))
+        })
+        .debounce(Duration::from_secs(3600));
+        task::spawn(process, tx);
+
+        let mut received = vec![];
+        while let Some(number) = rx.recv().await {
+            received.push(number);
+        }
+
+        assert_eq!(received, vec![1, 2, 3]);
+    }
+}
@@ -0,0 +1,80 @@
+use std::time::Duration;
+
+use tokio::sync::mpsc::UnboundedSender;
+use tokio::time::{self, Instant, MissedTickBehavior};
+
+use super::{send_blocking, Process};
+
+/// A `Process` that emits the message built by `message` periodically, e.g. to
+/// reload data whose changes can't be watched. The first message is emitted after
+/// one period has passed, not right away. If `message` returns `None`, nothing
+/// is emitted for this period.
+///
+/// Note that `message` is called on a thread where blocking is acceptable, so it
+/// may do some work like reloading data.
+pub struct Interval<F> {
+    period: Duration,
+    message: F,
+}
+
+impl<F> Interval<F> {
+    pub fn new(period: Duration, message: F) -> Self {
+        Self { period, message }
+    }
+}
+
+impl<M, F> Process<M> for Interval<F>
+where
+    M: Send + 'static,
+    F: FnMut() -> Option<M> + Send + 'static,
+{
+    async fn run(self, tx: UnboundedSender<M>) -> anyhow::Result<()> {
+        let mut ticker = time::interval_at(Instant::now() + self.period, self.period);
+        // Periods that were missed while building a message are not caught up.
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        let mut message = self.message;
+
+        loop {
+            tokio::select! {
+                _ = ticker.tick() => {
+                    match send_blocking(message, &tx).await? {
+                        Some(returned) => message = returned,
+                        None => return Ok(()),
+                    }
+                }
+                _ = tx.closed() => return Ok(()),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use pretty_assertions::assert_eq;
+    use tokio::sync::mpsc;
+    use tokio::time;
+
+    use crate::task;
+
+    use super::Interval;
+
+    #[tokio::test]
+    async fn messages_should_be_emitted_periodically() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let mut count = 0;
+        let interval = Interval::new(Duration::from_millis(10), move || {
+            count += 1;
+            // Every other period is skipped.
+            (count % 2 == 0).then_some(count)
+        });
+        let handle = task::spawn(interval, tx);
+
+        assert_eq!(rx.recv().await, Some(2));
+        assert_eq!(rx.recv().await, Some(4));
+
+        drop(rx);
+        assert!(time::timeout(Duration::from_secs(1), handle).await.is_ok());
+    }
+}
@@ -0,0 +1,228 @@
+use std::collections::VecDeque;
+use std::ffi::OsString;
+use std::process::Stdio;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::process::Command;
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio::sync::mpsc::UnboundedSender;
+
+use super::Process;
+
+/// A `Process` that runs an external program and talks to it over stdio, using a
+/// line-based protocol: every message the application handles is written to the
+/// program's stdin as a single line of JSON, and every line of JSON the program
+/// prints to stdout is parsed as a message and emitted into the application.
+/// Messages the program emitted itself aren't written back to it. Empty lines are
+/// ignored and lines that can't be parsed are logged and skipped. The handled
+/// messages are received from `Channel::subscribe`, see `forward`. Once the
+/// application's channel is closed, the program's stdin is closed.
+///
+/// If the program exits unsuccessfully, the message built by `crashed` is
+/// emitted, which usually interrupts the application with an error.
+///
+/// Note that this runs arbitrary programs and is only available if the `script`
+/// feature is enabled.
+pub struct ScriptProcess<M, F> {
+    program: OsString,
+    args: Vec<OsString>,
+    handled_rx: Option<broadcast::Receiver<M>>,
+    crashed: F,
+}
+
+impl<M, F> ScriptProcess<M, F> {
+    pub fn new(program: impl Into<OsString>, crashed: F) -> Self {
+        Self {
+            program: program.into(),
+            args: vec![],
+            handled_rx: None,
+            crashed,
+        }
+    }
+
+    pub fn arg(mut self, arg: impl Into<OsString>) -> Self {
+        self.args.push(arg.into());
+        self
+    }
+
+    /// Writes the messages received to the program's stdin, usually the ones the
+    /// application handles, see `Channel::subscribe`. Without them, the
+    /// program's stdin is closed right away.
+    pub fn forward(mut self, handled_rx: broadcast::Receiver<M>) -> Self {
+        self.handled_rx = Some(handled_rx);
+        self
+    }
+}
+
+impl<M, F> Process<M> for ScriptProcess<M, F>
+where
+    M: Clone + Serialize + DeserializeOwned + Send + 'static,
+    F: FnMut(String) -> Option<M> + Send + 'static,
+{
+    async fn run(self, tx: UnboundedSender<M>) -> anyhow::Result<()> {
+        let Self {
+            program,
+            args,
+            mut handled_rx,
+            mut crashed,
+        } = self;
+
+        let mut child = Command::new(&program)
+            .args(&args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .kill_on_drop(true)
+            .spawn()?;
+
+        let mut stdin = child.stdin.take().filter(|_| handled_rx.is_some());
+        let stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| anyhow::anyhow!("Could not read from script stdout"))?;
+        let mut lines = BufReader::new(stdout).lines();
+        // The lines of the messages the program emitted that were not handled
+        // yet, such that they're not written back to it.
+        let mut emitted = VecDeque::new();
+
+        loop {
+            tokio::select! {
+                message = recv(&mut handled_rx), if stdin.is_some() => match message {
+                    Ok(message) => {
+                        let line = serde_json::to_string(&message)?;
+                        if let Some(index) = emitted.iter().position(|emitted| *emitted == line) {
+                            emitted.remove(index);
+                            continue;
+                        }
+                        if let Some(writer) = stdin.as_mut() {
+                            if writer.write_all(format!("{line}\n").as_bytes()).await.is_err()
+                                || writer.flush().await.is_err()
+                            {
+                                // The program closed its stdin or exited, which is
+                                // handled once its stdout is closed.
+                                stdin = None;
+                            }
+                        }
+                    }
+                    Err(RecvError::Lagged(skipped)) => {
+                        log::warn!("Script missed {} messages", skipped);
+                    }
+                    Err(RecvError::Closed) => stdin = None,
+                },
+                line = lines.next_line() => match line? {
+                    Some(line) if line.trim().is_empty() => {}
+                    Some(line) => match serde_json::from_str::<M>(&line) {
+                        Ok(message) => {
+                            if handled_rx.is_some() {
+                                emitted.push_back(serde_json::to_string(&message)?);
+                            }
+                            if tx.send(message).is_err() {
+                                return Ok(());
+                            }
+                        }
+                        Err(err) => log::warn!("Ignoring invalid script message: {}", err),
+                    },
+                    None => break,
+                },
+                _ = tx.closed() => return Ok(()),
+            }
+        }
+
+        let status = child.wait().await?;
+        if !status.success() {
+            let reason = format!("{} exited with {}", program.to_string_lossy(), status);
+            log::warn!("Script crashed: {}", reason);
+
+            if let Some(message) = crashed(reason) {
+                let _ = tx.send(message);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Receives the next handled message, if messages are forwarded at all.
+async fn recv<M>(handled_rx: &mut Option<broadcast::Receiver<M>>) -> Result<M, RecvError>
+where
+    M: Clone,
+{
+    match handled_rx {
+        Some(handled_rx) => handled_rx.recv().await,
+        None => Err(RecvError::Closed),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use serde::{Deserialize, Serialize};
+    use tokio::sync::{broadcast, mpsc};
+
+    use crate::task::Process;
+
+    use super::ScriptProcess;
+
+    #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+    enum Message {
+        Select { id: usize },
+        Crashed { reason: String },
+    }
+
+    #[tokio::test]
+    async fn script_should_echo_forwarded_messages() -> anyhow::Result<()> {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let (handled_tx, handled_rx) = broadcast::channel(16);
+        let script = ScriptProcess::new("cat", |reason| Some(Message::Crashed { reason }))
+            .forward(handled_rx);
+
+        handled_tx.send(Message::Select { id: 1 })?;
+        drop(handled_tx);
+        script.run(tx).await?;
+
+        assert_eq!(rx.recv().await, Some(Message::Select { id: 1 }));
+        assert_eq!(rx.recv().await, None);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn emitted_messages_should_not_be_forwarded_back() -> anyhow::Result<()> {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let (handled_tx, handled_rx) = broadcast::channel(16);
+        let script = ScriptProcess::new("sh", |reason| Some(Message::Crashed { reason }))
+            .arg("-c")
+            .arg(r#"echo '{"Select":{"id":1}}'; cat"#)
+            .forward(handled_rx);
+        let script = tokio::spawn(script.run(tx));
+
+        // The application handles the emitted message, followed by another one.
+        let emitted = rx.recv().await;
+        assert_eq!(emitted, Some(Message::Select { id: 1 }));
+        handled_tx.send(Message::Select { id: 1 })?;
+        handled_tx.send(Message::Select { id: 2 })?;
+        drop(handled_tx);
+        script.await??;
+
+        assert_eq!(rx.recv().await, Some(Message::Select { id: 2 }));
+        assert_eq!(rx.recv().await, None);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn crashed_script_should_emit_message() -> anyhow::Result<()> {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let script = ScriptProcess::new("sh", |reason| Some(Message::Crashed { reason }))
+            .arg("-c")
+            .arg("exit 1");
+
+        script.run(tx).await?;
+
+        assert!(matches!(rx.recv().await, Some(Message::Crashed { .. })));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,127 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use tokio::time;
+
+use super::{send_blocking, Process};
+
+/// The default time filesystem events need to settle before a message is emitted.
+pub const DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// A `Process` that watches the given paths recursively and emits the message
+/// built by `message` whenever files below them changed. Rapid changes, e.g. a
+/// fetch that updates many refs, are debounced and result in a single message.
+///
+/// Note that `message` is called on a thread where blocking is acceptable, so it
+/// may do some work like reloading the data that changed.
+pub struct Watch<F> {
+    paths: Vec<PathBuf>,
+    debounce: Duration,
+    message: F,
+}
+
+impl<F> Watch<F> {
+    pub fn new(message: F) -> Self {
+        Self {
+            paths: vec![],
+            debounce: DEBOUNCE,
+            message,
+        }
+    }
+
+    pub fn path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.paths.push(path.into());
+        self
+    }
+
+    pub fn debounce(mut self, debounce: Duration) -> Self {
+        self.debounce = debounce;
+        self
+    }
+}
+
+impl<M, F> Process<M> for Watch<F>
+where
+    M: Send + 'static,
+    F: FnMut() -> Option<M> + Send + 'static,
+{
+    async fn run(self, tx: UnboundedSender<M>) -> anyhow::Result<()> {
+        let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+        let mut watcher = RecommendedWatcher::new(
+            move |event: notify::Result<notify::Event>| match event {
+                Ok(event) if !event.kind.is_access() => {
+                    let _ = event_tx.send(());
+                }
+                Ok(_) => {}
+                Err(err) => log::warn!("Failed to watch files: {}", err),
+            },
+            notify::Config::default(),
+        )?;
+
+        for path in &self.paths {
+            watcher.watch(path, RecursiveMode::Recursive)?;
+        }
+        let mut message = self.message;
+
+        loop {
+            tokio::select! {
+                settled = settle(&mut event_rx, self.debounce) => {
+                    if !settled {
+                        return Ok(());
+                    }
+                    match send_blocking(message, &tx).await? {
+                        Some(returned) => message = returned,
+                        None => return Ok(()),
+                    }
+                }
+                _ = tx.closed() => return Ok(()),
+            }
+        }
+    }
+}
+
+/// Waits for the next event and then until no further event was received for
+/// the given interval. Returns `false` if the channel was closed.
+async fn settle<T>(rx: &mut UnboundedReceiver<T>, interval: Duration) -> bool {
+    if rx.recv().await.is_none() {
+        return false;
+    }
+
+    loop {
+        match time::timeout(interval, rx.recv()).await {
+            Ok(Some(_)) => continue,
+            Ok(None) => return false,
+            Err(_) => return true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use tokio::sync::mpsc;
+
+    use super::settle;
+
+    #[tokio::test]
+    async fn rapid_events_should_settle_once() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        for _ in 0..10 {
+            tx.send(()).unwrap();
+        }
+
+        assert!(settle(&mut rx, Duration::from_millis(50)).await);
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn closed_channel_should_not_settle() {
+        let (tx, mut rx) = mpsc::unbounded_channel::<()>();
+        drop(tx);
+
+        assert!(!settle(&mut rx, Duration::from_millis(50)).await);
+    }
+}
@@ -1,10 +1,15 @@
+use std::env;
 use std::io::{self, Write};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
 use std::thread;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use ratatui::termion::screen::{AlternateScreen, IntoAlternateScreen};
-use termion::input::TermRead;
+use termion::event::Key;
 use termion::raw::{IntoRawMode, RawTerminal};
+use termion::screen::{ToAlternateScreen, ToMainScreen};
 
 use ratatui::{prelude::*, CompletedFrame};
 use ratatui::{TerminalOptions, Viewport};
@@ -17,6 +22,56 @@ pub type Backend<S> = TermionBackendExt<S>;
 
 pub type InlineTerminal = ratatui::Terminal<Backend<RawTerminal<io::Stdout>>>;
 pub type FullscreenTerminal = ratatui::Terminal<Backend<AlternateScreen<RawTerminal<io::Stdout>>>>;
+pub type TestTerminal = ratatui::Terminal<ratatui::backend::TestBackend>;
+
+/// The height of an inline viewport if none was requested.
+pub const INLINE_HEIGHT: u16 = 20;
+/// The pager used if `$PAGER` is not set.
+const DEFAULT_PAGER: &str = "less -R";
+/// The time `stdin` is polled for before checking if the terminal got suspended.
+const EVENTS_POLL_TIMEOUT: Duration = Duration::from_millis(50);
+
+/// Makes the terminal wrap pasted text in `PASTE_START` and `PASTE_END`.
+const ENABLE_BRACKETED_PASTE: &str = "\x1B[?2004h";
+const DISABLE_BRACKETED_PASTE: &str = "\x1B[?2004l";
+const PASTE_START: &[u8] = b"\x1B[200~";
+const PASTE_END: &[u8] = b"\x1B[201~";
+
+/// Makes the terminal report when its window gains or loses the focus.
+const ENABLE_FOCUS_REPORTING: &str = "\x1B[?1004h";
+const DISABLE_FOCUS_REPORTING: &str = "\x1B[?1004l";
+const FOCUS_IN: &[u8] = b"\x1B[I";
+const FOCUS_OUT: &[u8] = b"\x1B[O";
+
+/// If the terminal is suspended, e.g. while an external program is run. Input
+/// is not read from `stdin` and interrupt signals are ignored while suspended,
+/// since they belong to the external program.
+static SUSPENDED: AtomicBool = AtomicBool::new(false);
+
+/// Returns true if the terminal is currently suspended.
+pub fn is_suspended() -> bool {
+    SUSPENDED.load(Ordering::SeqCst)
+}
+
+/// Sends desktop notifications to the running frontend, see `notifications`.
+static NOTIFICATIONS: Mutex<Option<mpsc::UnboundedSender<String>>> = Mutex::new(None);
+
+/// Returns a receiver of the desktop notifications posted with `notify`. The
+/// frontend writes them to the terminal between two frames, such that they
+/// never interleave with a frame being drawn.
+pub fn notifications() -> mpsc::UnboundedReceiver<String> {
+    let (tx, rx) = mpsc::unbounded_channel();
+    *NOTIFICATIONS.lock().unwrap() = Some(tx);
+    rx
+}
+
+/// Posts the escape sequence of a desktop notification, which is written by
+/// the frontend. It's dropped if no frontend is running.
+pub fn notify(sequence: String) {
+    if let Some(tx) = NOTIFICATIONS.lock().unwrap().as_ref() {
+        let _ = tx.send(sequence);
+    }
+}
 
 pub enum Terminal {
     Inline(InlineTerminal),
@@ -36,6 +91,74 @@ impl Terminal {
                 inner.set_cursor_position(position)?;
             }
         }
+        self.write(DISABLE_BRACKETED_PASTE)?;
+        self.write(DISABLE_FOCUS_REPORTING)?;
+
+        Ok(())
+    }
+
+    /// Writes the given escape sequence to the terminal and flushes it.
+    fn write(&mut self, sequence: &str) -> io::Result<()> {
+        let writer: &mut dyn Write = match self {
+            Terminal::Inline(inner) => inner.backend_mut().writer_mut(),
+            Terminal::Fullscreen(inner) => inner.backend_mut().writer_mut(),
+        };
+        write!(writer, "{}", sequence)?;
+        writer.flush()
+    }
+
+    /// Writes the escape sequence of a desktop notification to the terminal.
+    pub fn notify(&mut self, sequence: &str) -> io::Result<()> {
+        self.write(sequence)
+    }
+
+    /// Hands the terminal over to an external program: stops reading input,
+    /// leaves the alternate screen if needed and disables raw mode.
+    pub fn suspend(&mut self) -> io::Result<()> {
+        SUSPENDED.store(true, Ordering::SeqCst);
+        self.restore()?;
+
+        match self {
+            Terminal::Inline(inner) => {
+                inner.show_cursor()?;
+                inner.backend_mut().writer_mut().suspend_raw_mode()?;
+            }
+            Terminal::Fullscreen(inner) => {
+                inner.show_cursor()?;
+
+                let writer = inner.backend_mut().writer_mut();
+                write!(writer, "{}", ToMainScreen)?;
+                writer.flush()?;
+                writer.suspend_raw_mode()?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Takes the terminal back from an external program: enables raw mode,
+    /// enters the alternate screen if needed and starts reading input again.
+    /// The next draw renders the whole frame.
+    pub fn resume(&mut self) -> io::Result<()> {
+        match self {
+            Terminal::Inline(inner) => {
+                inner.backend_mut().writer_mut().activate_raw_mode()?;
+                inner.hide_cursor()?;
+                inner.clear()?;
+            }
+            Terminal::Fullscreen(inner) => {
+                let writer = inner.backend_mut().writer_mut();
+                writer.activate_raw_mode()?;
+                write!(writer, "{}", ToAlternateScreen)?;
+                writer.flush()?;
+
+                inner.hide_cursor()?;
+                inner.clear()?;
+            }
+        }
+        self.write(ENABLE_BRACKETED_PASTE)?;
+        self.write(ENABLE_FOCUS_REPORTING)?;
+        SUSPENDED.store(false, Ordering::SeqCst);
 
         Ok(())
     }
@@ -51,6 +174,15 @@ impl Terminal {
     }
 }
 
+/// Clamps the height of an inline viewport to the height of the terminal, such
+/// that it always fits on screen. Other viewports are returned unchanged.
+pub fn clamp_viewport(viewport: Viewport, terminal_height: u16) -> Viewport {
+    match viewport {
+        Viewport::Inline(height) => Viewport::Inline(height.clamp(1, terminal_height.max(1))),
+        viewport => viewport,
+    }
+}
+
 impl TryFrom<Viewport> for Terminal {
     type Error = anyhow::Error;
 
@@ -64,15 +196,27 @@ impl TryFrom<Viewport> for Terminal {
 
                 terminal.clear()?;
 
-                Ok(Terminal::Fullscreen(terminal))
+                let mut terminal = Terminal::Fullscreen(terminal);
+                terminal.write(ENABLE_BRACKETED_PASTE)?;
+                terminal.write(ENABLE_FOCUS_REPORTING)?;
+
+                Ok(terminal)
             }
             _ => {
+                let viewport = match termion::terminal_size() {
+                    Ok((_, rows)) => clamp_viewport(viewport, rows),
+                    Err(_) => viewport,
+                };
                 let stdout = io::stdout().into_raw_mode()?;
                 let options = TerminalOptions { viewport };
                 let terminal =
                     ratatui::Terminal::with_options(TermionBackendExt::new(stdout), options)?;
 
-                Ok(Terminal::Inline(terminal))
+                let mut terminal = Terminal::Inline(terminal);
+                terminal.write(ENABLE_BRACKETED_PASTE)?;
+                terminal.write(ENABLE_FOCUS_REPORTING)?;
+
+                Ok(terminal)
             }
         }
     }
@@ -98,6 +242,10 @@ where
             inner: TermionBackend::new(writer),
         }
     }
+
+    pub fn writer_mut(&mut self) -> &mut W {
+        self.inner.writer_mut()
+    }
 }
 
 impl<W: Write> ratatui::backend::Backend for TermionBackendExt<W> {
@@ -157,20 +305,55 @@ impl<W: Write> ratatui::backend::Backend for TermionBackendExt<W> {
     }
 }
 
+/// Creates a headless terminal of the given size. It renders into an in-memory
+/// buffer instead of `stdout` and can be used to test rendering.
+pub fn test_backend(width: u16, height: u16) -> io::Result<TestTerminal> {
+    ratatui::Terminal::new(ratatui::backend::TestBackend::new(width, height))
+}
+
 /// Spawn one thread that polls `stdin` for new user input and another thread
 /// that polls UNIX signals, e.g. `SIGWINCH` when the terminal window size is
 /// being changed.
+///
+/// `stdin` is read directly and only if input is available, such that no input
+/// is taken away from external programs while the terminal is suspended.
 pub fn events() -> mpsc::UnboundedReceiver<Event> {
     let (tx, rx) = mpsc::unbounded_channel();
     let events_tx = tx.clone();
     thread::spawn(move || {
         let start = Instant::now();
-        let stdin = io::stdin();
-        for key in stdin.keys().flatten() {
-            // TODO(erikli): Remove this hack! Perhaps use `tokio::CancellationToken`?
-            if start.elapsed().as_millis() > 200 && events_tx.send(Event::Key(key)).is_err() {
+        let mut buffer = [0_u8; 1024];
+        let mut parser = InputParser::default();
+        loop {
+            if is_suspended() {
+                thread::sleep(EVENTS_POLL_TIMEOUT);
+                continue;
+            }
+            if !stdin_ready(EVENTS_POLL_TIMEOUT) || is_suspended() {
+                continue;
+            }
+
+            // SAFETY: `buffer` is valid for writes of its length.
+            let read = unsafe {
+                libc::read(
+                    libc::STDIN_FILENO,
+                    buffer.as_mut_ptr() as *mut libc::c_void,
+                    buffer.len(),
+                )
+            };
+            if read < 0 && io::Error::last_os_error().kind() == io::ErrorKind::Interrupted {
+                continue;
+            }
+            if read <= 0 {
                 return;
             }
+
+            for event in parser.parse(&buffer[..read as usize]) {
+                // TODO(erikli): Remove this hack! Perhaps use `tokio::CancellationToken`?
+                if start.elapsed().as_millis() > 200 && events_tx.send(event).is_err() {
+                    return;
+                }
+            }
         }
     });
 
@@ -186,3 +369,317 @@ pub fn events() -> mpsc::UnboundedReceiver<Event> {
     }
     rx
 }
+
+/// Waits for input on `stdin` until the timeout given and returns true if
+/// input is available.
+fn stdin_ready(timeout: Duration) -> bool {
+    let mut fds = libc::pollfd {
+        fd: libc::STDIN_FILENO,
+        events: libc::POLLIN,
+        revents: 0,
+    };
+
+    // SAFETY: `fds` is a single, valid `pollfd`.
+    unsafe { libc::poll(&mut fds, 1, timeout.as_millis() as libc::c_int) > 0 }
+}
+
+/// Parses the bytes read from `stdin` into events. Text that is pasted while
+/// bracketed paste is enabled is parsed into a single `Event::Paste`, even if it
+/// was read in multiple chunks. Focus changes are only parsed outside of pastes.
+/// Escape sequences and chars that are split across reads are parsed once they
+/// were read completely.
+#[derive(Default)]
+struct InputParser {
+    /// The text pasted so far, if the end of a paste was not read yet.
+    paste: Option<Vec<u8>>,
+    /// The start of an escape sequence or char that was cut off at the end of
+    /// the last read.
+    pending: Vec<u8>,
+}
+
+impl InputParser {
+    fn parse(&mut self, bytes: &[u8]) -> Vec<Event> {
+        let mut events = vec![];
+        let mut input = std::mem::take(&mut self.pending);
+        input.extend_from_slice(bytes);
+
+        loop {
+            match self.paste.take() {
+                Some(mut paste) => {
+                    paste.append(&mut input);
+
+                    match find(&paste, PASTE_END) {
+                        Some(end) => {
+                            input = paste.split_off(end).split_off(PASTE_END.len());
+                            events.push(Event::Paste(String::from_utf8_lossy(&paste).into()));
+                        }
+                        None => {
+                            self.paste = Some(paste);
+                            return events;
+                        }
+                    }
+                }
+                None => match find(&input, PASTE_START) {
+                    Some(start) => {
+                        let rest = input.split_off(start).split_off(PASTE_START.len());
+                        events.extend(parse_events(&input));
+
+                        self.paste = Some(vec![]);
+                        input = rest;
+                    }
+                    None => {
+                        self.pending = input.split_off(complete_len(&input));
+                        events.extend(parse_events(&input));
+                        return events;
+                    }
+                },
+            }
+        }
+    }
+}
+
+/// Returns the length of `bytes` without an escape sequence or char at its end
+/// that was cut off. A single escape byte is complete, since it's read as `Esc`.
+fn complete_len(bytes: &[u8]) -> usize {
+    if let Some(start) = bytes.iter().rposition(|byte| *byte == b'\x1B') {
+        let sequence = &bytes[start + 1..];
+        let incomplete = match sequence.first() {
+            // Control sequences end with a byte in this range, e.g. `A` of `Up`.
+            Some(b'[') => !sequence[1..]
+                .iter()
+                .any(|byte| (0x40..=0x7E).contains(byte)),
+            Some(b'O') => sequence.len() < 2,
+            _ => false,
+        };
+        if incomplete {
+            return start;
+        }
+    }
+
+    let lead = bytes
+        .iter()
+        .rev()
+        .take(4)
+        .position(|byte| byte & 0xC0 != 0x80)
+        .map(|offset| bytes.len() - 1 - offset);
+    if let Some(lead) = lead {
+        let len = match bytes[lead] {
+            byte if byte >= 0xF0 => 4,
+            byte if byte >= 0xE0 => 3,
+            byte if byte >= 0xC0 => 2,
+            _ => 1,
+        };
+        if bytes.len() - lead < len {
+            return lead;
+        }
+    }
+
+    bytes.len()
+}
+
+/// Returns the position of the first occurrence of `needle` in `haystack`.
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+/// Parses all keys and focus changes contained in the bytes read from `stdin`.
+fn parse_events(bytes: &[u8]) -> Vec<Event> {
+    let mut events = vec![];
+    let mut rest = bytes;
+
+    loop {
+        let focus = [
+            (FOCUS_IN, Event::FocusGained),
+            (FOCUS_OUT, Event::FocusLost),
+        ]
+        .into_iter()
+        .filter_map(|(sequence, event)| {
+            find(rest, sequence).map(|start| (start, sequence.len(), event))
+        })
+        .min_by_key(|(start, _, _)| *start);
+
+        match focus {
+            Some((start, len, event)) => {
+                events.extend(parse_keys(&rest[..start]).into_iter().map(Event::Key));
+                events.push(event);
+                rest = &rest[start + len..];
+            }
+            None => {
+                events.extend(parse_keys(rest).into_iter().map(Event::Key));
+                return events;
+            }
+        }
+    }
+}
+
+/// Parses all keys contained in the bytes read from `stdin`. A single escape
+/// byte is parsed as `Esc`, since it can not be the start of a sequence.
+fn parse_keys(bytes: &[u8]) -> Vec<Key> {
+    let mut keys = vec![];
+    let mut iter = bytes.iter().map(|byte| Ok::<u8, io::Error>(*byte));
+
+    while let Some(Ok(byte)) = iter.next() {
+        if byte == b'\x1B' && iter.len() == 0 {
+            keys.push(Key::Esc);
+        } else if let Ok(termion::event::Event::Key(key)) =
+            termion::event::parse_event(byte, &mut iter)
+        {
+            keys.push(key);
+        }
+    }
+
+    keys
+}
+
+/// A program that is run in the foreground while the terminal is suspended.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum External {
+    /// Shows the given content in `$PAGER`.
+    Pager(String),
+}
+
+impl External {
+    /// Runs the program and waits for it to exit.
+    pub fn run(&self) -> io::Result<()> {
+        match self {
+            External::Pager(content) => {
+                let pager = env::var("PAGER")
+                    .ok()
+                    .filter(|pager| !pager.trim().is_empty())
+                    .unwrap_or_else(|| DEFAULT_PAGER.to_string());
+                let mut args = pager.split_whitespace();
+                let program = args.next().unwrap_or("less");
+
+                let mut child = Command::new(program)
+                    .args(args)
+                    .stdin(Stdio::piped())
+                    .spawn()?;
+
+                if let Some(mut stdin) = child.stdin.take() {
+                    // The pager might exit before reading all content.
+                    match stdin.write_all(content.as_bytes()) {
+                        Err(err) if err.kind() != io::ErrorKind::BrokenPipe => return Err(err),
+                        _ => {}
+                    }
+                }
+                child.wait()?;
+
+                Ok(())
+            }
+        }
+    }
+
+    /// Runs the program on a thread that can be blocked and waits for it to
+    /// exit, such that the runtime it's awaited on is not blocked meanwhile.
+    pub async fn run_blocking(self) -> io::Result<()> {
+        tokio::task::spawn_blocking(move || self.run())
+            .await
+            .map_err(io::Error::other)?
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use ratatui::Viewport;
+    use termion::event::Key;
+
+    use crate::event::Event;
+
+    use super::{clamp_viewport, parse_events, parse_keys, InputParser};
+
+    #[test]
+    fn keys_should_be_parsed_from_raw_input() {
+        assert_eq!(parse_keys(b"j"), vec![Key::Char('j')]);
+        assert_eq!(parse_keys(b"\x1B"), vec![Key::Esc]);
+        assert_eq!(parse_keys(b"\x1B[A"), vec![Key::Up]);
+        assert_eq!(
+            parse_keys(b"5j\x1B[B\x03"),
+            vec![Key::Char('5'), Key::Char('j'), Key::Down, Key::Ctrl('c')]
+        );
+    }
+
+    #[test]
+    fn function_keys_should_be_parsed_from_raw_input() {
+        assert_eq!(parse_keys(b"\x1BOP"), vec![Key::F(1)]);
+        assert_eq!(parse_keys(b"\x1B[11~"), vec![Key::F(1)]);
+        assert_eq!(parse_keys(b"\x1B[15~\x1B[24~"), vec![Key::F(5), Key::F(12)]);
+    }
+
+    #[test]
+    fn bracketed_paste_should_be_parsed_into_single_event() {
+        let mut parser = InputParser::default();
+
+        let events = parser.parse(b"j\x1B[200~foo ");
+        assert!(matches!(events[..], [Event::Key(Key::Char('j'))]));
+
+        let events = parser.parse(b"bar\x1B[201~k");
+        assert!(matches!(
+            &events[..],
+            [Event::Paste(text), Event::Key(Key::Char('k'))] if text == "foo bar"
+        ));
+    }
+
+    #[test]
+    fn sequences_split_across_reads_should_be_parsed_once_complete() {
+        let mut parser = InputParser::default();
+
+        assert!(matches!(
+            parser.parse(b"j\x1B[")[..],
+            [Event::Key(Key::Char('j'))]
+        ));
+        assert!(matches!(parser.parse(b"A")[..], [Event::Key(Key::Up)]));
+
+        assert!(parser.parse(b"\x1B[20").is_empty());
+        assert!(matches!(
+            &parser.parse(b"0~foo\x1B[201~")[..],
+            [Event::Paste(text)] if text == "foo"
+        ));
+
+        assert!(parser.parse(&[0xC3]).is_empty());
+        assert!(matches!(
+            parser.parse(&[0xA9])[..],
+            [Event::Key(Key::Char('é'))]
+        ));
+        assert!(matches!(parser.parse(b"\x1B")[..], [Event::Key(Key::Esc)]));
+    }
+
+    #[test]
+    fn focus_changes_should_be_parsed_between_keys() {
+        let events = parse_events(b"j\x1B[Ok\x1B[I\x1B[A");
+        assert!(matches!(
+            events[..],
+            [
+                Event::Key(Key::Char('j')),
+                Event::FocusLost,
+                Event::Key(Key::Char('k')),
+                Event::FocusGained,
+                Event::Key(Key::Up),
+            ]
+        ));
+        assert!(matches!(
+            parse_events(b"\x1BOP")[..],
+            [Event::Key(Key::F(1))]
+        ));
+    }
+
+    #[test]
+    fn inline_viewport_should_be_clamped_to_terminal_height() {
+        assert_eq!(
+            clamp_viewport(Viewport::Inline(20), 50),
+            Viewport::Inline(20)
+        );
+        assert_eq!(
+            clamp_viewport(Viewport::Inline(80), 50),
+            Viewport::Inline(50)
+        );
+        assert_eq!(clamp_viewport(Viewport::Inline(0), 50), Viewport::Inline(1));
+        assert_eq!(
+            clamp_viewport(Viewport::Fullscreen, 50),
+            Viewport::Fullscreen
+        );
+    }
+}
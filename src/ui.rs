@@ -1,14 +1,28 @@
+pub mod diff;
 pub mod ext;
+pub mod format;
 pub mod im;
+pub mod keyhint;
 pub mod layout;
+pub mod palette;
+pub mod print;
 pub mod rm;
+pub mod scrollbar;
 pub mod span;
 pub mod theme;
+pub mod toast;
 pub mod utils;
 
+use std::fmt;
+
+use fuzzy_matcher::skim::SkimMatcherV2;
+use fuzzy_matcher::FuzzyMatcher;
+
 use ratatui::layout::Constraint;
-use ratatui::text::Text;
+use ratatui::style::{Style, Stylize};
+use ratatui::text::{Line, Span, Text};
 use ratatui::widgets::Cell;
+use termion::event::Key;
 
 use tui_tree_widget::TreeItem;
 
@@ -17,6 +31,12 @@ pub const RENDER_WIDTH_SMALL: usize = 70;
 pub const RENDER_WIDTH_MEDIUM: usize = 150;
 pub const RENDER_WIDTH_LARGE: usize = usize::MAX;
 
+thread_local! {
+    /// The matcher searches are matched with. It's built once per thread, since
+    /// rows are highlighted on every render.
+    static MATCHER: SkimMatcherV2 = SkimMatcherV2::default();
+}
+
 #[derive(Clone, Debug, Default)]
 pub struct ColumnView {
     small: bool,
@@ -95,9 +115,391 @@ impl<'a> Column<'a> {
     }
 }
 
+/// How important a shortcut is. If a shortcut bar overflows, less important
+/// shortcuts are left out first.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Priority {
+    Low,
+    #[default]
+    Normal,
+    /// Shortcuts that should always be visible, e.g. to quit or show the help.
+    Essential,
+}
+
+/// A shortcut hint. It consists of the keys that trigger an action, any of
+/// which can be pressed, and a short label describing the action. Keys are
+/// rendered conventionally, e.g. `^C` for `Ctrl-c` or `M-x` for `Alt-x`, such
+/// that the hint always matches the keys a handler checks with `matches`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Shortcut {
+    pub keys: Vec<Key>,
+    pub label: String,
+    /// Shortcuts to quit or show the help are essential, all others are of
+    /// normal priority, unless set otherwise.
+    pub priority: Priority,
+    /// Hint rendered instead of the keys. Only set for shortcuts converted from
+    /// strings that do not describe keys, e.g. key sequences like `za`.
+    hint: Option<String>,
+}
+
+impl Shortcut {
+    pub fn new(keys: impl IntoIterator<Item = Key>, label: impl ToString) -> Self {
+        let keys = keys.into_iter().collect::<Vec<_>>();
+        let essential = keys.iter().any(|key| {
+            matches!(
+                key,
+                Key::Esc | Key::Ctrl('c') | Key::Char('q') | Key::Char('?') | Key::F(1)
+            )
+        });
+
+        Self {
+            keys,
+            label: label.to_string(),
+            priority: if essential {
+                Priority::Essential
+            } else {
+                Priority::Normal
+            },
+            hint: None,
+        }
+    }
+
+    pub fn with_priority(mut self, priority: Priority) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// The width of this shortcut if rendered, e.g. `q quit`.
+    fn width(&self) -> usize {
+        self.hint().chars().count() + 1 + self.label.chars().count()
+    }
+
+    /// Returns `true` if the given key triggers this shortcut.
+    pub fn matches(&self, key: Key) -> bool {
+        self.keys.contains(&key)
+    }
+
+    /// Returns the rendered keys of this shortcut, e.g. `enter` or `+/-`.
+    pub fn hint(&self) -> String {
+        match &self.hint {
+            Some(hint) => hint.clone(),
+            None => self.keys.iter().map(key_name).collect::<Vec<_>>().join("/"),
+        }
+    }
+}
+
+impl fmt::Display for Shortcut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.hint(), self.label)
+    }
+}
+
+impl From<(&str, &str)> for Shortcut {
+    fn from((hint, label): (&str, &str)) -> Self {
+        let keys = match parse_key(hint) {
+            Some(key) => Some(vec![key]),
+            None => hint.split('/').map(parse_key).collect::<Option<Vec<_>>>(),
+        };
+
+        match keys {
+            Some(keys) => Self::new(keys, label),
+            None => Self {
+                keys: vec![],
+                label: label.to_string(),
+                priority: Priority::default(),
+                hint: Some(hint.to_string()),
+            },
+        }
+    }
+}
+
+impl From<&(&str, &str)> for Shortcut {
+    fn from(shortcut: &(&str, &str)) -> Self {
+        Self::from(*shortcut)
+    }
+}
+
+/// Lays out the given shortcuts in lines of the given width, separated by the
+/// divider. If they don't fit into `height` lines, shortcuts are left out, least
+/// important and last listed first, and the last line ends with an ellipsis.
+pub fn shortcut_lines(
+    shortcuts: &[Shortcut],
+    divider: char,
+    width: usize,
+    height: usize,
+    keys_style: Style,
+    action_style: Style,
+) -> Vec<Line<'static>> {
+    let divider = Span::styled(format!(" {divider} "), theme::style::gray().dim());
+    let ellipsis = Span::styled("…", theme::style::gray().dim());
+
+    let mut kept = shortcuts.iter().collect::<Vec<_>>();
+    let lines = loop {
+        let lines = wrap_shortcuts(&kept, width);
+
+        // The ellipsis is appended to the last line, following a divider.
+        let ellipsis_width = match lines.last() {
+            _ if kept.len() == shortcuts.len() => 0,
+            Some(line) => line_width(line) + 4,
+            None => 1,
+        };
+        let fits = lines.len() <= height.max(1)
+            && lines.iter().all(|line| line_width(line) <= width)
+            && ellipsis_width <= width;
+
+        if fits || kept.is_empty() {
+            break lines;
+        }
+
+        let lowest = kept
+            .iter()
+            .map(|shortcut| shortcut.priority)
+            .min()
+            .unwrap_or_default();
+        if let Some(index) = kept
+            .iter()
+            .rposition(|shortcut| shortcut.priority == lowest)
+        {
+            kept.remove(index);
+        }
+    };
+    let overflow = kept.len() < shortcuts.len();
+
+    let mut lines = lines
+        .into_iter()
+        .map(|shortcuts| {
+            let mut spans = vec![];
+            for (index, shortcut) in shortcuts.into_iter().enumerate() {
+                if index > 0 {
+                    spans.push(divider.clone());
+                }
+                spans.push(Span::styled(shortcut.hint(), keys_style));
+                spans.push(Span::raw(" "));
+                spans.push(Span::styled(shortcut.label.clone(), action_style));
+            }
+            Line::from(spans)
+        })
+        .collect::<Vec<_>>();
+
+    if overflow {
+        match lines.last_mut() {
+            Some(line) if !line.spans.is_empty() => {
+                line.spans.extend([divider, ellipsis]);
+            }
+            _ => lines = vec![Line::from(ellipsis)],
+        }
+    }
+
+    lines
+}
+
+/// Wraps the given shortcuts greedily into lines of the given width. Shortcuts
+/// wider than a line get a line of their own.
+fn wrap_shortcuts<'a>(shortcuts: &[&'a Shortcut], width: usize) -> Vec<Vec<&'a Shortcut>> {
+    let mut lines: Vec<Vec<&Shortcut>> = vec![];
+
+    for shortcut in shortcuts {
+        match lines.last_mut() {
+            Some(line) if line_width(line) + 3 + shortcut.width() <= width => {
+                line.push(shortcut);
+            }
+            _ => lines.push(vec![shortcut]),
+        }
+    }
+
+    lines
+}
+
+fn line_width(shortcuts: &[&Shortcut]) -> usize {
+    let dividers = shortcuts.len().saturating_sub(1) * 3;
+    shortcuts
+        .iter()
+        .map(|shortcut| shortcut.width())
+        .sum::<usize>()
+        + dividers
+}
+
+/// Returns the conventional name of a key as used in shortcut hints.
+pub fn key_name(key: &Key) -> String {
+    match key {
+        Key::Char('\n') => "enter".to_string(),
+        Key::Char('\t') => "tab".to_string(),
+        Key::Char(' ') => "space".to_string(),
+        Key::Char(c) => c.to_string(),
+        Key::Ctrl(c) => format!("^{}", c.to_ascii_uppercase()),
+        Key::Alt(c) => format!("M-{c}"),
+        Key::Esc => "esc".to_string(),
+        Key::Backspace => "backspace".to_string(),
+        Key::Delete => "del".to_string(),
+        Key::Insert => "ins".to_string(),
+        Key::BackTab => "shift-tab".to_string(),
+        Key::Up => "↑".to_string(),
+        Key::Down => "↓".to_string(),
+        Key::Left => "←".to_string(),
+        Key::Right => "→".to_string(),
+        Key::AltLeft => "M-←".to_string(),
+        Key::AltRight => "M-→".to_string(),
+        Key::Home => "home".to_string(),
+        Key::End => "end".to_string(),
+        Key::PageUp => "pgup".to_string(),
+        Key::PageDown => "pgdn".to_string(),
+        Key::F(n) => format!("F{n}"),
+        other => format!("{other:?}").to_lowercase(),
+    }
+}
+
+/// Parses a key from its name as returned by `key_name`. Single characters
+/// are parsed as is and function keys are named `F1` to `F12`.
+pub fn parse_key(name: &str) -> Option<Key> {
+    let mut chars = name.chars();
+
+    match (chars.next(), chars.next(), chars.next(), chars.next()) {
+        (Some(c), None, _, _) => return Some(Key::Char(c)),
+        (Some('^'), Some(c), None, _) => return Some(Key::Ctrl(c.to_ascii_lowercase())),
+        (Some('M'), Some('-'), Some(c), None) => return Some(Key::Alt(c)),
+        _ => {}
+    }
+
+    match name {
+        "enter" => Some(Key::Char('\n')),
+        "tab" => Some(Key::Char('\t')),
+        "space" => Some(Key::Char(' ')),
+        "esc" => Some(Key::Esc),
+        "backspace" => Some(Key::Backspace),
+        "del" => Some(Key::Delete),
+        "ins" => Some(Key::Insert),
+        "shift-tab" => Some(Key::BackTab),
+        "home" => Some(Key::Home),
+        "end" => Some(Key::End),
+        "pgup" => Some(Key::PageUp),
+        "pgdn" => Some(Key::PageDown),
+        _ => name
+            .strip_prefix('F')
+            .and_then(|n| n.parse().ok())
+            .map(Key::F),
+    }
+}
+
 /// Needs to be implemented for items that are supposed to be rendered in tables.
 pub trait ToRow<const W: usize> {
     fn to_row(&self) -> [Cell; W];
+
+    /// Same as `to_row`, but highlights the parts of the row that match the
+    /// search given. Items that don't support highlighting return their plain row.
+    fn to_highlighted_row(&self, _highlight: &Highlight) -> [Cell; W] {
+        self.to_row()
+    }
+}
+
+/// Same as `ToRow`, but the number of cells is only known at runtime. This allows
+/// a single item type to render different sets of columns, e.g. depending on a
+/// view mode. The cells need to match the columns of the table they're shown in.
+pub trait ToRowDyn {
+    fn to_dyn_row(&self) -> Vec<Cell>;
+
+    /// Same as `to_dyn_row`, but highlights the parts of the row that match the
+    /// search given.
+    fn to_highlighted_dyn_row(&self, _highlight: &Highlight) -> Vec<Cell> {
+        self.to_dyn_row()
+    }
+}
+
+/// The arity of rows built by `ToRow<W>`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Cells<const W: usize>;
+
+/// The arity of rows built by `ToRowDyn`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DynCells;
+
+/// Implemented for all items that can be rendered in tables, either by `ToRow` or
+/// by `ToRowDyn`. The arity `A` only tells both apart and is usually inferred.
+pub trait TableRow<A> {
+    /// Returns the cells of this item, highlighted if a search is given.
+    fn cells(&self, highlight: Option<&Highlight>) -> Vec<Cell>;
+}
+
+impl<R, const W: usize> TableRow<Cells<W>> for R
+where
+    R: ToRow<W>,
+{
+    fn cells(&self, highlight: Option<&Highlight>) -> Vec<Cell> {
+        match highlight {
+            Some(highlight) => self.to_highlighted_row(highlight).to_vec(),
+            None => self.to_row().to_vec(),
+        }
+    }
+}
+
+impl<R> TableRow<DynCells> for R
+where
+    R: ToRowDyn,
+{
+    fn cells(&self, highlight: Option<&Highlight>) -> Vec<Cell> {
+        match highlight {
+            Some(highlight) => self.to_highlighted_dyn_row(highlight),
+            None => self.to_dyn_row(),
+        }
+    }
+}
+
+/// A free-text search that items are filtered by, and the style characters that
+/// match it are highlighted in when items are rendered.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Highlight {
+    search: String,
+    style: Style,
+}
+
+impl Highlight {
+    pub fn new(search: impl ToString, style: Style) -> Self {
+        Self {
+            search: search.to_string(),
+            style,
+        }
+    }
+
+    /// Returns the indices of the chars of `text` that match the search. Since
+    /// searches are fuzzy, matching chars are not necessarily contiguous.
+    pub fn indices(&self, text: &str) -> Vec<usize> {
+        if self.search.trim().is_empty() {
+            return vec![];
+        }
+
+        MATCHER.with(|matcher| {
+            matcher
+                .fuzzy_indices(text, self.search.trim())
+                .map(|(_, indices)| indices)
+                .unwrap_or_default()
+        })
+    }
+
+    /// Returns `text` in the given style, with all chars that match the search
+    /// patched with the highlight style.
+    pub fn line<'a>(&self, text: &str, style: Style) -> Line<'a> {
+        let indices = self.indices(text);
+        let highlighted = style.patch(self.style);
+
+        let mut spans: Vec<Span<'a>> = vec![];
+        let mut current = String::new();
+        let mut current_matches = false;
+
+        for (index, c) in text.chars().enumerate() {
+            let matches = indices.binary_search(&index).is_ok();
+            if matches != current_matches && !current.is_empty() {
+                let style = if current_matches { highlighted } else { style };
+                spans.push(Span::styled(std::mem::take(&mut current), style));
+            }
+            current_matches = matches;
+            current.push(c);
+        }
+        if !current.is_empty() {
+            let style = if current_matches { highlighted } else { style };
+            spans.push(Span::styled(current, style));
+        }
+
+        Line::from(spans)
+    }
 }
 
 /// Needs to be implemented for items that are supposed to be rendered in trees.
@@ -161,6 +563,115 @@ where
 mod test {
     use super::*;
 
+    #[test]
+    fn fuzzy_matches_should_be_highlighted_per_char() {
+        use ratatui::style::Stylize;
+
+        let highlight = Highlight::new("fbr", Style::default().bold());
+        let line = highlight.line("foo bar", Style::default());
+
+        let spans = line
+            .spans
+            .iter()
+            .map(|span| (span.content.to_string(), span.style.add_modifier.is_empty()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            spans,
+            [
+                ("f".to_string(), false),
+                ("oo ".to_string(), true),
+                ("b".to_string(), false),
+                ("a".to_string(), true),
+                ("r".to_string(), false),
+            ]
+        );
+        assert_eq!(
+            Highlight::new("", Style::default().bold())
+                .line("foo", Style::default().dim())
+                .spans,
+            [Span::styled("foo", Style::default().dim())]
+        );
+    }
+
+    #[test]
+    fn shortcut_should_render_chords_conventionally() {
+        let quit = Shortcut::new([Key::Esc, Key::Ctrl('c')], "quit");
+
+        assert_eq!(quit.to_string(), "esc/^C quit");
+        assert_eq!(Shortcut::new([Key::Alt('x')], "run").hint(), "M-x");
+        assert!(quit.matches(Key::Ctrl('c')));
+        assert!(!quit.matches(Key::Char('c')));
+    }
+
+    #[test]
+    fn shortcut_should_be_converted_from_strings() {
+        assert_eq!(
+            Shortcut::from(("enter", "select")),
+            Shortcut::new([Key::Char('\n')], "select")
+        );
+        assert_eq!(
+            Shortcut::from(("+/-", "resize")),
+            Shortcut::new([Key::Char('+'), Key::Char('-')], "resize")
+        );
+        assert_eq!(
+            Shortcut::from(("/", "search")),
+            Shortcut::new([Key::Char('/')], "search")
+        );
+        assert_eq!(Shortcut::from(("^c", "quit")).keys, [Key::Ctrl('c')]);
+        assert_eq!(
+            Shortcut::from(("?/F1", "help")),
+            Shortcut::new([Key::Char('?'), Key::F(1)], "help")
+        );
+
+        let fold = Shortcut::from(("za", "fold"));
+        assert!(fold.keys.is_empty());
+        assert_eq!(fold.to_string(), "za fold");
+    }
+
+    #[test]
+    fn overflowing_shortcuts_should_keep_essentials() {
+        let shortcuts = [
+            ("enter", "show"),
+            ("c", "checkout"),
+            ("d", "diff"),
+            ("/", "search"),
+            ("?", "help"),
+            ("esc", "quit"),
+        ]
+        .iter()
+        .map(Shortcut::from)
+        .collect::<Vec<_>>();
+        let text = |width, height| {
+            shortcut_lines(
+                &shortcuts,
+                '∙',
+                width,
+                height,
+                Style::default(),
+                Style::default(),
+            )
+            .iter()
+            .map(|line| line.to_string())
+            .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            text(80, 1),
+            ["enter show ∙ c checkout ∙ d diff ∙ / search ∙ ? help ∙ esc quit"]
+        );
+        assert_eq!(text(40, 1), ["enter show ∙ ? help ∙ esc quit ∙ …"]);
+        assert_eq!(text(30, 1), ["? help ∙ esc quit ∙ …"]);
+        assert_eq!(
+            text(40, 2),
+            [
+                "enter show ∙ c checkout ∙ d diff",
+                "/ search ∙ ? help ∙ esc quit"
+            ]
+        );
+        assert_eq!(text(3, 1), ["…"]);
+    }
+
     #[test]
     fn state_value_read_should_succeed() {
         let value = BufferedValue::new(0);
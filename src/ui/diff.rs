@@ -0,0 +1,913 @@
+use std::ops::Range;
+
+use ratatui::layout::{Constraint, Layout, Position, Rect};
+use ratatui::style::{Style, Stylize};
+use ratatui::text::{Line, Span, Text};
+use ratatui::widgets::Paragraph;
+use ratatui::Frame;
+
+use serde::{Deserialize, Serialize};
+
+use unicode_width::UnicodeWidthChar;
+
+use crate::ui::im::widget::{max_line_len, render_block, scroll_with_input, Widget};
+use crate::ui::im::{Borders, Response, Ui};
+use crate::ui::scrollbar::{self, AutoHide, ScrollPosition};
+
+/// The minimum width a diff view needs in order to render a split diff. If
+/// the area available is narrower, the unified diff is rendered instead.
+pub const SPLIT_MIN_WIDTH: u16 = 120;
+
+/// The number of unchanged lines shown around changes if a diff is collapsed.
+pub const DEFAULT_CONTEXT: usize = 3;
+
+/// The number of unchanged lines revealed around changes each time the context
+/// of a collapsed diff is expanded.
+pub const CONTEXT_STEP: usize = 10;
+
+/// The number of columns tabs in diffs are expanded to, if not configured otherwise.
+pub const DEFAULT_TAB_WIDTH: usize = 4;
+
+/// The layout a diff is rendered in.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub enum DiffLayout {
+    /// Old and new lines are rendered below each other.
+    #[default]
+    Unified,
+    /// Old and new lines are rendered side-by-side.
+    Split,
+}
+
+impl DiffLayout {
+    pub fn toggle(&self) -> Self {
+        match self {
+            DiffLayout::Unified => DiffLayout::Split,
+            DiffLayout::Split => DiffLayout::Unified,
+        }
+    }
+}
+
+/// How the line numbers in the gutter of a diff are shown.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub enum LineNumbering {
+    /// Each row shows the old and new number of its line.
+    #[default]
+    Absolute,
+    /// Each row shows its distance to the top row, e.g. to tell how far to
+    /// move. The top row keeps its absolute line numbers.
+    Relative,
+}
+
+impl LineNumbering {
+    pub fn toggle(&self) -> Self {
+        match self {
+            LineNumbering::Absolute => LineNumbering::Relative,
+            LineNumbering::Relative => LineNumbering::Absolute,
+        }
+    }
+}
+
+/// A single line of a unified diff.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DiffLine<'a> {
+    /// A hunk header, e.g. `@@ -1,3 +1,4 @@`.
+    Header(Line<'a>),
+    /// An unchanged line, possibly rendered differently on the old and new side.
+    Context { old: Line<'a>, new: Line<'a> },
+    /// A line that was removed.
+    Deletion(Line<'a>),
+    /// A line that was added.
+    Addition(Line<'a>),
+}
+
+impl<'a> DiffLine<'a> {
+    /// Parses a line of a plain unified diff by looking at its prefix.
+    pub fn parse(line: Line<'a>) -> Self {
+        let content = line.to_string();
+
+        if content.starts_with("@@") {
+            DiffLine::Header(line)
+        } else if content.starts_with('+') {
+            DiffLine::Addition(line)
+        } else if content.starts_with('-') {
+            DiffLine::Deletion(line)
+        } else {
+            DiffLine::Context {
+                old: line.clone(),
+                new: line,
+            }
+        }
+    }
+
+    /// Returns `true` if this line was added or removed.
+    pub fn is_change(&self) -> bool {
+        matches!(self, DiffLine::Addition(_) | DiffLine::Deletion(_))
+    }
+}
+
+/// The line numbers of a diff line on the old and new side. Headers have none,
+/// deletions only an old and additions only a new line number.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct LineNumbers {
+    pub old: Option<usize>,
+    pub new: Option<usize>,
+}
+
+/// Parses the first old and new line number from a hunk header, e.g.
+/// `@@ -1,3 +1,4 @@`.
+pub fn parse_header(header: &str) -> Option<(usize, usize)> {
+    let mut ranges = header.strip_prefix("@@ ")?.split_whitespace();
+
+    let start = |range: &str| range.split(',').next()?.parse::<usize>().ok();
+    let old = start(ranges.next()?.strip_prefix('-')?)?;
+    let new = start(ranges.next()?.strip_prefix('+')?)?;
+
+    Some((old, new))
+}
+
+/// Returns the line numbers of all lines given, counting from the start lines
+/// of the preceding hunk header. Lines not preceded by a header have no line
+/// numbers.
+pub fn line_numbers(lines: &[DiffLine<'_>]) -> Vec<LineNumbers> {
+    let mut old = None;
+    let mut new = None;
+    let next = |counter: &mut Option<usize>| {
+        let current = *counter;
+        *counter = counter.map(|n| n.saturating_add(1));
+        current
+    };
+
+    lines
+        .iter()
+        .map(|line| match line {
+            DiffLine::Header(header) => {
+                let start = parse_header(&header.to_string());
+                old = start.map(|(old, _)| old);
+                new = start.map(|(_, new)| new);
+                LineNumbers::default()
+            }
+            DiffLine::Context { .. } => LineNumbers {
+                old: next(&mut old),
+                new: next(&mut new),
+            },
+            DiffLine::Deletion(_) => LineNumbers {
+                old: next(&mut old),
+                new: None,
+            },
+            DiffLine::Addition(_) => LineNumbers {
+                old: None,
+                new: next(&mut new),
+            },
+        })
+        .collect()
+}
+
+/// A row of a collapsed diff: either a line shown, referenced by its index, or
+/// a range of unchanged lines that are hidden.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Fold {
+    Line(usize),
+    Hidden(Range<usize>),
+}
+
+/// Collapses the unchanged lines of a diff that are more than `context` lines
+/// away from a change or a pinned line, e.g. a line with comments. Headers are
+/// always shown; consecutive hidden lines are folded into a single row.
+pub fn fold(lines: &[DiffLine<'_>], context: usize, pinned: impl Fn(usize) -> bool) -> Vec<Fold> {
+    let mut shown = lines
+        .iter()
+        .map(|line| matches!(line, DiffLine::Header(_)))
+        .collect::<Vec<_>>();
+
+    for (index, line) in lines.iter().enumerate() {
+        if line.is_change() || pinned(index) {
+            let start = index.saturating_sub(context);
+            let end = index
+                .saturating_add(context)
+                .min(lines.len().saturating_sub(1));
+            shown[start..=end]
+                .iter_mut()
+                .for_each(|shown| *shown = true);
+        }
+    }
+
+    let mut folds: Vec<Fold> = vec![];
+    for (index, shown) in shown.into_iter().enumerate() {
+        if shown {
+            folds.push(Fold::Line(index));
+        } else if let Some(Fold::Hidden(range)) = folds.last_mut() {
+            range.end = index + 1;
+        } else {
+            folds.push(Fold::Hidden(index..index + 1));
+        }
+    }
+
+    folds
+}
+
+/// Returns the gutter of a diff whose rows have the line numbers given: the
+/// old and new number of each row, right-aligned in columns as wide as the
+/// largest number, such that the gutter doesn't change its width while
+/// scrolling. Rows without numbers, e.g. headers, are left blank. If numbered
+/// relatively, all rows but the `top` row show their distance to it instead.
+pub fn gutter<'a>(
+    numbers: &[LineNumbers],
+    numbering: LineNumbering,
+    top: usize,
+    style: Style,
+) -> Vec<Line<'a>> {
+    let width = numbers
+        .iter()
+        .flat_map(|numbers| [numbers.old, numbers.new])
+        .flatten()
+        .max()
+        .unwrap_or_default()
+        .to_string()
+        .len();
+    let number = |number: Option<usize>| {
+        number
+            .map(|number| format!("{number:>width$}"))
+            .unwrap_or_else(|| " ".repeat(width))
+    };
+
+    numbers
+        .iter()
+        .enumerate()
+        .map(|(row, numbers)| {
+            let text = match numbering {
+                LineNumbering::Relative if row != top => {
+                    format!("{:>1$} ", row.abs_diff(top), width * 2 + 1)
+                }
+                _ => format!("{} {} ", number(numbers.old), number(numbers.new)),
+            };
+            Line::styled(text, style)
+        })
+        .collect()
+}
+
+/// Replaces the tabs of the given line by spaces up to the next multiple of
+/// `tab_width` columns. Terminals render tabs differently, which would break the
+/// alignment of lines otherwise. Columns are counted after the marker a diff
+/// line starts with, e.g. `+`, such that lines are aligned like in their file.
+/// The tab width needs to be positive.
+pub fn expand_tabs(line: Line<'_>, tab_width: usize) -> Line<'_> {
+    if !line.spans.iter().any(|span| span.content.contains('\t')) {
+        return line;
+    }
+    let marker = line
+        .spans
+        .iter()
+        .flat_map(|span| span.content.chars())
+        .next()
+        .filter(|c| matches!(c, '+' | '-' | ' '))
+        .map_or(0, |_| 1);
+    let mut column = 0;
+
+    let spans = line
+        .spans
+        .into_iter()
+        .map(|span| {
+            if !span.content.contains('\t') {
+                column += span.width();
+                return span;
+            }
+
+            let mut content = String::new();
+            for c in span.content.chars() {
+                if c == '\t' {
+                    let spaces = tab_width - column.saturating_sub(marker) % tab_width;
+                    content.push_str(&" ".repeat(spaces));
+                    column += spaces;
+                } else {
+                    content.push(c);
+                    column += c.width().unwrap_or_default();
+                }
+            }
+            Span::styled(content, span.style)
+        })
+        .collect::<Vec<_>>();
+
+    Line { spans, ..line }
+}
+
+/// Splits the given line into rows that are at most `width` columns wide. Lines
+/// are wrapped at any character, since breaking code at words would make
+/// indentation and changed whitespace ambiguous.
+pub fn wrap<'a>(line: &Line<'a>, width: usize) -> Vec<Line<'a>> {
+    let width = width.max(1);
+    let mut rows = vec![Line::default().style(line.style)];
+    let mut column = 0;
+
+    for span in &line.spans {
+        let mut content = String::new();
+        for c in span.content.chars() {
+            let char_width = c.width().unwrap_or_default();
+            if column + char_width > width && column > 0 {
+                if let Some(row) = rows.last_mut() {
+                    row.push_span(Span::styled(std::mem::take(&mut content), span.style));
+                }
+                rows.push(Line::default().style(line.style));
+                column = 0;
+            }
+            content.push(c);
+            column += char_width;
+        }
+        if !content.is_empty() {
+            if let Some(row) = rows.last_mut() {
+                row.push_span(Span::styled(content, span.style));
+            }
+        }
+    }
+
+    rows
+}
+
+/// Wraps the lines from `top` on until `height` rows are filled, and returns the
+/// rows of each line.
+fn wrapped<'a>(lines: &[Line<'a>], top: usize, width: u16, height: u16) -> Vec<Vec<Line<'a>>> {
+    let mut filled = 0;
+
+    lines
+        .iter()
+        .skip(top)
+        .map_while(|line| {
+            if filled >= height as usize {
+                return None;
+            }
+            let rows = wrap(line, width.into());
+            filled += rows.len();
+            Some(rows)
+        })
+        .collect()
+}
+
+/// Returns the line that is shown in place of the hidden lines given, e.g.
+/// `⋯ 8 unchanged lines (12-19)`.
+pub fn hidden_line<'a>(numbers: &[LineNumbers], hidden: &Range<usize>) -> Line<'a> {
+    let first = numbers.get(hidden.start).and_then(|numbers| numbers.new);
+    let last = hidden
+        .end
+        .checked_sub(1)
+        .and_then(|last| numbers.get(last))
+        .and_then(|numbers| numbers.new);
+
+    let label = match (first, last) {
+        (Some(first), Some(last)) => {
+            format!("⋯ {} unchanged lines ({first}-{last})", hidden.len())
+        }
+        _ => format!("⋯ {} unchanged lines", hidden.len()),
+    };
+
+    Line::from(label).dark_gray().italic()
+}
+
+/// Returns the index of the diff line shown in `row` of a folded diff, where
+/// the line at `index` takes up `height(index)` rows, e.g. because comments are
+/// rendered below it. Those rows belong to the line, too. Rows of hidden lines
+/// and rows past the end do not show a diff line.
+pub fn line_at_row(folds: &[Fold], height: impl Fn(usize) -> usize, row: usize) -> Option<usize> {
+    let mut start = 0;
+
+    for fold in folds {
+        let (line, rows) = match fold {
+            Fold::Line(index) => (Some(*index), height(*index).max(1)),
+            Fold::Hidden(_) => (None, 1),
+        };
+        if row < start + rows {
+            return line;
+        }
+        start += rows;
+    }
+
+    None
+}
+
+/// Collapses the lines of a diff like `fold` does and replaces hidden lines by
+/// a header that shows how many were hidden.
+pub fn collapse<'a>(lines: Vec<DiffLine<'a>>, context: usize) -> Vec<DiffLine<'a>> {
+    let numbers = line_numbers(&lines);
+
+    fold(&lines, context, |_| false)
+        .into_iter()
+        .map(|fold| match fold {
+            Fold::Line(index) => lines[index].clone(),
+            Fold::Hidden(hidden) => DiffLine::Header(hidden_line(&numbers, &hidden)),
+        })
+        .collect()
+}
+
+/// The old and new side of a diff. Both sides always have the same amount of
+/// lines such that deletions and additions that replace each other are rendered
+/// on the same row. Missing lines on either side are filled with blank lines.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SplitDiff<'a> {
+    old: Vec<Line<'a>>,
+    new: Vec<Line<'a>>,
+}
+
+impl<'a> SplitDiff<'a> {
+    pub fn new(lines: impl IntoIterator<Item = DiffLine<'a>>) -> Self {
+        let mut split = Self::default();
+        let mut deletions = vec![];
+        let mut additions = vec![];
+
+        for line in lines {
+            match line {
+                DiffLine::Deletion(line) => deletions.push(line),
+                DiffLine::Addition(line) => additions.push(line),
+                DiffLine::Header(line) => {
+                    split.flush(&mut deletions, &mut additions);
+                    split.old.push(line.clone());
+                    split.new.push(line);
+                }
+                DiffLine::Context { old, new } => {
+                    split.flush(&mut deletions, &mut additions);
+                    split.old.push(old);
+                    split.new.push(new);
+                }
+            }
+        }
+        split.flush(&mut deletions, &mut additions);
+
+        split
+    }
+
+    pub fn old(&self) -> &[Line<'a>] {
+        &self.old
+    }
+
+    pub fn new_lines(&self) -> &[Line<'a>] {
+        &self.new
+    }
+
+    pub fn len(&self) -> usize {
+        self.old.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.old.is_empty()
+    }
+
+    fn flush(&mut self, deletions: &mut Vec<Line<'a>>, additions: &mut Vec<Line<'a>>) {
+        let rows = deletions.len().max(additions.len());
+
+        deletions.resize(rows, Line::default());
+        additions.resize(rows, Line::default());
+
+        self.old.append(deletions);
+        self.new.append(additions);
+    }
+}
+
+impl<'a> From<Text<'a>> for SplitDiff<'a> {
+    fn from(text: Text<'a>) -> Self {
+        Self::new(text.lines.into_iter().map(DiffLine::parse))
+    }
+}
+
+/// A scrollable diff view. It renders a split diff if one is given and the
+/// area is wide enough, and falls back to the unified diff otherwise. The
+/// unified diff is preceded by a gutter if line numbers are given. Tabs are
+/// expanded to spaces, and long lines are wrapped if enabled. Wrapped diffs
+/// are still scrolled by line, not by row.
+pub struct DiffView<'a> {
+    unified: Text<'a>,
+    split: Option<SplitDiff<'a>>,
+    cursor: &'a mut Position,
+    borders: Option<Borders>,
+    line_numbers: Option<(Vec<LineNumbers>, LineNumbering)>,
+    tab_width: usize,
+    wrap: bool,
+}
+
+impl<'a> DiffView<'a> {
+    pub fn new(
+        unified: impl Into<Text<'a>>,
+        split: Option<SplitDiff<'a>>,
+        cursor: &'a mut Position,
+        borders: Option<Borders>,
+    ) -> Self {
+        Self {
+            unified: unified.into(),
+            split,
+            cursor,
+            borders,
+            line_numbers: None,
+            tab_width: DEFAULT_TAB_WIDTH,
+            wrap: false,
+        }
+    }
+
+    /// Sets the number of columns tabs are expanded to.
+    pub fn tab_width(mut self, tab_width: usize) -> Self {
+        self.tab_width = tab_width;
+        self
+    }
+
+    /// Wraps lines that are wider than the view instead of scrolling them
+    /// horizontally.
+    pub fn wrap(mut self, wrap: bool) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
+    /// Sets the line numbers of each row of the unified diff, which are shown
+    /// in its gutter.
+    pub fn line_numbers(mut self, numbers: Vec<LineNumbers>, numbering: LineNumbering) -> Self {
+        self.line_numbers = Some((numbers, numbering));
+        self
+    }
+}
+
+impl<'a> Widget for DiffView<'a> {
+    fn ui<M>(self, ui: &mut Ui<M>, frame: &mut Frame) -> Response
+    where
+        M: Clone,
+    {
+        let mut response = Response::default();
+
+        let (area, area_focus) = ui.next_area().unwrap_or_default();
+        if area.is_empty() {
+            return response;
+        }
+
+        let border_style = if area_focus && ui.has_focus() {
+            ui.theme().focus_border_style
+        } else {
+            ui.theme().border_style
+        };
+
+        let area = render_block(frame, area, self.borders, border_style, ui.theme().charset);
+        let area = Rect {
+            x: area.x.saturating_add(1),
+            width: area.width.saturating_sub(1),
+            ..area
+        };
+        if area.is_empty() {
+            return response;
+        }
+        let [content_area, scroller_area] =
+            Layout::horizontal([Constraint::Min(1), Constraint::Length(1)]).areas(area);
+
+        let tab_width = self.tab_width;
+        let expand = |lines: &[Line<'a>]| {
+            lines
+                .iter()
+                .cloned()
+                .map(|line| expand_tabs(line, tab_width))
+                .collect::<Vec<_>>()
+        };
+        let split = self
+            .split
+            .filter(|_| content_area.width >= SPLIT_MIN_WIDTH)
+            .map(|split| (expand(split.old()), expand(split.new_lines())));
+        let unified = expand(&self.unified.lines);
+        let top = self.cursor.x as usize;
+        let scroll = (self.cursor.x, self.cursor.y);
+        // The size of the page a line is shown on, which is only a part of the
+        // content area if there's a gutter or a split.
+        let mut page = content_area.as_size();
+
+        let lines: Vec<Line<'_>> = match &split {
+            Some((old, new)) => {
+                let [old_area, divider_area, new_area] = Layout::horizontal([
+                    Constraint::Fill(1),
+                    Constraint::Length(1),
+                    Constraint::Fill(1),
+                ])
+                .areas(content_area);
+                page.width = old_area.width;
+
+                let divider = (0..divider_area.height)
+                    .map(|_| Line::from(ui.theme().charset.line_set().vertical).style(border_style))
+                    .collect::<Vec<_>>();
+
+                if self.wrap {
+                    // Both sides of a row take as many rows as the side that
+                    // needs most, such that they stay aligned.
+                    let mut old_rows = vec![];
+                    let mut new_rows = vec![];
+                    for (old, new) in wrapped(old, top, old_area.width, old_area.height)
+                        .into_iter()
+                        .zip(wrapped(new, top, new_area.width, new_area.height))
+                    {
+                        let rows = old_rows.len() + old.len().max(new.len());
+                        old_rows.extend(old);
+                        new_rows.extend(new);
+                        old_rows.resize(rows, Line::default());
+                        new_rows.resize(rows, Line::default());
+                    }
+                    frame.render_widget(Paragraph::new(old_rows), old_area);
+                    frame.render_widget(Paragraph::new(new_rows), new_area);
+                } else {
+                    frame.render_widget(Paragraph::new(old.clone()).scroll(scroll), old_area);
+                    frame.render_widget(Paragraph::new(new.clone()).scroll(scroll), new_area);
+                }
+                frame.render_widget(Paragraph::new(divider), divider_area);
+
+                [old.as_slice(), new.as_slice()].concat()
+            }
+            None => {
+                let gutter = match &self.line_numbers {
+                    Some((numbers, numbering)) if !numbers.is_empty() => {
+                        Some(gutter(numbers, *numbering, top, ui.theme().gutter_style))
+                    }
+                    _ => None,
+                };
+                let width = gutter
+                    .as_ref()
+                    .and_then(|gutter| gutter.first().map(Line::width))
+                    .unwrap_or_default();
+                let [gutter_area, content_area] =
+                    Layout::horizontal([Constraint::Length(width as u16), Constraint::Min(1)])
+                        .areas(content_area);
+                page.width = content_area.width;
+
+                if self.wrap {
+                    let rows = wrapped(&unified, top, content_area.width, content_area.height);
+                    if let Some(gutter) = gutter {
+                        // Rows that continue a wrapped line are not numbered.
+                        let blank = Line::from(" ".repeat(width));
+                        let gutter = gutter
+                            .into_iter()
+                            .skip(top)
+                            .zip(&rows)
+                            .flat_map(|(line, rows)| {
+                                std::iter::once(line)
+                                    .chain(std::iter::repeat(blank.clone()).take(rows.len() - 1))
+                            })
+                            .collect::<Vec<_>>();
+                        frame.render_widget(Paragraph::new(gutter), gutter_area);
+                    }
+                    frame.render_widget(Paragraph::new(rows.concat()), content_area);
+                } else {
+                    if let Some(gutter) = gutter {
+                        frame.render_widget(
+                            Paragraph::new(gutter).scroll((self.cursor.x, 0)),
+                            gutter_area,
+                        );
+                    }
+                    frame.render_widget(
+                        Paragraph::new(unified.clone()).scroll(scroll),
+                        content_area,
+                    );
+                }
+
+                unified
+            }
+        };
+        if !area_focus && ui.theme().dim_no_focus {
+            frame
+                .buffer_mut()
+                .set_style(content_area, Style::default().dim());
+        }
+
+        let len = split
+            .as_ref()
+            .map(|(old, _)| old.len())
+            .unwrap_or(self.unified.lines.len());
+
+        scrollbar::render(
+            frame,
+            scroller_area,
+            ui.theme(),
+            area_focus,
+            AutoHide::WhenFits,
+            ScrollPosition::new(len, content_area.height.into(), self.cursor.x as usize),
+        );
+
+        let cursor = *self.cursor;
+        response.key = scroll_with_input(ui, self.cursor, len, page, || max_line_len(&lines));
+        response.changed = *self.cursor != cursor;
+
+        response
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use ratatui::text::{Line, Span, Text};
+
+    use ratatui::style::Style;
+
+    use super::{
+        collapse, expand_tabs, fold, gutter, line_at_row, line_numbers, wrap, DiffLine, Fold,
+        LineNumbering, LineNumbers, SplitDiff,
+    };
+
+    fn lines(diff: &str) -> Vec<DiffLine<'_>> {
+        Text::from(diff)
+            .lines
+            .into_iter()
+            .map(DiffLine::parse)
+            .collect()
+    }
+
+    #[test]
+    fn line_numbers_should_start_at_header() {
+        let lines = lines("@@ -10,3 +12,3 @@\n a\n-b\n+c\n d");
+
+        assert_eq!(
+            line_numbers(&lines),
+            [
+                LineNumbers::default(),
+                LineNumbers {
+                    old: Some(10),
+                    new: Some(12)
+                },
+                LineNumbers {
+                    old: Some(11),
+                    new: None
+                },
+                LineNumbers {
+                    old: None,
+                    new: Some(13)
+                },
+                LineNumbers {
+                    old: Some(12),
+                    new: Some(14)
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn gutter_should_number_lines_absolutely_or_relatively() {
+        let lines = lines("@@ -8,3 +9,3 @@\n a\n-b\n+c\n d");
+        let numbers = line_numbers(&lines);
+        let text = |numbering| {
+            gutter(&numbers, numbering, 1, Style::default())
+                .iter()
+                .map(|line| line.to_string())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            text(LineNumbering::Absolute),
+            ["      ", " 8  9 ", " 9    ", "   10 ", "10 11 "]
+        );
+        assert_eq!(
+            text(LineNumbering::Relative),
+            ["    1 ", " 8  9 ", "    1 ", "    2 ", "    3 "]
+        );
+    }
+
+    #[test]
+    fn tabs_should_be_expanded_to_tab_width() {
+        let line = Line::from(vec![Span::raw("+"), Span::raw("\tfoo\tbar")]);
+
+        assert_eq!(expand_tabs(line.clone(), 4).to_string(), "+    foo bar");
+        assert_eq!(
+            expand_tabs(line.clone(), 8).to_string(),
+            "+        foo     bar"
+        );
+        assert_eq!(expand_tabs(line, 4).spans.len(), 2);
+        assert_eq!(expand_tabs(Line::from("\tfoo"), 4).to_string(), "    foo");
+    }
+
+    #[test]
+    fn long_lines_should_be_wrapped_at_width() {
+        let line = Line::from(vec![Span::raw("+"), Span::raw("foo bar baz")]);
+        let rows = |width| {
+            wrap(&line, width)
+                .iter()
+                .map(|row| row.to_string())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(rows(5), ["+foo ", "bar b", "az"]);
+        assert_eq!(rows(20), ["+foo bar baz"]);
+        assert_eq!(
+            wrap(&Line::default(), 5)
+                .iter()
+                .map(|row| row.to_string())
+                .collect::<Vec<_>>(),
+            [""]
+        );
+    }
+
+    #[test]
+    fn unchanged_lines_should_be_folded() {
+        let lines = lines("@@ -1,8 +1,8 @@\n a\n b\n c\n d\n-e\n+f\n g\n h\n i");
+
+        assert_eq!(
+            fold(&lines, 1, |_| false),
+            [
+                Fold::Line(0),
+                Fold::Hidden(1..4),
+                Fold::Line(4),
+                Fold::Line(5),
+                Fold::Line(6),
+                Fold::Line(7),
+                Fold::Hidden(8..10),
+            ]
+        );
+        assert_eq!(
+            fold(&lines, 1, |index| index == 1)[..3],
+            [Fold::Line(0), Fold::Line(1), Fold::Line(2)]
+        );
+        assert!(fold(&lines, 10, |_| false)
+            .iter()
+            .all(|fold| matches!(fold, Fold::Line(_))));
+    }
+
+    #[test]
+    fn rows_should_be_mapped_to_diff_lines() {
+        let folds = [
+            Fold::Line(0),
+            Fold::Hidden(1..4),
+            Fold::Line(4),
+            Fold::Line(5),
+        ];
+        // The line at index 4 has a comment of 3 rows below it.
+        let height = |index: usize| if index == 4 { 4 } else { 1 };
+
+        let lines = (0..8)
+            .map(|row| line_at_row(&folds, height, row))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            lines,
+            [
+                Some(0),
+                None,
+                Some(4),
+                Some(4),
+                Some(4),
+                Some(4),
+                Some(5),
+                None
+            ]
+        );
+    }
+
+    #[test]
+    fn hidden_lines_should_be_replaced_by_header() {
+        let lines = lines("@@ -1,5 +1,5 @@\n a\n b\n c\n-d\n+e");
+        let collapsed = collapse(lines, 1)
+            .into_iter()
+            .map(|line| match line {
+                DiffLine::Header(line) => line.to_string(),
+                _ => String::new(),
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(collapsed[1], "⋯ 2 unchanged lines (1-2)");
+        assert_eq!(collapsed.len(), 5);
+    }
+
+    #[test]
+    fn replaced_lines_should_be_paired() -> anyhow::Result<()> {
+        let diff = Text::from(
+            r#"@@ -1,4 +1,4 @@
+ fn main() {
+-    println!("Hello");
+-    println!("World");
++    println!("Hello, world!");
+ }
++"#,
+        );
+
+        let split = SplitDiff::from(diff);
+
+        let old = split
+            .old()
+            .iter()
+            .map(|line| line.to_string())
+            .collect::<Vec<_>>();
+        let new = split
+            .new_lines()
+            .iter()
+            .map(|line| line.to_string())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            old,
+            [
+                "@@ -1,4 +1,4 @@",
+                " fn main() {",
+                "-    println!(\"Hello\");",
+                "-    println!(\"World\");",
+                " }",
+                "",
+            ]
+        );
+        assert_eq!(
+            new,
+            [
+                "@@ -1,4 +1,4 @@",
+                " fn main() {",
+                "+    println!(\"Hello, world!\");",
+                "",
+                " }",
+                "+",
+            ]
+        );
+        assert_eq!(split.len(), 6);
+        assert_eq!(split.old().last(), Some(&Line::default()));
+
+        Ok(())
+    }
+}
@@ -1,9 +1,10 @@
 use ratatui::buffer::Buffer;
 use ratatui::layout::{Position, Rect};
 use ratatui::style::Style;
-use ratatui::symbols;
 use ratatui::widgets::{BorderType, Borders, Widget};
 
+use crate::ui::theme::Charset;
+
 pub struct HeaderBlock {
     /// Visible borders
     borders: Borders,
@@ -12,6 +13,8 @@ pub struct HeaderBlock {
     /// Type of the border. The default is plain lines but one can choose to have rounded corners
     /// or doubled lines instead.
     border_type: BorderType,
+    /// The characters borders are drawn with.
+    charset: Charset,
     /// Widget style
     style: Style,
 }
@@ -22,6 +25,7 @@ impl Default for HeaderBlock {
             borders: Borders::NONE,
             border_style: Default::default(),
             border_type: BorderType::Rounded,
+            charset: Charset::current(),
             style: Default::default(),
         }
     }
@@ -47,6 +51,11 @@ impl HeaderBlock {
         self.border_type = border_type;
         self
     }
+
+    pub fn charset(mut self, charset: Charset) -> HeaderBlock {
+        self.charset = charset;
+        self
+    }
 }
 
 impl Widget for HeaderBlock {
@@ -55,7 +64,8 @@ impl Widget for HeaderBlock {
             return;
         }
         buf.set_style(area, self.style);
-        let symbols = BorderType::to_border_set(self.border_type);
+        let symbols = self.charset.border_set(self.border_type);
+        let line = self.charset.line_set();
 
         // Sides
         if self.borders.intersects(Borders::LEFT) {
@@ -96,7 +106,7 @@ impl Widget for HeaderBlock {
         // Corners
         if self.borders.contains(Borders::RIGHT | Borders::BOTTOM) {
             if let Some(cell) = buf.cell_mut(Position::new(area.right() - 1, area.bottom() - 1)) {
-                cell.set_symbol(symbols::line::VERTICAL_LEFT)
+                cell.set_symbol(line.vertical_left)
                     .set_style(self.border_style);
             }
         }
@@ -108,7 +118,7 @@ impl Widget for HeaderBlock {
         }
         if self.borders.contains(Borders::LEFT | Borders::BOTTOM) {
             if let Some(cell) = buf.cell_mut(Position::new(area.left(), area.bottom() - 1)) {
-                cell.set_symbol(symbols::line::VERTICAL_RIGHT)
+                cell.set_symbol(line.vertical_right)
                     .set_style(self.border_style);
             }
         }
@@ -137,6 +147,8 @@ pub struct FooterBlock {
     /// Type of the border. The default is plain lines but one can choose to have rounded corners
     /// or doubled lines instead.
     border_type: BorderType,
+    /// The characters borders are drawn with.
+    charset: Charset,
     /// Type of the footer block. The default is single.
     block_type: FooterBlockType,
     /// Widget style
@@ -150,6 +162,7 @@ impl Default for FooterBlock {
             borders: Self::borders(FooterBlockType::Single { top: true }),
             border_style: Default::default(),
             border_type: BorderType::Rounded,
+            charset: Charset::current(),
             style: Default::default(),
         }
     }
@@ -177,6 +190,11 @@ impl FooterBlock {
         self
     }
 
+    pub fn charset(mut self, charset: Charset) -> Self {
+        self.charset = charset;
+        self
+    }
+
     fn borders(block_type: FooterBlockType) -> Borders {
         match block_type {
             FooterBlockType::Single { top } => {
@@ -200,7 +218,8 @@ impl Widget for FooterBlock {
             return;
         }
         buf.set_style(area, self.style);
-        let symbols = BorderType::to_border_set(self.border_type);
+        let symbols = self.charset.border_set(self.border_type);
+        let line = self.charset.line_set();
 
         // Sides
         if self.borders.intersects(Borders::LEFT) {
@@ -241,7 +260,7 @@ impl Widget for FooterBlock {
         // Corners
         if self.borders.contains(Borders::RIGHT | Borders::BOTTOM) {
             let symbol = match self.block_type {
-                FooterBlockType::Begin | FooterBlockType::Repeat => symbols::line::HORIZONTAL_UP,
+                FooterBlockType::Begin | FooterBlockType::Repeat => line.horizontal_up,
                 _ => symbols.bottom_right,
             };
             if let Some(cell) = buf.cell_mut(Position::new(area.right() - 1, area.bottom() - 1)) {
@@ -250,8 +269,8 @@ impl Widget for FooterBlock {
         }
         if self.borders.contains(Borders::RIGHT | Borders::TOP) {
             let symbol = match self.block_type {
-                FooterBlockType::Begin | FooterBlockType::Repeat => symbols::line::HORIZONTAL_DOWN,
-                _ => symbols::line::VERTICAL_LEFT,
+                FooterBlockType::Begin | FooterBlockType::Repeat => line.horizontal_down,
+                _ => line.vertical_left,
             };
             if let Some(cell) = buf.cell_mut(Position::new(area.right() - 1, area.top())) {
                 cell.set_symbol(symbol).set_style(self.border_style);
@@ -265,7 +284,7 @@ impl Widget for FooterBlock {
         }
         if self.borders.contains(Borders::LEFT | Borders::TOP) {
             if let Some(cell) = buf.cell_mut(Position::new(area.left(), area.top())) {
-                cell.set_symbol(symbols::line::VERTICAL_RIGHT)
+                cell.set_symbol(line.vertical_right)
                     .set_style(self.border_style);
             }
         }
@@ -0,0 +1,200 @@
+use ratatui::style::{Modifier, Style, Stylize};
+use ratatui::text::{Line, Span, Text};
+
+use crate::ui::theme::{style, Charset};
+
+/// Markers of inline emphasis and the modifier they apply. Longer markers are
+/// matched first, such that `**` isn't read as two `*`.
+const EMPHASIS: [(&str, Modifier); 4] = [
+    ("**", Modifier::BOLD),
+    ("__", Modifier::BOLD),
+    ("*", Modifier::ITALIC),
+    ("_", Modifier::ITALIC),
+];
+
+/// Renders markdown as styled text. Only a lightweight subset is supported:
+/// headings, bold and italic text, code spans, bullet lists and fenced code
+/// blocks, whose whitespace is preserved. Every line of the markdown is rendered
+/// into exactly one line, such that positions in the rendered text are the same
+/// as in the raw one, e.g. to switch between both while scrolled.
+pub fn markdown(text: &str) -> Text<'static> {
+    let mut in_code_block = false;
+
+    text.lines()
+        .map(|line| {
+            let trimmed = line.trim_start();
+
+            if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+                in_code_block = !in_code_block;
+                Line::from(Span::styled(line.to_string(), style::darkgray()))
+            } else if in_code_block {
+                Line::from(Span::styled(line.to_string(), style::yellow()))
+            } else if let Some((level, title)) = heading(trimmed) {
+                let style = if level == 1 {
+                    Style::default().bold().underlined()
+                } else {
+                    Style::default().bold()
+                };
+                Line::from(inline(title, style))
+            } else if let Some((indent, item)) = bullet(line) {
+                let bullet = Charset::current().list_bullet();
+                let mut spans = vec![Span::raw(format!("{indent}{bullet} "))];
+                spans.extend(inline(item, Style::default()));
+                Line::from(spans)
+            } else {
+                Line::from(inline(line, Style::default()))
+            }
+        })
+        .collect::<Vec<_>>()
+        .into()
+}
+
+/// Returns the level and title of an ATX heading, e.g. `## Usage`.
+fn heading(line: &str) -> Option<(usize, &str)> {
+    let level = line.chars().take_while(|c| *c == '#').count();
+    let title = &line[level..];
+
+    ((1..=6).contains(&level) && (title.is_empty() || title.starts_with(' ')))
+        .then(|| (level, title.trim()))
+}
+
+/// Returns the indentation and content of a list item starting with `-`, `*`
+/// or `+`.
+fn bullet(line: &str) -> Option<(&str, &str)> {
+    let content = line.trim_start();
+    let indent = &line[..line.len() - content.len()];
+
+    ["- ", "* ", "+ "]
+        .iter()
+        .find_map(|marker| content.strip_prefix(marker))
+        .map(|item| (indent, item))
+}
+
+/// Renders the inline elements of a line in the given style: code spans and
+/// emphasis, which can be nested. Markers without a closing one are rendered
+/// as they are. Underscores only start emphasis at the beginning of a word,
+/// such that names like `snake_case` stay intact.
+fn inline(text: &str, base: Style) -> Vec<Span<'static>> {
+    let mut spans = vec![];
+    let mut plain = String::new();
+    let mut rest = text;
+    let mut prev: Option<char> = None;
+
+    'outer: while let Some(c) = rest.chars().next() {
+        if c == '`' {
+            if let Some(end) = rest[1..].find('`').filter(|end| *end > 0) {
+                flush(&mut spans, &mut plain, base);
+                spans.push(Span::styled(
+                    rest[1..=end].to_string(),
+                    base.patch(style::yellow()),
+                ));
+                rest = &rest[end + 2..];
+                prev = Some('`');
+                continue;
+            }
+        }
+
+        let word_start = !prev.is_some_and(|prev| prev.is_alphanumeric());
+        for (marker, modifier) in EMPHASIS {
+            if !rest.starts_with(marker) || (marker.starts_with('_') && !word_start) {
+                continue;
+            }
+            let inner = &rest[marker.len()..];
+            if let Some(end) = inner.find(marker).filter(|end| *end > 0) {
+                flush(&mut spans, &mut plain, base);
+                spans.extend(inline(&inner[..end], base.add_modifier(modifier)));
+                rest = &inner[end + marker.len()..];
+                prev = marker.chars().last();
+                continue 'outer;
+            }
+        }
+
+        plain.push(c);
+        rest = &rest[c.len_utf8()..];
+        prev = Some(c);
+    }
+    flush(&mut spans, &mut plain, base);
+
+    spans
+}
+
+fn flush(spans: &mut Vec<Span<'static>>, plain: &mut String, style: Style) {
+    if !plain.is_empty() {
+        spans.push(Span::styled(std::mem::take(plain), style));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use ratatui::style::{Style, Stylize};
+    use ratatui::text::{Line, Span};
+
+    use crate::ui::theme::style;
+
+    use super::markdown;
+
+    #[test]
+    fn markdown_should_style_inline_elements() {
+        let text = markdown("Run `rad sync` **now**, *not* later_on or _later_");
+
+        assert_eq!(
+            text.lines,
+            [Line::from(vec![
+                Span::raw("Run "),
+                Span::styled("rad sync", style::yellow()),
+                Span::raw(", "),
+                Span::styled("now", Style::default().bold()),
+                Span::raw(", "),
+                Span::styled("not", Style::default().italic()),
+                Span::raw(" later_on or "),
+                Span::styled("later", Style::default().italic()),
+            ])]
+        );
+        assert_eq!(
+            markdown("**bold `code`** and *unclosed").lines,
+            [Line::from(vec![
+                Span::styled("bold ", Style::default().bold()),
+                Span::styled("code", style::yellow().bold()),
+                Span::raw(" and *unclosed"),
+            ])]
+        );
+    }
+
+    #[test]
+    fn markdown_should_render_headings_and_lists() {
+        let text = markdown("# Title\n### Steps\n- one\n  * **two**\n#hashtag");
+
+        assert_eq!(
+            text.lines,
+            [
+                Line::from(Span::styled("Title", Style::default().bold().underlined())),
+                Line::from(Span::styled("Steps", Style::default().bold())),
+                Line::from(vec![Span::raw("• "), Span::raw("one")]),
+                Line::from(vec![
+                    Span::raw("  • "),
+                    Span::styled("two", Style::default().bold())
+                ]),
+                Line::from(Span::raw("#hashtag")),
+            ]
+        );
+    }
+
+    #[test]
+    fn code_blocks_should_be_kept_as_they_are() {
+        let raw = "```rust\nfn main() {\n    let _x = **y;\n}\n```\n- after";
+        let text = markdown(raw);
+
+        assert_eq!(text.lines.len(), raw.lines().count());
+        assert_eq!(
+            text.lines[1..4],
+            [
+                Line::from(Span::styled("fn main() {", style::yellow())),
+                Line::from(Span::styled("    let _x = **y;", style::yellow())),
+                Line::from(Span::styled("}", style::yellow())),
+            ]
+        );
+        assert_eq!(text.lines[5].to_string(), "• after");
+    }
+}
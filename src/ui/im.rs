@@ -1,35 +1,55 @@
 pub mod widget;
 
-use std::collections::VecDeque;
-use std::fmt::Debug;
+#[cfg(test)]
+pub(crate) mod testing;
+
+use std::collections::{HashMap, VecDeque};
+use std::env;
+use std::fmt::{Debug, Display};
+use std::panic::Location;
+use std::path::Path;
 use std::rc::Rc;
-use std::time::Duration;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use anyhow::Result;
 
-use ratatui::style::Stylize;
-use ratatui::text::{Span, Text};
+use ratatui::style::{Style, Stylize};
+use ratatui::text::{Line, Span, Text};
+use ratatui::widgets::Clear;
 use tokio::sync::broadcast;
-use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
 
 use termion::event::Key;
 
-use ratatui::layout::{Constraint, Position, Rect};
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Constraint, Margin, Position, Rect};
 use ratatui::{Frame, Viewport};
 
 use crate::event::Event;
 use crate::store::Update;
 use crate::task::Interrupted;
 use crate::terminal;
-use crate::terminal::Terminal;
-use crate::ui::theme::Theme;
-use crate::ui::{Column, ToRow};
+use crate::terminal::{External, Terminal};
+use crate::ui::diff::{DiffView, SplitDiff};
+use crate::ui::keyhint::{KeyHint, PendingPrefix, PrefixMap};
+use crate::ui::theme::{style, Charset, Theme};
+use crate::ui::toast::Toasts;
+use crate::ui::{key_name, Column, Shortcut, TableRow};
+use crate::Exit;
 
-use crate::ui::im::widget::{HeaderedTable, Widget};
+use crate::ui::im::widget::{HeaderedTable, Widget, Window};
 
 use self::widget::AddContentFn;
 
+pub use crate::ui::toast::ToastLevel;
+
 const RENDERING_TICK_RATE: Duration = Duration::from_millis(250);
+const REPEAT_COUNT_TIMEOUT: Duration = Duration::from_secs(2);
+const REPEAT_COUNT_MAX: usize = 9999;
+/// If set to a non-empty value, the callers that consumed each key are shown in
+/// the last line and logged, to debug why a key did or didn't trigger an action.
+const KEY_AUDIT_ENV: &str = "RAD_TUI_KEY_AUDIT";
 
 /// The main UI trait for the ability to render an application.
 pub trait Show<M> {
@@ -45,6 +65,8 @@ impl Frontend {
         state_tx: UnboundedSender<M>,
         mut state_rx: UnboundedReceiver<S>,
         mut interrupt_rx: broadcast::Receiver<Interrupted<P>>,
+        mut external_rx: UnboundedReceiver<External>,
+        mut toast_rx: UnboundedReceiver<(ToastLevel, String)>,
         viewport: Viewport,
     ) -> anyhow::Result<Interrupted<P>>
     where
@@ -53,28 +75,68 @@ impl Frontend {
         P: Clone + Send + Sync + Debug,
     {
         let mut ticker = tokio::time::interval(RENDERING_TICK_RATE);
+        // Ticks are paused while the terminal window has no focus.
+        let mut focused = true;
 
         let mut terminal = Terminal::try_from(viewport)?;
         let mut events_rx = terminal::events();
+        let mut notifications_rx = terminal::notifications();
 
         let mut state = state_rx.recv().await.unwrap();
-        let mut ctx = Context::default().with_sender(state_tx);
+        Charset::init(Charset::detect());
+        let mut ctx = Context::default()
+            .with_sender(state_tx)
+            .with_theme(Theme::default().with_charset(Charset::current()));
+        if env::var(KEY_AUDIT_ENV).is_ok_and(|value| !value.is_empty()) {
+            ctx = ctx.with_key_audit();
+        }
 
         let result: anyhow::Result<Interrupted<P>> = loop {
-            tokio::select! {
+            // Frames only change on ticks if they're animated, so other ticks
+            // don't redraw.
+            let dirty = tokio::select! {
                 // Tick to terminate the select every N milliseconds
-                _ = ticker.tick() => (),
+                _ = ticker.tick(), if focused => ctx.is_animated(),
                 // Handle input events
                 Some(event) = events_rx.recv() => {
                     log::info!("Received event: {:?}", event);
                     match event {
-                        Event::Key(key) => ctx.store_input(key),
-                        Event::Resize => (),
+                        Event::FocusGained => {
+                            focused = true;
+                            ticker.reset();
+                        }
+                        Event::FocusLost => focused = false,
+                        _ => (),
                     }
+                    ctx.store_event(event);
+                    true
                 },
                 // Handle state updates
                 Some(s) = state_rx.recv() => {
                     state = s;
+                    true
+                },
+                // Run external programs while the terminal is suspended
+                Some(external) = external_rx.recv() => {
+                    terminal.suspend()?;
+                    if let Err(err) = external.run_blocking().await {
+                        log::warn!("Running external program failed: {}", err);
+                    }
+                    terminal.resume()?;
+                    ctx.clear_inputs();
+                    true
+                },
+                // Show toasts requested by the state
+                Some((level, message)) = toast_rx.recv() => {
+                    ctx.push_toast(message, level);
+                    true
+                },
+                // Write desktop notifications between two frames
+                Some(notification) = notifications_rx.recv() => {
+                    if let Err(err) = terminal.notify(&notification) {
+                        log::warn!("Failed to post desktop notification: {}", err);
+                    }
+                    false
                 },
                 // Catch and handle interrupt signal to gracefully shutdown
                 Ok(interrupted) = interrupt_rx.recv() => {
@@ -83,14 +145,15 @@ impl Frontend {
 
                     break Ok(interrupted);
                 }
+            };
+            ctx.expire_toasts(Instant::now());
+
+            if !dirty {
+                log::trace!("Skipping draw on idle tick");
+                continue;
             }
-            terminal.draw(|frame| {
-                let ctx = ctx.clone().with_frame_size(frame.area());
 
-                if let Err(err) = state.show(&ctx, frame) {
-                    log::warn!("Drawing failed: {}", err);
-                }
-            })?;
+            terminal.draw(|frame| draw(&state, &ctx, frame, focused))?;
 
             ctx.clear_inputs();
         };
@@ -100,9 +163,164 @@ impl Frontend {
     }
 }
 
+/// Draws a frame of the application: its state, followed by the overlays of the
+/// context, e.g. toasts. Frames are dimmed while the terminal has no focus.
+fn draw<S, M>(state: &S, ctx: &Context<M>, frame: &mut Frame, focused: bool)
+where
+    S: Show<M>,
+    M: Clone,
+{
+    let ctx = ctx.clone().with_frame_size(frame.area());
+
+    if let Err(err) = state.show(&ctx, frame) {
+        log::warn!("Drawing failed: {}", err);
+    }
+    ctx.finish_frame();
+
+    render_key_hint(&ctx, frame);
+    render_toasts(&ctx, frame);
+    render_key_audit(&ctx, frame);
+
+    if !focused {
+        let area = frame.area();
+        frame.buffer_mut().set_style(area, Style::default().dim());
+    }
+}
+
+/// Renders a single frame of the given size into a headless terminal and returns
+/// the rendered buffer. `add_contents` is run on a root `Ui` that has focus, the
+/// same way an application is shown by the `Frontend`. Meant to be used in tests.
+pub fn render_once<M>(
+    width: u16,
+    height: u16,
+    ctx: Context<M>,
+    add_contents: impl FnOnce(&mut Ui<M>, &mut Frame),
+) -> Result<Buffer>
+where
+    M: Clone,
+{
+    let mut terminal = terminal::test_backend(width, height)?;
+    let frame = terminal.draw(|frame| {
+        let ctx = ctx.with_frame_size(frame.area());
+
+        Window::default().show(&ctx, |ui| add_contents(ui, frame));
+        ctx.finish_frame();
+    })?;
+
+    Ok(frame.buffer.clone())
+}
+
+/// Drives an application the way the `Frontend` does, but renders into a
+/// headless terminal, such that key sequences can be tested end-to-end. Events
+/// are queued and each frame run handles the next one. Messages sent by the
+/// application are captured and passed to its `update`, until it exits. Meant to
+/// be used in tests.
+pub struct TestDriver<S, M, P> {
+    state: S,
+    ctx: Context<M>,
+    terminal: terminal::TestTerminal,
+    events_tx: UnboundedSender<Event>,
+    events_rx: UnboundedReceiver<Event>,
+    messages_rx: UnboundedReceiver<M>,
+    messages: Vec<M>,
+    exit: Option<Exit<P>>,
+}
+
+impl<S, M, P> TestDriver<S, M, P>
+where
+    S: Update<M, Return = P> + Show<M>,
+    M: Clone,
+{
+    pub fn new(state: S, width: u16, height: u16) -> Result<Self> {
+        let (events_tx, events_rx) = mpsc::unbounded_channel();
+        let (messages_tx, messages_rx) = mpsc::unbounded_channel();
+
+        Ok(Self {
+            state,
+            ctx: Context::default().with_sender(messages_tx),
+            terminal: terminal::test_backend(width, height)?,
+            events_tx,
+            events_rx,
+            messages_rx,
+            messages: vec![],
+            exit: None,
+        })
+    }
+
+    /// Queues the given event, e.g. a key press.
+    pub fn event(&mut self, event: Event) -> &mut Self {
+        // The receiver is owned by the driver and never closed.
+        let _ = self.events_tx.send(event);
+        self
+    }
+
+    /// Queues a key press for each of the given keys.
+    pub fn keys(&mut self, keys: impl IntoIterator<Item = Key>) -> &mut Self {
+        for key in keys {
+            self.event(Event::Key(key));
+        }
+        self
+    }
+
+    /// Runs the given number of frames, or less if the application exits. Each
+    /// frame handles at most one queued event.
+    pub fn run(&mut self, frames: usize) -> Result<&mut Self> {
+        for _ in 0..frames {
+            if self.exit.is_some() {
+                break;
+            }
+            if let Ok(event) = self.events_rx.try_recv() {
+                self.ctx.store_event(event);
+            }
+
+            self.terminal
+                .draw(|frame| draw(&self.state, &self.ctx, frame, true))?;
+            self.ctx.clear_inputs();
+
+            while let Ok(message) = self.messages_rx.try_recv() {
+                self.messages.push(message.clone());
+
+                if let Some(exit) = self.state.update_batch(vec![message]) {
+                    self.exit = Some(exit);
+                    break;
+                }
+            }
+            for (level, message) in self.state.take_toasts() {
+                self.ctx.push_toast(message, level);
+            }
+        }
+
+        Ok(self)
+    }
+
+    pub fn state(&self) -> &S {
+        &self.state
+    }
+
+    /// Returns all messages the application sent, in order.
+    pub fn messages(&self) -> &[M] {
+        &self.messages
+    }
+
+    /// Returns how the application exited, if it did.
+    pub fn exit(&self) -> Option<&Exit<P>> {
+        self.exit.as_ref()
+    }
+
+    /// Returns the last frame drawn.
+    pub fn buffer(&self) -> &Buffer {
+        self.terminal.backend().buffer()
+    }
+}
+
 #[derive(Default, Debug)]
 pub struct Response {
+    /// If the state of the widget changed, e.g. its selection moved.
     pub changed: bool,
+    /// The key that changed the widget, if any. It lets callers react to the
+    /// navigation that happened, e.g. a page scrolled, without checking the
+    /// inputs again, which the widget consumed already.
+    pub key: Option<Key>,
 }
 
 #[derive(Debug)]
@@ -120,24 +338,125 @@ impl<R> InnerResponse<R> {
     }
 }
 
+/// Records which callers consumed the inputs of a frame. It's purely diagnostic
+/// and only built if inputs are audited.
+#[derive(Debug, Default)]
+struct KeyAudit {
+    /// The keys consumed since the last summary and the callers of the `Ui`
+    /// input methods that consumed them.
+    consumed: Vec<(Key, &'static Location<'static>)>,
+    /// The summary of the last frame that had inputs.
+    summary: String,
+}
+
+impl KeyAudit {
+    fn record(&mut self, key: Key, location: &'static Location<'static>) {
+        if !self.consumed.contains(&(key, location)) {
+            self.consumed.push((key, location));
+        }
+    }
+
+    /// Summarizes by whom the given inputs were consumed, and starts a new record.
+    /// The summary is kept as long as frames don't have any inputs.
+    fn summarize(&mut self, inputs: &VecDeque<Key>) -> &str {
+        if !inputs.is_empty() {
+            self.summary = inputs
+                .iter()
+                .map(|key| {
+                    let callers = self
+                        .consumed
+                        .iter()
+                        .filter(|(consumed, _)| consumed == key)
+                        .map(|(_, location)| {
+                            let file = Path::new(location.file())
+                                .file_name()
+                                .map(|name| name.to_string_lossy())
+                                .unwrap_or_default();
+                            format!("{file}:{}", location.line())
+                        })
+                        .collect::<Vec<_>>();
+                    let callers = if callers.is_empty() {
+                        "unused".to_string()
+                    } else {
+                        callers.join(", ")
+                    };
+
+                    format!("{} → {callers}", key_name(key))
+                })
+                .collect::<Vec<_>>()
+                .join(" │ ");
+        }
+        self.consumed.clear();
+
+        &self.summary
+    }
+}
+
 /// A `Context` is held by the `Ui` and reflects the environment a `Ui` runs in.
 #[derive(Clone, Debug)]
 pub struct Context<M> {
     /// Currently captured user inputs. Inputs that where stored via `store_input`
     /// need to be cleared manually via `clear_inputs` (usually for each frame drawn).
     inputs: VecDeque<Key>,
+    /// Text pasted since the inputs were cleared the last time.
+    paste: Option<String>,
+    /// Key chords completed since the inputs were cleared the last time, e.g.
+    /// `]c`, as pairs of the prefix key and the key that followed it.
+    chords: Vec<(Key, Key)>,
     /// Current frame of the application.
     pub(crate) frame_size: Rect,
     /// The message sender used by the `Ui` to send application messages.
     pub(crate) sender: Option<UnboundedSender<M>>,
+    /// Transient messages and the time they expire at. These are shared between
+    /// all clones of a context, such that toasts added by a `Ui` outlive the frame
+    /// they were added in.
+    toasts: Arc<Mutex<Toasts>>,
+    /// A numeric prefix typed before a navigation key and the time the last digit
+    /// was typed at. Shared between all clones of a context, since the digits
+    /// are usually typed in separate frames.
+    repeat_count: Arc<Mutex<Option<(Instant, usize)>>>,
+    /// Prefix keys of the application and the one that is pending. Shared between
+    /// all clones of a context, since prefixes are set by a `Ui`, but tracked
+    /// when inputs are stored.
+    prefixes: Arc<Mutex<(PrefixMap, PendingPrefix)>>,
+    /// The theme root `Ui`s are built with.
+    theme: Theme,
+    /// The callers that consumed inputs, if they're audited. Shared between all
+    /// clones of a context, since inputs are consumed by child `Ui`s.
+    key_audit: Option<Arc<Mutex<KeyAudit>>>,
+    /// The modals shown in this and the previous frame. Shared between all clones
+    /// of a context, since modals are shown by child `Ui`s.
+    modals: Arc<Mutex<Modals>>,
+    /// If the areas of panes can be focused, as added in the previous frame, by
+    /// the caller of `Ui::panes`. Shared between all clones of a context, since
+    /// panes are shown by child `Ui`s.
+    panes: Arc<Mutex<HashMap<&'static Location<'static>, Vec<bool>>>>,
+}
+
+/// Counts the modals shown per frame, see `Ui::modal`. Since the widgets below a
+/// modal are added before it, the modals shown in the previous frame decide which
+/// layer of the current one receives inputs.
+#[derive(Clone, Copy, Debug, Default)]
+struct Modals {
+    previous: usize,
+    current: usize,
 }
 
 impl<M> Default for Context<M> {
     fn default() -> Self {
         Self {
             inputs: VecDeque::default(),
+            paste: None,
+            chords: vec![],
             frame_size: Rect::default(),
             sender: None,
+            toasts: Arc::new(Mutex::new(Toasts::default())),
+            repeat_count: Arc::new(Mutex::new(None)),
+            prefixes: Arc::new(Mutex::new(Default::default())),
+            theme: Theme::default(),
+            key_audit: None,
+            modals: Arc::new(Mutex::new(Modals::default())),
+            panes: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 }
@@ -155,6 +474,11 @@ impl<M> Context<M> {
         self
     }
 
+    pub fn with_paste(mut self, paste: impl Into<String>) -> Self {
+        self.paste = Some(paste.into());
+        self
+    }
+
     pub fn with_frame_size(mut self, frame_size: Rect) -> Self {
         self.frame_size = frame_size;
         self
@@ -165,17 +489,219 @@ impl<M> Context<M> {
         self
     }
 
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// Records which callers consume the inputs, see `audit_inputs`.
+    pub fn with_key_audit(mut self) -> Self {
+        self.key_audit = Some(Arc::new(Mutex::new(KeyAudit::default())));
+        self
+    }
+
+    pub fn theme(&self) -> &Theme {
+        &self.theme
+    }
+
+    fn record_input(&self, key: Key, location: &'static Location<'static>) {
+        if let Some(audit) = &self.key_audit {
+            audit.lock().unwrap().record(key, location);
+        }
+    }
+
+    /// Returns which callers of the `Ui` input methods consumed the current
+    /// inputs, e.g. `j → widget.rs:498 │ x → unused`, if inputs are audited.
+    /// Each call starts a new record, so it should be called once per frame.
+    pub fn audit_inputs(&self) -> Option<String> {
+        self.key_audit
+            .as_ref()
+            .map(|audit| audit.lock().unwrap().summarize(&self.inputs).to_string())
+    }
+
     pub fn frame_size(&self) -> Rect {
         self.frame_size
     }
 
+    /// Registers a modal shown in the current frame and returns its layer: `1`
+    /// for the first one, `2` for one shown on top of it etc.
+    fn push_modal(&self) -> usize {
+        let mut modals = self.modals.lock().unwrap();
+        modals.current += 1;
+        modals.current
+    }
+
+    /// Returns the layer that receives inputs: the one of the topmost modal shown
+    /// in the previous frame, or `0` if there was none.
+    fn input_layer(&self) -> usize {
+        self.modals.lock().unwrap().previous
+    }
+
+    /// Returns if the areas of the panes added by the given caller could be
+    /// focused in the previous frame. Unknown areas are focusable.
+    fn panes_focusable(&self, location: &'static Location<'static>) -> Vec<bool> {
+        self.panes
+            .lock()
+            .unwrap()
+            .get(location)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    fn set_panes_focusable(&self, location: &'static Location<'static>, focusable: Vec<bool>) {
+        self.panes.lock().unwrap().insert(location, focusable);
+    }
+
+    /// Finishes a frame: the modals shown in it decide which layer receives the
+    /// inputs of the next one.
+    pub fn finish_frame(&self) {
+        let mut modals = self.modals.lock().unwrap();
+        modals.previous = std::mem::take(&mut modals.current);
+    }
+
+    /// Stores a key the user pressed. A key that follows a pending prefix
+    /// completes a chord and is not stored as a plain input.
     pub fn store_input(&mut self, key: Key) {
-        self.inputs.push_back(key);
+        let (prefixes, pending) = &mut *self.prefixes.lock().unwrap();
+
+        match pending.key() {
+            Some(prefix) => {
+                pending.clear();
+                self.chords.push((prefix, key));
+            }
+            None => {
+                pending.press(prefixes, key, Instant::now());
+                self.inputs.push_back(key);
+            }
+        }
+    }
+
+    /// Stores pasted text. Text pasted within the same frame is appended.
+    pub fn store_paste(&mut self, text: String) {
+        match &mut self.paste {
+            Some(paste) => paste.push_str(&text),
+            None => self.paste = Some(text),
+        }
+    }
+
+    /// Stores the input an event carries, i.e. a key pressed or text pasted.
+    pub fn store_event(&mut self, event: Event) {
+        match event {
+            Event::Key(key) => self.store_input(key),
+            Event::Paste(text) => self.store_paste(text),
+            Event::Resize | Event::FocusGained | Event::FocusLost => (),
+        }
     }
 
     pub fn clear_inputs(&mut self) {
         self.inputs.clear();
+        self.paste = None;
+        self.chords.clear();
+    }
+
+    /// Adds a toast, see `Toasts::push`.
+    pub fn push_toast(&self, message: impl ToString, level: ToastLevel) {
+        self.toasts.lock().unwrap().push(message, level);
+    }
+
+    /// Removes all toasts that expired at the given time.
+    pub fn expire_toasts(&self, now: Instant) {
+        self.toasts.lock().unwrap().expire(now);
+    }
+
+    /// Appends a digit to the pending repeat count. A pending count that timed
+    /// out is replaced.
+    pub fn push_repeat_digit(&self, digit: u32, now: Instant) {
+        let mut repeat_count = self.repeat_count.lock().unwrap();
+        let count = match *repeat_count {
+            Some((typed, count)) if now.duration_since(typed) < REPEAT_COUNT_TIMEOUT => count
+                .saturating_mul(10)
+                .saturating_add(digit as usize)
+                .min(REPEAT_COUNT_MAX),
+            _ => digit as usize,
+        };
+
+        *repeat_count = Some((now, count));
+    }
+
+    /// Returns the pending repeat count if it did not time out yet, and resets it.
+    pub fn take_repeat_count(&self, now: Instant) -> Option<usize> {
+        self.repeat_count
+            .lock()
+            .unwrap()
+            .take()
+            .filter(|(typed, _)| now.duration_since(*typed) < REPEAT_COUNT_TIMEOUT)
+            .map(|(_, count)| count)
+    }
+
+    /// Sets the prefix keys whose follow-up keys are hinted if they're pending.
+    pub fn set_prefixes(&self, prefixes: PrefixMap) {
+        self.prefixes.lock().unwrap().0 = prefixes;
+    }
+
+    /// Returns the pending prefix key and its follow-up keys, if it's pending
+    /// long enough to be hinted.
+    pub fn key_hint(&self, now: Instant) -> Option<(Key, Vec<Shortcut>)> {
+        let (prefixes, pending) = &*self.prefixes.lock().unwrap();
+        let key = pending.due(now)?;
+
+        prefixes.hints(key).map(|hints| (key, hints.to_vec()))
+    }
+
+    /// Returns if frames change over time, even if nothing happened, e.g. because
+    /// toasts are shown until they expire or a key hint is about to be shown.
+    pub fn is_animated(&self) -> bool {
+        !self.toasts.lock().unwrap().is_empty() || self.prefixes.lock().unwrap().1.key().is_some()
+    }
+
+    /// Returns all toasts that did not expire yet, oldest first.
+    pub fn toasts(&self) -> Vec<(ToastLevel, String)> {
+        self.toasts.lock().unwrap().messages()
+    }
+}
+
+/// Renders the follow-up keys of a pending prefix key as an overlay at the bottom
+/// right of the frame, above the toasts.
+fn render_key_hint<M>(ctx: &Context<M>, frame: &mut Frame) {
+    let Some((prefix, hints)) = ctx.key_hint(Instant::now()) else {
+        return;
+    };
+    let area = frame.area();
+    let toasts = ctx.toasts.lock().unwrap().height();
+    let area = Rect {
+        height: area.height.saturating_sub(toasts),
+        ..area
+    };
+
+    frame.render_widget(KeyHint::new(prefix, &hints), area);
+}
+
+/// Renders the most recent toasts as an overlay at the bottom of the frame.
+fn render_toasts<M>(ctx: &Context<M>, frame: &mut Frame) {
+    frame.render_widget(&*ctx.toasts.lock().unwrap(), frame.area());
+}
+
+/// Renders the summary of the key audit in the last line of the frame, over
+/// everything else, if inputs are audited.
+fn render_key_audit<M>(ctx: &Context<M>, frame: &mut Frame) {
+    let Some(summary) = ctx.audit_inputs() else {
+        return;
+    };
+    log::info!("Key audit: {}", summary);
+
+    let area = frame.area();
+    if area.is_empty() {
+        return;
     }
+    let area = Rect {
+        y: area.bottom().saturating_sub(1),
+        height: 1,
+        ..area
+    };
+    let line = Line::from(format!(" keys: {summary} ")).style(style::magenta().reversed());
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(line, area);
 }
 
 /// `Borders` defines which borders should be drawn around a widget.
@@ -202,6 +728,13 @@ pub enum Layout {
     Expandable3 {
         left_only: bool,
     },
+    /// A single area that keeps the given amount of cells free on each side.
+    Padding {
+        top: u16,
+        bottom: u16,
+        left: u16,
+        right: u16,
+    },
 }
 
 impl From<ratatui::layout::Layout> for Layout {
@@ -211,9 +744,20 @@ impl From<ratatui::layout::Layout> for Layout {
 }
 
 impl Layout {
+    /// Returns a `Padding` layout that keeps the same amount of cells free on all sides.
+    pub fn uniform_padding(amount: u16) -> Self {
+        Layout::Padding {
+            top: amount,
+            bottom: amount,
+            left: amount,
+            right: amount,
+        }
+    }
+
     pub fn len(&self) -> usize {
         match self {
             Layout::None => 0,
+            Layout::Padding { .. } => 1,
             Layout::Wrapped { internal } => internal.split(Rect::default()).len(),
             Layout::Expandable3 { left_only } => {
                 if *left_only {
@@ -233,6 +777,23 @@ impl Layout {
         match self {
             Layout::None => Rc::new([]),
             Layout::Wrapped { internal } => internal.split(area),
+            Layout::Padding {
+                top,
+                bottom,
+                left,
+                right,
+            } => {
+                let x = area.x.saturating_add(*left).min(area.right());
+                let y = area.y.saturating_add(*top).min(area.bottom());
+
+                [Rect {
+                    x,
+                    y,
+                    width: area.right().saturating_sub(x).saturating_sub(*right),
+                    height: area.bottom().saturating_sub(y).saturating_sub(*bottom),
+                }]
+                .into()
+            }
             Layout::Expandable3 { left_only } => {
                 use ratatui::layout::Layout;
 
@@ -282,24 +843,96 @@ pub struct Ui<M> {
     /// Current rendering counter that is increased whenever the next area to draw
     /// on is requested.
     count: usize,
+    /// If leading digits are collected into a repeat count for the next
+    /// navigation key, e.g. `5j`.
+    repeat_counts: bool,
+    /// If the areas requested so far can be focused, by index.
+    focusable: Vec<bool>,
+    /// Cells kept free on each side of the area before it's split by the layout.
+    margin: u16,
+    /// The modal this is shown in, or `0` if it's not shown in one. Only the
+    /// topmost layer receives inputs.
+    layer: usize,
 }
 
 impl<M> Ui<M> {
+    #[track_caller]
     pub fn input(&mut self, f: impl Fn(Key) -> bool) -> bool {
-        self.has_focus && self.is_area_focused() && self.ctx.inputs.iter().any(|key| f(*key))
+        let focused = self.has_focus && self.is_area_focused();
+        self.find_input(focused, f).is_some()
     }
 
+    #[track_caller]
     pub fn input_global(&mut self, f: impl Fn(Key) -> bool) -> bool {
-        self.has_focus && self.ctx.inputs.iter().any(|key| f(*key))
+        self.find_input(self.has_focus, f).is_some()
     }
 
+    #[track_caller]
     pub fn input_with_key(&mut self, f: impl Fn(Key) -> bool) -> Option<Key> {
-        if self.has_focus && self.is_area_focused() {
-            self.ctx.inputs.iter().find(|key| f(**key)).copied()
+        let focused = self.has_focus && self.is_area_focused();
+        self.find_input(focused, f)
+    }
+
+    /// Returns the first input `f` matches, if `focused`. If inputs are audited,
+    /// the caller of the public input method is recorded as having consumed it.
+    #[track_caller]
+    fn find_input(&self, focused: bool, f: impl Fn(Key) -> bool) -> Option<Key> {
+        if !focused || !self.is_top_layer() {
+            return None;
+        }
+        let key = self.ctx.inputs.iter().find(|key| f(**key)).copied()?;
+        self.ctx.record_input(key, Location::caller());
+
+        Some(key)
+    }
+
+    /// Returns `true` if the chord of the given prefix and key was completed,
+    /// regardless of the area that has focus.
+    pub fn input_chord(&mut self, prefix: Key, key: Key) -> bool {
+        self.has_focus && self.is_top_layer() && self.ctx.chords.contains(&(prefix, key))
+    }
+
+    /// Returns the text pasted in this frame if the current area has focus.
+    pub fn input_paste(&mut self) -> Option<String> {
+        if self.has_focus && self.is_area_focused() && self.is_top_layer() {
+            self.ctx.paste.clone()
         } else {
             None
         }
     }
+
+    /// Same as `input_with_key`, but also returns how often the key should be
+    /// repeated. If repeat counts are enabled, digits are not returned but collected
+    /// into the count for the next key. A leading `0` is returned as a plain key.
+    #[track_caller]
+    pub fn input_with_count(&mut self, f: impl Fn(Key) -> bool) -> Option<(Key, usize)> {
+        let key = self.input_with_key(f)?;
+        if !self.repeat_counts {
+            return Some((key, 1));
+        }
+
+        let now = Instant::now();
+        match key {
+            Key::Char(c) if c.is_ascii_digit() => {
+                let digit = c.to_digit(10).unwrap_or_default();
+                let pending = self.ctx.repeat_count.lock().unwrap().is_some();
+
+                if digit > 0 || pending {
+                    self.ctx.push_repeat_digit(digit, now);
+                    None
+                } else {
+                    Some((key, 1))
+                }
+            }
+            _ => Some((key, self.ctx.take_repeat_count(now).unwrap_or(1).max(1))),
+        }
+    }
+
+    /// Returns `true` if this is not covered by a modal. A modal that is shown
+    /// the first time is on top as well, since it wasn't registered yet.
+    fn is_top_layer(&self) -> bool {
+        self.layer >= self.ctx.input_layer()
+    }
 }
 
 impl<M> Default for Ui<M> {
@@ -312,6 +945,10 @@ impl<M> Default for Ui<M> {
             has_focus: true,
             count: 0,
             ctx: Context::default(),
+            repeat_counts: false,
+            focusable: vec![],
+            margin: 0,
+            layer: 0,
         }
     }
 }
@@ -329,6 +966,11 @@ impl<M> Ui<M> {
         self
     }
 
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
     pub fn with_layout(mut self, layout: Layout) -> Self {
         self.layout = layout;
         self
@@ -349,6 +991,24 @@ impl<M> Ui<M> {
         self
     }
 
+    /// Sets the prefix keys of the application. If one of them was pressed and
+    /// isn't followed by another key for a moment, its follow-up keys are hinted.
+    pub fn set_prefixes(&mut self, prefixes: PrefixMap) {
+        self.ctx.set_prefixes(prefixes);
+    }
+
+    /// Enables or disables repeat counts for this and all child `Ui`s. Apps that
+    /// use digits as plain shortcuts should keep them disabled.
+    pub fn set_repeat_counts(&mut self, enabled: bool) {
+        self.repeat_counts = enabled;
+    }
+
+    /// Keeps the given amount of cells free on each side of the area of this
+    /// and all child `Ui`s before the areas of their widgets are computed.
+    pub fn set_margin(&mut self, margin: u16) {
+        self.margin = margin;
+    }
+
     pub fn theme(&self) -> &Theme {
         &self.theme
     }
@@ -362,9 +1022,14 @@ impl<M> Ui<M> {
             .focus_area
             .map(|focus| self.count == focus)
             .unwrap_or(false);
-        let rect = self.layout.split(self.area).get(self.count).cloned();
+        let rect = self
+            .layout
+            .split(self.inner_area())
+            .get(self.count)
+            .cloned();
 
         self.count += 1;
+        self.focusable.push(true);
 
         rect.map(|rect| (rect, area_focus))
     }
@@ -373,11 +1038,20 @@ impl<M> Ui<M> {
         let count = self.count.saturating_sub(1);
 
         let area_focus = self.focus_area.map(|focus| count == focus).unwrap_or(false);
-        let rect = self.layout.split(self.area).get(self.count).cloned();
+        let rect = self
+            .layout
+            .split(self.inner_area())
+            .get(self.count)
+            .cloned();
 
         rect.map(|rect| (rect, area_focus))
     }
 
+    /// Returns the area without the margin, which the layout is split in.
+    fn inner_area(&self) -> Rect {
+        self.area.inner(Margin::new(self.margin, self.margin))
+    }
+
     pub fn is_area_focused(&self) -> bool {
         let count = self.count.saturating_sub(1);
         self.focus_area.map(|focus| count == focus).unwrap_or(false)
@@ -391,19 +1065,56 @@ impl<M> Ui<M> {
         self.count
     }
 
-    pub fn focus_next(&mut self) {
-        if self.focus_area.is_none() {
-            self.focus_area = Some(0);
-        } else {
-            self.focus_area = Some(self.focus_area.unwrap().saturating_add(1));
+    /// Marks the area requested last as focusable or not. Areas are focusable by
+    /// default. Non-interactive widgets like labels or bars are not, so that
+    /// cycling the focus skips them.
+    pub fn set_focusable(&mut self, focusable: bool) {
+        if let Some(last) = self.focusable.last_mut() {
+            *last = focusable;
         }
     }
 
+    /// Returns `true` if any of the areas requested so far can be focused, or if
+    /// no area was requested at all.
+    pub fn is_focusable(&self) -> bool {
+        self.focusable.is_empty() || self.focusable.iter().any(|focusable| *focusable)
+    }
+
+    /// Moves the focus to the next area, skipping areas that are not focusable.
+    pub fn focus_next(&mut self) {
+        let next = self
+            .focus_area
+            .map(|focus| focus.saturating_add(1))
+            .unwrap_or_default();
+
+        self.focus_area =
+            (next..).find(|index| self.focusable.get(*index).copied().unwrap_or(true));
+    }
+
     pub fn send_message(&self, message: M) {
         if let Some(sender) = &self.ctx.sender {
             let _ = sender.send(message);
         }
     }
+
+    /// Shows a transient message at the bottom of the frame for a few seconds.
+    pub fn toast(&self, message: impl ToString, level: ToastLevel) {
+        self.ctx.push_toast(message, level);
+    }
+
+    /// Runs `add_contents` without passing the current inputs to it, e.g. to
+    /// render the widgets below a prompt that takes all input.
+    pub fn without_inputs<R>(&mut self, add_contents: impl FnOnce(&mut Self) -> R) -> R {
+        let inputs = std::mem::take(&mut self.ctx.inputs);
+        let paste = self.ctx.paste.take();
+        let chords = std::mem::take(&mut self.ctx.chords);
+        let inner = add_contents(self);
+        self.ctx.inputs = inputs;
+        self.ctx.paste = paste;
+        self.ctx.chords = chords;
+
+        inner
+    }
 }
 
 impl<M> Ui<M>
@@ -415,10 +1126,16 @@ where
     }
 
     pub fn child_ui(&mut self, area: Rect, layout: impl Into<Layout>) -> Self {
-        Ui::default()
-            .with_area(area)
-            .with_layout(layout.into())
-            .with_ctx(self.ctx.clone())
+        Ui {
+            theme: self.theme.clone(),
+            repeat_counts: self.repeat_counts,
+            margin: self.margin,
+            layer: self.layer,
+            ..Ui::default()
+                .with_area(area)
+                .with_layout(layout.into())
+                .with_ctx(self.ctx.clone())
+        }
     }
 
     pub fn layout<R>(
@@ -444,7 +1161,91 @@ where
             ..self.child_ui(area, layout)
         };
 
-        InnerResponse::new(add_contents(&mut child_ui), Response::default())
+        let inner = add_contents(&mut child_ui);
+        self.set_focusable(child_ui.is_focusable());
+
+        InnerResponse::new(inner, Response::default())
+    }
+
+    /// Runs `add_contents` in a child `Ui` that arranges its areas in a single
+    /// row, sized by `constraints`. It's a shorthand for `layout` with a horizontal
+    /// `ratatui` layout: the row takes up the next area of this `Ui`, and every
+    /// call to `next_area` in `add_contents`, e.g. by adding a widget, takes the
+    /// next cell of the row from the left. Areas requested beyond the constraints
+    /// given are empty. None of the cells has the focus; use `layout` if one
+    /// should.
+    pub fn horizontal<R, I>(
+        &mut self,
+        constraints: I,
+        add_contents: impl FnOnce(&mut Self) -> R,
+    ) -> InnerResponse<R>
+    where
+        I: IntoIterator,
+        I::Item: Into<Constraint>,
+    {
+        self.layout(
+            ratatui::layout::Layout::horizontal(constraints),
+            None,
+            add_contents,
+        )
+    }
+
+    /// Like `horizontal`, but arranges the areas in a single column, which are
+    /// taken from the top.
+    pub fn vertical<R, I>(
+        &mut self,
+        constraints: I,
+        add_contents: impl FnOnce(&mut Self) -> R,
+    ) -> InnerResponse<R>
+    where
+        I: IntoIterator,
+        I::Item: Into<Constraint>,
+    {
+        self.layout(
+            ratatui::layout::Layout::vertical(constraints),
+            None,
+            add_contents,
+        )
+    }
+
+    /// Runs `add_contents` in a child `Ui` that subdivides the next area with
+    /// `layout`, e.g. a single region of `Layout::Expandable3`. Like with `layout`,
+    /// the child requests its areas starting from the first one, independent of
+    /// this `Ui`. But it only has the focus if this `Ui` has it as well, so that
+    /// sub-layouts can be nested to any depth.
+    pub fn nested_layout<R>(
+        &mut self,
+        layout: impl Into<Layout>,
+        focus: Option<usize>,
+        add_contents: impl FnOnce(&mut Self) -> R,
+    ) -> InnerResponse<R> {
+        let (area, area_focus) = self.next_area().unwrap_or_default();
+
+        let mut child_ui = Ui {
+            has_focus: self.has_focus && area_focus,
+            focus_area: focus,
+            ..self.child_ui(area, layout)
+        };
+
+        let inner = add_contents(&mut child_ui);
+        self.set_focusable(child_ui.is_focusable());
+
+        InnerResponse::new(inner, Response::default())
+    }
+
+    /// Runs `add_contents` with the theme given, e.g. to render a pane without
+    /// focus dimmed. Unlike `layout`, this doesn't take up an area: widgets are
+    /// added to this `Ui` as usual. Its theme is restored afterwards.
+    pub fn scoped_theme<R>(
+        &mut self,
+        theme: Theme,
+        add_contents: impl FnOnce(&mut Self) -> R,
+    ) -> InnerResponse<R> {
+        let theme = std::mem::replace(&mut self.theme, theme);
+        let inner = add_contents(self);
+        self.theme = theme;
+
+        InnerResponse::new(inner, Response::default())
     }
 }
 
@@ -452,6 +1253,7 @@ impl<M> Ui<M>
 where
     M: Clone,
 {
+    #[track_caller]
     pub fn panes<R>(
         &mut self,
         layout: impl Into<Layout>,
@@ -470,7 +1272,10 @@ where
             ..self.child_ui(area, layout)
         };
 
-        widget::Panes::new(len, focus).show(&mut child_ui, add_contents)
+        let response = widget::Panes::new(len, focus).show(&mut child_ui, add_contents);
+        self.set_focusable(child_ui.is_focusable());
+
+        response
     }
 
     pub fn composite<R>(
@@ -484,7 +1289,10 @@ where
         let mut child_ui = self.child_ui(area, layout);
         child_ui.has_focus = area_focus;
 
-        widget::Composite::new(focus).show(&mut child_ui, add_contents)
+        let response = widget::Composite::new(focus).show(&mut child_ui, add_contents);
+        self.set_focusable(child_ui.is_focusable());
+
+        response
     }
 
     pub fn label<'a>(&mut self, frame: &mut Frame, content: impl Into<Text<'a>>) -> Response {
@@ -492,19 +1300,15 @@ where
     }
 
     pub fn overline(&mut self, frame: &mut Frame) -> Response {
-        let overline = String::from("▔").repeat(256);
+        let overline = self.theme.charset.overline().repeat(256);
         self.label(frame, Span::raw(overline).cyan())
     }
 
-    pub fn separator(&mut self, frame: &mut Frame) -> Response {
-        let overline = String::from("─").repeat(256);
-        self.label(
-            frame,
-            Span::raw(overline).fg(self.theme.border_style.fg.unwrap_or_default()),
-        )
+    pub fn separator<'a>(&mut self, frame: &mut Frame, title: Option<Line<'a>>) -> Response {
+        widget::Separator::new(title).ui(self, frame)
     }
 
-    pub fn table<'a, R, const W: usize>(
+    pub fn table<'a, R, A>(
         &mut self,
         frame: &mut Frame,
         selected: &mut Option<usize>,
@@ -513,12 +1317,25 @@ where
         borders: Option<Borders>,
     ) -> Response
     where
-        R: ToRow<W> + Clone,
+        R: TableRow<A> + Clone,
     {
-        widget::Table::new(selected, items, columns, borders).ui(self, frame)
+        widget::Table::<R, A>::new(selected, items, columns, borders).ui(self, frame)
     }
 
-    pub fn headered_table<'a, R, const W: usize>(
+    pub fn list<'a, T>(
+        &mut self,
+        frame: &mut Frame,
+        selected: &'a mut Option<usize>,
+        items: &'a [T],
+        borders: Option<Borders>,
+    ) -> Response
+    where
+        T: Display,
+    {
+        widget::List::new(selected, items, borders).ui(self, frame)
+    }
+
+    pub fn headered_table<'a, R, A>(
         &mut self,
         frame: &mut Frame,
         selected: &'a mut Option<usize>,
@@ -527,17 +1344,20 @@ where
         columns: impl IntoIterator<Item = Column<'a>>,
     ) -> Response
     where
-        R: ToRow<W> + Clone,
+        R: TableRow<A> + Clone,
     {
-        HeaderedTable::<R, W>::new(selected, items, header, columns).ui(self, frame)
+        HeaderedTable::<R, A>::new(selected, items, header, columns).ui(self, frame)
     }
 
-    pub fn shortcuts(
+    pub fn shortcuts<S>(
         &mut self,
         frame: &mut Frame,
-        shortcuts: &[(&str, &str)],
+        shortcuts: impl IntoIterator<Item = S>,
         divider: char,
-    ) -> Response {
+    ) -> Response
+    where
+        S: Into<Shortcut>,
+    {
         widget::Shortcuts::new(shortcuts, divider).ui(self, frame)
     }
 
@@ -559,6 +1379,30 @@ where
         widget::Bar::new(columns, borders).ui(self, frame)
     }
 
+    pub fn gauge<'a>(
+        &mut self,
+        frame: &mut Frame,
+        ratio: f64,
+        label: impl Into<Span<'a>>,
+    ) -> Response {
+        widget::Gauge::new(ratio, label).ui(self, frame)
+    }
+
+    /// Renders a gauge that shows how many of `len` steps are done, e.g. how many
+    /// items of a known total are loaded already.
+    pub fn progress_bar(&mut self, frame: &mut Frame, step: usize, len: usize) -> Response {
+        let ratio = if len > 0 {
+            step as f64 / len as f64
+        } else {
+            0.0
+        };
+        widget::Gauge::new(ratio, format!(" {step}/{len} ")).ui(self, frame)
+    }
+
+    pub fn sparkline(&mut self, frame: &mut Frame, counts: &[u64]) -> Response {
+        widget::Sparkline::new(counts).ui(self, frame)
+    }
+
     pub fn text_view<'a>(
         &mut self,
         frame: &mut Frame,
@@ -569,6 +1413,30 @@ where
         widget::TextView::new(text, scroll, borders).ui(self, frame)
     }
 
+    /// Shows `len` lines built by `line` from their index in a vertically
+    /// scrollable area. Only the visible lines are built, see `ScrollArea`.
+    pub fn vertical_scroll_area<'a>(
+        &mut self,
+        frame: &mut Frame,
+        len: usize,
+        line: impl Fn(usize) -> Line<'a>,
+        scroll: &'a mut Position,
+        borders: Option<Borders>,
+    ) -> Response {
+        widget::ScrollArea::new(len, line, scroll, borders).ui(self, frame)
+    }
+
+    pub fn diff_view<'a>(
+        &mut self,
+        frame: &mut Frame,
+        unified: impl Into<Text<'a>>,
+        split: Option<SplitDiff<'a>>,
+        scroll: &'a mut Position,
+        borders: Option<Borders>,
+    ) -> Response {
+        DiffView::new(unified, split, scroll, borders).ui(self, frame)
+    }
+
     pub fn centered_text_view<'a>(
         &mut self,
         frame: &mut Frame,
@@ -578,6 +1446,61 @@ where
         widget::CenteredTextView::new(text, borders).ui(self, frame)
     }
 
+    /// Runs `add_contents` in a modal on top of everything added before, which is
+    /// dimmed. Modals can be stacked, and only the topmost one receives inputs
+    /// until it's not shown anymore. Widgets below it don't, which is why the
+    /// modals of the previous frame are tracked by the context: a modal shown
+    /// the first time shares the inputs of its frame with the widgets below.
+    /// Add these with `without_inputs` while the modal is shown to avoid that.
+    pub fn modal<R>(
+        &mut self,
+        frame: &mut Frame,
+        add_contents: impl FnOnce(&mut Self, &mut Frame) -> R,
+    ) -> R {
+        let layer = self.ctx.push_modal();
+        frame
+            .buffer_mut()
+            .set_style(self.area, Style::default().dim());
+
+        let mut modal_ui = Ui {
+            has_focus: true,
+            focus_area: Some(0),
+            layer,
+            ..self.child_ui(
+                self.area,
+                ratatui::layout::Layout::horizontal([Constraint::Min(1)]),
+            )
+        };
+
+        add_contents(&mut modal_ui, frame)
+    }
+
+    /// Shows a `widget::Confirm` in a modal.
+    pub fn confirm<'a>(
+        &mut self,
+        frame: &mut Frame,
+        title: impl ToString,
+        message: impl Into<Text<'a>>,
+        answer: &'a mut Option<bool>,
+    ) -> Response {
+        self.modal(frame, |ui, frame| {
+            widget::Confirm::new(title, message, answer).ui(ui, frame)
+        })
+    }
+
+    /// Shows a `widget::Popup` in a modal.
+    pub fn popup<'a>(
+        &mut self,
+        frame: &mut Frame,
+        title: impl ToString,
+        text: impl Into<Text<'a>>,
+        open: &'a mut bool,
+    ) -> Response {
+        self.modal(frame, |ui, frame| {
+            widget::Popup::new(title, text, open).ui(ui, frame)
+        })
+    }
+
     pub fn text_edit_singleline(
         &mut self,
         frame: &mut Frame,
@@ -601,3 +1524,423 @@ where
             .ui(self, frame)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::time::Instant;
+
+    use ratatui::buffer::Buffer;
+    use ratatui::layout::{Constraint, Layout, Position, Rect};
+    use ratatui::style::Modifier;
+
+    use termion::event::Key;
+
+    use crate::terminal;
+    use crate::ui::im::testing::{lines, Item};
+    use crate::ui::im::widget::Window;
+    use crate::ui::keyhint::{PrefixMap, KEY_HINT_DELAY};
+    use crate::ui::Column;
+
+    use crate::ui::theme::Theme;
+
+    use crate::ui::toast::TOAST_DURATION;
+
+    use super::{Borders, Context, ToastLevel, Ui, REPEAT_COUNT_TIMEOUT};
+
+    #[test]
+    fn widgets_should_not_panic_on_degenerate_frames() -> anyhow::Result<()> {
+        let mut terminal = terminal::test_backend(80, 20)?;
+
+        let items = vec![Item("foo"), Item("bar")];
+        let mut selected = Some(0);
+        let mut scroll = Position::default();
+        let mut text = String::from("search");
+        let mut cursor = text.len();
+
+        // Shrink the frame step by step, the same way a terminal resize would.
+        for (width, height) in [(80, 20), (20, 3), (1, 1), (0, 0), (1, 0), (0, 1)] {
+            terminal.backend_mut().resize(width, height);
+
+            terminal.draw(|frame| {
+                let ctx = Context::<()>::default()
+                    .with_inputs([Key::Down, Key::Char('a')].into())
+                    .with_frame_size(frame.area());
+
+                Window::default().show(&ctx, |ui| {
+                    ui.layout(
+                        Layout::vertical([
+                            Constraint::Length(1),
+                            Constraint::Fill(1),
+                            Constraint::Fill(1),
+                            Constraint::Length(2),
+                            Constraint::Length(1),
+                        ]),
+                        Some(1),
+                        |ui| {
+                            ui.label(frame, "Label");
+                            ui.headered_table(
+                                frame,
+                                &mut selected,
+                                &items,
+                                [Column::new("Name", Constraint::Fill(1))],
+                                [Column::new("", Constraint::Fill(1))],
+                            );
+                            ui.text_view(frame, "foo\nbar\nbaz", &mut scroll, Some(Borders::All));
+                            ui.text_edit_labeled_singleline(
+                                frame,
+                                &mut text,
+                                &mut cursor,
+                                "Search",
+                                Some(Borders::None),
+                            );
+                            ui.shortcuts(frame, &[("q", "quit")], '∙');
+                        },
+                    );
+                });
+            })?;
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn responses_should_name_the_key_that_changed_the_widget() -> anyhow::Result<()> {
+        let items = vec![Item("foo"), Item("bar")];
+        let columns = [Column::new("", Constraint::Fill(1))].to_vec();
+        let show = |key: Key| -> anyhow::Result<(Option<Key>, Option<Key>)> {
+            let mut table = None;
+            let mut text = None;
+            let mut selected = Some(0);
+            let mut scroll = Position::default();
+
+            let ctx = Context::<()>::default().with_inputs([key].into());
+            super::render_once(20, 10, ctx.clone(), |ui, frame| {
+                let response = ui.table(
+                    frame,
+                    &mut selected,
+                    &items,
+                    columns.clone(),
+                    Some(Borders::None),
+                );
+                assert_eq!(response.changed, response.key.is_some());
+                table = response.key;
+            })?;
+            super::render_once(20, 10, ctx, |ui, frame| {
+                text = ui
+                    .text_view(frame, "foo\nbar", &mut scroll, Some(Borders::None))
+                    .key;
+            })?;
+
+            Ok((table, text))
+        };
+
+        assert_eq!(show(Key::Down)?, (Some(Key::Down), Some(Key::Down)));
+        assert_eq!(show(Key::PageUp)?, (Some(Key::PageUp), Some(Key::PageUp)));
+        // Tables ignore keys they don't navigate with.
+        assert_eq!(show(Key::Char('x'))?.0, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn horizontal_and_vertical_should_take_areas_in_order() -> anyhow::Result<()> {
+        let buffer = super::render_once(8, 2, Context::<()>::default(), |ui, frame| {
+            ui.vertical([Constraint::Length(1), Constraint::Length(1)], |ui| {
+                ui.horizontal([Constraint::Length(3), Constraint::Fill(1)], |ui| {
+                    ui.label(frame, "foo");
+                    ui.label(frame, "bar");
+                });
+                ui.label(frame, "baz");
+            });
+        })?;
+
+        assert_eq!(lines(&buffer), ["foobar  ", "baz     "]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn confirm_should_take_inputs_from_widgets_below() -> anyhow::Result<()> {
+        let answer = |key: Key| -> anyhow::Result<(Option<bool>, bool)> {
+            let mut answer = None;
+            let mut below = false;
+            let ctx = Context::<()>::default().with_inputs([key].into());
+
+            super::render_once(40, 10, ctx, |ui, frame| {
+                below = ui.without_inputs(|ui| ui.input_global(|_| true));
+                ui.confirm(frame, "Quit", "Quit?", &mut answer);
+            })?;
+
+            Ok((answer, below))
+        };
+
+        assert_eq!(answer(Key::Char('y'))?, (Some(true), false));
+        assert_eq!(answer(Key::Char('\n'))?, (Some(true), false));
+        assert_eq!(answer(Key::Esc)?, (Some(false), false));
+        assert_eq!(answer(Key::Char('q'))?, (None, false));
+
+        Ok(())
+    }
+
+    #[test]
+    fn topmost_modal_should_take_inputs() -> anyhow::Result<()> {
+        let ctx = Context::<()>::default();
+        let show = |ctx: Context<()>, modals: usize| -> anyhow::Result<(Vec<bool>, Buffer)> {
+            let mut inputs = vec![];
+            let buffer = super::render_once(20, 5, ctx, |ui, frame| {
+                ui.label(frame, "below");
+                inputs.push(ui.input_global(|_| true));
+                for _ in 0..modals {
+                    inputs.push(ui.modal(frame, |ui, _| ui.input_global(|_| true)));
+                }
+            })?;
+
+            Ok((inputs, buffer))
+        };
+
+        // The modals are registered in the first frame.
+        show(ctx.clone(), 2)?;
+        let (inputs, buffer) = show(ctx.clone().with_inputs([Key::Char('x')].into()), 2)?;
+        assert_eq!(inputs, vec![false, false, true]);
+        assert!(buffer[(0, 0)].modifier.contains(Modifier::DIM));
+
+        // Once the modals are gone, the widgets below take inputs again.
+        show(ctx.clone(), 0)?;
+        let (inputs, buffer) = show(ctx.with_inputs([Key::Char('x')].into()), 0)?;
+        assert_eq!(inputs, vec![true]);
+        assert!(!buffer[(0, 0)].modifier.contains(Modifier::DIM));
+
+        Ok(())
+    }
+
+    #[test]
+    fn key_audit_should_name_the_callers_that_consumed_keys() -> anyhow::Result<()> {
+        let ctx = Context::<()>::default()
+            .with_inputs([Key::Char('j'), Key::Char('x')].into())
+            .with_key_audit();
+        let items = vec![Item("foo"), Item("bar")];
+        let columns = [Column::new("", Constraint::Fill(1))].to_vec();
+        let mut selected = Some(0);
+        let mut line = 0;
+
+        super::render_once(20, 5, ctx.clone(), |ui, frame| {
+            ui.table(frame, &mut selected, &items, columns, Some(Borders::None));
+            (_, line) = (ui.input_global(|key| key == Key::Char('j')), line!());
+        })?;
+
+        let summary = ctx.audit_inputs().unwrap_or_default();
+        assert!(summary.starts_with("j → widget.rs:"));
+        assert!(summary.contains(&format!(", im.rs:{line} │ x → unused")));
+
+        // The summary is kept for frames without inputs.
+        let empty = ctx.clone().with_inputs([].into());
+        assert_eq!(empty.audit_inputs(), Some(summary));
+        assert_eq!(Context::<()>::default().audit_inputs(), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn scoped_theme_should_be_restored() {
+        let mut ui = Ui::<()>::default();
+        let dimmed = Theme::default().dimmed();
+
+        let scoped = ui.scoped_theme(dimmed.clone(), |ui| ui.theme().border_style);
+
+        assert_eq!(scoped.inner, dimmed.border_style);
+        assert_eq!(ui.theme().border_style, Theme::default().border_style);
+        assert!(!ui.theme().dim_no_focus);
+    }
+
+    #[test]
+    fn toasts_should_be_shared_and_expire() {
+        let ctx = Context::<()>::default();
+        let ui = Ui::default().with_ctx(ctx.clone());
+
+        ui.toast("Failed to accept hunk", ToastLevel::Error);
+        ui.toast("Failed to accept hunk", ToastLevel::Error);
+        ui.toast("Hunk accepted", ToastLevel::Info);
+
+        assert_eq!(
+            ctx.toasts(),
+            vec![
+                (ToastLevel::Error, "Failed to accept hunk".to_string()),
+                (ToastLevel::Info, "Hunk accepted".to_string()),
+            ]
+        );
+
+        ctx.expire_toasts(Instant::now() + TOAST_DURATION);
+        assert!(ctx.toasts().is_empty());
+    }
+
+    #[test]
+    fn repeat_count_should_accumulate_and_time_out() {
+        let ctx = Context::<()>::default();
+        let now = Instant::now();
+
+        ctx.push_repeat_digit(1, now);
+        ctx.push_repeat_digit(2, now);
+        assert_eq!(ctx.take_repeat_count(now), Some(12));
+        assert_eq!(ctx.take_repeat_count(now), None);
+
+        ctx.push_repeat_digit(5, now);
+        assert_eq!(ctx.take_repeat_count(now + REPEAT_COUNT_TIMEOUT), None);
+
+        ctx.push_repeat_digit(3, now);
+        ctx.push_repeat_digit(4, now + REPEAT_COUNT_TIMEOUT);
+        assert_eq!(ctx.take_repeat_count(now + REPEAT_COUNT_TIMEOUT), Some(4));
+    }
+
+    #[test]
+    fn input_with_count_should_collect_digits_if_enabled() {
+        let ctx = Context::<()>::default();
+        let input = |key: Key, enabled: bool| {
+            let mut ui = Ui::default()
+                .with_ctx(ctx.clone().with_inputs([key].into()))
+                .with_area_focus(Some(0));
+            ui.set_repeat_counts(enabled);
+            ui.input_with_count(|_| true)
+        };
+
+        assert_eq!(input(Key::Char('5'), false), Some((Key::Char('5'), 1)));
+        assert_eq!(input(Key::Char('j'), false), Some((Key::Char('j'), 1)));
+
+        assert_eq!(input(Key::Char('0'), true), Some((Key::Char('0'), 1)));
+        assert_eq!(input(Key::Char('1'), true), None);
+        assert_eq!(input(Key::Char('0'), true), None);
+        assert_eq!(input(Key::Char('j'), true), Some((Key::Char('j'), 10)));
+        assert_eq!(input(Key::Char('k'), true), Some((Key::Char('k'), 1)));
+    }
+
+    #[test]
+    fn nested_layouts_should_split_regions_independently() {
+        let area = Rect::new(0, 0, 100, 30);
+        let expandable = super::Layout::Expandable3 { left_only: false };
+        let regions = expandable.split(area);
+        let column = Layout::vertical([Constraint::Length(3), Constraint::Fill(1)]);
+        let rows = column.split(regions[1]);
+
+        let mut ui = Ui::<()>::new(area)
+            .with_layout(expandable)
+            .with_area_focus(Some(1));
+
+        let list = ui.next_area();
+        let (details, comments, focus) = ui
+            .nested_layout(column, Some(1), |ui| {
+                let details = ui.next_area();
+                let comments =
+                    ui.nested_layout(Layout::vertical([Constraint::Fill(1)]), Some(0), |ui| {
+                        (ui.next_area(), ui.has_focus())
+                    });
+                (details, comments.inner, ui.has_focus())
+            })
+            .inner;
+        let body = ui
+            .nested_layout(Layout::vertical([Constraint::Fill(1)]), None, |ui| {
+                (ui.next_area(), ui.has_focus())
+            })
+            .inner;
+
+        assert_eq!(list, Some((regions[0], false)));
+        assert_eq!(details, Some((rows[0], false)));
+        assert_eq!(comments, (Some((rows[1], true)), true));
+        assert!(focus);
+        assert_eq!(body, (Some((regions[2], false)), false));
+    }
+
+    #[test]
+    fn margin_and_padding_should_shrink_child_areas() {
+        let area = Rect::new(0, 0, 20, 10);
+
+        let mut ui =
+            Ui::<()>::new(area).with_layout(Layout::vertical([Constraint::Fill(1)]).into());
+        ui.set_margin(1);
+        assert_eq!(ui.next_area(), Some((Rect::new(1, 1, 18, 8), false)));
+
+        let mut child = ui.child_ui(
+            Rect::new(1, 1, 18, 8),
+            Layout::vertical([Constraint::Fill(1)]),
+        );
+        assert_eq!(child.next_area(), Some((Rect::new(2, 2, 16, 6), false)));
+
+        let padding = super::Layout::Padding {
+            top: 1,
+            bottom: 2,
+            left: 3,
+            right: 4,
+        };
+        assert_eq!(padding.split(area).to_vec(), [Rect::new(3, 1, 13, 7)]);
+        assert_eq!(
+            super::Layout::uniform_padding(2).split(area).to_vec(),
+            [Rect::new(2, 2, 16, 6)]
+        );
+        assert_eq!(
+            super::Layout::uniform_padding(20).split(area).to_vec(),
+            [Rect::new(20, 10, 0, 0)]
+        );
+    }
+
+    #[test]
+    fn pending_prefix_should_be_hinted_after_delay() {
+        let mut ctx = Context::<()>::default();
+
+        Ui::default()
+            .with_ctx(ctx.clone())
+            .set_prefixes(PrefixMap::default().prefix(Key::Char('g'), [("g", "top")]));
+
+        ctx.store_input(Key::Char('g'));
+        let now = Instant::now();
+        assert_eq!(ctx.key_hint(now), None);
+        assert_eq!(
+            ctx.key_hint(now + KEY_HINT_DELAY)
+                .map(|(key, hints)| (key, hints.len())),
+            Some((Key::Char('g'), 1))
+        );
+
+        ctx.store_input(Key::Char('g'));
+        ctx.store_input(Key::Char('j'));
+        assert_eq!(ctx.key_hint(now + KEY_HINT_DELAY), None);
+    }
+
+    #[test]
+    fn idle_frames_should_only_be_redrawn_if_animated() {
+        let mut ctx = Context::<()>::default();
+        assert!(!ctx.is_animated());
+
+        Ui::default()
+            .with_ctx(ctx.clone())
+            .set_prefixes(PrefixMap::default().prefix(Key::Char('z'), [("a", "toggle")]));
+
+        ctx.store_input(Key::Char('z'));
+        assert!(ctx.is_animated());
+        ctx.store_input(Key::Char('a'));
+        assert!(!ctx.is_animated());
+
+        ctx.push_toast("Hunk accepted", ToastLevel::Info);
+        assert!(ctx.is_animated());
+        ctx.expire_toasts(Instant::now() + TOAST_DURATION);
+        assert!(!ctx.is_animated());
+    }
+
+    #[test]
+    fn keys_following_a_prefix_should_complete_chords() {
+        let mut ctx = Context::<()>::default();
+
+        Ui::default()
+            .with_ctx(ctx.clone())
+            .set_prefixes(PrefixMap::default().prefix(Key::Char(']'), [("c", "next comment")]));
+
+        ctx.store_input(Key::Char(']'));
+        ctx.store_input(Key::Char('c'));
+        ctx.store_input(Key::Char('c'));
+
+        let mut ui = Ui::default().with_ctx(ctx.clone());
+        assert!(ui.input_chord(Key::Char(']'), Key::Char('c')));
+        assert!(!ui.input_chord(Key::Char('['), Key::Char('c')));
+        assert_eq!(ctx.inputs, [Key::Char(']'), Key::Char('c')]);
+
+        ctx.clear_inputs();
+        let mut ui = Ui::default().with_ctx(ctx);
+        assert!(!ui.input_chord(Key::Char(']'), Key::Char('c')));
+    }
+}
@@ -0,0 +1,28 @@
+//! Fixtures and helpers shared by the tests of widgets, e.g. to inspect what
+//! `render_once` rendered.
+
+use ratatui::buffer::Buffer;
+use ratatui::widgets::Cell;
+
+use crate::ui::ToRow;
+
+/// An item that is rendered as a single cell with the given text.
+#[derive(Clone)]
+pub struct Item(pub &'static str);
+
+impl ToRow<1> for Item {
+    fn to_row(&self) -> [Cell; 1] {
+        [Cell::from(self.0)]
+    }
+}
+
+/// Returns the symbols of each row of the given buffer.
+pub fn lines(buffer: &Buffer) -> Vec<String> {
+    (0..buffer.area.height)
+        .map(|y| {
+            (0..buffer.area.width)
+                .map(|x| buffer[(x, y)].symbol())
+                .collect::<String>()
+        })
+        .collect()
+}
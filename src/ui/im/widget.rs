@@ -1,17 +1,24 @@
 use std::cmp;
+use std::fmt;
+use std::marker::PhantomData;
+use std::panic::Location;
 
-use ratatui::layout::{Direction, Layout, Position, Rect};
+use ratatui::layout::{Alignment, Direction, Layout, Position, Rect, Size};
 use ratatui::style::{Style, Stylize};
 use ratatui::text::{Line, Span, Text};
-use ratatui::widgets::{Block, BorderType, Row, Scrollbar, ScrollbarState};
+use ratatui::widgets::{Block, BorderType, ListState, Row};
 use ratatui::Frame;
 use ratatui::{layout::Constraint, widgets::Paragraph};
 use termion::event::Key;
 
+use ansi_to_tui::IntoText;
+
+use crate::event;
 use crate::ui::ext::{FooterBlock, FooterBlockType, HeaderBlock};
-use crate::ui::theme::style;
+use crate::ui::scrollbar::{self, AutoHide, ScrollPosition};
+use crate::ui::theme::{Charset, HighlightStyle};
 use crate::ui::{layout, span};
-use crate::ui::{Column, ToRow};
+use crate::ui::{shortcut_lines, Column, Highlight, Shortcut, TableRow};
 
 use super::{Borders, Context, InnerResponse, Response, Ui};
 
@@ -50,6 +57,7 @@ impl Window {
         let mut ui = Ui::default()
             .with_focus()
             .with_area(ctx.frame_size())
+            .with_theme(ctx.theme().clone())
             .with_ctx(ctx.clone())
             .with_layout(Layout::horizontal([Constraint::Min(1)]).into())
             .with_area_focus(Some(0));
@@ -64,11 +72,22 @@ impl Window {
 pub struct PanesState {
     len: usize,
     focus: Option<usize>,
+    /// If a pane can be focused, by index. Panes without an entry are focusable.
+    focusable: Vec<bool>,
 }
 
 impl PanesState {
     pub fn new(len: usize, focus: Option<usize>) -> Self {
-        Self { len, focus }
+        Self {
+            len,
+            focus,
+            focusable: vec![],
+        }
+    }
+
+    pub fn with_focusable(mut self, focusable: Vec<bool>) -> Self {
+        self.focusable = focusable;
+        self
     }
 
     pub fn focus(&self) -> Option<usize> {
@@ -83,14 +102,27 @@ impl PanesState {
         self.len == 0
     }
 
+    /// Moves the focus to the next focusable pane. Keeps the focus if there is none.
     pub fn focus_next(&mut self) {
-        self.focus = self
-            .focus
-            .map(|focus| cmp::min(focus.saturating_add(1), self.len.saturating_sub(1)))
+        self.focus = self.focus.map(|focus| {
+            (focus.saturating_add(1)..self.len)
+                .find(|index| self.is_focusable(*index))
+                .unwrap_or(focus)
+        })
     }
 
+    /// Moves the focus to the previous focusable pane. Keeps the focus if there is none.
     pub fn focus_prev(&mut self) {
-        self.focus = self.focus.map(|focus| focus.saturating_sub(1))
+        self.focus = self.focus.map(|focus| {
+            (0..focus)
+                .rev()
+                .find(|index| self.is_focusable(*index))
+                .unwrap_or(focus)
+        })
+    }
+
+    fn is_focusable(&self, index: usize) -> bool {
+        self.focusable.get(index).copied().unwrap_or(true)
     }
 }
 
@@ -104,6 +136,7 @@ impl<'a> Panes<'a> {
         Self { len, focus }
     }
 
+    #[track_caller]
     pub fn show<M, R>(
         self,
         ui: &mut Ui<M>,
@@ -115,6 +148,10 @@ impl<'a> Panes<'a> {
         self.show_dyn(ui, Box::new(add_contents))
     }
 
+    /// Moves the focus before the panes are added, such that a changed focus is
+    /// rendered right away. It skips the panes that registered as not focusable
+    /// in the previous frame, which are looked up by the caller.
+    #[track_caller]
     pub fn show_dyn<M, R>(
         self,
         ui: &mut Ui<M>,
@@ -124,11 +161,10 @@ impl<'a> Panes<'a> {
         M: Clone,
     {
         let mut response = Response::default();
+        let location = Location::caller();
 
-        let mut state = PanesState {
-            focus: *self.focus,
-            len: self.len,
-        };
+        let mut state =
+            PanesState::new(self.len, *self.focus).with_focusable(ui.ctx.panes_focusable(location));
 
         if ui.input_global(|key| key == Key::Char('\t')) {
             state.focus_next();
@@ -140,12 +176,14 @@ impl<'a> Panes<'a> {
         }
         *self.focus = state.focus;
 
-        let mut ui = Ui {
-            focus_area: state.focus,
+        let mut child_ui = Ui {
+            focus_area: *self.focus,
             ..ui.clone()
         };
 
-        let inner = add_contents(&mut ui);
+        let inner = add_contents(&mut child_ui);
+        ui.focusable = child_ui.focusable;
+        ui.ctx.set_panes_focusable(location, ui.focusable.clone());
 
         InnerResponse::new(inner, response)
     }
@@ -203,12 +241,13 @@ impl Composite {
     where
         M: Clone,
     {
-        let mut ui = Ui {
+        let mut child_ui = Ui {
             focus_area: Some(self.focus),
             ..ui.clone()
         };
 
-        let inner = add_contents(&mut ui);
+        let inner = add_contents(&mut child_ui);
+        ui.focusable = child_ui.focusable;
 
         InnerResponse::new(inner, Response::default())
     }
@@ -229,12 +268,82 @@ impl<'a> Label<'a> {
 impl<'a> Widget for Label<'a> {
     fn ui<M>(self, ui: &mut Ui<M>, frame: &mut Frame) -> Response {
         let (area, _) = ui.next_area().unwrap_or_default();
+        ui.set_focusable(false);
+        if area.is_empty() {
+            return Response::default();
+        }
         frame.render_widget(self.content, area);
 
         Response::default()
     }
 }
 
+/// A horizontal line that separates groups of widgets, e.g. recent and all
+/// items. It can show a title, which is left-aligned by default. The separator
+/// only renders into the first row of the area it was given, so the layout
+/// should reserve a single row for it.
+pub struct Separator<'a> {
+    title: Option<Line<'a>>,
+    alignment: Alignment,
+}
+
+impl<'a> Separator<'a> {
+    pub fn new(title: Option<Line<'a>>) -> Self {
+        Self {
+            title,
+            alignment: Alignment::Left,
+        }
+    }
+
+    pub fn centered(mut self) -> Self {
+        self.alignment = Alignment::Center;
+        self
+    }
+
+    fn line(self, width: usize, style: Style, charset: Charset) -> Line<'a> {
+        let horizontal = charset.line_set().horizontal;
+        let rule = |width: usize| Span::styled(horizontal.repeat(width), style);
+
+        match self.title {
+            Some(title) if title.width().saturating_add(4) <= width => {
+                let remaining = width.saturating_sub(title.width()).saturating_sub(2);
+                let left = match self.alignment {
+                    Alignment::Center => remaining / 2,
+                    _ => 2.min(remaining),
+                };
+                let right = remaining.saturating_sub(left);
+
+                let mut spans = vec![rule(left), Span::raw(" ")];
+                spans.extend(title.spans);
+                spans.extend([Span::raw(" "), rule(right)]);
+
+                Line::from(spans)
+            }
+            _ => Line::from(rule(width)),
+        }
+    }
+}
+
+impl<'a> Widget for Separator<'a> {
+    fn ui<M>(self, ui: &mut Ui<M>, frame: &mut Frame) -> Response {
+        let (area, _) = ui.next_area().unwrap_or_default();
+        ui.set_focusable(false);
+        if area.is_empty() {
+            return Response::default();
+        }
+
+        let area = Rect { height: 1, ..area };
+        let line = self.line(
+            area.width.into(),
+            ui.theme().density.separator_style(ui.theme().border_style),
+            ui.theme().charset,
+        );
+        frame.render_widget(line, area);
+
+        Response::default()
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct TableState {
     internal: ratatui::widgets::TableState,
@@ -313,18 +422,21 @@ impl TableState {
     }
 }
 
-pub struct Table<'a, R, const W: usize> {
+pub struct Table<'a, R, A> {
     items: &'a Vec<R>,
     selected: &'a mut Option<usize>,
     columns: Vec<Column<'a>>,
     borders: Option<Borders>,
     show_scrollbar: bool,
     dim: bool,
+    highlight: Option<Highlight>,
+    highlight_style: Option<HighlightStyle>,
+    phantom: PhantomData<A>,
 }
 
-impl<'a, R, const W: usize> Table<'a, R, W>
+impl<'a, R, A> Table<'a, R, A>
 where
-    R: ToRow<W>,
+    R: TableRow<A>,
 {
     pub fn new(
         selected: &'a mut Option<usize>,
@@ -339,6 +451,9 @@ where
             borders,
             show_scrollbar: true,
             dim: false,
+            highlight: None,
+            highlight_style: None,
+            phantom: PhantomData,
         }
     }
 
@@ -346,11 +461,22 @@ where
         self.dim = dim;
         self
     }
+
+    pub fn highlight(mut self, highlight: Option<Highlight>) -> Self {
+        self.highlight = highlight;
+        self
+    }
+
+    /// Overrides the theme's style of the selected row.
+    pub fn highlight_style(mut self, style: Option<HighlightStyle>) -> Self {
+        self.highlight_style = style;
+        self
+    }
 }
 
-impl<'a, R, const W: usize> Widget for Table<'a, R, W>
+impl<'a, R, A> Widget for Table<'a, R, A>
 where
-    R: ToRow<W> + Clone,
+    R: TableRow<A> + Clone,
 {
     fn ui<M>(self, ui: &mut Ui<M>, frame: &mut Frame) -> Response
     where
@@ -359,6 +485,9 @@ where
         let mut response = Response::default();
 
         let (area, area_focus) = ui.next_area().unwrap_or_default();
+        if area.is_empty() {
+            return response;
+        }
 
         let show_scrollbar = self.show_scrollbar && self.items.len() >= area.height.into();
         let has_items = !self.items.is_empty();
@@ -377,27 +506,38 @@ where
             ui.theme.border_style
         };
 
-        let area = render_block(frame, area, self.borders, border_style);
+        let area = render_block(frame, area, self.borders, border_style, ui.theme().charset);
+        if area.is_empty() {
+            return response;
+        }
 
-        if let Some(key) = ui.input_with_key(|_| true) {
+        if let Some((key, count)) = ui.input_with_count(|_| true) {
             let len = self.items.len();
             let page_size = area.height as usize;
 
             match key {
                 Key::Up | Key::Char('k') => {
-                    state.prev();
+                    (0..count).for_each(|_| {
+                        state.prev();
+                    });
                     response.changed = true;
                 }
                 Key::Down | Key::Char('j') => {
-                    state.next(len);
+                    (0..count).for_each(|_| {
+                        state.next(len);
+                    });
                     response.changed = true;
                 }
                 Key::PageUp => {
-                    state.prev_page(page_size);
+                    (0..count).for_each(|_| {
+                        state.prev_page(page_size);
+                    });
                     response.changed = true;
                 }
                 Key::PageDown => {
-                    state.next_page(len, page_size);
+                    (0..count).for_each(|_| {
+                        state.next_page(len, page_size);
+                    });
                     response.changed = true;
                 }
                 Key::Home => {
@@ -410,6 +550,9 @@ where
                 }
                 _ => {}
             }
+            if response.changed {
+                response.key = Some(key);
+            }
         }
 
         let widths: Vec<Constraint> = self
@@ -434,8 +577,9 @@ where
                 .map(|item| {
                     let mut cells = vec![];
                     let mut it = self.columns.iter();
+                    let row = item.cells(self.highlight.as_ref());
 
-                    for cell in item.to_row() {
+                    for cell in row {
                         if let Some(col) = it.next() {
                             if !col.skip && col.displayed(table_area.width as usize) {
                                 cells.push(cell.clone())
@@ -452,10 +596,14 @@ where
             let table = ratatui::widgets::Table::default()
                 .rows(rows)
                 .widths(widths)
-                .column_spacing(1)
-                .row_highlight_style(style::highlight(area_focus));
+                .column_spacing(ui.theme.density.column_spacing())
+                .row_highlight_style(
+                    self.highlight_style
+                        .unwrap_or(ui.theme.highlight_style)
+                        .style(area_focus),
+                );
 
-            let table = if !area_focus && self.dim {
+            let table = if !area_focus && (self.dim || ui.theme.dim_no_focus) {
                 table.dim()
             } else {
                 table
@@ -464,26 +612,137 @@ where
             frame.render_stateful_widget(table, table_area, &mut state.internal);
 
             if show_scrollbar {
-                let content_length = self.items.len();
-                let scroller = Scrollbar::default()
-                    .begin_symbol(None)
-                    .track_symbol(None)
-                    .end_symbol(None)
-                    .thumb_symbol("┃")
-                    .style(if area_focus {
-                        Style::default()
-                    } else {
-                        Style::default().dim()
-                    });
+                scrollbar::render(
+                    frame,
+                    scroller_area,
+                    &ui.theme,
+                    area_focus,
+                    AutoHide::WhenFits,
+                    ScrollPosition::new(
+                        self.items.len(),
+                        table_area.height.into(),
+                        state.internal.offset(),
+                    ),
+                );
+            }
+        } else {
+            let center = layout::centered_rect(area, 50, 10);
+            let hint = Text::from(span::default("Nothing to show"))
+                .centered()
+                .light_magenta()
+                .dim();
 
-                let mut state = ScrollbarState::default()
-                    .content_length(content_length)
-                    .viewport_content_length(1)
-                    .position(state.internal.offset());
+            frame.render_widget(hint, center);
+        }
 
-                frame.render_stateful_widget(scroller, scroller_area, &mut state);
-            }
+        *self.selected = state.selected();
+
+        response
+    }
+}
+
+/// A single-column list of selectable items. It uses the same navigation as
+/// `Table`, but renders each item as one line.
+pub struct List<'a, T> {
+    items: &'a [T],
+    selected: &'a mut Option<usize>,
+    borders: Option<Borders>,
+    highlight_style: Option<HighlightStyle>,
+}
+
+impl<'a, T> List<'a, T>
+where
+    T: fmt::Display,
+{
+    pub fn new(selected: &'a mut Option<usize>, items: &'a [T], borders: Option<Borders>) -> Self {
+        Self {
+            items,
+            selected,
+            borders,
+            highlight_style: None,
+        }
+    }
+
+    /// Overrides the theme's style of the selected item.
+    pub fn highlight_style(mut self, style: Option<HighlightStyle>) -> Self {
+        self.highlight_style = style;
+        self
+    }
+}
+
+impl<'a, T> Widget for List<'a, T>
+where
+    T: fmt::Display,
+{
+    fn ui<M>(self, ui: &mut Ui<M>, frame: &mut Frame) -> Response
+    where
+        M: Clone,
+    {
+        let mut response = Response::default();
+
+        let (area, area_focus) = ui.next_area().unwrap_or_default();
+        if area.is_empty() {
+            return response;
+        }
+
+        let mut state = TableState::new(*self.selected);
+
+        let border_style = if area_focus && ui.has_focus {
+            ui.theme.focus_border_style
         } else {
+            ui.theme.border_style
+        };
+
+        let area = render_block(frame, area, self.borders, border_style, ui.theme().charset);
+        if area.is_empty() {
+            return response;
+        }
+
+        if let Some((key, count)) = ui.input_with_count(|_| true) {
+            let len = self.items.len();
+            let page_size = area.height as usize;
+
+            match key {
+                Key::Up | Key::Char('k') => {
+                    (0..count).for_each(|_| {
+                        state.prev();
+                    });
+                    response.changed = true;
+                }
+                Key::Down | Key::Char('j') => {
+                    (0..count).for_each(|_| {
+                        state.next(len);
+                    });
+                    response.changed = true;
+                }
+                Key::PageUp => {
+                    (0..count).for_each(|_| {
+                        state.prev_page(page_size);
+                    });
+                    response.changed = true;
+                }
+                Key::PageDown => {
+                    (0..count).for_each(|_| {
+                        state.next_page(len, page_size);
+                    });
+                    response.changed = true;
+                }
+                Key::Home => {
+                    state.begin();
+                    response.changed = true;
+                }
+                Key::End => {
+                    state.end(len);
+                    response.changed = true;
+                }
+                _ => {}
+            }
+            if response.changed {
+                response.key = Some(key);
+            }
+        }
+
+        if self.items.is_empty() {
             let center = layout::centered_rect(area, 50, 10);
             let hint = Text::from(span::default("Nothing to show"))
                 .centered()
@@ -491,6 +750,20 @@ where
                 .dim();
 
             frame.render_widget(hint, center);
+        } else {
+            let items = self
+                .items
+                .iter()
+                .map(|item| Line::from(item.to_string()))
+                .collect::<Vec<_>>();
+            let list = ratatui::widgets::List::new(items).highlight_style(
+                self.highlight_style
+                    .unwrap_or(ui.theme.highlight_style)
+                    .style(area_focus),
+            );
+            let mut list_state = ListState::default().with_selected(state.selected());
+
+            frame.render_stateful_widget(list, area, &mut list_state);
         }
 
         *self.selected = state.selected();
@@ -499,14 +772,17 @@ where
     }
 }
 
-pub struct HeaderedTable<'a, R, const W: usize> {
+pub struct HeaderedTable<'a, R, A> {
     items: &'a Vec<R>,
     selected: &'a mut Option<usize>,
     header: Vec<Column<'a>>,
     columns: Vec<Column<'a>>,
+    highlight: Option<Highlight>,
+    highlight_style: Option<HighlightStyle>,
+    phantom: PhantomData<A>,
 }
 
-impl<'a, R, const W: usize> HeaderedTable<'a, R, W> {
+impl<'a, R, A> HeaderedTable<'a, R, A> {
     pub fn new(
         selected: &'a mut Option<usize>,
         items: &'a Vec<R>,
@@ -518,9 +794,23 @@ impl<'a, R, const W: usize> HeaderedTable<'a, R, W> {
             selected,
             header: header.into_iter().collect(),
             columns: columns.into_iter().collect(),
+            highlight: None,
+            highlight_style: None,
+            phantom: PhantomData,
         }
     }
 
+    pub fn highlight(mut self, highlight: Option<Highlight>) -> Self {
+        self.highlight = highlight;
+        self
+    }
+
+    /// Overrides the theme's style of the selected row.
+    pub fn highlight_style(mut self, style: Option<HighlightStyle>) -> Self {
+        self.highlight_style = style;
+        self
+    }
+
     pub fn items(&self) -> &Vec<R> {
         self.items
     }
@@ -528,9 +818,9 @@ impl<'a, R, const W: usize> HeaderedTable<'a, R, W> {
 
 /// TODO(erikli): Implement `show` that returns an `InnerResponse` such that it can
 /// used like a group.
-impl<'a, R, const W: usize> Widget for HeaderedTable<'a, R, W>
+impl<'a, R, A> Widget for HeaderedTable<'a, R, A>
 where
-    R: ToRow<W> + Clone,
+    R: TableRow<A> + Clone,
 {
     fn ui<M>(self, ui: &mut Ui<M>, frame: &mut Frame) -> Response
     where
@@ -544,14 +834,17 @@ where
             |ui| {
                 ui.columns(frame, self.header.clone().to_vec(), Some(Borders::Top));
 
-                let table = ui.table(
-                    frame,
+                let table = Table::<R, A>::new(
                     self.selected,
                     self.items,
                     self.columns.to_vec(),
                     Some(Borders::BottomSides),
-                );
+                )
+                .highlight(self.highlight)
+                .highlight_style(self.highlight_style);
+                let table = table.ui(ui, frame);
                 response.changed |= table.changed;
+                response.key = response.key.or(table.key);
             },
         );
 
@@ -576,14 +869,19 @@ impl<'a> Widget for Columns<'a> {
         M: Clone,
     {
         let (area, _) = ui.next_area().unwrap_or_default();
+        ui.set_focusable(false);
+        if area.is_empty() {
+            return Response::default();
+        }
 
         let border_style = if ui.has_focus {
             ui.theme.focus_border_style
         } else {
             ui.theme.border_style
         };
+        let border_style = ui.theme.density.separator_style(border_style);
 
-        let area = render_block(frame, area, self.borders, border_style);
+        let area = render_block(frame, area, self.borders, border_style, ui.theme().charset);
         let area = Rect {
             width: area.width.saturating_sub(1),
             ..area
@@ -609,7 +907,7 @@ impl<'a> Widget for Columns<'a> {
             .collect::<Vec<_>>();
 
         let table = ratatui::widgets::Table::default()
-            .column_spacing(1)
+            .column_spacing(ui.theme.density.column_spacing())
             .rows([Row::new(cells)])
             .widths(widths);
         frame.render_widget(table, area);
@@ -635,6 +933,10 @@ impl<'a> Widget for Bar<'a> {
         M: Clone,
     {
         let (area, area_focus) = ui.next_area().unwrap_or_default();
+        ui.set_focusable(false);
+        if area.is_empty() {
+            return Response::default();
+        }
 
         let border_style = if area_focus {
             ui.theme.focus_border_style
@@ -649,7 +951,7 @@ impl<'a> Widget for Bar<'a> {
             .map(|c| c.text.clone())
             .collect::<Vec<_>>();
 
-        let area = render_block(frame, area, self.borders, border_style);
+        let area = render_block(frame, area, self.borders, border_style, ui.theme().charset);
         let table = ratatui::widgets::Table::default()
             .header(Row::new(cells))
             .widths(widths)
@@ -660,108 +962,446 @@ impl<'a> Widget for Bar<'a> {
     }
 }
 
-#[derive(Clone, Debug)]
-pub struct TextViewState {
-    cursor: Position,
+/// The minimum width of the bar rendered by a `Gauge`. If there is less space
+/// available next to its label, only the label is rendered.
+pub const GAUGE_MIN_WIDTH: u16 = 10;
+
+/// A horizontal bar that is filled according to a ratio and labeled with the
+/// percentage. The given label is rendered right next to the bar.
+pub struct Gauge<'a> {
+    ratio: f64,
+    label: Span<'a>,
 }
 
-impl TextViewState {
-    pub fn new(cursor: Position) -> Self {
-        Self { cursor }
+impl<'a> Gauge<'a> {
+    pub fn new(ratio: f64, label: impl Into<Span<'a>>) -> Self {
+        Self {
+            ratio,
+            label: label.into(),
+        }
     }
+}
 
-    pub fn cursor(&self) -> Position {
-        self.cursor
+impl<'a> Widget for Gauge<'a> {
+    fn ui<M>(self, ui: &mut Ui<M>, frame: &mut Frame) -> Response
+    where
+        M: Clone,
+    {
+        let (area, _) = ui.next_area().unwrap_or_default();
+        ui.set_focusable(false);
+        if area.is_empty() {
+            return Response::default();
+        }
+
+        let label_width = self.label.width() as u16;
+        if area.width < label_width.saturating_add(GAUGE_MIN_WIDTH) {
+            frame.render_widget(self.label.into_right_aligned_line(), area);
+            return Response::default();
+        }
+
+        let ratio = if self.ratio.is_finite() {
+            self.ratio.clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        let percentage = format!("{}%", (ratio * 100.0).round() as u16);
+
+        let [bar_area, label_area] =
+            Layout::horizontal([Constraint::Fill(1), Constraint::Length(label_width)]).areas(area);
+        let gauge = ratatui::widgets::Gauge::default()
+            .ratio(ratio)
+            .label(percentage)
+            .gauge_style(ui.theme.gauge_style)
+            .use_unicode(true);
+
+        frame.render_widget(gauge, bar_area);
+        frame.render_widget(Line::from(self.label), label_area);
+
+        Response::default()
     }
 }
 
-impl TextViewState {
-    fn scroll_up(&mut self) {
-        self.cursor.x = self.cursor.x.saturating_sub(1);
-    }
+/// Renders counts, e.g. of events per day, as a single row of bars whose heights
+/// are relative to the largest count. The latest counts are expected last and
+/// rendered right-aligned; older ones are cut off if the area is too narrow.
+pub struct Sparkline<'a> {
+    counts: &'a [u64],
+}
 
-    fn scroll_down(&mut self, len: usize, page_size: usize) {
-        let end = len.saturating_sub(page_size);
-        self.cursor.x = std::cmp::min(self.cursor.x.saturating_add(1), end as u16);
-    }
+impl<'a> Sparkline<'a> {
+    pub fn new(counts: &'a [u64]) -> Self {
+        Self { counts }
+    }
+
+    fn line(&self, width: usize, charset: Charset) -> Line<'static> {
+        let bars = charset.bar_set();
+        let levels = [
+            bars.empty,
+            bars.one_eighth,
+            bars.one_quarter,
+            bars.three_eighths,
+            bars.half,
+            bars.five_eighths,
+            bars.three_quarters,
+            bars.seven_eighths,
+            bars.full,
+        ];
+        let counts = &self.counts[self.counts.len().saturating_sub(width)..];
+        let max = counts.iter().copied().max().unwrap_or_default();
+
+        let symbols = counts
+            .iter()
+            .map(|count| match count {
+                0 => levels[0],
+                // Non-zero counts take at least the lowest bar.
+                count => levels[(count * 8).div_ceil(max) as usize],
+            })
+            .collect::<String>();
 
-    fn scroll_left(&mut self) {
-        self.cursor.y = self.cursor.y.saturating_sub(3);
+        Line::from(symbols).right_aligned()
     }
+}
 
-    fn scroll_right(&mut self, max_line_length: usize) {
-        self.cursor.y = std::cmp::min(
-            self.cursor.y.saturating_add(3),
-            max_line_length.saturating_add(3) as u16,
-        );
-    }
+impl<'a> Widget for Sparkline<'a> {
+    fn ui<M>(self, ui: &mut Ui<M>, frame: &mut Frame) -> Response
+    where
+        M: Clone,
+    {
+        let (area, _) = ui.next_area().unwrap_or_default();
+        ui.set_focusable(false);
+        if area.is_empty() {
+            return Response::default();
+        }
 
-    fn prev_page(&mut self, page_size: usize) {
-        self.cursor.x = self.cursor.x.saturating_sub(page_size as u16);
+        let line = self
+            .line(area.width.into(), ui.theme.charset)
+            .style(ui.theme.gauge_style);
+        frame.render_widget(line, area);
+
+        Response::default()
     }
+}
 
-    fn next_page(&mut self, len: usize, page_size: usize) {
-        let end = len.saturating_sub(page_size);
+/// A modal that asks the user to confirm (`y`, `Enter`) or to cancel (`n`, `Esc`)
+/// an action. It's rendered on top of everything rendered before, centered in the
+/// area of the `Ui` it's added to, and does not take an area from its layout.
+pub struct Confirm<'a> {
+    title: String,
+    message: Text<'a>,
+    answer: &'a mut Option<bool>,
+}
 
-        self.cursor.x = std::cmp::min(self.cursor.x.saturating_add(page_size as u16), end as u16);
+impl<'a> Confirm<'a> {
+    pub fn new(
+        title: impl ToString,
+        message: impl Into<Text<'a>>,
+        answer: &'a mut Option<bool>,
+    ) -> Self {
+        Self {
+            title: title.to_string(),
+            message: message.into(),
+            answer,
+        }
     }
+}
 
-    fn begin(&mut self) {
-        self.cursor.x = 0;
-    }
+impl<'a> Widget for Confirm<'a> {
+    fn ui<M>(self, ui: &mut Ui<M>, frame: &mut Frame) -> Response
+    where
+        M: Clone,
+    {
+        let mut response = Response::default();
 
-    fn end(&mut self, len: usize, page_size: usize) {
-        self.cursor.x = len.saturating_sub(page_size) as u16;
+        let hint = Line::from(
+            [
+                span::default("y").bold(),
+                span::default(" yes "),
+                span::default("∙").dim(),
+                span::default(" n").bold(),
+                span::default(" no"),
+            ]
+            .to_vec(),
+        );
+        let content_width = self
+            .message
+            .lines
+            .iter()
+            .map(|line| line.width())
+            .chain([hint.width(), self.title.chars().count().saturating_add(2)])
+            .max()
+            .unwrap_or_default() as u16;
+
+        let area = modal_area(
+            ui.area(),
+            content_width.saturating_add(4),
+            (self.message.lines.len() as u16).saturating_add(4),
+        );
+
+        if !area.is_empty() {
+            let block = Block::default()
+                .title(format!(" {} ", self.title))
+                .borders(ratatui::widgets::Borders::ALL)
+                .border_style(ui.theme.focus_border_style)
+                .border_set(ui.theme.charset.border_set(BorderType::Rounded))
+                .padding(ratatui::widgets::Padding::horizontal(1));
+            let lines = [self.message.lines, [Line::default(), hint].to_vec()].concat();
+
+            frame.render_widget(ratatui::widgets::Clear, area);
+            frame.render_widget(Paragraph::new(lines).block(block), area);
+        }
+
+        if ui.input_global(|key| matches!(key, Key::Char('y') | Key::Char('\n'))) {
+            *self.answer = Some(true);
+            response.changed = true;
+        } else if ui.input_global(|key| matches!(key, Key::Char('n') | Key::Esc)) {
+            *self.answer = Some(false);
+            response.changed = true;
+        }
+
+        response
     }
 }
 
-pub struct TextView<'a> {
+/// A modal that shows the given text until it's closed (`Esc`, `Enter`). Like
+/// `Confirm`, it's rendered on top of everything rendered before and centered in
+/// the area of the `Ui` it's added to. Lines that don't fit are cut off.
+pub struct Popup<'a> {
+    title: String,
     text: Text<'a>,
-    borders: Option<Borders>,
-    cursor: &'a mut Position,
+    open: &'a mut bool,
 }
 
-impl<'a> TextView<'a> {
-    pub fn new(
-        text: impl Into<Text<'a>>,
-        cursor: &'a mut Position,
-        borders: Option<Borders>,
-    ) -> Self {
+impl<'a> Popup<'a> {
+    pub fn new(title: impl ToString, text: impl Into<Text<'a>>, open: &'a mut bool) -> Self {
         Self {
+            title: title.to_string(),
             text: text.into(),
-            borders,
-            cursor,
+            open,
         }
     }
 }
 
-impl<'a> Widget for TextView<'a> {
+impl<'a> Widget for Popup<'a> {
     fn ui<M>(self, ui: &mut Ui<M>, frame: &mut Frame) -> Response
     where
         M: Clone,
     {
         let mut response = Response::default();
 
-        let (area, area_focus) = ui.next_area().unwrap_or_default();
+        let hint = Line::from([span::default("esc").bold(), span::default(" close")].to_vec());
+        let content_width = self
+            .text
+            .lines
+            .iter()
+            .map(|line| line.width())
+            .chain([hint.width(), self.title.chars().count().saturating_add(2)])
+            .max()
+            .unwrap_or_default() as u16;
+
+        let area = modal_area(
+            ui.area(),
+            content_width.saturating_add(4),
+            (self.text.lines.len() as u16).saturating_add(4),
+        );
 
-        let show_scrollbar = true;
-        let border_style = if area_focus && ui.has_focus() {
-            ui.theme.focus_border_style
+        if !area.is_empty() {
+            let block = Block::default()
+                .title(format!(" {} ", self.title))
+                .borders(ratatui::widgets::Borders::ALL)
+                .border_style(ui.theme.focus_border_style)
+                .border_set(ui.theme.charset.border_set(BorderType::Rounded))
+                .padding(ratatui::widgets::Padding::horizontal(1));
+            let [text_area, hint_area] =
+                Layout::vertical([Constraint::Fill(1), Constraint::Length(2)])
+                    .areas(block.inner(area));
+
+            frame.render_widget(ratatui::widgets::Clear, area);
+            frame.render_widget(block, area);
+            frame.render_widget(Paragraph::new(self.text), text_area);
+            frame.render_widget(Paragraph::new([Line::default(), hint].to_vec()), hint_area);
+        }
+
+        if ui.input_global(|key| matches!(key, Key::Esc | Key::Char('\n'))) {
+            *self.open = false;
+            response.changed = true;
+        }
+
+        response
+    }
+}
+
+/// Returns an area of the given size, centered in `area` and clamped to it.
+fn modal_area(area: Rect, width: u16, height: u16) -> Rect {
+    let [_, area, _] = Layout::vertical([
+        Constraint::Fill(1),
+        Constraint::Length(height.min(area.height)),
+        Constraint::Fill(1),
+    ])
+    .areas(area);
+    let [_, area, _] = Layout::horizontal([
+        Constraint::Fill(1),
+        Constraint::Length(width.min(area.width)),
+        Constraint::Fill(1),
+    ])
+    .areas(area);
+
+    area
+}
+
+#[derive(Clone, Debug)]
+pub struct TextViewState {
+    cursor: Position,
+}
+
+impl TextViewState {
+    pub fn new(cursor: Position) -> Self {
+        Self { cursor }
+    }
+
+    pub fn cursor(&self) -> Position {
+        self.cursor
+    }
+}
+
+impl TextViewState {
+    pub(crate) fn scroll_up(&mut self) {
+        self.cursor.x = self.cursor.x.saturating_sub(1);
+    }
+
+    pub(crate) fn scroll_down(&mut self, len: usize, page_size: usize) {
+        let end = len.saturating_sub(page_size);
+        self.cursor.x = std::cmp::min(self.cursor.x.saturating_add(1), end as u16);
+    }
+
+    pub(crate) fn scroll_left(&mut self) {
+        self.cursor.y = self.cursor.y.saturating_sub(3);
+    }
+
+    pub(crate) fn scroll_right(&mut self, max_line_length: usize) {
+        self.cursor.y = std::cmp::min(
+            self.cursor.y.saturating_add(3),
+            max_line_length.saturating_add(3) as u16,
+        );
+    }
+
+    pub(crate) fn prev_page(&mut self, page_size: usize) {
+        self.cursor.x = self.cursor.x.saturating_sub(page_size as u16);
+    }
+
+    pub(crate) fn next_page(&mut self, len: usize, page_size: usize) {
+        let end = len.saturating_sub(page_size);
+
+        self.cursor.x = std::cmp::min(self.cursor.x.saturating_add(page_size as u16), end as u16);
+    }
+
+    pub(crate) fn begin(&mut self) {
+        self.cursor.x = 0;
+    }
+
+    pub(crate) fn end(&mut self, len: usize, page_size: usize) {
+        self.cursor.x = len.saturating_sub(page_size) as u16;
+    }
+}
+
+pub struct TextView<'a> {
+    text: Text<'a>,
+    borders: Option<Borders>,
+    cursor: &'a mut Position,
+    ansi: bool,
+}
+
+impl<'a> TextView<'a> {
+    pub fn new(
+        text: impl Into<Text<'a>>,
+        cursor: &'a mut Position,
+        borders: Option<Borders>,
+    ) -> Self {
+        Self {
+            text: text.into(),
+            borders,
+            cursor,
+            ansi: false,
+        }
+    }
+
+    /// Renders ANSI SGR sequences in the text, e.g. of colored command output,
+    /// as styles instead of literally.
+    pub fn ansi(mut self, ansi: bool) -> Self {
+        self.ansi = ansi;
+        self
+    }
+}
+
+/// Converts the ANSI SGR sequences of the given text into styled spans. An
+/// escape sequence that is cut off at the end, e.g. of a truncated buffer, is
+/// dropped. If the text can't be parsed, it's returned as is.
+pub fn ansi_text<'a>(text: Text<'a>) -> Text<'a> {
+    let content = text
+        .lines
+        .iter()
+        .map(|line| line.to_string())
+        .collect::<Vec<_>>()
+        .join("\n");
+    if !content.contains('\x1b') {
+        return text;
+    }
+
+    // A control sequence ends with a byte in `@..=~` after its introducer, so a
+    // trailing one without it is incomplete.
+    let content = match content.rfind('\x1b') {
+        Some(start)
+            if !content[start + 1..]
+                .strip_prefix('[')
+                .is_some_and(|rest| rest.contains(|c| ('@'..='~').contains(&c))) =>
+        {
+            &content[..start]
+        }
+        _ => content.as_str(),
+    };
+
+    match content.into_text() {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            log::warn!("Failed to parse ANSI sequences: {}", err);
+            text
+        }
+    }
+}
+
+impl<'a> Widget for TextView<'a> {
+    fn ui<M>(self, ui: &mut Ui<M>, frame: &mut Frame) -> Response
+    where
+        M: Clone,
+    {
+        let mut response = Response::default();
+
+        let (area, area_focus) = ui.next_area().unwrap_or_default();
+        if area.is_empty() {
+            return response;
+        }
+
+        let show_scrollbar = true;
+        let border_style = if area_focus && ui.has_focus() {
+            ui.theme.focus_border_style
         } else {
             ui.theme.border_style
         };
-        let length = self.text.lines.len();
-        // let virtual_length = length * ((length as f64).log2() as usize) / 100;
-        // let content_length = area.height as usize + virtual_length;
-        // let content_length = length;
-        let content_length = area.height as usize;
+        let text = if self.ansi {
+            ansi_text(self.text)
+        } else {
+            self.text
+        };
+        let length = text.lines.len();
 
-        let area = render_block(frame, area, self.borders, border_style);
+        let area = render_block(frame, area, self.borders, border_style, ui.theme().charset);
         let area = Rect {
             x: area.x.saturating_add(1),
             width: area.width.saturating_sub(1),
             ..area
         };
+        if area.is_empty() {
+            return response;
+        }
         let [text_area, scroller_area] = Layout::horizontal([
             Constraint::Min(1),
             if show_scrollbar {
@@ -772,75 +1412,178 @@ impl<'a> Widget for TextView<'a> {
         ])
         .areas(area);
 
-        let scroller = Scrollbar::default()
-            .begin_symbol(None)
-            .track_symbol(None)
-            .end_symbol(None)
-            .thumb_symbol("┃")
-            .style(if area_focus {
-                Style::default()
-            } else {
-                Style::default().dim()
-            });
-
-        let mut scroller_state = ScrollbarState::default()
-            .content_length(length.saturating_sub(content_length))
-            .viewport_content_length(1)
-            .position(self.cursor.x as usize);
-
-        frame.render_stateful_widget(scroller, scroller_area, &mut scroller_state);
+        scrollbar::render(
+            frame,
+            scroller_area,
+            &ui.theme,
+            area_focus,
+            AutoHide::WhenFits,
+            ScrollPosition::new(length, text_area.height.into(), self.cursor.x as usize),
+        );
         frame.render_widget(
-            Paragraph::new(self.text.clone()).scroll((self.cursor.x, self.cursor.y)),
+            Paragraph::new(text.clone()).scroll((self.cursor.x, self.cursor.y)),
             text_area,
         );
 
-        let mut state = TextViewState::new(*self.cursor);
+        let cursor = *self.cursor;
+        response.key = scroll_with_input(ui, self.cursor, length, text_area.as_size(), || {
+            max_line_len(&text.lines)
+        });
+        response.changed = *self.cursor != cursor;
 
-        if let Some(key) = ui.input_with_key(|_| true) {
-            let lines = self.text.lines.clone();
-            let len = lines.clone().len();
-            let max_line_len = lines
-                .into_iter()
-                .map(|l| l.to_string().chars().count())
-                .max()
-                .unwrap_or_default();
-            let page_size = area.height as usize;
+        response
+    }
+}
 
-            match key {
-                Key::Up | Key::Char('k') => {
-                    state.scroll_up();
-                }
-                Key::Down | Key::Char('j') => {
-                    state.scroll_down(len, page_size);
-                }
-                Key::Left | Key::Char('h') => {
-                    state.scroll_left();
-                }
-                Key::Right | Key::Char('l') => {
-                    state.scroll_right(max_line_len.saturating_sub(area.height.into()));
-                }
-                Key::PageUp => {
-                    state.prev_page(page_size);
-                }
-                Key::PageDown => {
-                    state.next_page(len, page_size);
-                }
-                Key::Home => {
-                    state.begin();
-                }
-                Key::End => {
-                    state.end(len, page_size);
-                }
-                _ => {}
-            }
-            *self.cursor = state.cursor;
-            response.changed = true;
+/// A vertically scrollable area of `len` lines, which are built by `line` from
+/// their index. Other than a `TextView`, it only builds the lines that are
+/// visible, which makes it suitable for very long content like large patch
+/// descriptions. Lines are not wrapped, and horizontal scrolling is limited
+/// by the longest visible line.
+pub struct ScrollArea<'a, F> {
+    len: usize,
+    line: F,
+    borders: Option<Borders>,
+    cursor: &'a mut Position,
+}
+
+impl<'a, F> ScrollArea<'a, F>
+where
+    F: Fn(usize) -> Line<'a>,
+{
+    pub fn new(len: usize, line: F, cursor: &'a mut Position, borders: Option<Borders>) -> Self {
+        Self {
+            len,
+            line,
+            borders,
+            cursor,
+        }
+    }
+}
+
+impl<'a, F> Widget for ScrollArea<'a, F>
+where
+    F: Fn(usize) -> Line<'a>,
+{
+    fn ui<M>(self, ui: &mut Ui<M>, frame: &mut Frame) -> Response
+    where
+        M: Clone,
+    {
+        let mut response = Response::default();
+
+        let (area, area_focus) = ui.next_area().unwrap_or_default();
+        if area.is_empty() {
+            return response;
+        }
+
+        let border_style = if area_focus && ui.has_focus() {
+            ui.theme.focus_border_style
+        } else {
+            ui.theme.border_style
+        };
+
+        let area = render_block(frame, area, self.borders, border_style, ui.theme().charset);
+        let area = Rect {
+            x: area.x.saturating_add(1),
+            width: area.width.saturating_sub(1),
+            ..area
+        };
+        if area.is_empty() {
+            return response;
         }
+        let [text_area, scroller_area] =
+            Layout::horizontal([Constraint::Min(1), Constraint::Length(1)]).areas(area);
+
+        // The scrollbar is computed against all lines, but only the visible ones
+        // are built.
+        let offset = cmp::min(self.cursor.x as usize, self.len);
+        let end = cmp::min(offset.saturating_add(text_area.height as usize), self.len);
+        let visible = (offset..end).map(&self.line).collect::<Vec<_>>();
+
+        scrollbar::render(
+            frame,
+            scroller_area,
+            &ui.theme,
+            area_focus,
+            AutoHide::WhenFits,
+            ScrollPosition::new(self.len, text_area.height.into(), offset),
+        );
+        frame.render_widget(
+            Paragraph::new(visible.clone()).scroll((0, self.cursor.y)),
+            text_area,
+        );
+
+        let cursor = *self.cursor;
+        response.key = scroll_with_input(ui, self.cursor, self.len, text_area.as_size(), || {
+            max_line_len(&visible)
+        });
+        response.changed = *self.cursor != cursor;
 
         response
     }
 }
 
+pub(crate) fn max_line_len(lines: &[Line]) -> usize {
+    lines
+        .iter()
+        .map(|l| l.to_string().chars().count())
+        .max()
+        .unwrap_or_default()
+}
+
+/// Moves the scroll `cursor` of a view showing `len` lines on a `page` of
+/// text, if a navigation key was pressed, as often as the count typed before it.
+/// Scrolling to the right is limited by how far the longest line exceeds the
+/// width of the page. Returns the key that was handled.
+pub(crate) fn scroll_with_input<M>(
+    ui: &mut Ui<M>,
+    cursor: &mut Position,
+    len: usize,
+    page: Size,
+    max_line_len: impl FnOnce() -> usize,
+) -> Option<Key>
+where
+    M: Clone,
+{
+    let (key, count) = ui.input_with_count(|_| true)?;
+
+    let mut state = TextViewState::new(*cursor);
+    let max_line_len = max_line_len();
+    let page_size = page.height as usize;
+    let page_width = page.width as usize;
+
+    match key {
+        Key::Up | Key::Char('k') => {
+            (0..count).for_each(|_| state.scroll_up());
+        }
+        Key::Down | Key::Char('j') => {
+            (0..count).for_each(|_| state.scroll_down(len, page_size));
+        }
+        Key::Left | Key::Char('h') => {
+            (0..count).for_each(|_| state.scroll_left());
+        }
+        Key::Right | Key::Char('l') => {
+            (0..count).for_each(|_| state.scroll_right(max_line_len.saturating_sub(page_width)));
+        }
+        Key::PageUp => {
+            (0..count).for_each(|_| state.prev_page(page_size));
+        }
+        Key::PageDown => {
+            (0..count).for_each(|_| state.next_page(len, page_size));
+        }
+        Key::Home => {
+            state.begin();
+        }
+        Key::End => {
+            state.end(len, page_size);
+        }
+        _ => {}
+    }
+    *cursor = state.cursor;
+
+    Some(key)
+}
+
 pub struct CenteredTextView<'a> {
     content: Text<'a>,
     borders: Option<Borders>,
@@ -858,6 +1601,9 @@ impl<'a> CenteredTextView<'a> {
 impl<'a> Widget for CenteredTextView<'a> {
     fn ui<M>(self, ui: &mut Ui<M>, frame: &mut Frame) -> Response {
         let (area, area_focus) = ui.next_area().unwrap_or_default();
+        if area.is_empty() {
+            return Response::default();
+        }
 
         let border_style = if area_focus && ui.has_focus() {
             ui.theme.focus_border_style
@@ -865,7 +1611,7 @@ impl<'a> Widget for CenteredTextView<'a> {
             ui.theme.border_style
         };
 
-        let area = render_block(frame, area, self.borders, border_style);
+        let area = render_block(frame, area, self.borders, border_style, ui.theme().charset);
         let area = Rect {
             x: area.x.saturating_add(1),
             width: area.width.saturating_sub(1),
@@ -902,6 +1648,20 @@ impl TextEditState {
         self.move_cursor_right();
     }
 
+    /// Inserts the given text at the cursor in a single edit and moves the cursor
+    /// behind it. The cursor counts chars, not bytes.
+    fn insert_str(&mut self, text: &str) {
+        let index = self
+            .text
+            .char_indices()
+            .nth(self.cursor)
+            .map(|(index, _)| index)
+            .unwrap_or(self.text.len());
+
+        self.text.insert_str(index, text);
+        self.cursor = self.cursor.saturating_add(text.chars().count());
+    }
+
     fn delete_char_right(&mut self) {
         self.text = self.text.clone();
 
@@ -1001,7 +1761,17 @@ impl<'a> TextEdit<'a> {
             ui.theme.border_style
         };
 
-        let area = render_block(frame, area, self.borders, border_style);
+        let area = render_block(frame, area, self.borders, border_style, ui.theme().charset);
+        // The input and its overline need at least two rows.
+        if area.height < 2 || area.width == 0 {
+            return TextEditOutput {
+                response,
+                state: TextEditState {
+                    text: self.text.to_string(),
+                    cursor: *self.cursor,
+                },
+            };
+        }
 
         let layout = Layout::vertical(Constraint::from_lengths([1, 1])).split(area);
 
@@ -1011,7 +1781,7 @@ impl<'a> TextEdit<'a> {
         };
 
         let label_content = format!(" {} ", self.label.unwrap_or_default());
-        let overline = String::from("▔").repeat(area.width as usize);
+        let overline = ui.theme().charset.overline().repeat(area.width as usize);
         let cursor_pos = *self.cursor as u16;
 
         let (label, input, overline) = if !area_focus && self.dim {
@@ -1043,7 +1813,8 @@ impl<'a> TextEdit<'a> {
             frame.render_widget(overline, layout[1]);
 
             if self.show_cursor {
-                let position = Position::new(top_layout[2].x + cursor_pos, top_layout[2].y);
+                let position =
+                    Position::new(top_layout[2].x.saturating_add(cursor_pos), top_layout[2].y);
                 frame.set_cursor_position(position)
             }
         } else {
@@ -1054,11 +1825,16 @@ impl<'a> TextEdit<'a> {
             frame.render_widget(bottom, layout[1]);
 
             if self.show_cursor {
-                let position = Position::new(area.x + cursor_pos, area.y);
+                let position = Position::new(area.x.saturating_add(cursor_pos), area.y);
                 frame.set_cursor_position(position);
             }
         }
 
+        if let Some(paste) = ui.input_paste() {
+            state.insert_str(&event::single_line(&paste));
+            response.changed = true;
+        }
+
         if let Some(key) = ui.input_with_key(|_| true) {
             match key {
                 Key::Char(to_insert)
@@ -1083,6 +1859,7 @@ impl<'a> TextEdit<'a> {
                 _ => {}
             }
             response.changed = true;
+            response.key = Some(key);
         }
 
         *self.text = state.text.clone();
@@ -1102,17 +1879,17 @@ impl<'a> Widget for TextEdit<'a> {
 }
 
 pub struct Shortcuts {
-    pub shortcuts: Vec<(String, String)>,
+    pub shortcuts: Vec<Shortcut>,
     pub divider: char,
 }
 
 impl Shortcuts {
-    pub fn new(shortcuts: &[(&str, &str)], divider: char) -> Self {
+    pub fn new<S>(shortcuts: impl IntoIterator<Item = S>, divider: char) -> Self
+    where
+        S: Into<Shortcut>,
+    {
         Self {
-            shortcuts: shortcuts
-                .iter()
-                .map(|(s, a)| (s.to_string(), a.to_string()))
-                .collect(),
+            shortcuts: shortcuts.into_iter().map(Into::into).collect(),
             divider,
         }
     }
@@ -1123,48 +1900,33 @@ impl Widget for Shortcuts {
     where
         M: Clone,
     {
-        use ratatui::widgets::Table;
-
         let (area, _) = ui.next_area().unwrap_or_default();
-
-        let mut shortcuts = self.shortcuts.iter().peekable();
-        let mut row = vec![];
-
-        while let Some(shortcut) = shortcuts.next() {
-            let short = Text::from(shortcut.0.clone()).style(ui.theme.shortcuts_keys_style);
-            let long = Text::from(shortcut.1.clone()).style(ui.theme.shortcuts_action_style);
-            let spacer = Text::from(String::new());
-            let divider = Text::from(format!(" {} ", self.divider)).style(style::gray().dim());
-
-            row.push((shortcut.0.chars().count(), short));
-            row.push((1, spacer));
-            row.push((shortcut.1.chars().count(), long));
-
-            if shortcuts.peek().is_some() {
-                row.push((3, divider));
-            }
+        ui.set_focusable(false);
+        if area.is_empty() {
+            return Response::default();
         }
 
-        let row_copy = row.clone();
-        let row: Vec<Text<'_>> = row_copy
-            .clone()
-            .iter()
-            .map(|(_, text)| text.clone())
-            .collect();
-        let widths: Vec<Constraint> = row_copy
-            .clone()
-            .iter()
-            .map(|(width, _)| Constraint::Length(*width as u16))
-            .collect();
-        let table = Table::new([Row::new(row)], widths).column_spacing(0);
-
-        frame.render_widget(table, area);
+        let lines = shortcut_lines(
+            &self.shortcuts,
+            self.divider,
+            area.width.into(),
+            area.height.into(),
+            ui.theme.shortcuts_keys_style,
+            ui.theme.shortcuts_action_style,
+        );
+        frame.render_widget(Text::from(lines), area);
 
         Response::default()
     }
 }
 
-fn render_block(frame: &mut Frame, area: Rect, borders: Option<Borders>, style: Style) -> Rect {
+pub(crate) fn render_block(
+    frame: &mut Frame,
+    area: Rect,
+    borders: Option<Borders>,
+    style: Style,
+    charset: Charset,
+) -> Rect {
     if let Some(border) = borders {
         match border {
             Borders::None => area,
@@ -1179,7 +1941,7 @@ fn render_block(frame: &mut Frame, area: Rect, borders: Option<Borders>, style:
             Borders::All => {
                 let block = Block::default()
                     .border_style(style)
-                    .border_type(BorderType::Rounded)
+                    .border_set(charset.border_set(BorderType::Rounded))
                     .borders(ratatui::widgets::Borders::ALL);
                 frame.render_widget(block.clone(), area);
 
@@ -1189,6 +1951,7 @@ fn render_block(frame: &mut Frame, area: Rect, borders: Option<Borders>, style:
                 let block = HeaderBlock::default()
                     .border_style(style)
                     .border_type(BorderType::Rounded)
+                    .charset(charset)
                     .borders(ratatui::widgets::Borders::ALL);
                 frame.render_widget(block, area);
 
@@ -1204,7 +1967,7 @@ fn render_block(frame: &mut Frame, area: Rect, borders: Option<Borders>, style:
             Borders::Sides => {
                 let block = Block::default()
                     .border_style(style)
-                    .border_type(BorderType::Rounded)
+                    .border_set(charset.border_set(BorderType::Rounded))
                     .borders(ratatui::widgets::Borders::LEFT | ratatui::widgets::Borders::RIGHT);
                 frame.render_widget(block.clone(), area);
 
@@ -1220,6 +1983,7 @@ fn render_block(frame: &mut Frame, area: Rect, borders: Option<Borders>, style:
 
                 let footer_block = FooterBlock::default()
                     .border_style(style)
+                    .charset(charset)
                     .block_type(FooterBlockType::Single { top: true });
                 frame.render_widget(footer_block, area);
 
@@ -1234,6 +1998,7 @@ fn render_block(frame: &mut Frame, area: Rect, borders: Option<Borders>, style:
 
                 let footer_block = FooterBlock::default()
                     .border_style(style)
+                    .charset(charset)
                     .block_type(FooterBlockType::Single { top: false });
                 frame.render_widget(footer_block, area);
 
@@ -1247,3 +2012,683 @@ fn render_block(frame: &mut Frame, area: Rect, borders: Option<Borders>, style:
         area
     }
 }
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use ratatui::layout::{Constraint, Layout, Position};
+    use ratatui::style::{Color, Modifier, Style};
+    use ratatui::text::{Line, Text};
+    use ratatui::widgets::Cell;
+    use termion::event::Key;
+
+    use crate::ui::im::testing::{lines, Item};
+    use crate::ui::im::{render_once, Borders, Context};
+    use crate::ui::theme::{Charset, Density, HighlightStyle, Theme};
+
+    use super::{ansi_text, PanesState, Separator, Table, TextEdit, TextView};
+    use crate::ui::{Column, ToRow};
+
+    #[derive(Clone)]
+    struct Pair(&'static str, &'static str);
+
+    impl ToRow<2> for Pair {
+        fn to_row(&self) -> [Cell; 2] {
+            [Cell::from(self.0), Cell::from(self.1)]
+        }
+    }
+
+    fn columns() -> Vec<Column<'static>> {
+        [Column::new("", Constraint::Fill(1))].to_vec()
+    }
+
+    #[test]
+    fn popup_should_render_text_until_closed() -> anyhow::Result<()> {
+        let render = |key: Key| -> anyhow::Result<(bool, Vec<String>)> {
+            let mut open = true;
+            let ctx = Context::<()>::default().with_inputs([key].into());
+
+            let buffer = render_once(30, 8, ctx, |ui, frame| {
+                ui.popup(frame, "Blame", "a1b2c3d alice", &mut open);
+            })?;
+
+            Ok((open, lines(&buffer)))
+        };
+
+        let (open, lines) = render(Key::Esc)?;
+        let row = |text: &str| lines.iter().position(|line| line.contains(text));
+        assert!(!open);
+        assert!(row("a1b2c3d alice").is_some());
+        assert_eq!(row("╭ Blame "), row("a1b2c3d alice").map(|row| row - 1));
+        assert_eq!(row("esc close"), row("a1b2c3d alice").map(|row| row + 2));
+
+        assert!(!render(Key::Char('\n'))?.0);
+        assert!(render(Key::Char('q'))?.0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn table_should_render_items_with_focus_border() -> anyhow::Result<()> {
+        let items = vec![Item("foo"), Item("bar")];
+        let mut selected = Some(0);
+
+        let buffer = render_once(20, 5, Context::<()>::default(), |ui, frame| {
+            ui.table(frame, &mut selected, &items, columns(), Some(Borders::All));
+        })?;
+        let lines = lines(&buffer);
+
+        assert_eq!(lines[0], format!("╭{}╮", "─".repeat(18)));
+        assert!(lines[1].starts_with("│foo "));
+        assert!(lines[2].starts_with("│bar "));
+        assert_eq!(buffer[(0, 0)].fg, Color::Indexed(246));
+
+        Ok(())
+    }
+
+    #[test]
+    fn table_should_render_unfocused_border() -> anyhow::Result<()> {
+        let items = vec![Item("foo")];
+        let mut selected = Some(0);
+
+        let buffer = render_once(20, 5, Context::<()>::default(), |ui, frame| {
+            ui.layout(Layout::vertical([Constraint::Fill(1)]), None, |ui| {
+                ui.table(frame, &mut selected, &items, columns(), Some(Borders::All));
+            });
+        })?;
+
+        assert_eq!(buffer[(0, 0)].symbol(), "╭");
+        assert_eq!(buffer[(0, 0)].fg, Color::Indexed(240));
+
+        Ok(())
+    }
+
+    #[test]
+    fn compact_table_should_not_space_columns() -> anyhow::Result<()> {
+        let items = vec![Pair("foo", "bar")];
+        let columns = [
+            Column::new("", Constraint::Length(3)),
+            Column::new("", Constraint::Length(3)),
+        ]
+        .to_vec();
+        let mut selected = Some(0);
+
+        let buffer = render_once(20, 3, Context::<()>::default(), |ui, frame| {
+            ui.table(frame, &mut selected, &items, columns.clone(), None);
+        })?;
+        assert!(lines(&buffer)[0].starts_with("foo bar"));
+
+        let theme = Theme::default().with_density(Density::Compact);
+        let ctx = Context::<()>::default().with_theme(theme);
+        let buffer = render_once(20, 3, ctx, |ui, frame| {
+            ui.table(frame, &mut selected, &items, columns.clone(), None);
+        })?;
+        assert!(lines(&buffer)[0].starts_with("foobar"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn focused_table_should_use_highlight_style_of_theme() -> anyhow::Result<()> {
+        let items = vec![Item("foo"), Item("bar")];
+        let mut selected = Some(1);
+
+        let theme = Theme::high_contrast();
+        let ctx = Context::<()>::default().with_theme(theme);
+        let buffer = render_once(20, 5, ctx, |ui, frame| {
+            ui.table(frame, &mut selected, &items, columns(), Some(Borders::All));
+        })?;
+
+        assert_eq!(buffer[(1, 1)].bg, Color::Reset);
+        assert_eq!(buffer[(1, 2)].bg, Color::Yellow);
+        assert_eq!(buffer[(1, 2)].fg, Color::Black);
+        assert!(buffer[(1, 2)].modifier.contains(Modifier::BOLD));
+
+        let style = HighlightStyle {
+            focus: Style::default().bg(Color::Green),
+            no_focus: Style::default(),
+        };
+        let buffer = render_once(20, 5, Context::<()>::default(), |ui, frame| {
+            ui.add(
+                frame,
+                Table::new(&mut selected, &items, columns(), Some(Borders::All))
+                    .highlight_style(Some(style)),
+            );
+        })?;
+
+        assert_eq!(buffer[(1, 2)].bg, Color::Green);
+
+        Ok(())
+    }
+
+    #[test]
+    fn table_should_center_empty_message() -> anyhow::Result<()> {
+        let items: Vec<Item> = vec![];
+        let mut selected = None;
+
+        let buffer = render_once(40, 20, Context::<()>::default(), |ui, frame| {
+            ui.table(frame, &mut selected, &items, columns(), Some(Borders::None));
+        })?;
+        let lines = lines(&buffer);
+
+        let rows = lines
+            .iter()
+            .enumerate()
+            .filter(|(_, line)| line.contains("Nothing to show"))
+            .collect::<Vec<_>>();
+        assert_eq!(rows.len(), 1);
+
+        let (row, line) = rows[0];
+        let leading = line.len() - line.trim_start().len();
+        let trailing = line.len() - line.trim_end().len();
+
+        assert!(row > 0 && row < lines.len() - 1);
+        assert!(leading.abs_diff(trailing) <= 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn separator_should_render_title_in_its_row() -> anyhow::Result<()> {
+        let buffer = render_once(20, 3, Context::<()>::default(), |ui, frame| {
+            ui.layout(
+                Layout::vertical([
+                    Constraint::Length(1),
+                    Constraint::Length(1),
+                    Constraint::Length(1),
+                ]),
+                None,
+                |ui| {
+                    ui.label(frame, "foo");
+                    ui.separator(frame, Some("Recent".into()));
+                    ui.add(frame, Separator::new(Some("All".into())).centered());
+                },
+            );
+        })?;
+        assert_eq!(
+            lines(&buffer),
+            [
+                "foo                 ",
+                "── Recent ──────────",
+                "─────── All ────────"
+            ]
+        );
+
+        let buffer = render_once(5, 1, Context::<()>::default(), |ui, frame| {
+            ui.separator(frame, Some("Recent".into()));
+        })?;
+        assert_eq!(lines(&buffer), ["─────"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn paste_should_be_inserted_at_cursor() -> anyhow::Result<()> {
+        let mut text = String::from("authors:[] ✓");
+        let mut cursor = 9;
+        let mut state = None;
+
+        let paste = "did:key:z6MkkpTPzcq1ybmjQyQpyre15JUeMvZY6toxoZVpLZ8YarsB,\ndid:key:z6Mku8hpprWTmCv3BqkssCYDfr2feUdyLSUnycVajFo9XVAx";
+        let ctx = Context::<()>::default().with_paste(paste);
+
+        render_once(80, 2, ctx, |ui, frame| {
+            state = Some(
+                TextEdit::new(&mut text, &mut cursor, None)
+                    .show(ui, frame)
+                    .state,
+            );
+        })?;
+        let state = state.unwrap();
+
+        assert_eq!(
+            state.text,
+            "authors:[did:key:z6MkkpTPzcq1ybmjQyQpyre15JUeMvZY6toxoZVpLZ8YarsB, did:key:z6Mku8hpprWTmCv3BqkssCYDfr2feUdyLSUnycVajFo9XVAx] ✓"
+        );
+        assert_eq!(state.cursor, 9 + paste.chars().count());
+
+        Ok(())
+    }
+
+    #[test]
+    fn shortcuts_should_render_keys_and_actions() -> anyhow::Result<()> {
+        let buffer = render_once(40, 1, Context::<()>::default(), |ui, frame| {
+            ui.shortcuts(frame, &[("q", "quit"), ("?", "help")], '∙');
+        })?;
+        let lines = lines(&buffer);
+
+        assert_eq!(lines[0].trim_end(), "q quit ∙ ? help");
+        assert_eq!(buffer[(0, 0)].fg, Color::Yellow);
+
+        Ok(())
+    }
+
+    #[test]
+    fn narrow_shortcuts_should_keep_quit_and_help() -> anyhow::Result<()> {
+        let shortcuts = [
+            ("enter", "show"),
+            ("c", "checkout"),
+            ("d", "diff"),
+            ("?", "help"),
+            ("esc", "quit"),
+        ];
+        let buffer = render_once(24, 1, Context::<()>::default(), |ui, frame| {
+            ui.shortcuts(frame, &shortcuts, '∙');
+        })?;
+
+        assert_eq!(lines(&buffer)[0].trim_end(), "? help ∙ esc quit ∙ …");
+
+        let buffer = render_once(36, 2, Context::<()>::default(), |ui, frame| {
+            ui.shortcuts(frame, &shortcuts, '∙');
+        })?;
+        let lines = lines(&buffer);
+
+        assert_eq!(lines[0].trim_end(), "enter show ∙ c checkout ∙ d diff");
+        assert_eq!(lines[1].trim_end(), "? help ∙ esc quit");
+
+        Ok(())
+    }
+
+    #[test]
+    fn ascii_charset_should_not_render_unicode() -> anyhow::Result<()> {
+        let items = (0..20).map(|_| Item("foo")).collect::<Vec<_>>();
+        let header = [Column::new("Items", Constraint::Fill(1))].to_vec();
+        let mut selected = Some(0);
+        let mut text = String::from("foo");
+        let mut cursor = text.len();
+
+        let ctx =
+            Context::<()>::default().with_theme(Theme::default().with_charset(Charset::Ascii));
+        let buffer = render_once(30, 20, ctx, |ui, frame| {
+            let layout = Layout::vertical([
+                Constraint::Length(8),
+                Constraint::Length(6),
+                Constraint::Length(1),
+                Constraint::Length(2),
+            ]);
+            ui.layout(layout, Some(0), |ui| {
+                ui.headered_table(frame, &mut selected, &items, header.clone(), columns());
+                ui.table(frame, &mut selected, &items, columns(), Some(Borders::All));
+                ui.separator(frame, Some("All".into()));
+                ui.text_edit_labeled_singleline(
+                    frame,
+                    &mut text,
+                    &mut cursor,
+                    "Search",
+                    Some(Borders::None),
+                );
+            });
+        })?;
+
+        assert!(lines(&buffer).iter().all(|line| line.is_ascii()));
+        assert!(lines(&buffer)[0].starts_with("+-"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn sparkline_should_scale_counts_and_keep_the_latest() -> anyhow::Result<()> {
+        let counts = [4, 0, 1, 2, 8];
+
+        let buffer = render_once(6, 1, Context::<()>::default(), |ui, frame| {
+            ui.sparkline(frame, &counts);
+        })?;
+        assert_eq!(lines(&buffer), [" ▄ ▁▂█"]);
+
+        let buffer = render_once(3, 1, Context::<()>::default(), |ui, frame| {
+            ui.sparkline(frame, &counts);
+        })?;
+        assert_eq!(lines(&buffer), ["▁▂█"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn text_edit_should_render_label_and_overline() -> anyhow::Result<()> {
+        let mut text = String::from("foo");
+        let mut cursor = text.len();
+
+        let buffer = render_once(30, 2, Context::<()>::default(), |ui, frame| {
+            ui.text_edit_labeled_singleline(
+                frame,
+                &mut text,
+                &mut cursor,
+                "Search",
+                Some(Borders::None),
+            );
+        })?;
+        let lines = lines(&buffer);
+
+        assert_eq!(lines[0].trim_end(), " Search  foo");
+        assert_eq!(lines[1], "▔".repeat(30));
+        assert!(buffer[(1, 0)].modifier.contains(Modifier::REVERSED));
+        assert!(!buffer[(1, 0)].modifier.contains(Modifier::DIM));
+
+        Ok(())
+    }
+
+    #[test]
+    fn text_edit_should_be_dimmed_without_focus() -> anyhow::Result<()> {
+        let mut text = String::from("foo");
+        let mut cursor = text.len();
+
+        let buffer = render_once(30, 2, Context::<()>::default(), |ui, frame| {
+            ui.layout(Layout::vertical([Constraint::Fill(1)]), None, |ui| {
+                ui.text_edit_labeled_singleline(
+                    frame,
+                    &mut text,
+                    &mut cursor,
+                    "Search",
+                    Some(Borders::None),
+                );
+            });
+        })?;
+
+        assert!(buffer[(1, 0)].modifier.contains(Modifier::REVERSED));
+        assert!(buffer[(1, 0)].modifier.contains(Modifier::DIM));
+
+        Ok(())
+    }
+
+    fn select_in_list(selected: Option<usize>, key: Key) -> anyhow::Result<Option<usize>> {
+        let items = ["foo", "bar", "baz"];
+        let mut selected = selected;
+
+        render_once(
+            20,
+            5,
+            Context::<()>::default().with_inputs([key].into()),
+            |ui, frame| {
+                ui.list(frame, &mut selected, &items, Some(Borders::None));
+            },
+        )?;
+
+        Ok(selected)
+    }
+
+    #[test]
+    fn list_should_render_one_line_per_item() -> anyhow::Result<()> {
+        let items = ["foo", "bar"];
+        let mut selected = Some(0);
+
+        let buffer = render_once(20, 3, Context::<()>::default(), |ui, frame| {
+            ui.list(frame, &mut selected, &items, Some(Borders::None));
+        })?;
+        let lines = lines(&buffer);
+
+        assert_eq!(lines[0].trim_end(), "foo");
+        assert_eq!(lines[1].trim_end(), "bar");
+        assert_eq!(lines[2].trim_end(), "");
+
+        Ok(())
+    }
+
+    #[test]
+    fn list_should_navigate_between_items() -> anyhow::Result<()> {
+        assert_eq!(select_in_list(Some(0), Key::Down)?, Some(1));
+        assert_eq!(select_in_list(Some(1), Key::Char('k'))?, Some(0));
+        assert_eq!(select_in_list(Some(0), Key::End)?, Some(2));
+        assert_eq!(select_in_list(Some(2), Key::Home)?, Some(0));
+
+        Ok(())
+    }
+
+    #[test]
+    fn list_should_clamp_at_the_first_item() -> anyhow::Result<()> {
+        assert_eq!(select_in_list(Some(0), Key::Up)?, Some(0));
+        assert_eq!(select_in_list(Some(0), Key::Char('k'))?, Some(0));
+        assert_eq!(select_in_list(Some(1), Key::PageUp)?, Some(0));
+
+        Ok(())
+    }
+
+    #[test]
+    fn list_should_clamp_at_the_last_item() -> anyhow::Result<()> {
+        assert_eq!(select_in_list(Some(2), Key::Down)?, Some(2));
+        assert_eq!(select_in_list(Some(2), Key::Char('j'))?, Some(2));
+        assert_eq!(select_in_list(Some(0), Key::PageDown)?, Some(2));
+
+        Ok(())
+    }
+
+    #[test]
+    fn table_should_repeat_navigation_with_count_prefix() -> anyhow::Result<()> {
+        let items = (0..10).map(|_| Item("foo")).collect::<Vec<_>>();
+
+        for (enabled, expected) in [(true, Some(3)), (false, Some(1))] {
+            let ctx = Context::<()>::default();
+            let mut selected = Some(0);
+
+            for key in [Key::Char('3'), Key::Char('j')] {
+                render_once(
+                    20,
+                    12,
+                    ctx.clone().with_inputs([key].into()),
+                    |ui, frame| {
+                        ui.set_repeat_counts(enabled);
+                        ui.table(frame, &mut selected, &items, columns(), Some(Borders::None));
+                    },
+                )?;
+            }
+
+            assert_eq!(selected, expected);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn list_should_repeat_navigation_with_count_prefix() -> anyhow::Result<()> {
+        let items = (0..10).map(|idx| idx.to_string()).collect::<Vec<_>>();
+        let ctx = Context::<()>::default();
+        let mut selected = Some(0);
+
+        for key in [Key::Char('3'), Key::Char('j')] {
+            render_once(
+                20,
+                12,
+                ctx.clone().with_inputs([key].into()),
+                |ui, frame| {
+                    ui.set_repeat_counts(true);
+                    ui.list(frame, &mut selected, &items, Some(Borders::None));
+                },
+            )?;
+        }
+
+        assert_eq!(selected, Some(3));
+
+        Ok(())
+    }
+
+    #[test]
+    fn text_view_should_scroll_right_by_page_width() -> anyhow::Result<()> {
+        let text = "a".repeat(40);
+        let ctx = Context::<()>::default();
+        let mut cursor = Position::default();
+
+        for key in [Key::Char('9'), Key::Char('l')] {
+            render_once(20, 3, ctx.clone().with_inputs([key].into()), |ui, frame| {
+                ui.set_repeat_counts(true);
+                ui.text_view(frame, text.clone(), &mut cursor, Some(Borders::None));
+            })?;
+        }
+
+        // The text is shown on 18 columns, next to the padding and the scrollbar.
+        assert_eq!(cursor, Position::new(0, 40 - 18 + 3));
+
+        Ok(())
+    }
+
+    #[test]
+    fn gauge_should_render_percentage_next_to_label() -> anyhow::Result<()> {
+        let buffer = render_once(30, 1, Context::<()>::default(), |ui, frame| {
+            ui.gauge(frame, 0.5, " 1/2 ");
+        })?;
+        let lines = lines(&buffer);
+
+        assert!(lines[0].ends_with(" 1/2 "));
+        assert!(lines[0].contains("50%"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn gauge_should_render_label_only_if_too_narrow() -> anyhow::Result<()> {
+        let buffer = render_once(12, 1, Context::<()>::default(), |ui, frame| {
+            ui.gauge(frame, 0.5, " 1/2 ");
+        })?;
+        let lines = lines(&buffer);
+
+        assert_eq!(lines[0], format!("{} 1/2 ", " ".repeat(7)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn ansi_text_should_be_styled() {
+        let text = ansi_text(Text::from("\x1b[32mok\x1b[0m done\nplain"));
+
+        assert_eq!(text.lines.len(), 2);
+        assert_eq!(text.lines[0].to_string(), "ok done");
+        assert_eq!(
+            text.lines[0]
+                .spans
+                .iter()
+                .find(|span| span.content == "ok")
+                .and_then(|span| span.style.fg),
+            Some(Color::Green)
+        );
+        assert_eq!(text.lines[1].to_string(), "plain");
+    }
+
+    #[test]
+    fn ansi_text_should_drop_incomplete_sequence() {
+        for content in ["foo\x1b", "foo\x1b[", "foo\x1b[1;3"] {
+            assert_eq!(ansi_text(Text::from(content)).to_string(), "foo");
+        }
+    }
+
+    #[test]
+    fn text_view_should_render_ansi_only_if_enabled() -> anyhow::Result<()> {
+        for (ansi, literal) in [(false, true), (true, false)] {
+            let mut cursor = Position::default();
+            let buffer = render_once(20, 1, Context::<()>::default(), |ui, frame| {
+                ui.add(
+                    frame,
+                    TextView::new("\x1b[31mred\x1b[0m", &mut cursor, Some(Borders::None))
+                        .ansi(ansi),
+                );
+            })?;
+
+            let line = &lines(&buffer)[0];
+            assert!(line.contains("red"));
+            assert_eq!(line.contains("[31m"), literal);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn progress_bar_should_render_steps() -> anyhow::Result<()> {
+        let buffer = render_once(30, 2, Context::<()>::default(), |ui, frame| {
+            ui.layout(
+                Layout::vertical([Constraint::Length(1), Constraint::Length(1)]),
+                None,
+                |ui| {
+                    ui.progress_bar(frame, 25, 100);
+                    ui.progress_bar(frame, 0, 0);
+                },
+            );
+        })?;
+        let lines = lines(&buffer);
+
+        assert!(lines[0].ends_with(" 25/100 "));
+        assert!(lines[0].contains("25%"));
+        assert!(lines[1].ends_with(" 0/0 "));
+        assert!(lines[1].contains("0%"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn panes_focus_should_skip_non_focusable_areas() -> anyhow::Result<()> {
+        let items = vec![Item("foo")];
+        let mut selected = Some(0);
+        let mut focus = Some(0);
+        let ctx = Context::<()>::default();
+
+        // The first frame registers which panes can be focused.
+        for (keys, expected) in [
+            (vec![], Some(0)),
+            (vec![Key::Char('\t')], Some(2)),
+            (vec![Key::BackTab], Some(0)),
+        ] {
+            let ctx = ctx.clone().with_inputs(keys.into());
+
+            render_once(20, 6, ctx, |ui, frame| {
+                ui.panes(Layout::vertical([2, 2, 2]), &mut focus, |ui| {
+                    ui.table(frame, &mut selected, &items, columns(), None);
+                    ui.layout(Layout::vertical([1, 1]), None, |ui| {
+                        ui.label(frame, "label");
+                        ui.bar(frame, columns(), None);
+                    });
+                    ui.table(frame, &mut selected, &items, columns(), None);
+                });
+            })?;
+
+            assert_eq!(focus, expected);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn panes_state_should_keep_focus_without_focusable_panes() {
+        let mut state = PanesState::new(3, Some(0)).with_focusable(vec![true, false, false]);
+
+        state.focus_next();
+        assert_eq!(state.focus(), Some(0));
+
+        state.focus_prev();
+        assert_eq!(state.focus(), Some(0));
+    }
+
+    #[test]
+    fn scroll_area_should_render_like_text_view() -> anyhow::Result<()> {
+        let text = (0..50_000)
+            .map(|idx| format!("line {idx} of a long description"))
+            .collect::<Vec<_>>();
+        let line = |idx: usize| Line::raw(text[idx].clone());
+
+        for keys in [vec![], vec![Key::PageDown, Key::Right], vec![Key::End]] {
+            let mut expected_cursor = Position::new(24_990, 0);
+            let mut actual_cursor = expected_cursor;
+
+            let ctx = Context::<()>::default().with_inputs(keys.clone().into());
+            let expected = render_once(30, 12, ctx, |ui, frame| {
+                ui.text_view(
+                    frame,
+                    text.join("\n"),
+                    &mut expected_cursor,
+                    Some(Borders::All),
+                );
+            })?;
+
+            let ctx = Context::<()>::default().with_inputs(keys.into());
+            let actual = render_once(30, 12, ctx, |ui, frame| {
+                ui.vertical_scroll_area(
+                    frame,
+                    text.len(),
+                    line,
+                    &mut actual_cursor,
+                    Some(Borders::All),
+                );
+            })?;
+
+            assert_eq!(lines(&actual), lines(&expected));
+            assert_eq!(actual_cursor, expected_cursor);
+        }
+
+        Ok(())
+    }
+}
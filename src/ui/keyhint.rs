@@ -0,0 +1,207 @@
+use std::time::{Duration, Instant};
+
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::{Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, BorderType, Borders, Clear, Padding, Paragraph, Widget};
+use termion::event::Key;
+
+use super::theme::{style, Theme};
+use super::{key_name, Shortcut};
+
+/// The time a prefix key needs to be pending before its follow-up keys are hinted.
+pub const KEY_HINT_DELAY: Duration = Duration::from_millis(400);
+
+/// Maps prefix keys, e.g. `z` for folding, to the keys that can follow them.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PrefixMap {
+    prefixes: Vec<(Key, Vec<Shortcut>)>,
+}
+
+impl PrefixMap {
+    pub fn prefix<S>(mut self, key: Key, hints: impl IntoIterator<Item = S>) -> Self
+    where
+        S: Into<Shortcut>,
+    {
+        let hints = hints.into_iter().map(Into::into).collect();
+
+        match self.prefixes.iter_mut().find(|(prefix, _)| *prefix == key) {
+            Some((_, existing)) => *existing = hints,
+            None => self.prefixes.push((key, hints)),
+        }
+        self
+    }
+
+    /// Returns the follow-up keys of the given key, if it's a prefix.
+    pub fn hints(&self, key: Key) -> Option<&[Shortcut]> {
+        self.prefixes
+            .iter()
+            .find(|(prefix, _)| *prefix == key)
+            .map(|(_, hints)| hints.as_slice())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.prefixes.is_empty()
+    }
+}
+
+/// Tracks the prefix key that was pressed last and when, until the chord is
+/// completed or cancelled by any other key.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PendingPrefix {
+    pending: Option<(Instant, Key)>,
+}
+
+impl PendingPrefix {
+    /// Marks the given key as pending if it's a prefix, and clears the pending
+    /// prefix otherwise.
+    pub fn press(&mut self, prefixes: &PrefixMap, key: Key, now: Instant) {
+        self.pending = prefixes.hints(key).map(|_| (now, key));
+    }
+
+    /// Returns the pending prefix key, regardless of how long it's pending.
+    pub fn key(&self) -> Option<Key> {
+        self.pending.map(|(_, key)| key)
+    }
+
+    pub fn clear(&mut self) {
+        self.pending = None;
+    }
+
+    /// Returns the pending prefix key if it was pressed at least `KEY_HINT_DELAY`
+    /// before the given time.
+    pub fn due(&self, now: Instant) -> Option<Key> {
+        self.pending
+            .filter(|(pressed, _)| now.duration_since(*pressed) >= KEY_HINT_DELAY)
+            .map(|(_, key)| key)
+    }
+}
+
+/// A small box that lists the follow-up keys of a prefix and their actions, e.g.
+/// after `z` was pressed. It's rendered at the bottom right of the area given and
+/// clears everything below it.
+pub struct KeyHint<'a> {
+    prefix: Key,
+    hints: &'a [Shortcut],
+    border_style: Style,
+}
+
+impl<'a> KeyHint<'a> {
+    pub fn new(prefix: Key, hints: &'a [Shortcut]) -> Self {
+        Self {
+            prefix,
+            hints,
+            border_style: Theme::default().focus_border_style,
+        }
+    }
+
+    pub fn border_style(mut self, style: Style) -> Self {
+        self.border_style = style;
+        self
+    }
+
+    fn lines(&self) -> Vec<Line<'static>> {
+        let width = self
+            .hints
+            .iter()
+            .map(|hint| hint.hint().chars().count())
+            .max()
+            .unwrap_or_default();
+
+        self.hints
+            .iter()
+            .map(|hint| {
+                Line::from(vec![
+                    Span::styled(format!("{:width$}", hint.hint()), style::yellow()),
+                    Span::raw("  "),
+                    Span::styled(hint.label.clone(), style::gray().dim()),
+                ])
+            })
+            .collect()
+    }
+}
+
+impl Widget for KeyHint<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let title = format!(" {} … ", key_name(&self.prefix));
+        let lines = self.lines();
+
+        let content_width = lines
+            .iter()
+            .map(|line| line.width())
+            .chain([title.chars().count()])
+            .max()
+            .unwrap_or_default() as u16;
+        let width = content_width.saturating_add(4).min(area.width);
+        let height = (lines.len() as u16).saturating_add(2).min(area.height);
+
+        let area = Rect {
+            x: area.right().saturating_sub(width),
+            y: area.bottom().saturating_sub(height),
+            width,
+            height,
+        };
+
+        let block = Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .border_style(self.border_style)
+            .border_type(BorderType::Rounded)
+            .padding(Padding::horizontal(1));
+
+        Clear.render(area, buf);
+        Paragraph::new(lines).block(block).render(area, buf);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Instant;
+
+    use pretty_assertions::assert_eq;
+
+    use ratatui::buffer::Buffer;
+    use ratatui::layout::Rect;
+    use ratatui::widgets::Widget;
+    use termion::event::Key;
+
+    use crate::ui::im::testing::lines;
+
+    use super::{KeyHint, PendingPrefix, PrefixMap, KEY_HINT_DELAY};
+
+    fn prefixes() -> PrefixMap {
+        PrefixMap::default().prefix(Key::Char('z'), [("a", "toggle"), ("M", "fold all")])
+    }
+
+    #[test]
+    fn prefix_should_be_due_after_delay() {
+        let prefixes = prefixes();
+        let now = Instant::now();
+        let mut pending = PendingPrefix::default();
+
+        pending.press(&prefixes, Key::Char('z'), now);
+        assert_eq!(pending.due(now), None);
+        assert_eq!(pending.due(now + KEY_HINT_DELAY), Some(Key::Char('z')));
+
+        pending.press(&prefixes, Key::Char('a'), now);
+        assert_eq!(pending.due(now + KEY_HINT_DELAY), None);
+    }
+
+    #[test]
+    fn key_hint_should_render_at_bottom_right() {
+        let prefixes = prefixes();
+        let hints = prefixes.hints(Key::Char('z')).unwrap_or_default();
+
+        let mut buf = Buffer::empty(Rect::new(0, 0, 30, 6));
+        KeyHint::new(Key::Char('z'), hints).render(buf.area, &mut buf);
+
+        let rows = lines(&buf);
+
+        assert_eq!(rows[1].trim(), "");
+        assert!(rows[2].trim_start().starts_with("╭ z … "));
+        assert!(rows[3].contains("a  toggle"));
+        assert!(rows[4].contains("M  fold all"));
+        assert!(rows[5].ends_with('╯'));
+    }
+}
@@ -0,0 +1,253 @@
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::{Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{
+    Block, BorderType, Borders, Clear, List, ListState, Padding, StatefulWidget, Widget,
+};
+use termion::event::Key;
+
+use super::theme::{style, Charset, Theme};
+use super::{Highlight, Shortcut};
+
+/// The key that opens the palette.
+pub const PALETTE_KEY: Key = Key::Char(':');
+
+/// What happened to a palette after a key was passed to it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PaletteEvent {
+    /// The palette is still open, e.g. because the search changed.
+    Open,
+    /// The palette was closed without picking anything.
+    Closed,
+    /// The shortcut with the given key was picked, the palette is closed.
+    Picked(Key),
+}
+
+/// An operation palette: it lists the shortcuts given, narrowed down by what's
+/// typed into it. Picking a shortcut returns its first key, which is then
+/// handled as if it was pressed, such that the palette doesn't need to know
+/// what the shortcut does.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Palette {
+    shortcuts: Vec<Shortcut>,
+    search: String,
+    selected: usize,
+    /// The style of matching characters of the labels.
+    search_highlight_style: Style,
+    border_style: Style,
+    /// The style of the selected shortcut.
+    highlight_style: Style,
+}
+
+impl Palette {
+    /// Builds a palette of all given shortcuts that can be triggered by a key.
+    /// Shortcuts only described by a hint, e.g. key sequences, are left out.
+    pub fn new(shortcuts: impl IntoIterator<Item = Shortcut>) -> Self {
+        Self {
+            shortcuts: shortcuts
+                .into_iter()
+                .filter(|shortcut| !shortcut.keys.is_empty() && !shortcut.matches(PALETTE_KEY))
+                .collect(),
+            search: String::new(),
+            selected: 0,
+            search_highlight_style: Style::default(),
+            border_style: Style::default(),
+            highlight_style: Style::default(),
+        }
+        .with_theme(&Theme::default())
+    }
+
+    /// Renders the palette in the styles of the given theme, e.g. the one of
+    /// the application it's opened in.
+    pub fn with_theme(mut self, theme: &Theme) -> Self {
+        self.search_highlight_style = theme.search_highlight_style;
+        self.border_style = theme.focus_border_style;
+        self.highlight_style = theme.highlight_style.style(true);
+        self
+    }
+
+    /// Returns all shortcuts whose label matches the search.
+    pub fn matches(&self) -> Vec<&Shortcut> {
+        let highlight = self.highlight();
+
+        self.shortcuts
+            .iter()
+            .filter(|shortcut| {
+                self.search.trim().is_empty() || !highlight.indices(&shortcut.label).is_empty()
+            })
+            .collect()
+    }
+
+    pub fn handle_key(&mut self, key: Key) -> PaletteEvent {
+        match key {
+            Key::Esc | Key::Ctrl('c') => return PaletteEvent::Closed,
+            Key::Char('\n') => {
+                return match self.matches().get(self.selected) {
+                    Some(shortcut) => PaletteEvent::Picked(shortcut.keys[0]),
+                    None => PaletteEvent::Closed,
+                };
+            }
+            Key::Up | Key::Ctrl('p') => {
+                self.selected = self.selected.saturating_sub(1);
+            }
+            Key::Down | Key::Ctrl('n') => {
+                let last = self.matches().len().saturating_sub(1);
+                self.selected = self.selected.saturating_add(1).min(last);
+            }
+            Key::Backspace => {
+                self.search.pop();
+                self.selected = 0;
+            }
+            Key::Char(c) => {
+                self.search.push(c);
+                self.selected = 0;
+            }
+            _ => {}
+        }
+        PaletteEvent::Open
+    }
+
+    fn highlight(&self) -> Highlight {
+        Highlight::new(&self.search, self.search_highlight_style)
+    }
+}
+
+/// Renders the palette as a box at the bottom of the area given, with the
+/// search as title. It clears everything below it.
+impl Widget for &Palette {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let highlight = self.highlight();
+        let matches = self.matches();
+
+        let width = matches
+            .iter()
+            .map(|shortcut| shortcut.hint().chars().count())
+            .max()
+            .unwrap_or_default();
+        let items = matches
+            .iter()
+            .map(|shortcut| {
+                let mut line = Line::from(Span::styled(
+                    format!("{:width$}  ", shortcut.hint()),
+                    style::yellow(),
+                ));
+                line.extend(highlight.line(&shortcut.label, Style::default()).spans);
+                line
+            })
+            .collect::<Vec<_>>();
+
+        let height = (items.len().max(1) as u16)
+            .saturating_add(2)
+            .min(area.height);
+        let area = Rect {
+            y: area.bottom().saturating_sub(height),
+            height,
+            ..area
+        };
+
+        let block = Block::default()
+            .title(Line::from(vec![
+                Span::raw(" : "),
+                Span::raw(self.search.clone()).bold(),
+                Span::raw(" "),
+            ]))
+            .borders(Borders::ALL)
+            .border_style(self.border_style)
+            .border_set(Charset::current().border_set(BorderType::Rounded))
+            .padding(Padding::horizontal(1));
+
+        let mut state = ListState::default().with_selected(Some(self.selected));
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(self.highlight_style);
+
+        Clear.render(area, buf);
+        StatefulWidget::render(list, area, buf, &mut state);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use ratatui::buffer::Buffer;
+    use ratatui::layout::Rect;
+    use ratatui::widgets::Widget;
+
+    use termion::event::Key;
+
+    use crate::ui::theme::Theme;
+    use crate::ui::Shortcut;
+
+    use super::{Palette, PaletteEvent};
+
+    fn palette() -> Palette {
+        Palette::new(
+            [
+                ("enter", "show"),
+                ("c", "checkout"),
+                ("d", "diff"),
+                ("za", "toggle"),
+                (":", "operations"),
+            ]
+            .iter()
+            .map(Shortcut::from),
+        )
+    }
+
+    fn labels(palette: &Palette) -> Vec<&str> {
+        palette
+            .matches()
+            .iter()
+            .map(|shortcut| shortcut.label.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn palette_should_only_list_shortcuts_with_keys() {
+        assert_eq!(labels(&palette()), ["show", "checkout", "diff"]);
+    }
+
+    #[test]
+    fn palette_should_filter_and_pick_by_label() {
+        let mut palette = palette();
+
+        assert_eq!(palette.handle_key(Key::Char('d')), PaletteEvent::Open);
+        assert_eq!(labels(&palette), ["diff"]);
+        assert_eq!(palette.handle_key(Key::Backspace), PaletteEvent::Open);
+
+        palette.handle_key(Key::Char('c'));
+        assert_eq!(labels(&palette), ["checkout"]);
+        assert_eq!(
+            palette.handle_key(Key::Char('\n')),
+            PaletteEvent::Picked(Key::Char('c'))
+        );
+    }
+
+    #[test]
+    fn palette_should_be_closed_without_matches() {
+        let mut palette = palette();
+
+        palette.handle_key(Key::Char('x'));
+        assert!(palette.matches().is_empty());
+        assert_eq!(palette.handle_key(Key::Char('\n')), PaletteEvent::Closed);
+        assert_eq!(palette().handle_key(Key::Esc), PaletteEvent::Closed);
+    }
+
+    #[test]
+    fn palette_should_be_rendered_in_theme() {
+        let theme = Theme::high_contrast();
+        let palette = palette().with_theme(&theme);
+        let area = Rect::new(0, 0, 20, 5);
+        let mut buf = Buffer::empty(area);
+
+        palette.render(area, &mut buf);
+
+        assert_eq!(buf[(0, 0)].fg, theme.focus_border_style.fg.unwrap());
+        assert_eq!(
+            buf[(2, 1)].bg,
+            theme.highlight_style.focus.bg.unwrap_or_default()
+        );
+    }
+}
@@ -0,0 +1,216 @@
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Constraint, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::widgets::{Row, Table, Widget};
+
+use unicode_width::UnicodeWidthStr;
+
+use crate::ui::{Column, TableRow};
+
+/// The width tables are printed in if the width of the terminal is unknown,
+/// e.g. because the output is piped.
+pub const DEFAULT_WIDTH: u16 = 120;
+
+/// Returns the width of the terminal, or `DEFAULT_WIDTH` if stdout is not a
+/// terminal.
+pub fn terminal_width() -> u16 {
+    termion::terminal_size()
+        .map(|(width, _)| width)
+        .unwrap_or(DEFAULT_WIDTH)
+}
+
+/// Renders the given items as plain text, one line per item, preceded by the
+/// column headers. Columns are left out and sized exactly like a table of the
+/// given width does, such that the output aligns like the interface. Styles
+/// are kept as ANSI escape sequences if `color` is set, and dropped otherwise,
+/// e.g. if the output is piped.
+pub fn table<R, A>(items: &[R], columns: &[Column<'_>], width: u16, color: bool) -> String
+where
+    R: TableRow<A>,
+{
+    let shown = |column: &Column<'_>| !column.skip && column.displayed(width as usize);
+    let widths = columns
+        .iter()
+        .filter(|column| shown(column))
+        .map(|column| column.width)
+        .collect::<Vec<_>>();
+
+    let header = Row::new(
+        columns
+            .iter()
+            .filter(|column| shown(column))
+            .map(|column| column.text.clone()),
+    )
+    .style(Style::default().add_modifier(Modifier::BOLD));
+    let rows = items.iter().map(|item| {
+        Row::new(
+            item.cells(None)
+                .into_iter()
+                .zip(columns)
+                .filter(|(_, column)| shown(column))
+                .map(|(cell, _)| cell),
+        )
+    });
+
+    // Every row is rendered on its own, such that the amount of items is not
+    // limited by the size of a buffer. Since widths only depend on the columns,
+    // all rows are aligned nevertheless.
+    [header]
+        .into_iter()
+        .chain(rows)
+        .map(|row| render_row(row, &widths, width, color))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn render_row(row: Row<'_>, widths: &[Constraint], width: u16, color: bool) -> String {
+    let area = Rect::new(0, 0, width, 1);
+    let mut buffer = Buffer::empty(area);
+    Table::new([row], widths.to_vec())
+        .column_spacing(1)
+        .render(area, &mut buffer);
+
+    // Trailing blanks are left out, unless they are filled, e.g. by a badge.
+    let cells = buffer.content();
+    let end = cells
+        .iter()
+        .rposition(|cell| {
+            let style = cell.style();
+            !cell.symbol().trim().is_empty()
+                || (color
+                    && (style.bg.is_some_and(|bg| bg != Color::Reset)
+                        || style.add_modifier.contains(Modifier::REVERSED)))
+        })
+        .map(|last| last + 1)
+        .unwrap_or_default();
+
+    let mut line = String::new();
+    let mut style = None;
+    let mut skip = 0;
+
+    for cell in &cells[..end] {
+        // Symbols wider than one column are followed by cells that are not
+        // printed.
+        if skip > 0 {
+            skip -= 1;
+            continue;
+        }
+        skip = cell.symbol().width().saturating_sub(1);
+
+        if color && style != Some(cell.style()) {
+            style = Some(cell.style());
+            line.push_str(&sgr(cell.style()));
+        }
+        line.push_str(cell.symbol());
+    }
+    if style.is_some_and(|style| sgr(style) != sgr(Style::reset())) {
+        line.push_str(&sgr(Style::reset()));
+    }
+
+    line
+}
+
+/// Returns the ANSI escape sequence that resets the terminal to the style given.
+fn sgr(style: Style) -> String {
+    let modifiers = [
+        (Modifier::BOLD, "1"),
+        (Modifier::DIM, "2"),
+        (Modifier::ITALIC, "3"),
+        (Modifier::UNDERLINED, "4"),
+        (Modifier::REVERSED, "7"),
+        (Modifier::CROSSED_OUT, "9"),
+    ];
+
+    let mut codes = vec!["0".to_string()];
+    codes.extend(
+        modifiers
+            .iter()
+            .filter(|(modifier, _)| style.add_modifier.contains(*modifier))
+            .map(|(_, code)| code.to_string()),
+    );
+    codes.extend(style.fg.and_then(|fg| color_code(fg, false)));
+    codes.extend(style.bg.and_then(|bg| color_code(bg, true)));
+
+    format!("\x1b[{}m", codes.join(";"))
+}
+
+fn color_code(color: Color, background: bool) -> Option<String> {
+    let offset = if background { 10 } else { 0 };
+    let basic = |code: u8| Some((code + offset).to_string());
+
+    match color {
+        Color::Reset => None,
+        Color::Black => basic(30),
+        Color::Red => basic(31),
+        Color::Green => basic(32),
+        Color::Yellow => basic(33),
+        Color::Blue => basic(34),
+        Color::Magenta => basic(35),
+        Color::Cyan => basic(36),
+        Color::Gray => basic(37),
+        Color::DarkGray => basic(90),
+        Color::LightRed => basic(91),
+        Color::LightGreen => basic(92),
+        Color::LightYellow => basic(93),
+        Color::LightBlue => basic(94),
+        Color::LightMagenta => basic(95),
+        Color::LightCyan => basic(96),
+        Color::White => basic(97),
+        Color::Indexed(index) => Some(format!("{};5;{index}", 38 + offset)),
+        Color::Rgb(r, g, b) => Some(format!("{};2;{r};{g};{b}", 38 + offset)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use ratatui::layout::Constraint;
+    use ratatui::style::Stylize;
+    use ratatui::text::Span;
+    use ratatui::widgets::Cell;
+
+    use crate::ui::{Column, ToRow};
+
+    use super::table;
+
+    struct Item(&'static str, &'static str);
+
+    impl ToRow<3> for Item {
+        fn to_row(&self) -> [Cell; 3] {
+            [
+                Span::raw(self.0).green().into(),
+                Span::raw(self.1).into(),
+                Span::raw("★").into(),
+            ]
+        }
+    }
+
+    fn columns() -> Vec<Column<'static>> {
+        vec![
+            Column::new("ID", Constraint::Length(4)),
+            Column::new("Title", Constraint::Fill(1)),
+            Column::new("", Constraint::Length(2)).hide_small(),
+        ]
+    }
+
+    #[test]
+    fn table_should_be_printed_without_styles() {
+        let items = [Item("1", "Fix crash"), Item("22", "Add print mode")];
+
+        assert_eq!(
+            table(&items, &columns(), 30, false),
+            "ID   Title\n1    Fix crash\n22   Add print mode"
+        );
+    }
+
+    #[test]
+    fn table_should_keep_styles_as_escape_sequences() {
+        let items = [Item("1", "Fix")];
+
+        assert_eq!(
+            table(&items, &columns(), 30, true).lines().last(),
+            Some("\x1b[0;32m1\x1b[0m    Fix")
+        );
+    }
+}
@@ -1,8 +1,9 @@
 pub mod widget;
 
 use std::fmt::Debug;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
+use ratatui::style::{Style, Stylize};
 use ratatui::Viewport;
 use tokio::sync::broadcast;
 use tokio::sync::mpsc::UnboundedReceiver;
@@ -11,9 +12,12 @@ use crate::event::Event;
 use crate::store::Update;
 use crate::task::Interrupted;
 use crate::terminal;
-use crate::terminal::Terminal;
+use crate::terminal::{External, Terminal};
+use crate::ui::keyhint::KEY_HINT_DELAY;
 use crate::ui::rm::widget::RenderProps;
 use crate::ui::rm::widget::Widget;
+use crate::ui::theme::Charset;
+use crate::ui::toast::{ToastLevel, Toasts};
 
 const RENDERING_TICK_RATE: Duration = Duration::from_millis(250);
 
@@ -40,11 +44,23 @@ impl Frontend {
     ///
     /// Interrupt messages are being sent to broadcast channel for retrieving the
     /// application kill signal.
+    ///
+    /// External programs are being sent by the applications' `Store`. The terminal
+    /// is suspended while they run and resumed afterwards.
+    ///
+    /// Toasts are being sent by the applications' `Store` as well. They're shown
+    /// at the bottom of the view until they expire.
+    ///
+    /// While the terminal window has no focus, the view is dimmed and only redrawn
+    /// on events, state updates and interrupts, but not periodically. Idle ticks
+    /// don't redraw either, since nothing changed.
     pub async fn run<S, M, R>(
         self,
         mut root: Widget<S, M>,
         mut state_rx: UnboundedReceiver<S>,
         mut interrupt_rx: broadcast::Receiver<Interrupted<R>>,
+        mut external_rx: UnboundedReceiver<External>,
+        mut toast_rx: UnboundedReceiver<(ToastLevel, String)>,
         viewport: Viewport,
     ) -> anyhow::Result<Interrupted<R>>
     where
@@ -53,9 +69,16 @@ impl Frontend {
         R: Clone + Send + Sync + Debug,
     {
         let mut ticker = tokio::time::interval(RENDERING_TICK_RATE);
+        // Ticks are paused while the terminal window has no focus.
+        let mut focused = true;
 
         let mut terminal = Terminal::try_from(viewport)?;
         let mut events_rx = terminal::events();
+        let mut notifications_rx = terminal::notifications();
+
+        // Retained mode widgets don't have a theme, but draw with the charset
+        // detected here.
+        Charset::init(Charset::detect());
 
         let mut root = {
             let state = state_rx.recv().await.unwrap();
@@ -64,18 +87,63 @@ impl Frontend {
             root
         };
 
+        // The time the last key was pressed at.
+        let mut pressed_at = None;
+        let mut toasts = Toasts::default();
+
         let result: anyhow::Result<Interrupted<R>> = loop {
-            tokio::select! {
-                // Tick to terminate the select every N milliseconds
-                _ = ticker.tick() => (),
+            let dirty = tokio::select! {
+                // Tick to terminate the select every N milliseconds. Views only
+                // change on ticks while the key hint of a prefix key that was just
+                // pressed is due or toasts are shown, so other ticks don't redraw.
+                _ = ticker.tick(), if focused => {
+                    !toasts.is_empty()
+                        || pressed_at.is_some_and(|at: Instant| {
+                            at.elapsed() < KEY_HINT_DELAY + RENDERING_TICK_RATE
+                        })
+                },
                 // Handle input events
-                Some(event) = events_rx.recv() => match event {
-                    Event::Key(key) => root.handle_event(key),
-                    Event::Resize => (),
+                Some(event) = events_rx.recv() => {
+                    match event {
+                        Event::Key(key) => {
+                            pressed_at = Some(Instant::now());
+                            root.handle_event(key);
+                        }
+                        Event::Paste(text) => root.handle_paste(&text),
+                        Event::Resize => (),
+                        Event::FocusGained => {
+                            focused = true;
+                            ticker.reset();
+                        }
+                        Event::FocusLost => focused = false,
+                    }
+                    true
                 },
                 // Handle state updates
                 Some(state) = state_rx.recv() => {
                     root.update(&state);
+                    true
+                },
+                // Run external programs while the terminal is suspended
+                Some(external) = external_rx.recv() => {
+                    terminal.suspend()?;
+                    if let Err(err) = external.run_blocking().await {
+                        log::warn!("Running external program failed: {}", err);
+                    }
+                    terminal.resume()?;
+                    true
+                },
+                // Show toasts requested by the state
+                Some((level, message)) = toast_rx.recv() => {
+                    toasts.push(message, level);
+                    true
+                },
+                // Write desktop notifications between two frames
+                Some(notification) = notifications_rx.recv() => {
+                    if let Err(err) = terminal.notify(&notification) {
+                        log::warn!("Failed to post desktop notification: {}", err);
+                    }
+                    false
                 },
                 // Catch and handle interrupt signal to gracefully shutdown
                 Ok(interrupted) = interrupt_rx.recv() => {
@@ -83,8 +151,22 @@ impl Frontend {
 
                     break Ok(interrupted);
                 }
+            };
+            toasts.expire(Instant::now());
+
+            if !dirty {
+                log::trace!("Skipping draw on idle tick");
+                continue;
             }
-            terminal.draw(|frame| root.render(RenderProps::from(frame.area()), frame))?;
+            terminal.draw(|frame| {
+                root.render(RenderProps::from(frame.area()), frame);
+                frame.render_widget(&toasts, frame.area());
+
+                if !focused {
+                    let area = frame.area();
+                    frame.buffer_mut().set_style(area, Style::default().dim());
+                }
+            })?;
         };
         terminal.restore()?;
 
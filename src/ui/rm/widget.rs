@@ -13,8 +13,11 @@ use termion::event::Key;
 
 use ratatui::prelude::*;
 
+use crate::event;
+use crate::BoxedAny;
+
 use self::{
-    container::SectionGroupState,
+    container::{SectionGroupState, SplitContainerState},
     input::{TextAreaState, TextViewState},
 };
 
@@ -73,6 +76,7 @@ pub enum ViewState {
     TextView(TextViewState),
     TextArea(TextAreaState),
     SectionGroup(SectionGroupState),
+    SplitContainer(SplitContainerState),
 }
 
 impl ViewState {
@@ -118,6 +122,13 @@ impl ViewState {
         }
     }
 
+    pub fn unwrap_split_container(&self) -> Option<SplitContainerState> {
+        match self {
+            ViewState::SplitContainer(state) => Some(state.clone()),
+            _ => None,
+        }
+    }
+
     pub fn unwrap_tree(&self) -> Option<Vec<String>> {
         match self {
             ViewState::Tree(value) => Some(value.clone().to_vec()),
@@ -228,6 +239,19 @@ pub trait View {
         None
     }
 
+    /// Should return true if this view, or the child keys are passed to, takes
+    /// text input at the moment, e.g. a search field that is shown.
+    fn takes_text(&self, _props: Option<&ViewProps>) -> bool {
+        false
+    }
+
+    /// Should return the key the `on_event` callback is called with, after `key`
+    /// was handled by `handle_event`. Views that take keys themselves, e.g. while
+    /// a palette is open, can return `None` or the key that was picked instead.
+    fn event_key(&mut self, key: Key) -> Option<Key> {
+        Some(key)
+    }
+
     /// Should update the internal props of this and all children.
     fn update(&mut self, _props: Option<&ViewProps>, _state: &Self::State) {}
 
@@ -255,7 +279,7 @@ pub struct Widget<S, M> {
     view: BoxedView<S, M>,
     props: Option<ViewProps>,
     sender: UnboundedSender<M>,
-    on_update: Option<UpdateCallback<S>>,
+    on_update: Option<Box<dyn Fn(&S) -> ViewProps>>,
     on_event: Option<EventCallback<M>>,
     on_render: Option<RenderCallback<M>>,
 }
@@ -281,13 +305,17 @@ impl<S: 'static, M: 'static> Widget<S, M> {
         self.view.reset()
     }
 
-    /// Calls `handle_event` on the wrapped view as well as the `on_event` callback.
-    /// Sends any message returned by either the view or the callback.
+    /// Calls `handle_event` on the wrapped view as well as the `on_event` callback,
+    /// with the key returned by the view's `event_key`. Sends any message returned
+    /// by either the view or the callback.
     pub fn handle_event(&mut self, key: Key) {
         if let Some(message) = self.view.handle_event(self.props.as_ref(), key) {
             let _ = self.sender.send(message);
         }
 
+        let Some(key) = self.view.event_key(key) else {
+            return;
+        };
         if let Some(on_event) = self.on_event {
             if let Some(message) =
                 (on_event)(key, self.view.view_state().as_ref(), self.props.as_ref())
@@ -297,6 +325,25 @@ impl<S: 'static, M: 'static> Widget<S, M> {
         }
     }
 
+    /// Returns true if the wrapped view takes text input at the moment.
+    pub fn takes_text(&self) -> bool {
+        self.view.takes_text(self.props.as_ref())
+    }
+
+    /// Types pasted text into the wrapped view, if it takes text input. Views
+    /// only handle keys, so each character of the text is passed as a key.
+    /// Otherwise, the text is dropped, since its characters would trigger
+    /// shortcuts.
+    pub fn handle_paste(&mut self, text: &str) {
+        if !self.takes_text() {
+            log::debug!("Dropping pasted text, since no text input has focus");
+            return;
+        }
+        for c in event::single_line(text).chars() {
+            self.handle_event(Key::Char(c));
+        }
+    }
+
     /// Applications are usually defined by app-specific widgets that do know
     /// the type of `state`. These can use widgets from the library that do not know the
     /// type of `state`.
@@ -306,7 +353,7 @@ impl<S: 'static, M: 'static> Widget<S, M> {
     /// props directly via their state converters, whereas library widgets can just fallback
     /// to their current props.
     pub fn update(&mut self, state: &S) {
-        self.props = self.on_update.map(|on_update| (on_update)(state));
+        self.props = self.on_update.as_ref().map(|on_update| (on_update)(state));
         self.view.update(self.props.as_ref(), state);
     }
 
@@ -334,7 +381,21 @@ impl<S: 'static, M: 'static> Widget<S, M> {
     where
         Self: Sized,
     {
-        self.on_update = Some(callback);
+        self.on_update = Some(Box::new(callback));
+        self
+    }
+
+    /// Sets the update handler to a function that builds the props of the wrapped
+    /// view from the state. Same as `on_update`, but the props don't need to be
+    /// boxed by the callback, e.g. `.bind(|state| TableProps::default().items(..))`.
+    pub fn bind<P>(mut self, props: fn(&S) -> P) -> Self
+    where
+        Self: Sized,
+        P: Clone + 'static,
+    {
+        self.on_update = Some(Box::new(move |state: &S| {
+            props(state).to_boxed_any().into()
+        }));
         self
     }
 
@@ -369,3 +430,40 @@ where
         Widget::new(self, tx)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use ratatui::Frame;
+    use termion::event::Key;
+    use tokio::sync::mpsc;
+
+    use super::{RenderProps, ToWidget, View, ViewProps};
+
+    /// Returns its props as message on every key.
+    struct Echo;
+
+    impl View for Echo {
+        type State = usize;
+        type Message = usize;
+
+        fn handle_event(&mut self, props: Option<&ViewProps>, _key: Key) -> Option<usize> {
+            props.and_then(|props| props.inner_ref::<usize>()).copied()
+        }
+
+        fn render(&mut self, _props: Option<&ViewProps>, _render: RenderProps, _frame: &mut Frame) {
+        }
+    }
+
+    #[test]
+    fn bound_props_should_be_built_from_state() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let mut widget = Echo.to_widget(tx).bind(|state: &usize| state * 2);
+
+        widget.update(&21);
+        widget.handle_event(Key::Char('x'));
+
+        assert_eq!(rx.try_recv().ok(), Some(42));
+    }
+}
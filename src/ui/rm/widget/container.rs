@@ -7,7 +7,7 @@ use ratatui::prelude::*;
 use ratatui::widgets::{Block, BorderType, Borders, Row};
 
 use crate::ui::ext::{FooterBlock, FooterBlockType, HeaderBlock};
-use crate::ui::theme::{style, Theme};
+use crate::ui::theme::{style, Charset, Density, Theme};
 use crate::ui::Column;
 
 use super::{PredefinedLayout, RenderProps, View, ViewProps, ViewState, Widget};
@@ -19,6 +19,11 @@ pub struct HeaderProps<'a> {
     pub cutoff_after: usize,
     pub border_style: Style,
     pub focus_border_style: Style,
+    /// The index of the column items are sorted by, and if they're sorted in
+    /// ascending order. The column is marked with an arrow.
+    pub active_sort: Option<(usize, bool)>,
+    /// How densely columns are laid out.
+    pub density: Density,
 }
 
 impl<'a> HeaderProps<'a> {
@@ -27,6 +32,11 @@ impl<'a> HeaderProps<'a> {
         self
     }
 
+    pub fn active_sort(mut self, active_sort: Option<(usize, bool)>) -> Self {
+        self.active_sort = active_sort;
+        self
+    }
+
     pub fn cutoff(mut self, cutoff: usize, cutoff_after: usize) -> Self {
         self.cutoff = cutoff;
         self.cutoff_after = cutoff_after;
@@ -42,6 +52,11 @@ impl<'a> HeaderProps<'a> {
         self.focus_border_style = color;
         self
     }
+
+    pub fn density(mut self, density: Density) -> Self {
+        self.density = density;
+        self
+    }
 }
 
 impl<'a> Default for HeaderProps<'a> {
@@ -54,6 +69,8 @@ impl<'a> Default for HeaderProps<'a> {
             cutoff_after: usize::MAX,
             border_style: theme.border_style,
             focus_border_style: theme.focus_border_style,
+            active_sort: None,
+            density: theme.density,
         }
     }
 }
@@ -98,9 +115,15 @@ impl<'a: 'static, S, M> View for Header<S, M> {
         let cells = props
             .columns
             .iter()
-            .filter_map(|column| {
+            .enumerate()
+            .filter_map(|(index, column)| {
                 if !column.skip && column.displayed(width as usize) {
-                    Some(column.text.clone())
+                    Some(sorted_column_text(
+                        column,
+                        index,
+                        props.active_sort,
+                        render.focus,
+                    ))
                 } else {
                     None
                 }
@@ -112,6 +135,7 @@ impl<'a: 'static, S, M> View for Header<S, M> {
         } else {
             props.border_style
         };
+        let border_style = props.density.separator_style(border_style);
 
         // Render header
         let block = HeaderBlock::default()
@@ -128,7 +152,7 @@ impl<'a: 'static, S, M> View for Header<S, M> {
 
         let header = Row::new(cells).style(style::reset().bold());
         let header = ratatui::widgets::Table::default()
-            .column_spacing(1)
+            .column_spacing(props.density.column_spacing())
             .header(header)
             .widths(widths.clone());
 
@@ -137,6 +161,34 @@ impl<'a: 'static, S, M> View for Header<S, M> {
     }
 }
 
+/// Returns the text of a header column. If the column is the active sort key, an
+/// arrow pointing in the sort direction is appended, which is dimmed like other
+/// widgets if the header does not have focus.
+fn sorted_column_text<'a>(
+    column: &Column<'a>,
+    index: usize,
+    active_sort: Option<(usize, bool)>,
+    focus: bool,
+) -> Text<'a> {
+    let mut text = column.text.clone();
+
+    if let Some((_, ascending)) = active_sort.filter(|(active, _)| *active == index) {
+        let arrow = format!(" {}", Charset::current().sort_arrow(ascending));
+        let style = if focus {
+            style::cyan()
+        } else {
+            style::cyan().dim()
+        };
+
+        match text.lines.last_mut() {
+            Some(line) => line.push_span(Span::styled(arrow, style)),
+            None => text.lines.push(Line::styled(arrow, style)),
+        }
+    }
+
+    text
+}
+
 #[derive(Clone, Debug)]
 pub struct FooterProps<'a> {
     pub columns: Vec<Column<'a>>,
@@ -354,6 +406,12 @@ where
     type Message = M;
     type State = S;
 
+    fn takes_text(&self, _props: Option<&ViewProps>) -> bool {
+        self.content
+            .as_ref()
+            .is_some_and(|content| content.takes_text())
+    }
+
     fn handle_event(&mut self, _props: Option<&ViewProps>, key: Key) -> Option<Self::Message> {
         if let Some(content) = &mut self.content {
             content.handle_event(key);
@@ -414,7 +472,7 @@ where
 
         let block = Block::default()
             .border_style(border_style)
-            .border_type(BorderType::Rounded)
+            .border_set(Charset::current().border_set(BorderType::Rounded))
             .borders(borders);
         frame.render_widget(block.clone(), content_area);
 
@@ -442,10 +500,63 @@ pub enum SplitContainerFocus {
     Bottom,
 }
 
+/// The ratio of a resizable `SplitContainer`. It is the height of the top
+/// section in percent of the available height.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SplitContainerState {
+    ratio: u16,
+}
+
+impl SplitContainerState {
+    /// The minimum and maximum ratio, such that both sections stay visible.
+    const RATIO_MIN: u16 = 20;
+    const RATIO_MAX: u16 = 80;
+    /// The amount the ratio is changed by when growing or shrinking.
+    const RATIO_STEP: u16 = 10;
+    /// The minimum height of each section, including its borders.
+    const HEIGHT_MIN: u16 = 3;
+
+    pub fn new(ratio: u16) -> Self {
+        Self {
+            ratio: ratio.clamp(Self::RATIO_MIN, Self::RATIO_MAX),
+        }
+    }
+
+    pub fn ratio(&self) -> u16 {
+        self.ratio
+    }
+
+    /// Grows the top section.
+    pub fn grow(&mut self) {
+        *self = Self::new(self.ratio.saturating_add(Self::RATIO_STEP));
+    }
+
+    /// Shrinks the top section.
+    pub fn shrink(&mut self) {
+        *self = Self::new(self.ratio.saturating_sub(Self::RATIO_STEP));
+    }
+
+    fn heights(&self) -> [Constraint; 2] {
+        [
+            Constraint::Percentage(self.ratio),
+            Constraint::Min(Self::HEIGHT_MIN),
+        ]
+    }
+}
+
+impl Default for SplitContainerState {
+    fn default() -> Self {
+        Self::new(50)
+    }
+}
+
 #[derive(Clone)]
 pub struct SplitContainerProps {
     split_focus: SplitContainerFocus,
     heights: [Constraint; 2],
+    /// The ratio of a resizable container. If not set, the container can not be
+    /// resized and uses `heights`.
+    split: Option<SplitContainerState>,
     border_style: Style,
     focus_border_style: Style,
 }
@@ -457,6 +568,7 @@ impl Default for SplitContainerProps {
         Self {
             split_focus: SplitContainerFocus::default(),
             heights: [Constraint::Percentage(50), Constraint::Percentage(50)],
+            split: None,
             border_style: theme.border_style,
             focus_border_style: theme.focus_border_style,
         }
@@ -474,6 +586,12 @@ impl SplitContainerProps {
         self
     }
 
+    /// Makes the container resizable with `+` and `-`, starting with the given ratio.
+    pub fn split(mut self, split: SplitContainerState) -> Self {
+        self.split = Some(split);
+        self
+    }
+
     pub fn border_style(mut self, color: Style) -> Self {
         self.border_style = color;
         self
@@ -490,6 +608,8 @@ pub struct SplitContainer<S, M> {
     top: Option<Widget<S, M>>,
     /// Content bottom
     bottom: Option<Widget<S, M>>,
+    /// Internal ratio, if resizable
+    state: SplitContainerState,
 }
 
 impl<S, M> Default for SplitContainer<S, M> {
@@ -497,6 +617,7 @@ impl<S, M> Default for SplitContainer<S, M> {
         Self {
             top: None,
             bottom: None,
+            state: SplitContainerState::default(),
         }
     }
 }
@@ -521,12 +642,39 @@ where
     type Message = M;
     type State = S;
 
+    fn takes_text(&self, props: Option<&ViewProps>) -> bool {
+        let default = SplitContainerProps::default();
+        let props = props
+            .and_then(|props| props.inner_ref::<SplitContainerProps>())
+            .unwrap_or(&default);
+
+        let focused = match props.split_focus {
+            SplitContainerFocus::Top => self.top.as_ref(),
+            SplitContainerFocus::Bottom => self.bottom.as_ref(),
+        };
+        focused.is_some_and(|focused| focused.takes_text())
+    }
+
     fn handle_event(&mut self, props: Option<&ViewProps>, key: Key) -> Option<Self::Message> {
         let default = SplitContainerProps::default();
         let props = props
             .and_then(|props| props.inner_ref::<SplitContainerProps>())
             .unwrap_or(&default);
 
+        if props.split.is_some() {
+            match key {
+                Key::Char('+') => {
+                    self.state.grow();
+                    return None;
+                }
+                Key::Char('-') => {
+                    self.state.shrink();
+                    return None;
+                }
+                _ => {}
+            }
+        }
+
         match props.split_focus {
             SplitContainerFocus::Top => {
                 if let Some(top) = self.top.as_mut() {
@@ -543,7 +691,12 @@ where
         None
     }
 
-    fn update(&mut self, _props: Option<&ViewProps>, state: &Self::State) {
+    fn update(&mut self, props: Option<&ViewProps>, state: &Self::State) {
+        let default = SplitContainerProps::default();
+        let props = props
+            .and_then(|props| props.inner_ref::<SplitContainerProps>())
+            .unwrap_or(&default);
+
         if let Some(top) = self.top.as_mut() {
             top.update(state);
         }
@@ -551,6 +704,14 @@ where
         if let Some(bottom) = self.bottom.as_mut() {
             bottom.update(state);
         }
+
+        if let Some(split) = &props.split {
+            self.state = split.clone();
+        }
+    }
+
+    fn view_state(&self) -> Option<ViewState> {
+        Some(ViewState::SplitContainer(self.state.clone()))
     }
 
     fn render(&mut self, props: Option<&ViewProps>, render: RenderProps, frame: &mut Frame) {
@@ -560,7 +721,11 @@ where
             .unwrap_or(&default);
 
         let heights = props
-            .heights
+            .split
+            .as_ref()
+            .map(|_| self.state.heights())
+            .unwrap_or(props.heights);
+        let heights = heights
             .iter()
             .map(|c| {
                 if let Constraint::Length(l) = c {
@@ -600,7 +765,7 @@ where
             let block = Block::default()
                 .borders(Borders::LEFT | Borders::RIGHT | Borders::BOTTOM)
                 .border_style(border_style)
-                .border_type(BorderType::Rounded);
+                .border_set(Charset::current().border_set(BorderType::Rounded));
 
             frame.render_widget(block, bottom_area);
 
@@ -697,6 +862,13 @@ where
     type State = S;
     type Message = M;
 
+    fn takes_text(&self, _props: Option<&ViewProps>) -> bool {
+        self.state
+            .focus
+            .and_then(|focus| self.sections.get(focus))
+            .is_some_and(|section| section.takes_text())
+    }
+
     fn handle_event(&mut self, props: Option<&ViewProps>, key: Key) -> Option<Self::Message> {
         let default = SectionGroupProps::default();
         let props = props
@@ -766,3 +938,147 @@ where
         Some(ViewState::SectionGroup(self.state.clone()))
     }
 }
+
+#[derive(Clone, Debug)]
+pub struct PopupProps<'a> {
+    pub title: String,
+    pub lines: Vec<Line<'a>>,
+    pub border_style: Style,
+}
+
+impl<'a> PopupProps<'a> {
+    pub fn title(mut self, title: &str) -> Self {
+        self.title = title.to_string();
+        self
+    }
+
+    pub fn lines(mut self, lines: Vec<Line<'a>>) -> Self {
+        self.lines = lines;
+        self
+    }
+
+    pub fn border_style(mut self, style: Style) -> Self {
+        self.border_style = style;
+        self
+    }
+}
+
+impl<'a> Default for PopupProps<'a> {
+    fn default() -> Self {
+        let theme = Theme::default();
+
+        Self {
+            title: String::new(),
+            lines: vec![],
+            border_style: theme.focus_border_style,
+        }
+    }
+}
+
+/// A bordered box that is centered in the area given and only takes as much
+/// space as its lines need. Clears everything rendered below it.
+pub struct Popup<S, M> {
+    /// Phantom
+    phantom: PhantomData<(S, M)>,
+}
+
+impl<S, M> Default for Popup<S, M> {
+    fn default() -> Self {
+        Self {
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<'a: 'static, S, M> View for Popup<S, M> {
+    type Message = M;
+    type State = S;
+
+    fn render(&mut self, props: Option<&ViewProps>, render: RenderProps, frame: &mut Frame) {
+        let default = PopupProps::default();
+        let props = props
+            .and_then(|props| props.inner_ref::<PopupProps>())
+            .unwrap_or(&default);
+
+        let content_width = props
+            .lines
+            .iter()
+            .map(|line| line.width())
+            .chain([props.title.chars().count()])
+            .max()
+            .unwrap_or_default() as u16;
+        let width = content_width.saturating_add(4).min(render.area.width);
+        let height = (props.lines.len() as u16)
+            .saturating_add(2)
+            .min(render.area.height);
+
+        let [_, area, _] = Layout::vertical([
+            Constraint::Fill(1),
+            Constraint::Length(height),
+            Constraint::Fill(1),
+        ])
+        .areas(render.area);
+        let [_, area, _] = Layout::horizontal([
+            Constraint::Fill(1),
+            Constraint::Length(width),
+            Constraint::Fill(1),
+        ])
+        .areas(area);
+
+        let block = Block::default()
+            .title(if props.title.is_empty() {
+                String::new()
+            } else {
+                format!(" {} ", props.title)
+            })
+            .borders(Borders::ALL)
+            .border_style(props.border_style)
+            .border_set(Charset::current().border_set(BorderType::Rounded))
+            .padding(ratatui::widgets::Padding::horizontal(1));
+
+        frame.render_widget(ratatui::widgets::Clear, area);
+        frame.render_widget(
+            ratatui::widgets::Paragraph::new(props.lines.clone()).block(block),
+            area,
+        );
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use ratatui::layout::Constraint;
+
+    use crate::ui::Column;
+
+    use super::sorted_column_text;
+
+    #[test]
+    fn active_sort_column_should_have_arrow() {
+        let column = Column::new("Updated", Constraint::Length(10));
+
+        assert_eq!(
+            sorted_column_text(&column, 2, Some((2, false)), true).to_string(),
+            "Updated ▼"
+        );
+        assert_eq!(
+            sorted_column_text(&column, 2, Some((2, true)), false).to_string(),
+            "Updated ▲"
+        );
+        assert_eq!(
+            sorted_column_text(&column, 1, Some((2, true)), true).to_string(),
+            "Updated"
+        );
+        assert_eq!(
+            sorted_column_text(
+                &Column::new("", Constraint::Length(3)),
+                0,
+                Some((0, true)),
+                true
+            )
+            .to_string(),
+            " ▲"
+        );
+    }
+}
@@ -8,7 +8,7 @@ use ratatui::text::{Line, Span, Text};
 use ratatui::widgets::Paragraph;
 use ratatui::Frame;
 
-use crate::ui::theme::Theme;
+use crate::ui::theme::{Charset, Theme};
 
 use super::{utils, RenderProps, View, ViewProps, ViewState};
 
@@ -204,6 +204,10 @@ where
         };
     }
 
+    fn takes_text(&self, _props: Option<&ViewProps>) -> bool {
+        true
+    }
+
     fn handle_event(&mut self, _props: Option<&ViewProps>, key: Key) -> Option<Self::Message> {
         match key {
             Key::Char(to_insert)
@@ -255,7 +259,7 @@ where
         let text = self.state.text.clone().unwrap_or_default();
         let input = text.as_str();
         let label_content = format!(" {} ", props.title);
-        let overline = String::from("▔").repeat(area.width as usize);
+        let overline = Charset::current().overline().repeat(area.width as usize);
         let cursor_pos = self.state.cursor_position as u16;
 
         let (label, input, overline) = if !render.focus && props.dim {
@@ -406,6 +410,12 @@ impl<'a, S, M> View for TextArea<'a, S, M> {
     type State = S;
     type Message = M;
 
+    fn takes_text(&self, props: Option<&ViewProps>) -> bool {
+        props
+            .and_then(|props| props.inner_ref::<TextAreaProps>())
+            .is_some_and(|props| props.handle_keys && props.insert_mode)
+    }
+
     fn handle_event(&mut self, props: Option<&ViewProps>, key: Key) -> Option<Self::Message> {
         use tui_textarea::Input;
 
@@ -607,6 +617,10 @@ pub struct TextViewProps<'a> {
     show_scroll_progress: bool,
     /// An optional text that is rendered inside the footer bar on the bottom.
     footer: Option<Text<'a>>,
+    /// Styled text that is rendered instead of the content of the state, e.g.
+    /// rendered markdown. Scrolling still follows the content of the state, so
+    /// both should have the same lines.
+    text: Option<Text<'a>>,
     /// The style used whenever the widget has focus.
     content_style: Style,
     /// Default scroll progress style.
@@ -632,6 +646,14 @@ impl<'a> TextViewProps<'a> {
         self
     }
 
+    pub fn text<T>(mut self, text: Option<T>) -> Self
+    where
+        T: Into<Text<'a>>,
+    {
+        self.text = text.map(|t| t.into());
+        self
+    }
+
     pub fn show_scroll_progress(mut self, show_scroll_progress: bool) -> Self {
         self.show_scroll_progress = show_scroll_progress;
         self
@@ -672,6 +694,7 @@ impl<'a> Default for TextViewProps<'a> {
             handle_keys: true,
             show_scroll_progress: false,
             footer: None,
+            text: None,
             content_style: theme.textview_style,
             scroll_style: theme.textview_scroll_style,
             focus_scroll_style: theme.textview_focus_scroll_style,
@@ -751,7 +774,11 @@ impl<S, M> TextView<S, M> {
             props.content_style
         };
 
-        let content = Paragraph::new(self.state.content.clone())
+        let text = props
+            .text
+            .clone()
+            .unwrap_or_else(|| self.state.content.clone().into());
+        let content = Paragraph::new(text)
             .style(content_style)
             .scroll((self.state.cursor.0 as u16, self.state.cursor.1 as u16));
 
@@ -10,33 +10,42 @@ use ratatui::style::{Style, Stylize};
 use ratatui::symbols::border;
 use ratatui::text::Text;
 use ratatui::widgets::TableState;
-use ratatui::widgets::{Block, Borders, Row, Scrollbar, ScrollbarOrientation, ScrollbarState};
+use ratatui::widgets::{Block, Borders, Row};
 use ratatui::Frame;
 
 use tui_tree_widget::TreeState;
 
-use crate::ui::theme::style;
+use crate::ui::scrollbar::{self, AutoHide, ScrollPosition};
+use crate::ui::theme::{Charset, Density, HighlightStyle, Theme};
 use crate::ui::{layout, span};
-use crate::ui::{Column, ToRow, ToTree};
+use crate::ui::{Column, Highlight, TableRow, ToTree};
 
 use super::{utils, ViewProps, ViewState};
 use super::{RenderProps, View};
 
 #[derive(Clone, Debug)]
-pub struct TableProps<'a, R, const W: usize>
+pub struct TableProps<'a, R, A>
 where
-    R: ToRow<W>,
+    R: TableRow<A>,
 {
     pub items: Vec<R>,
     pub selected: Option<usize>,
     pub columns: Vec<Column<'a>>,
     pub show_scrollbar: bool,
     pub dim: bool,
+    /// The search matching parts of rows are highlighted for.
+    pub highlight: Option<Highlight>,
+    /// Overrides the theme's style of the selected row.
+    pub highlight_style: Option<HighlightStyle>,
+    /// How densely columns are laid out.
+    pub density: Density,
+    /// Phantom
+    phantom: PhantomData<A>,
 }
 
-impl<'a, R, const W: usize> Default for TableProps<'a, R, W>
+impl<'a, R, A> Default for TableProps<'a, R, A>
 where
-    R: ToRow<W>,
+    R: TableRow<A>,
 {
     fn default() -> Self {
         Self {
@@ -45,13 +54,17 @@ where
             show_scrollbar: true,
             selected: Some(0),
             dim: false,
+            highlight: None,
+            highlight_style: None,
+            density: Theme::default().density,
+            phantom: PhantomData,
         }
     }
 }
 
-impl<'a, R, const W: usize> TableProps<'a, R, W>
+impl<'a, R, A> TableProps<'a, R, A>
 where
-    R: ToRow<W>,
+    R: TableRow<A>,
 {
     pub fn items(mut self, items: Vec<R>) -> Self {
         self.items = items;
@@ -77,23 +90,38 @@ where
         self.dim = dim;
         self
     }
+
+    pub fn highlight(mut self, highlight: Option<Highlight>) -> Self {
+        self.highlight = highlight;
+        self
+    }
+
+    pub fn highlight_style(mut self, style: Option<HighlightStyle>) -> Self {
+        self.highlight_style = style;
+        self
+    }
+
+    pub fn density(mut self, density: Density) -> Self {
+        self.density = density;
+        self
+    }
 }
 
-pub struct Table<S, M, R, const W: usize>
+pub struct Table<S, M, R, A>
 where
-    R: ToRow<W>,
+    R: TableRow<A>,
 {
     /// Internal selection and offset state
     state: (TableState, usize),
     /// Phantom
-    phantom: PhantomData<(S, M, R)>,
+    phantom: PhantomData<(S, M, R, A)>,
     /// Current render height
     height: u16,
 }
 
-impl<S, M, R, const W: usize> Default for Table<S, M, R, W>
+impl<S, M, R, A> Default for Table<S, M, R, A>
 where
-    R: ToRow<W>,
+    R: TableRow<A>,
 {
     fn default() -> Self {
         Self {
@@ -104,9 +132,9 @@ where
     }
 }
 
-impl<S, M, R, const W: usize> Table<S, M, R, W>
+impl<S, M, R, A> Table<S, M, R, A>
 where
-    R: ToRow<W>,
+    R: TableRow<A>,
 {
     fn prev(&mut self) -> Option<usize> {
         let selected = self
@@ -161,11 +189,12 @@ where
     }
 }
 
-impl<S, M, R, const W: usize> View for Table<S, M, R, W>
+impl<S, M, R, A> View for Table<S, M, R, A>
 where
     S: 'static,
     M: 'static,
-    R: ToRow<W> + Clone + 'static,
+    R: TableRow<A> + Clone + 'static,
+    A: Clone + 'static,
 {
     type Message = M;
     type State = S;
@@ -173,7 +202,7 @@ where
     fn handle_event(&mut self, props: Option<&ViewProps>, key: Key) -> Option<Self::Message> {
         let default = TableProps::default();
         let props = props
-            .and_then(|props| props.inner_ref::<TableProps<R, W>>())
+            .and_then(|props| props.inner_ref::<TableProps<R, A>>())
             .unwrap_or(&default);
 
         let page_size = self.height;
@@ -206,7 +235,7 @@ where
     fn update(&mut self, props: Option<&ViewProps>, _state: &Self::State) {
         let default = TableProps::default();
         let props = props
-            .and_then(|props| props.inner_ref::<TableProps<R, W>>())
+            .and_then(|props| props.inner_ref::<TableProps<R, A>>())
             .unwrap_or(&default);
 
         if props.selected != self.state.0.selected() {
@@ -218,7 +247,7 @@ where
     fn render(&mut self, props: Option<&ViewProps>, render: RenderProps, frame: &mut Frame) {
         let default = TableProps::default();
         let props = props
-            .and_then(|props| props.inner_ref::<TableProps<R, W>>())
+            .and_then(|props| props.inner_ref::<TableProps<R, A>>())
             .unwrap_or(&default);
 
         let show_scrollbar = props.show_scrollbar && props.items.len() >= self.height.into();
@@ -253,8 +282,9 @@ where
                 .map(|item| {
                     let mut cells = vec![];
                     let mut it = props.columns.iter();
+                    let row = item.cells(props.highlight.as_ref());
 
-                    for cell in item.to_row() {
+                    for cell in row {
                         if let Some(col) = it.next() {
                             if !col.skip && col.displayed(render.area.width as usize) {
                                 cells.push(cell.clone())
@@ -271,8 +301,13 @@ where
             let table = ratatui::widgets::Table::default()
                 .rows(rows)
                 .widths(widths)
-                .column_spacing(1)
-                .row_highlight_style(style::highlight(render.focus));
+                .column_spacing(props.density.column_spacing())
+                .row_highlight_style(
+                    props
+                        .highlight_style
+                        .unwrap_or(Theme::default().highlight_style)
+                        .style(render.focus),
+                );
 
             let table = if !render.focus && props.dim {
                 table.dim()
@@ -282,20 +317,18 @@ where
 
             frame.render_stateful_widget(table, table_area, &mut self.state.0);
 
-            let scroller = Scrollbar::default()
-                .begin_symbol(None)
-                .track_symbol(None)
-                .end_symbol(None)
-                .thumb_symbol("┃")
-                .style(if render.focus {
-                    Style::default()
-                } else {
-                    Style::default().dim()
-                });
-            let mut scroller_state = ScrollbarState::default()
-                .content_length(props.items.len().saturating_sub(self.height.into()))
-                .position(self.state.0.offset());
-            frame.render_stateful_widget(scroller, scroller_area, &mut scroller_state);
+            scrollbar::render(
+                frame,
+                scroller_area,
+                &Theme::default().with_charset(Charset::current()),
+                render.focus,
+                AutoHide::WhenFits,
+                ScrollPosition::new(
+                    props.items.len(),
+                    table_area.height.into(),
+                    self.state.0.offset(),
+                ),
+            );
         } else {
             let center = layout::centered_rect(render.area, 50, 10);
             let hint = Text::from(span::default("Nothing to show"))
@@ -342,6 +375,8 @@ where
     /// Set to `true` if the content style should be dimmed whenever the widget
     /// has no focus.
     pub dim: bool,
+    /// Overrides the theme's style of the selected item.
+    pub highlight_style: Option<HighlightStyle>,
 }
 
 impl<R, Id> Default for TreeProps<R, Id>
@@ -356,6 +391,7 @@ where
             show_scrollbar: true,
             opened: None,
             dim: false,
+            highlight_style: None,
         }
     }
 }
@@ -389,6 +425,11 @@ where
         self.dim = dim;
         self
     }
+
+    pub fn highlight_style(mut self, style: Option<HighlightStyle>) -> Self {
+        self.highlight_style = style;
+        self
+    }
 }
 
 /// A `Tree` is an expandable, collapsable and scrollable tree widget, that takes
@@ -491,6 +532,11 @@ where
             Style::default()
         };
 
+        let highlight_style = props
+            .highlight_style
+            .unwrap_or(Theme::default().highlight_style)
+            .style(render.focus);
+
         let tree = if props.show_scrollbar {
             tui_tree_widget::Tree::new(&items)
                 .expect("all item identifiers are unique")
@@ -507,20 +553,17 @@ where
                             Style::default().dim()
                         }),
                 )
-                .experimental_scrollbar(Some(
-                    Scrollbar::new(ScrollbarOrientation::VerticalRight)
-                        .begin_symbol(None)
-                        .track_symbol(None)
-                        .end_symbol(None)
-                        .thumb_symbol("┃"),
-                ))
-                .highlight_style(style::highlight(render.focus))
+                .experimental_scrollbar(Some(scrollbar::scrollbar(
+                    &Theme::default().with_charset(Charset::current()),
+                    true,
+                )))
+                .highlight_style(highlight_style)
                 .style(tree_style)
         } else {
             tui_tree_widget::Tree::new(&items)
                 .expect("all item identifiers are unique")
                 .style(tree_style)
-                .highlight_style(style::highlight(render.focus))
+                .highlight_style(highlight_style)
         };
 
         frame.render_stateful_widget(tree, render.area, &mut self.state);
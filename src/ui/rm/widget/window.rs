@@ -1,15 +1,18 @@
 use std::hash::Hash;
+use std::time::Instant;
 use std::{collections::HashMap, marker::PhantomData};
 
 use ratatui::Frame;
 use termion::event::Key;
 
 use ratatui::layout::{Constraint, Layout};
-use ratatui::style::{Style, Stylize};
+use ratatui::style::Style;
 use ratatui::text::Text;
-use ratatui::widgets::Row;
 
-use crate::ui::theme::{style, Theme};
+use crate::ui::keyhint::{KeyHint, PendingPrefix, PrefixMap};
+use crate::ui::palette::{Palette, PaletteEvent, PALETTE_KEY};
+use crate::ui::theme::Theme;
+use crate::ui::{shortcut_lines, Shortcut};
 
 use super::{RenderProps, View, ViewProps, Widget};
 
@@ -64,6 +67,14 @@ where
     type Message = M;
     type State = S;
 
+    fn takes_text(&self, props: Option<&ViewProps>) -> bool {
+        props
+            .and_then(|props| props.inner_ref::<WindowProps<Id>>())
+            .and_then(|props| props.current_page.as_ref())
+            .and_then(|id| self.pages.get(id))
+            .is_some_and(|page| page.takes_text())
+    }
+
     fn handle_event(&mut self, props: Option<&ViewProps>, key: Key) -> Option<Self::Message> {
         let default = WindowProps::default();
         let props = props
@@ -121,6 +132,11 @@ where
 pub struct PageProps {
     /// If this view's should handle keys
     pub handle_keys: bool,
+    /// If the overlay should be rendered on top of the content. Keys are
+    /// passed to the overlay instead of the content while it's shown.
+    pub show_overlay: bool,
+    /// The theme the operation palette is rendered in.
+    pub theme: Theme,
 }
 
 impl PageProps {
@@ -128,20 +144,46 @@ impl PageProps {
         self.handle_keys = handle_keys;
         self
     }
+
+    pub fn show_overlay(mut self, show_overlay: bool) -> Self {
+        self.show_overlay = show_overlay;
+        self
+    }
+
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
 }
 
 pub struct Page<S, M> {
     /// Content widget
     content: Option<Widget<S, M>>,
+    /// Overlay widget, e.g. a popup
+    overlay: Option<Widget<S, M>>,
     /// Shortcut widget
     shortcuts: Option<Widget<S, M>>,
+    /// Prefix keys of this page and their follow-up keys
+    prefixes: PrefixMap,
+    /// The prefix key that was pressed last. It's tracked by the page, such
+    /// that its hint is due on the frontend's ticks, not on the store's.
+    pending: PendingPrefix,
+    /// The operation palette, if it's open
+    palette: Option<Palette>,
+    /// The key the page's event callback is called with next
+    event_key: Option<Key>,
 }
 
 impl<S, M> Default for Page<S, M> {
     fn default() -> Self {
         Self {
             content: None,
+            overlay: None,
             shortcuts: None,
+            prefixes: PrefixMap::default(),
+            pending: PendingPrefix::default(),
+            palette: None,
+            event_key: None,
         }
     }
 }
@@ -152,10 +194,20 @@ impl<S, M> Page<S, M> {
         self
     }
 
+    pub fn overlay(mut self, overlay: Widget<S, M>) -> Self {
+        self.overlay = Some(overlay);
+        self
+    }
+
     pub fn shortcuts(mut self, shortcuts: Widget<S, M>) -> Self {
         self.shortcuts = Some(shortcuts);
         self
     }
+
+    pub fn prefixes(mut self, prefixes: PrefixMap) -> Self {
+        self.prefixes = prefixes;
+        self
+    }
 }
 
 impl<S, M> View for Page<S, M>
@@ -166,24 +218,101 @@ where
     type State = S;
     type Message = M;
 
-    fn handle_event(&mut self, _props: Option<&ViewProps>, key: Key) -> Option<Self::Message> {
-        if let Some(content) = self.content.as_mut() {
-            content.handle_event(key);
+    fn takes_text(&self, props: Option<&ViewProps>) -> bool {
+        let default = PageProps::default();
+        let props = props
+            .and_then(|props| props.inner_ref::<PageProps>())
+            .unwrap_or(&default);
+
+        let target = if props.show_overlay {
+            self.overlay.as_ref()
+        } else {
+            self.content.as_ref()
+        };
+        self.palette.is_some() || target.is_some_and(|target| target.takes_text())
+    }
+
+    fn handle_event(&mut self, props: Option<&ViewProps>, key: Key) -> Option<Self::Message> {
+        let default = PageProps::default();
+        let props = props
+            .and_then(|props| props.inner_ref::<PageProps>())
+            .unwrap_or(&default);
+
+        self.pending.clear();
+
+        // While the palette is open, it takes all keys. A shortcut picked from
+        // it is handled as if its key was pressed.
+        self.event_key = Some(key);
+        let key = if let Some(palette) = self.palette.as_mut() {
+            match palette.handle_key(key) {
+                PaletteEvent::Open => {
+                    self.event_key = None;
+                    return None;
+                }
+                PaletteEvent::Closed => {
+                    self.palette = None;
+                    self.event_key = None;
+                    return None;
+                }
+                PaletteEvent::Picked(picked) => {
+                    self.palette = None;
+                    self.event_key = Some(picked);
+                    picked
+                }
+            }
+        } else if key == PALETTE_KEY && props.handle_keys && !props.show_overlay {
+            let shortcuts = self
+                .shortcuts
+                .as_ref()
+                .and_then(|shortcuts| shortcuts.props.as_ref())
+                .and_then(|props| props.inner_ref::<ShortcutsProps>())
+                .map(|props| props.shortcuts.clone())
+                .unwrap_or_default();
+            self.palette = Some(Palette::new(shortcuts).with_theme(&props.theme));
+            self.event_key = None;
+            return None;
+        } else {
+            key
+        };
+
+        if props.handle_keys && !props.show_overlay {
+            self.pending.press(&self.prefixes, key, Instant::now());
+        }
+
+        let target = if props.show_overlay {
+            self.overlay.as_mut()
+        } else {
+            self.content.as_mut()
+        };
+        if let Some(target) = target {
+            target.handle_event(key);
         }
 
         None
     }
 
+    fn event_key(&mut self, _key: Key) -> Option<Key> {
+        self.event_key.take()
+    }
+
     fn update(&mut self, _props: Option<&ViewProps>, state: &Self::State) {
         if let Some(content) = self.content.as_mut() {
             content.update(state);
         }
+        if let Some(overlay) = self.overlay.as_mut() {
+            overlay.update(state);
+        }
         if let Some(shortcuts) = self.shortcuts.as_mut() {
             shortcuts.update(state);
         }
     }
 
-    fn render(&mut self, _props: Option<&ViewProps>, render: RenderProps, frame: &mut Frame) {
+    fn render(&mut self, props: Option<&ViewProps>, render: RenderProps, frame: &mut Frame) {
+        let default = PageProps::default();
+        let props = props
+            .and_then(|props| props.inner_ref::<PageProps>())
+            .unwrap_or(&default);
+
         let [content_area, shortcuts_area] =
             Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).areas(render.area);
 
@@ -191,11 +320,29 @@ where
             content.render(
                 RenderProps::from(content_area)
                     .layout(Layout::horizontal([Constraint::Min(1)]))
-                    .focus(true),
+                    .focus(!props.show_overlay),
                 frame,
             );
         }
 
+        if props.show_overlay {
+            if let Some(overlay) = self.overlay.as_mut() {
+                overlay.render(RenderProps::from(content_area).focus(true), frame);
+            }
+        }
+
+        // Key hints don't take any input, such that the follow-up key is still
+        // handled by the content.
+        if let Some(prefix) = self.pending.due(Instant::now()) {
+            if let Some(hints) = self.prefixes.hints(prefix) {
+                frame.render_widget(KeyHint::new(prefix, hints), content_area);
+            }
+        }
+
+        if let Some(palette) = self.palette.as_ref() {
+            frame.render_widget(palette, content_area);
+        }
+
         if let Some(shortcuts) = self.shortcuts.as_mut() {
             shortcuts.render(RenderProps::from(shortcuts_area), frame);
         }
@@ -204,7 +351,7 @@ where
 
 #[derive(Clone)]
 pub struct ShortcutsProps {
-    pub shortcuts: Vec<(String, String)>,
+    pub shortcuts: Vec<Shortcut>,
     pub divider: char,
     pub shortcuts_keys_style: Style,
     pub shortcuts_action_style: Style,
@@ -216,11 +363,11 @@ impl ShortcutsProps {
         self
     }
 
-    pub fn shortcuts(mut self, shortcuts: &[(&str, &str)]) -> Self {
-        self.shortcuts.clear();
-        for (short, long) in shortcuts {
-            self.shortcuts.push((short.to_string(), long.to_string()));
-        }
+    pub fn shortcuts<S>(mut self, shortcuts: impl IntoIterator<Item = S>) -> Self
+    where
+        S: Into<Shortcut>,
+    {
+        self.shortcuts = shortcuts.into_iter().map(Into::into).collect();
         self
     }
 
@@ -266,44 +413,19 @@ impl<S, M> View for Shortcuts<S, M> {
     type State = S;
 
     fn render(&mut self, props: Option<&ViewProps>, render: RenderProps, frame: &mut Frame) {
-        use ratatui::widgets::Table;
-
         let default = ShortcutsProps::default();
         let props = props
             .and_then(|props| props.inner_ref::<ShortcutsProps>())
             .unwrap_or(&default);
 
-        let mut shortcuts = props.shortcuts.iter().peekable();
-        let mut row = vec![];
-
-        while let Some(shortcut) = shortcuts.next() {
-            let short = Text::from(shortcut.0.clone()).style(props.shortcuts_keys_style);
-            let long = Text::from(shortcut.1.clone()).style(props.shortcuts_action_style);
-            let spacer = Text::from(String::new());
-            let divider = Text::from(format!(" {} ", props.divider)).style(style::gray().dim());
-
-            row.push((shortcut.0.chars().count(), short));
-            row.push((1, spacer));
-            row.push((shortcut.1.chars().count(), long));
-
-            if shortcuts.peek().is_some() {
-                row.push((3, divider));
-            }
-        }
-
-        let row_copy = row.clone();
-        let row: Vec<Text<'_>> = row_copy
-            .clone()
-            .iter()
-            .map(|(_, text)| text.clone())
-            .collect();
-        let widths: Vec<Constraint> = row_copy
-            .clone()
-            .iter()
-            .map(|(width, _)| Constraint::Length(*width as u16))
-            .collect();
-
-        let table = Table::new([Row::new(row)], widths).column_spacing(0);
-        frame.render_widget(table, render.area);
+        let lines = shortcut_lines(
+            &props.shortcuts,
+            props.divider,
+            render.area.width.into(),
+            render.area.height.into(),
+            props.shortcuts_keys_style,
+            props.shortcuts_action_style,
+        );
+        frame.render_widget(Text::from(lines), render.area);
     }
 }
@@ -0,0 +1,133 @@
+//! Vertical scrollbars for widgets that render scrollable content.
+
+use ratatui::layout::Rect;
+use ratatui::style::{Style, Stylize};
+use ratatui::widgets::{Scrollbar, ScrollbarState};
+use ratatui::Frame;
+
+use crate::ui::theme::Theme;
+
+/// Defines if a scrollbar is hidden when there's nothing to scroll.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum AutoHide {
+    /// The scrollbar is always rendered.
+    Never,
+    /// The scrollbar is hidden if all content fits into the viewport.
+    #[default]
+    WhenFits,
+}
+
+/// The scroll position of content that is rendered in a viewport.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ScrollPosition {
+    /// Amount of lines of the content.
+    pub len: usize,
+    /// Amount of lines that fit into the viewport.
+    pub height: usize,
+    /// Amount of lines scrolled.
+    pub offset: usize,
+}
+
+impl ScrollPosition {
+    pub fn new(len: usize, height: usize, offset: usize) -> Self {
+        Self {
+            len,
+            height,
+            offset,
+        }
+    }
+
+    /// Returns true if all content fits into the viewport.
+    pub fn fits(&self) -> bool {
+        self.len <= self.height
+    }
+
+    /// The state of a scrollbar for this position. Every offset the content can be
+    /// scrolled to is a position of the scrollbar, such that the size of the thumb
+    /// is proportional to the visible part and the thumb reaches the end of the track
+    /// once the last line is visible.
+    pub fn state(&self) -> ScrollbarState {
+        let positions = self.len.saturating_sub(self.height).saturating_add(1);
+
+        ScrollbarState::new(positions)
+            .viewport_content_length(self.height)
+            .position(self.offset.min(positions - 1))
+    }
+}
+
+/// Builds a vertical scrollbar with the symbols of the given theme. It's dimmed
+/// if the widget it belongs to does not have focus.
+pub fn scrollbar<'a>(theme: &Theme, focus: bool) -> Scrollbar<'a> {
+    Scrollbar::default()
+        .begin_symbol(None)
+        .track_symbol(theme.scrollbar_track_symbol)
+        .end_symbol(None)
+        .thumb_symbol(theme.scrollbar_thumb_symbol)
+        .style(if focus {
+            Style::default()
+        } else {
+            Style::default().dim()
+        })
+}
+
+/// Renders a scrollbar for the given position, unless it should be hidden.
+pub fn render(
+    frame: &mut Frame,
+    area: Rect,
+    theme: &Theme,
+    focus: bool,
+    auto_hide: AutoHide,
+    position: ScrollPosition,
+) {
+    if auto_hide == AutoHide::WhenFits && position.fits() {
+        return;
+    }
+
+    frame.render_stateful_widget(scrollbar(theme, focus), area, &mut position.state());
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use ratatui::buffer::Buffer;
+    use ratatui::layout::Rect;
+    use ratatui::widgets::StatefulWidget;
+
+    use crate::ui::im::testing::lines;
+    use crate::ui::theme::Theme;
+
+    use super::{scrollbar, ScrollPosition};
+
+    fn render(position: ScrollPosition) -> Vec<String> {
+        let theme = Theme::default();
+        let area = Rect::new(0, 0, 1, position.height as u16);
+        let mut buffer = Buffer::empty(area);
+
+        scrollbar(&theme, true).render(area, &mut buffer, &mut position.state());
+
+        lines(&buffer)
+    }
+
+    #[test]
+    fn thumb_should_reach_bottom_when_scrolled_to_last_item() {
+        let track = render(ScrollPosition::new(100, 10, 90));
+
+        assert_eq!(track.last().map(String::as_str), Some("┃"));
+        assert_eq!(track.first().map(String::as_str), Some(" "));
+    }
+
+    #[test]
+    fn thumb_should_start_at_top_when_not_scrolled() {
+        let track = render(ScrollPosition::new(100, 10, 0));
+
+        assert_eq!(track.first().map(String::as_str), Some("┃"));
+        assert_eq!(track.last().map(String::as_str), Some(" "));
+    }
+
+    #[test]
+    fn content_that_fits_should_fit() {
+        assert!(ScrollPosition::new(10, 10, 0).fits());
+        assert!(!ScrollPosition::new(11, 10, 0).fits());
+    }
+}
@@ -1,7 +1,7 @@
 use ratatui::style::{Style, Stylize};
 use ratatui::text::Span;
 
-use crate::ui::theme::style;
+use crate::ui::theme::{style, BadgeKind, Theme};
 
 pub fn blank() -> Span<'static> {
     Span::styled("", Style::default())
@@ -31,9 +31,11 @@ pub fn negative(content: &str) -> Span<'static> {
     default(content).style(style::red())
 }
 
-pub fn badge(content: &str) -> Span<'static> {
+/// Renders the given text as a pill filled with the color the theme uses for
+/// the kind of status given.
+pub fn badge(content: &str, kind: BadgeKind) -> Span<'static> {
     let content = &format!(" {content} ");
-    default(content).magenta().reversed()
+    default(content).style(Theme::default().badge_styles.style(kind))
 }
 
 pub fn alias(content: &str) -> Span<'static> {
@@ -59,18 +61,18 @@ pub fn notification_type(content: &str) -> Span<'static> {
 pub fn step(step: usize, len: usize, fill_zeros: bool) -> Span<'static> {
     if fill_zeros {
         if len > 10 {
-            badge(&format!("{:-02}/{:-02}", step, len))
+            badge(&format!("{:-02}/{:-02}", step, len), BadgeKind::Neutral)
         } else if len > 100 {
-            badge(&format!("{:-03}/{:-03}", step, len))
+            badge(&format!("{:-03}/{:-03}", step, len), BadgeKind::Neutral)
         } else if len > 1000 {
-            badge(&format!("{:-04}/{:-04}", step, len))
+            badge(&format!("{:-04}/{:-04}", step, len), BadgeKind::Neutral)
         } else if len > 10000 {
-            badge(&format!("{:-05}/{:-05}", step, len))
+            badge(&format!("{:-05}/{:-05}", step, len), BadgeKind::Neutral)
         } else {
-            badge(&format!("{}/{}", step, len))
+            badge(&format!("{}/{}", step, len), BadgeKind::Neutral)
         }
     } else {
-        badge(&format!("{}/{}", step, len))
+        badge(&format!("{}/{}", step, len), BadgeKind::Neutral)
     }
 }
 
@@ -1,4 +1,307 @@
+use std::env;
+use std::sync::OnceLock;
+
 use ratatui::style::{Color, Style, Stylize};
+use ratatui::symbols::{bar, border, line};
+use ratatui::widgets::BorderType;
+
+/// The charset detected by the running frontend, see `Charset::current`.
+static CURRENT: OnceLock<Charset> = OnceLock::new();
+
+/// The characters widgets are drawn with. Terminals that can't render Unicode,
+/// e.g. because of their locale or font, fall back to plain ASCII.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Charset {
+    #[default]
+    Unicode,
+    Ascii,
+}
+
+impl Charset {
+    /// An ASCII replacement for all box-drawing characters.
+    const ASCII_BORDER: border::Set = border::Set {
+        top_left: "+",
+        top_right: "+",
+        bottom_left: "+",
+        bottom_right: "+",
+        vertical_left: "|",
+        vertical_right: "|",
+        horizontal_top: "-",
+        horizontal_bottom: "-",
+    };
+
+    const ASCII_BAR: bar::Set = bar::Set {
+        full: "#",
+        seven_eighths: "#",
+        three_quarters: "=",
+        five_eighths: "=",
+        half: "-",
+        three_eighths: "-",
+        one_quarter: ".",
+        one_eighth: ".",
+        empty: " ",
+    };
+
+    const ASCII_LINE: line::Set = line::Set {
+        vertical: "|",
+        horizontal: "-",
+        top_right: "+",
+        top_left: "+",
+        bottom_right: "+",
+        bottom_left: "+",
+        vertical_left: "+",
+        vertical_right: "+",
+        horizontal_down: "+",
+        horizontal_up: "+",
+        cross: "+",
+    };
+
+    /// Returns the charset the running frontend detected, or `Unicode` if there
+    /// is none, e.g. in tests. It's used where there's no theme at hand, e.g. by
+    /// retained mode widgets and in rows of items.
+    pub fn current() -> Self {
+        CURRENT.get().copied().unwrap_or_default()
+    }
+
+    /// Sets the charset returned by `current`. It's set once by the frontend,
+    /// later calls are ignored.
+    pub fn init(charset: Self) {
+        let _ = CURRENT.set(charset);
+    }
+
+    /// Detects the charset from the environment. Falls back to ASCII on dumb
+    /// terminals and if the locale does not use UTF-8.
+    pub fn detect() -> Self {
+        let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+            .iter()
+            .filter_map(|name| env::var(name).ok())
+            .find(|value| !value.is_empty());
+
+        Self::from_env(env::var("TERM").ok().as_deref(), locale.as_deref())
+    }
+
+    fn from_env(term: Option<&str>, locale: Option<&str>) -> Self {
+        let dumb = term.is_some_and(|term| term == "dumb");
+        let utf8 = locale.map_or(true, |locale| {
+            let locale = locale.to_lowercase();
+            locale.contains("utf-8") || locale.contains("utf8")
+        });
+
+        if dumb || !utf8 {
+            Charset::Ascii
+        } else {
+            Charset::Unicode
+        }
+    }
+
+    /// Returns the border symbols of the given type, or plain ASCII ones.
+    pub fn border_set(&self, border_type: BorderType) -> border::Set {
+        match self {
+            Charset::Unicode => border_type.to_border_set(),
+            Charset::Ascii => Self::ASCII_BORDER,
+        }
+    }
+
+    /// Returns the symbols used to connect borders, e.g. the junction of a
+    /// header and the table below it.
+    pub fn line_set(&self) -> line::Set {
+        match self {
+            Charset::Unicode => line::ROUNDED,
+            Charset::Ascii => Self::ASCII_LINE,
+        }
+    }
+
+    /// Returns the symbol of a line drawn at the very top of a cell, e.g. above
+    /// a footer.
+    pub fn overline(&self) -> &'static str {
+        match self {
+            Charset::Unicode => "▔",
+            Charset::Ascii => "-",
+        }
+    }
+
+    /// Returns the symbols of bars that fill a cell partially, e.g. in sparklines.
+    pub fn bar_set(&self) -> bar::Set {
+        match self {
+            Charset::Unicode => bar::NINE_LEVELS,
+            Charset::Ascii => Self::ASCII_BAR,
+        }
+    }
+
+    pub fn scrollbar_thumb(&self) -> &'static str {
+        match self {
+            Charset::Unicode => "┃",
+            Charset::Ascii => "|",
+        }
+    }
+
+    pub fn bullet(&self) -> &'static str {
+        match self {
+            Charset::Unicode => "●",
+            Charset::Ascii => "*",
+        }
+    }
+
+    /// Returns the bullet of list items, e.g. in markdown.
+    pub fn list_bullet(&self) -> &'static str {
+        match self {
+            Charset::Unicode => "•",
+            Charset::Ascii => "*",
+        }
+    }
+
+    /// Returns the marker of a group of items that is expanded if `expanded`,
+    /// and collapsed otherwise.
+    pub fn fold_marker(&self, expanded: bool) -> &'static str {
+        match (self, expanded) {
+            (Charset::Unicode, true) => "▾",
+            (Charset::Unicode, false) => "▸",
+            (Charset::Ascii, true) => "v",
+            (Charset::Ascii, false) => ">",
+        }
+    }
+
+    /// Returns the symbol that connects the last item of a group to it.
+    pub fn branch(&self) -> &'static str {
+        match self {
+            Charset::Unicode => "└",
+            Charset::Ascii => "`",
+        }
+    }
+
+    /// Returns the symbol progress bars are drawn with.
+    pub fn progress(&self) -> &'static str {
+        match self {
+            Charset::Unicode => "━",
+            Charset::Ascii => "=",
+        }
+    }
+
+    /// Returns an arrow pointing up if `ascending`, and down otherwise.
+    pub fn sort_arrow(&self, ascending: bool) -> &'static str {
+        match (self, ascending) {
+            (Charset::Unicode, true) => "▲",
+            (Charset::Unicode, false) => "▼",
+            (Charset::Ascii, true) => "^",
+            (Charset::Ascii, false) => "v",
+        }
+    }
+}
+
+/// The style of the selected item of tables, lists and trees, depending on
+/// whether the widget has the focus.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HighlightStyle {
+    pub focus: Style,
+    pub no_focus: Style,
+}
+
+impl Default for HighlightStyle {
+    fn default() -> Self {
+        Self {
+            focus: style::highlight(true),
+            no_focus: style::highlight(false),
+        }
+    }
+}
+
+impl HighlightStyle {
+    pub fn style(&self, focus: bool) -> Style {
+        if focus {
+            self.focus
+        } else {
+            self.no_focus
+        }
+    }
+}
+
+/// The kind of status a badge shows, e.g. the state of an issue or patch.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BadgeKind {
+    Open,
+    Closed,
+    Merged,
+    Draft,
+    Archived,
+    Updated,
+    Neutral,
+}
+
+/// The styles badges are filled with, one per kind of status.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BadgeStyles {
+    pub open: Style,
+    pub closed: Style,
+    pub merged: Style,
+    pub draft: Style,
+    pub archived: Style,
+    pub updated: Style,
+    pub neutral: Style,
+}
+
+impl Default for BadgeStyles {
+    fn default() -> Self {
+        Self {
+            open: style::green().reversed(),
+            closed: style::red().reversed(),
+            merged: style::magenta().reversed(),
+            draft: style::gray().dim().reversed(),
+            archived: style::yellow().reversed(),
+            updated: style::cyan().reversed(),
+            neutral: style::magenta().reversed(),
+        }
+    }
+}
+
+impl BadgeStyles {
+    pub fn style(&self, kind: BadgeKind) -> Style {
+        match kind {
+            BadgeKind::Open => self.open,
+            BadgeKind::Closed => self.closed,
+            BadgeKind::Merged => self.merged,
+            BadgeKind::Draft => self.draft,
+            BadgeKind::Archived => self.archived,
+            BadgeKind::Updated => self.updated,
+            BadgeKind::Neutral => self.neutral,
+        }
+    }
+}
+
+/// How densely tables are laid out. Compact tables fit more content by leaving
+/// out the space between columns and dimming separators, such that the content
+/// stands out nevertheless.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Density {
+    #[default]
+    Comfortable,
+    Compact,
+}
+
+impl Density {
+    /// Returns the other density.
+    pub fn toggle(&self) -> Self {
+        match self {
+            Density::Comfortable => Density::Compact,
+            Density::Compact => Density::Comfortable,
+        }
+    }
+
+    /// The space between table columns.
+    pub fn column_spacing(&self) -> u16 {
+        match self {
+            Density::Comfortable => 1,
+            Density::Compact => 0,
+        }
+    }
+
+    /// Returns the style of separators, e.g. the line below table headers.
+    pub fn separator_style(&self, style: Style) -> Style {
+        match self {
+            Density::Comfortable => style,
+            Density::Compact => style.dim(),
+        }
+    }
+}
 
 #[derive(Clone, Debug)]
 pub struct Theme {
@@ -10,7 +313,22 @@ pub struct Theme {
     pub textview_scroll_style: Style,
     pub textview_focus_scroll_style: Style,
     pub bar_on_black_style: Style,
+    pub gauge_style: Style,
+    /// The style of line numbers next to a diff.
+    pub gutter_style: Style,
+    /// The style of characters that match a search.
+    pub search_highlight_style: Style,
+    /// The style of selected items. Widgets may override it.
+    pub highlight_style: HighlightStyle,
+    /// The styles of status badges.
+    pub badge_styles: BadgeStyles,
+    pub scrollbar_thumb_symbol: &'static str,
+    pub scrollbar_track_symbol: Option<&'static str>,
     pub dim_no_focus: bool,
+    /// The characters widgets are drawn with.
+    pub charset: Charset,
+    /// How densely tables are laid out.
+    pub density: Density,
 }
 
 impl Default for Theme {
@@ -30,7 +348,16 @@ impl Theme {
             textview_scroll_style: style::cyan().dim(),
             textview_focus_scroll_style: style::cyan(),
             bar_on_black_style: Style::default().on_gray(),
+            gauge_style: style::cyan().on_gray(),
+            gutter_style: Style::default().fg(Color::Rgb(170, 170, 170)),
+            search_highlight_style: Style::default().bold().underlined(),
+            highlight_style: HighlightStyle::default(),
+            badge_styles: BadgeStyles::default(),
+            scrollbar_thumb_symbol: Charset::Unicode.scrollbar_thumb(),
+            scrollbar_track_symbol: None,
             dim_no_focus: false,
+            charset: Charset::Unicode,
+            density: Density::Comfortable,
         }
     }
 
@@ -44,9 +371,93 @@ impl Theme {
             textview_scroll_style: style::cyan().dim(),
             textview_focus_scroll_style: style::cyan(),
             bar_on_black_style: Style::default().on_black(),
+            gauge_style: style::cyan().dim().on_black(),
+            gutter_style: style::gray().dim(),
+            search_highlight_style: style::yellow().bold(),
+            highlight_style: HighlightStyle::default(),
+            badge_styles: BadgeStyles::default(),
+            scrollbar_thumb_symbol: Charset::Unicode.scrollbar_thumb(),
+            scrollbar_track_symbol: None,
+            dim_no_focus: false,
+            charset: Charset::Unicode,
+            density: Density::Comfortable,
+        }
+    }
+
+    /// A theme that doesn't rely on dimmed text or subtle shades, e.g. for
+    /// low-vision users or terminals with few colors.
+    pub fn high_contrast() -> Self {
+        Self {
+            border_style: style::gray(),
+            focus_border_style: Style::default().fg(Color::White).bold(),
+            shortcuts_keys_style: style::yellow().bold(),
+            shortcuts_action_style: Style::default().fg(Color::White),
+            textview_style: style::reset(),
+            textview_scroll_style: style::gray(),
+            textview_focus_scroll_style: Style::default().fg(Color::White),
+            bar_on_black_style: Style::default().on_black(),
+            gauge_style: style::yellow().on_black(),
+            gutter_style: style::gray(),
+            search_highlight_style: style::yellow().bold().underlined(),
+            highlight_style: HighlightStyle {
+                focus: Style::default().fg(Color::Black).bg(Color::Yellow).bold(),
+                no_focus: Style::default().fg(Color::Black).bg(Color::White),
+            },
+            badge_styles: BadgeStyles {
+                draft: style::gray().reversed(),
+                ..BadgeStyles::default()
+            },
+            scrollbar_thumb_symbol: Charset::Unicode.scrollbar_thumb(),
+            scrollbar_track_symbol: None,
             dim_no_focus: false,
+            charset: Charset::Unicode,
+            density: Density::Comfortable,
         }
     }
+
+    /// Returns this theme with all styles dimmed, e.g. for a region without
+    /// focus. Widgets in it also dim their content if they don't have the focus.
+    pub fn dimmed(mut self) -> Self {
+        for style in [
+            &mut self.border_style,
+            &mut self.focus_border_style,
+            &mut self.shortcuts_keys_style,
+            &mut self.shortcuts_action_style,
+            &mut self.textview_style,
+            &mut self.textview_scroll_style,
+            &mut self.textview_focus_scroll_style,
+            &mut self.bar_on_black_style,
+            &mut self.gauge_style,
+            &mut self.gutter_style,
+            &mut self.search_highlight_style,
+            &mut self.highlight_style.focus,
+            &mut self.highlight_style.no_focus,
+            &mut self.badge_styles.open,
+            &mut self.badge_styles.closed,
+            &mut self.badge_styles.merged,
+            &mut self.badge_styles.draft,
+            &mut self.badge_styles.archived,
+            &mut self.badge_styles.updated,
+            &mut self.badge_styles.neutral,
+        ] {
+            *style = style.dim();
+        }
+        self.dim_no_focus = true;
+        self
+    }
+
+    /// Returns this theme with tables laid out in the given density.
+    pub fn with_density(mut self, density: Density) -> Self {
+        self.density = density;
+        self
+    }
+
+    /// Returns this theme drawn with the given charset.
+    pub fn with_charset(mut self, charset: Charset) -> Self {
+        self.charset = charset;
+        self.scrollbar_thumb_symbol = charset.scrollbar_thumb();
+        self
+    }
 }
 
 pub mod style {
@@ -100,3 +511,28 @@ pub mod style {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::Charset;
+
+    #[test]
+    fn charset_should_fall_back_to_ascii() {
+        assert_eq!(Charset::from_env(None, None), Charset::Unicode);
+        assert_eq!(
+            Charset::from_env(Some("xterm-256color"), Some("en_US.UTF-8")),
+            Charset::Unicode
+        );
+        assert_eq!(
+            Charset::from_env(Some("xterm"), Some("C.utf8")),
+            Charset::Unicode
+        );
+        assert_eq!(Charset::from_env(Some("xterm"), Some("C")), Charset::Ascii);
+        assert_eq!(
+            Charset::from_env(Some("dumb"), Some("en_US.UTF-8")),
+            Charset::Ascii
+        );
+    }
+}
@@ -0,0 +1,140 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::{Style, Stylize};
+use ratatui::text::Line;
+use ratatui::widgets::{Clear, Widget};
+
+use super::theme::style;
+
+/// The time a toast is shown for.
+pub const TOAST_DURATION: Duration = Duration::from_secs(4);
+/// The number of toasts that are shown at most, the most recent ones.
+pub const TOAST_MAX_VISIBLE: usize = 3;
+
+/// The level of a toast message. It determines the style a toast is rendered in.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ToastLevel {
+    Info,
+    Warning,
+    Error,
+}
+
+impl ToastLevel {
+    fn style(&self) -> Style {
+        match self {
+            ToastLevel::Info => style::cyan(),
+            ToastLevel::Warning => style::yellow(),
+            ToastLevel::Error => style::red(),
+        }
+    }
+}
+
+/// Transient messages and the time they expire at, oldest first. Rendered as an
+/// overlay at the bottom of an area, the newest one being rendered in the last
+/// line.
+#[derive(Clone, Debug, Default)]
+pub struct Toasts {
+    toasts: VecDeque<(Instant, ToastLevel, String)>,
+}
+
+impl Toasts {
+    /// Adds a toast that expires after `TOAST_DURATION`. A toast that is equal
+    /// to one that did not expire yet, is ignored.
+    pub fn push(&mut self, message: impl ToString, level: ToastLevel) {
+        let message = message.to_string();
+
+        if !self
+            .toasts
+            .iter()
+            .any(|(_, l, m)| *l == level && *m == message)
+        {
+            self.toasts
+                .push_back((Instant::now() + TOAST_DURATION, level, message));
+        }
+    }
+
+    /// Removes all toasts that expired at the given time.
+    pub fn expire(&mut self, now: Instant) {
+        self.toasts.retain(|(expires, _, _)| *expires > now);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.toasts.is_empty()
+    }
+
+    /// Returns the number of lines the toasts are rendered in.
+    pub fn height(&self) -> u16 {
+        self.toasts.len().min(TOAST_MAX_VISIBLE) as u16
+    }
+
+    /// Returns all toasts that did not expire yet, oldest first.
+    pub fn messages(&self) -> Vec<(ToastLevel, String)> {
+        self.toasts
+            .iter()
+            .map(|(_, level, message)| (*level, message.clone()))
+            .collect()
+    }
+}
+
+impl Widget for &Toasts {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let toasts = self.toasts.iter().rev().take(TOAST_MAX_VISIBLE);
+
+        for (row, (_, level, message)) in toasts.enumerate() {
+            let Some(y) = area.bottom().checked_sub(row as u16 + 1) else {
+                break;
+            };
+            if y < area.top() {
+                break;
+            }
+            let line_area = Rect {
+                y,
+                height: 1,
+                ..area
+            };
+            let line = Line::from(format!(" {message} ")).style(level.style().reversed());
+
+            Clear.render(line_area, buf);
+            line.render(line_area, buf);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Instant;
+
+    use pretty_assertions::assert_eq;
+
+    use ratatui::buffer::Buffer;
+    use ratatui::layout::Rect;
+    use ratatui::widgets::Widget;
+
+    use crate::ui::im::testing::lines;
+
+    use super::{ToastLevel, Toasts, TOAST_DURATION};
+
+    #[test]
+    fn newest_toasts_should_render_at_bottom() {
+        let mut toasts = Toasts::default();
+        for index in 0..5 {
+            toasts.push(format!("toast {index}"), ToastLevel::Info);
+        }
+        toasts.push("toast 4", ToastLevel::Info);
+
+        let mut buf = Buffer::empty(Rect::new(0, 0, 10, 4));
+        (&toasts).render(buf.area, &mut buf);
+
+        assert_eq!(toasts.height(), 3);
+        assert_eq!(
+            lines(&buf),
+            ["          ", " toast 2  ", " toast 3  ", " toast 4  "]
+        );
+
+        toasts.expire(Instant::now() + TOAST_DURATION);
+        assert!(toasts.is_empty());
+    }
+}
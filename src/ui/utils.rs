@@ -8,21 +8,70 @@ impl LineMerger {
         mixins: HashMap<usize, Vec<Vec<T>>>,
         start: usize,
     ) -> Vec<T> {
-        let mut merged = vec![];
-        for (idx, line) in lines.iter().enumerate() {
-            merged.push(line.clone());
+        Self::rows(lines, mixins, start).concat()
+    }
 
-            let actual_idx = idx.saturating_add(start);
-            if let Some(mixins) = mixins.get(&actual_idx) {
-                for mixin in mixins {
-                    for mixin_line in mixin {
-                        merged.push(mixin_line.clone());
+    /// Like `merge`, but keeps each line and the mixins that follow it in a
+    /// row of their own.
+    pub fn rows<T: Clone>(
+        lines: Vec<T>,
+        mixins: HashMap<usize, Vec<Vec<T>>>,
+        start: usize,
+    ) -> Vec<Vec<T>> {
+        lines
+            .into_iter()
+            .enumerate()
+            .map(|(idx, line)| {
+                let mut row = vec![line];
+
+                let actual_idx = idx.saturating_add(start);
+                if let Some(mixins) = mixins.get(&actual_idx) {
+                    for mixin in mixins {
+                        row.extend(mixin.iter().cloned());
                     }
                 }
+                row
+            })
+            .collect()
+    }
+}
+
+/// The number of seconds in a day.
+const DAY: u64 = 86400;
+
+/// Counts events per day within a window of days that ends today, e.g. to render
+/// the activity of a patch in a sparkline.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DateHistogram {
+    days: usize,
+}
+
+impl DateHistogram {
+    pub fn new(days: usize) -> Self {
+        Self { days }
+    }
+
+    /// Returns the number of events for each day of the window, oldest first.
+    /// Timestamps and `now` are seconds since the epoch; events outside of the
+    /// window are ignored.
+    pub fn counts(&self, now: u64, timestamps: impl IntoIterator<Item = u64>) -> Vec<u64> {
+        let mut counts = vec![0; self.days];
+        let today = now / DAY;
+
+        for timestamp in timestamps {
+            let Some(age) = today.checked_sub(timestamp / DAY) else {
+                continue;
+            };
+            if let Some(count) = usize::try_from(age)
+                .ok()
+                .and_then(|age| self.days.checked_sub(age + 1))
+                .and_then(|idx| counts.get_mut(idx))
+            {
+                *count += 1;
             }
         }
 
-        merged
+        counts
     }
 }
 
@@ -32,7 +81,16 @@ mod test {
 
     use pretty_assertions::assert_eq;
 
-    use crate::ui::utils::LineMerger;
+    use crate::ui::utils::{DateHistogram, LineMerger, DAY};
+
+    #[test]
+    fn events_should_be_counted_per_day() {
+        let now = 100 * DAY + 10;
+        let timestamps = [now, 100 * DAY, 99 * DAY + 5, 97 * DAY, 90 * DAY, 101 * DAY];
+
+        assert_eq!(DateHistogram::new(4).counts(now, timestamps), [1, 0, 1, 2]);
+        assert!(DateHistogram::new(0).counts(now, timestamps).is_empty());
+    }
 
     #[test]
     fn lines_should_be_merged_correctly() -> anyhow::Result<()> {